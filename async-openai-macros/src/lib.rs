@@ -139,3 +139,105 @@ pub fn byot(args: TokenStream, item: TokenStream) -> TokenStream {
 
     expanded.into()
 }
+
+/// Arguments of `#[tool(name = "...", description = "...")]`.
+#[derive(Default)]
+struct ToolArgs {
+    name: Option<syn::LitStr>,
+    description: Option<syn::LitStr>,
+}
+
+impl ToolArgs {
+    fn parse(attrs: &[syn::Attribute]) -> syn::Result<Self> {
+        let mut args = ToolArgs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("tool")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("name") {
+                    args.name = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("description") {
+                    args.description = Some(meta.value()?.parse()?);
+                } else {
+                    return Err(meta.error("expected `name` or `description`"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(args)
+    }
+}
+
+/// The doc comment of an item, its lines trimmed and joined.
+fn doc_comment(attrs: &[syn::Attribute]) -> Option<String> {
+    let lines: Vec<String> = attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"))
+        .filter_map(|attr| match &attr.meta {
+            syn::Meta::NameValue(syn::MetaNameValue {
+                value:
+                    syn::Expr::Lit(syn::ExprLit {
+                        lit: syn::Lit::Str(line),
+                        ..
+                    }),
+                ..
+            }) => Some(line.value().trim().to_string()),
+            _ => None,
+        })
+        .collect();
+    let doc = lines.join("\n").trim().to_string();
+    (!doc.is_empty()).then_some(doc)
+}
+
+/// `GetWeather` to `get_weather`.
+fn snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut snake = String::new();
+    for (index, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lower = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase()
+                || previous.is_ascii_digit()
+                || (previous.is_uppercase() && next_is_lower)
+            {
+                snake.push('_');
+            }
+        }
+        snake.extend(c.to_lowercase());
+    }
+    snake
+}
+
+/// Implement `async_openai::traits::OpenAiTool` for the arguments of a function tool, named
+/// after the type in snake case and described by its doc comment, unless set with
+/// `#[tool(name = "...", description = "...")]`.
+#[proc_macro_derive(OpenAiTool, attributes(tool))]
+pub fn derive_openai_tool(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    let args = match ToolArgs::parse(&input.attrs) {
+        Ok(args) => args,
+        Err(e) => return e.to_compile_error().into(),
+    };
+
+    let ident = &input.ident;
+    let name = args
+        .name
+        .map(|name| name.value())
+        .unwrap_or_else(|| snake_case(&ident.to_string()));
+    let description = match args
+        .description
+        .map(|description| description.value())
+        .or_else(|| doc_comment(&input.attrs))
+    {
+        Some(description) => quote! { ::std::option::Option::Some(#description) },
+        None => quote! { ::std::option::Option::None },
+    };
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let expanded = quote! {
+        impl #impl_generics ::async_openai::traits::OpenAiTool for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+            const DESCRIPTION: ::std::option::Option<&'static str> = #description;
+        }
+    };
+    expanded.into()
+}
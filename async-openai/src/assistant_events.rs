@@ -0,0 +1,210 @@
+//! Typed callbacks for the events of a streamed run, driven by [crate::Threads::handle_stream].
+//!
+//! An [AssistantEventHandler] receives messages and their deltas, run steps and the deltas of
+//! their tool calls, and decides the tool outputs of a run requiring action: the outputs are
+//! submitted and the handler keeps receiving the events of the continued run.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     assistant_events::AssistantEventHandler,
+//!     error::OpenAIError,
+//!     types::{
+//!         CreateRunRequestArgs, MessageDeltaContent, MessageDeltaObject, RunObject, ToolsOutputs,
+//!     },
+//!     Client,
+//! };
+//! use futures::future::BoxFuture;
+//!
+//! struct Printer;
+//!
+//! impl AssistantEventHandler for Printer {
+//!     fn on_message_delta(&mut self, delta: &MessageDeltaObject) {
+//!         for content in delta.delta.content.iter().flatten() {
+//!             if let MessageDeltaContent::Text(text) = content {
+//!                 if let Some(value) = text.text.as_ref().and_then(|text| text.value.as_ref()) {
+//!                     print!("{value}");
+//!                 }
+//!             }
+//!         }
+//!     }
+//!
+//!     fn on_requires_action<'a>(
+//!         &'a mut self,
+//!         run: &'a RunObject,
+//!     ) -> BoxFuture<'a, Result<Option<Vec<ToolsOutputs>>, OpenAIError>> {
+//!         Box::pin(async move {
+//!             let tool_calls = &run.required_action.as_ref().unwrap().submit_tool_outputs.tool_calls;
+//!             Ok(Some(
+//!                 tool_calls
+//!                     .iter()
+//!                     .map(|call| ToolsOutputs {
+//!                         tool_call_id: Some(call.id.clone()),
+//!                         output: Some("57".into()),
+//!                     })
+//!                     .collect(),
+//!             ))
+//!         })
+//!     }
+//! }
+//!
+//! # async fn run() -> Result<(), OpenAIError> {
+//! let client = Client::new();
+//! let threads = client.threads();
+//! let request = CreateRunRequestArgs::default()
+//!     .assistant_id("asst_abc123")
+//!     .build()?;
+//! let stream = threads.runs("thread_abc123").create_stream(request).await?;
+//! let run = threads.handle_stream(stream, &mut Printer).await?;
+//! # Ok(())
+//! # }
+//! ```
+use futures::future::BoxFuture;
+
+use crate::{
+    error::{ApiError, OpenAIError},
+    types::{
+        AssistantStreamEvent, DeltaStepDetails, MessageDeltaObject, MessageObject, RunObject,
+        RunStepDeltaObject, RunStepDeltaStepDetailsToolCalls, RunStepObject, ThreadObject,
+        ToolsOutputs,
+    },
+};
+
+/// Callbacks for the events of a streamed run, all of them doing nothing by default.
+pub trait AssistantEventHandler: Send {
+    fn on_thread_created(&mut self, _thread: &ThreadObject) {}
+
+    /// A run changed status: created, queued, in progress, requiring action, cancelling,
+    /// or ended.
+    fn on_run(&mut self, _run: &RunObject) {}
+
+    /// A run step was created or changed status.
+    fn on_run_step(&mut self, _step: &RunStepObject) {}
+
+    fn on_run_step_delta(&mut self, _delta: &RunStepDeltaObject) {}
+
+    /// A delta of a tool call of the run step `step_id`, after [Self::on_run_step_delta].
+    fn on_tool_call_delta(&mut self, _step_id: &str, _delta: &RunStepDeltaStepDetailsToolCalls) {}
+
+    /// A message was created or moved to `in_progress`.
+    fn on_message(&mut self, _message: &MessageObject) {}
+
+    fn on_message_delta(&mut self, _delta: &MessageDeltaObject) {}
+
+    /// A message was completed, or ended before it was completed.
+    fn on_message_done(&mut self, _message: &MessageObject) {}
+
+    /// An error event; the stream ends after it.
+    fn on_error(&mut self, _error: &ApiError) {}
+
+    /// The outputs of the tool calls of a run requiring action, after [Self::on_run]:
+    /// `Some` outputs are submitted and the run continues, `None` stops handling the run.
+    fn on_requires_action<'a>(
+        &'a mut self,
+        _run: &'a RunObject,
+    ) -> BoxFuture<'a, Result<Option<Vec<ToolsOutputs>>, OpenAIError>> {
+        Box::pin(async { Ok(None) })
+    }
+}
+
+/// Route `event` to `handler`, returning the run of a run event.
+pub(crate) fn dispatch<H: AssistantEventHandler + ?Sized>(
+    handler: &mut H,
+    event: AssistantStreamEvent,
+) -> Option<RunObject> {
+    match event {
+        AssistantStreamEvent::ThreadCreated(thread) => handler.on_thread_created(&thread),
+        AssistantStreamEvent::ThreadRunCreated(run)
+        | AssistantStreamEvent::ThreadRunQueued(run)
+        | AssistantStreamEvent::ThreadRunInProgress(run)
+        | AssistantStreamEvent::ThreadRunRequiresAction(run)
+        | AssistantStreamEvent::ThreadRunCompleted(run)
+        | AssistantStreamEvent::ThreadRunIncomplete(run)
+        | AssistantStreamEvent::ThreadRunFailed(run)
+        | AssistantStreamEvent::ThreadRunCancelling(run)
+        | AssistantStreamEvent::ThreadRunCancelled(run)
+        | AssistantStreamEvent::ThreadRunExpired(run) => {
+            handler.on_run(&run);
+            return Some(run);
+        }
+        AssistantStreamEvent::ThreadRunStepCreated(step)
+        | AssistantStreamEvent::ThreadRunStepInProgress(step)
+        | AssistantStreamEvent::ThreadRunStepCompleted(step)
+        | AssistantStreamEvent::ThreadRunStepFailed(step)
+        | AssistantStreamEvent::ThreadRunStepCancelled(step)
+        | AssistantStreamEvent::ThreadRunStepExpired(step) => handler.on_run_step(&step),
+        AssistantStreamEvent::ThreadRunStepDelta(delta) => {
+            handler.on_run_step_delta(&delta);
+            if let DeltaStepDetails::ToolCalls(details) = &delta.delta.step_details {
+                for tool_call in details.tool_calls.iter().flatten() {
+                    handler.on_tool_call_delta(&delta.id, tool_call);
+                }
+            }
+        }
+        AssistantStreamEvent::ThreadMessageCreated(message)
+        | AssistantStreamEvent::ThreadMessageInProgress(message) => handler.on_message(&message),
+        AssistantStreamEvent::ThreadMessageDelta(delta) => handler.on_message_delta(&delta),
+        AssistantStreamEvent::ThreadMessageCompleted(message)
+        | AssistantStreamEvent::ThreadMessageIncomplete(message) => {
+            handler.on_message_done(&message)
+        }
+        AssistantStreamEvent::ErrorEvent(error) => handler.on_error(&error),
+        AssistantStreamEvent::Done(_) => {}
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{types::MessageDeltaContent, Client};
+
+    #[derive(Default)]
+    struct Collect {
+        text: String,
+        tool_calls: Vec<String>,
+    }
+
+    impl AssistantEventHandler for Collect {
+        fn on_message_delta(&mut self, delta: &MessageDeltaObject) {
+            for content in delta.delta.content.iter().flatten() {
+                if let MessageDeltaContent::Text(text) = content {
+                    if let Some(value) = text.text.as_ref().and_then(|text| text.value.as_ref()) {
+                        self.text.push_str(value);
+                    }
+                }
+            }
+        }
+
+        fn on_tool_call_delta(&mut self, step_id: &str, _delta: &RunStepDeltaStepDetailsToolCalls) {
+            self.tool_calls.push(step_id.to_string());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handle_stream() {
+        let events: Vec<AssistantStreamEvent> = serde_json::from_value(serde_json::json!([
+            {"event": "thread.message.delta", "data": {"id": "msg_1", "object": "thread.message.delta",
+                "delta": {"content": [{"index": 0, "type": "text", "text": {"value": "Hello"}}]}}},
+            {"event": "thread.run.step.delta", "data": {"id": "step_1", "object": "thread.run.step.delta",
+                "delta": {"step_details": {"type": "tool_calls", "tool_calls": [
+                    {"index": 0, "type": "function", "function": {"arguments": "{}"}}
+                ]}}}},
+            {"event": "thread.message.delta", "data": {"id": "msg_1", "object": "thread.message.delta",
+                "delta": {"content": [{"index": 0, "type": "text", "text": {"value": " world"}}]}}},
+            {"event": "done", "data": "[DONE]"},
+        ]))
+        .unwrap();
+        let stream = Box::pin(futures::stream::iter(events.into_iter().map(Ok)));
+
+        let client = Client::new();
+        let mut handler = Collect::default();
+        let run = client
+            .threads()
+            .handle_stream(stream, &mut handler)
+            .await
+            .unwrap();
+        assert!(run.is_none());
+        assert_eq!(handler.text, "Hello world");
+        assert_eq!(handler.tool_calls, ["step_1"]);
+    }
+}
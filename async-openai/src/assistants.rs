@@ -3,23 +3,39 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    file::Files,
+    poll::PollOptions,
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        AssistantObject, CreateAssistantRequest, DeleteAssistantResponse, ListAssistantsResponse,
-        ModifyAssistantRequest,
+        AssistantObject, AssistantToolFileSearchResources, AssistantToolResources, AssistantTools,
+        CreateAssistantRequest, CreateVectorStoreFileBatchRequest, CreateVectorStoreRequest,
+        DeleteAssistantResponse, FilePurpose, FileReference, ListAssistantsResponse, Metadata,
+        ModifyAssistantRequest, ProvisionedFileSearch, VectorStoreFileBatchObject,
+        VectorStoreObject,
     },
-    Client,
+    Client, VectorStores,
 };
 
+/// Metadata key of the vector store dedicated to an assistant, set to the assistant ID.
+const ASSISTANT_ID_METADATA: &str = "assistant_id";
+
 /// Build assistants that can call models and use tools to perform tasks.
 ///
 /// [Get started with the Assistants API](https://platform.openai.com/docs/assistants)
 pub struct Assistants<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Assistants);
+crate::util::impl_api_group_debug!(Assistants, "/assistants");
+
 impl<'c, C: Config> Assistants<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Create an assistant with a model and instructions.
@@ -28,14 +44,19 @@ impl<'c, C: Config> Assistants<'c, C> {
         &self,
         request: CreateAssistantRequest,
     ) -> Result<AssistantObject, OpenAIError> {
-        self.client.post("/assistants", request).await
+        self.client
+            .post("/assistants", request, &self.request_options)
+            .await
     }
 
     /// Retrieves an assistant.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, assistant_id: &str) -> Result<AssistantObject, OpenAIError> {
         self.client
-            .get(&format!("/assistants/{assistant_id}"))
+            .get(
+                &format!("/assistants/{assistant_id}"),
+                &self.request_options,
+            )
             .await
     }
 
@@ -47,7 +68,11 @@ impl<'c, C: Config> Assistants<'c, C> {
         request: ModifyAssistantRequest,
     ) -> Result<AssistantObject, OpenAIError> {
         self.client
-            .post(&format!("/assistants/{assistant_id}"), request)
+            .post(
+                &format!("/assistants/{assistant_id}"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -55,16 +80,141 @@ impl<'c, C: Config> Assistants<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, assistant_id: &str) -> Result<DeleteAssistantResponse, OpenAIError> {
         self.client
-            .delete(&format!("/assistants/{assistant_id}"))
+            .delete(
+                &format!("/assistants/{assistant_id}"),
+                &self.request_options,
+            )
             .await
     }
 
+    /// Index `files` in the vector store dedicated to the assistant and wait until they are
+    /// processed, uploading the files which are not file IDs with the `assistants` purpose.
+    ///
+    /// The dedicated vector store is created on first use, with the `assistant_id` metadata, and
+    /// attached to the `file_search` tool of the assistant, which is enabled if needed. An
+    /// assistant has at most one vector store: one which is not dedicated is replaced.
+    ///
+    /// ```no_run
+    /// use async_openai::{poll::PollOptions, types::FileReference, Client};
+    ///
+    /// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+    /// let provisioned = Client::new()
+    ///     .assistants()
+    ///     .provision_file_search(
+    ///         "asst_abc123",
+    ///         [FileReference::path("docs/guide.md"), FileReference::path("docs/faq.md")],
+    ///         &PollOptions::new(),
+    ///     )
+    ///     .await?;
+    /// println!("{:?}", provisioned.batch.file_counts);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn provision_file_search<I, F>(
+        &self,
+        assistant_id: &str,
+        files: I,
+        options: &PollOptions,
+    ) -> Result<ProvisionedFileSearch, OpenAIError>
+    where
+        I: IntoIterator<Item = F>,
+        F: Into<FileReference>,
+    {
+        let files: Vec<FileReference> = files.into_iter().map(Into::into).collect();
+        if files.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "no files to index in the vector store".into(),
+            ));
+        }
+        let assistant: AssistantObject = self.retrieve(assistant_id).await?;
+        let vector_stores =
+            VectorStores::new(self.client).with_options(self.request_options.clone());
+
+        let attached = assistant
+            .tool_resources
+            .as_ref()
+            .and_then(|resources| resources.file_search.as_ref())
+            .and_then(|file_search| file_search.vector_store_ids.first());
+        let dedicated = match attached {
+            Some(vector_store_id) => {
+                let vector_store: VectorStoreObject =
+                    vector_stores.retrieve(vector_store_id).await?;
+                vector_store
+                    .metadata
+                    .as_ref()
+                    .and_then(|metadata| metadata.get(ASSISTANT_ID_METADATA))
+                    .filter(|id| *id == assistant_id)
+                    .map(|_| vector_store.id)
+            }
+            None => None,
+        };
+        let vector_store_id = match dedicated {
+            Some(vector_store_id) => vector_store_id,
+            None => {
+                let name = assistant.name.as_deref().unwrap_or(assistant_id);
+                let request = CreateVectorStoreRequest {
+                    name: Some(format!("{name} files")),
+                    metadata: Some(Metadata::from([(ASSISTANT_ID_METADATA, assistant_id)])),
+                    ..Default::default()
+                };
+                let vector_store: VectorStoreObject = vector_stores.create(request).await?;
+                vector_store.id
+            }
+        };
+
+        let files_api = Files::new(self.client).with_options(self.request_options.clone());
+        let file_ids = futures::future::try_join_all(
+            files
+                .into_iter()
+                .map(|file| files_api.resolve(file, FilePurpose::Assistants)),
+        )
+        .await?;
+        let file_batches = vector_stores.file_batches(&vector_store_id);
+        let batch: VectorStoreFileBatchObject = file_batches
+            .create(CreateVectorStoreFileBatchRequest {
+                file_ids,
+                ..Default::default()
+            })
+            .await?;
+        let batch = file_batches.wait(&batch.id, options).await?;
+
+        let has_file_search = assistant
+            .tools
+            .iter()
+            .any(|tool| matches!(tool, AssistantTools::FileSearch(_)));
+        if attached == Some(&vector_store_id) && has_file_search {
+            return Ok(ProvisionedFileSearch { assistant, batch });
+        }
+        let mut tools = assistant.tools.clone();
+        if !has_file_search {
+            tools.push(AssistantTools::FileSearch(Default::default()));
+        }
+        let request = ModifyAssistantRequest {
+            tools: Some(tools),
+            tool_resources: Some(AssistantToolResources {
+                code_interpreter: assistant
+                    .tool_resources
+                    .and_then(|resources| resources.code_interpreter),
+                file_search: Some(AssistantToolFileSearchResources {
+                    vector_store_ids: vec![vector_store_id],
+                }),
+            }),
+            ..Default::default()
+        };
+        let assistant: AssistantObject = self.update(assistant_id, request).await?;
+        Ok(ProvisionedFileSearch { assistant, batch })
+    }
+
     /// Returns a list of assistants.
+    ///
+    /// `query` is usually a [ListAssistantsQuery](crate::types::ListAssistantsQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListAssistantsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
-        self.client.get_with_query("/assistants", &query).await
+        self.client
+            .get_with_query("/assistants", &query, &self.request_options)
+            .await
     }
 }
@@ -1,13 +1,18 @@
 use bytes::Bytes;
+use futures::TryStreamExt;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        CreateSpeechRequest, CreateSpeechResponse, CreateTranscriptionRequest,
-        CreateTranscriptionResponseJson, CreateTranscriptionResponseVerboseJson,
-        CreateTranslationRequest, CreateTranslationResponseJson,
-        CreateTranslationResponseVerboseJson,
+        AudioInput, ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+        ConverseOptions, ConverseResponse, CreateChatCompletionRequest,
+        CreateChatCompletionResponse, CreateSpeechRequest, CreateSpeechResponse,
+        CreateTranscriptionRequest, CreateTranscriptionResponseJson,
+        CreateTranscriptionResponseVerboseJson, CreateTranslationRequest,
+        CreateTranslationResponseJson, CreateTranslationResponseVerboseJson, SpeechResponseStream,
+        SpeechToSpeechOptions, SpeechToSpeechResponse,
     },
     Client,
 };
@@ -16,11 +21,18 @@ use crate::{
 /// Related guide: [Speech to text](https://platform.openai.com/docs/guides/speech-to-text)
 pub struct Audio<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Audio);
+crate::util::impl_api_group_debug!(Audio, "/audio");
+
 impl<'c, C: Config> Audio<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Transcribes audio into the input language.
@@ -34,7 +46,7 @@ impl<'c, C: Config> Audio<'c, C> {
         request: CreateTranscriptionRequest,
     ) -> Result<CreateTranscriptionResponseJson, OpenAIError> {
         self.client
-            .post_form("/audio/transcriptions", request)
+            .post_form("/audio/transcriptions", request, &self.request_options)
             .await
     }
 
@@ -49,7 +61,7 @@ impl<'c, C: Config> Audio<'c, C> {
         request: CreateTranscriptionRequest,
     ) -> Result<CreateTranscriptionResponseVerboseJson, OpenAIError> {
         self.client
-            .post_form("/audio/transcriptions", request)
+            .post_form("/audio/transcriptions", request, &self.request_options)
             .await
     }
 
@@ -59,7 +71,7 @@ impl<'c, C: Config> Audio<'c, C> {
         request: CreateTranscriptionRequest,
     ) -> Result<Bytes, OpenAIError> {
         self.client
-            .post_form_raw("/audio/transcriptions", request)
+            .post_form_raw("/audio/transcriptions", request, &self.request_options)
             .await
     }
 
@@ -73,7 +85,9 @@ impl<'c, C: Config> Audio<'c, C> {
         &self,
         request: CreateTranslationRequest,
     ) -> Result<CreateTranslationResponseJson, OpenAIError> {
-        self.client.post_form("/audio/translations", request).await
+        self.client
+            .post_form("/audio/translations", request, &self.request_options)
+            .await
     }
 
     /// Translates audio into English.
@@ -86,7 +100,9 @@ impl<'c, C: Config> Audio<'c, C> {
         &self,
         request: CreateTranslationRequest,
     ) -> Result<CreateTranslationResponseVerboseJson, OpenAIError> {
-        self.client.post_form("/audio/translations", request).await
+        self.client
+            .post_form("/audio/translations", request, &self.request_options)
+            .await
     }
 
     /// Transcribes audio into the input language.
@@ -95,7 +111,7 @@ impl<'c, C: Config> Audio<'c, C> {
         request: CreateTranslationRequest,
     ) -> Result<Bytes, OpenAIError> {
         self.client
-            .post_form_raw("/audio/translations", request)
+            .post_form_raw("/audio/translations", request, &self.request_options)
             .await
     }
 
@@ -104,8 +120,220 @@ impl<'c, C: Config> Audio<'c, C> {
         &self,
         request: CreateSpeechRequest,
     ) -> Result<CreateSpeechResponse, OpenAIError> {
-        let bytes = self.client.post_raw("/audio/speech", request).await?;
+        let bytes = self
+            .client
+            .post_raw("/audio/speech", request, &self.request_options)
+            .await?;
 
         Ok(CreateSpeechResponse { bytes })
     }
+
+    /// Generates audio from the input text, as a stream of chunks to play while it is generated.
+    pub async fn speech_stream(
+        &self,
+        request: CreateSpeechRequest,
+    ) -> Result<SpeechResponseStream, OpenAIError> {
+        let response = self
+            .client
+            .post_response("/audio/speech", request, &self.request_options)
+            .await?;
+
+        Ok(Box::pin(
+            response.bytes_stream().map_err(OpenAIError::Reqwest),
+        ))
+    }
+
+    /// Transcribes `audio`, replies to the transcript with a chat model and speaks the reply.
+    ///
+    /// The transcript and the reply can be appended to [ConverseOptions::history] for the
+    /// next turn.
+    ///
+    /// ```no_run
+    /// use async_openai::{types::ConverseOptionsArgs, Client};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+    /// let options = ConverseOptionsArgs::default()
+    ///     .instructions("You are a helpful voice assistant, answer briefly.")
+    ///     .build()?;
+    /// let mut turn = Client::new()
+    ///     .audio()
+    ///     .converse("question.mp3", options)
+    ///     .await?;
+    /// println!("{} -> {}", turn.transcript, turn.reply);
+    /// while let Some(chunk) = turn.audio.next().await {
+    ///     let chunk = chunk?;
+    ///     // play chunk
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn converse<A: Into<AudioInput>>(
+        &self,
+        audio: A,
+        options: ConverseOptions,
+    ) -> Result<ConverseResponse, OpenAIError> {
+        let (transcript, reply, speech) = self.reply(audio.into(), options.into()).await?;
+        let audio = self.speech_stream(speech).await?;
+
+        Ok(ConverseResponse {
+            transcript,
+            reply,
+            audio,
+        })
+    }
+
+    /// Transcribes `audio`, replies to the transcript with a chat model and speaks the reply,
+    /// returning the whole audio of the reply. The hooks of `options` can inspect or edit the
+    /// transcript, the chat request and the reply along the way.
+    ///
+    /// ```no_run
+    /// use async_openai::{
+    ///     types::{ConverseOptionsArgs, SpeechToSpeechOptions},
+    ///     Client,
+    /// };
+    ///
+    /// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+    /// let options = SpeechToSpeechOptions::new(
+    ///     ConverseOptionsArgs::default()
+    ///         .instructions("You are a helpful voice assistant, answer briefly.")
+    ///         .build()?,
+    /// )
+    /// .with_on_transcript(|transcript| println!("user: {transcript}"));
+    /// let turn = Client::new()
+    ///     .audio()
+    ///     .speech_to_speech("question.mp3", options)
+    ///     .await?;
+    /// std::fs::write("reply.mp3", &turn.audio).unwrap();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn speech_to_speech<A: Into<AudioInput>>(
+        &self,
+        audio: A,
+        options: SpeechToSpeechOptions,
+    ) -> Result<SpeechToSpeechResponse, OpenAIError> {
+        let (transcript, reply, speech) = self.reply(audio.into(), options).await?;
+        let audio = self.speech(speech).await?.bytes;
+
+        Ok(SpeechToSpeechResponse {
+            transcript,
+            reply,
+            audio,
+        })
+    }
+
+    /// Transcript of `audio` and reply of the chat model, with the request speaking the reply.
+    async fn reply(
+        &self,
+        audio: AudioInput,
+        options: SpeechToSpeechOptions,
+    ) -> Result<(String, String, CreateSpeechRequest), OpenAIError> {
+        let converse = options.converse.clone();
+        let transcription = CreateTranscriptionRequest {
+            file: audio,
+            model: converse.transcription_model,
+            language: converse.language,
+            ..Default::default()
+        };
+        let transcription: CreateTranscriptionResponseJson = self.transcribe(transcription).await?;
+        let mut transcript = transcription.text;
+        options.transcript(&mut transcript);
+
+        let mut messages = Vec::with_capacity(converse.history.len() + 2);
+        if let Some(instructions) = converse.instructions {
+            messages.push(ChatCompletionRequestSystemMessage::from(instructions).into());
+        }
+        messages.extend(converse.history);
+        messages.push(ChatCompletionRequestUserMessage::from(transcript.as_str()).into());
+        let mut chat = CreateChatCompletionRequest {
+            model: converse.chat_model,
+            messages,
+            ..Default::default()
+        };
+        options.chat_request(&mut chat);
+        let response: CreateChatCompletionResponse = self
+            .client
+            .chat()
+            .with_options(self.request_options.clone())
+            .create(chat)
+            .await?;
+        let mut reply = response
+            .choices
+            .into_iter()
+            .next()
+            .and_then(|choice| choice.message.content.or(choice.message.refusal))
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument("the chat completion has no reply to speak".into())
+            })?;
+        options.reply(&mut reply);
+
+        let speech = CreateSpeechRequest {
+            input: reply.clone(),
+            model: converse.speech_model,
+            voice: converse.voice,
+            response_format: converse.response_format,
+            ..Default::default()
+        };
+        Ok((transcript, reply, speech))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        transport::mock::{MockResponse, MockTransport},
+        types::ConverseOptionsArgs,
+    };
+
+    /// Answers transcriptions, chat completions and speech.
+    fn voice_bot() -> std::sync::Arc<MockTransport> {
+        MockTransport::new(|request, _| {
+            Ok(match request.path() {
+                "/v1/audio/transcriptions" => MockResponse::new(r#"{"text": "what time is it"}"#),
+                "/v1/chat/completions" => MockResponse::new(
+                    r#"{"id":"1","object":"chat.completion","created":1,"model":"m","choices":[{"index":0,"message":{"role":"assistant","content":"It is **noon**."},"finish_reason":"stop"}]}"#,
+                ),
+                _ => MockResponse::new("audio"),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_speech_to_speech() {
+        let bot = voice_bot();
+        let client = Client::new().with_transport(bot.clone());
+        let options = SpeechToSpeechOptions::new(
+            ConverseOptionsArgs::default()
+                .instructions("Answer briefly.")
+                .build()
+                .unwrap(),
+        )
+        .with_on_transcript(|transcript| transcript.push('?'))
+        .with_on_chat_request(|request| request.temperature = Some(0.2))
+        .with_on_reply(|reply| *reply = reply.replace("**", ""));
+
+        let audio = AudioInput::from_vec_u8("question.wav".into(), vec![0; 4]);
+        let turn = client
+            .audio()
+            .speech_to_speech(audio, options)
+            .await
+            .unwrap();
+        assert_eq!(turn.transcript, "what time is it?");
+        assert_eq!(turn.reply, "It is noon.");
+        assert_eq!(turn.audio, Bytes::from_static(b"audio"));
+
+        let requests = bot.requests();
+        let chat = requests
+            .iter()
+            .find(|request| request.path() == "/v1/chat/completions")
+            .unwrap()
+            .json();
+        assert_eq!(chat["temperature"], serde_json::json!(0.2));
+        assert_eq!(
+            chat["messages"][1]["content"],
+            serde_json::json!("what time is it?")
+        );
+    }
 }
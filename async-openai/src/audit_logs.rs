@@ -1,27 +1,101 @@
+use futures::{stream, Stream, TryStreamExt};
 use serde::Serialize;
 
-use crate::{config::Config, error::OpenAIError, types::ListAuditLogsResponse, Client};
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::CursorPage,
+    request_options::{impl_request_options, RequestOptions},
+    types::{AuditLog, ListAuditLogsQuery, ListAuditLogsResponse},
+    Client,
+};
 
 /// Logs of user actions and configuration changes within this organization.
 /// To log events, you must activate logging in the [Organization Settings](https://platform.openai.com/settings/organization/general).
 /// Once activated, for security reasons, logging cannot be deactivated.
 pub struct AuditLogs<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(AuditLogs);
+crate::util::impl_api_group_debug!(AuditLogs, "/organization/audit_logs");
+
 impl<'c, C: Config> AuditLogs<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// List user actions and configuration changes within this organization.
+    ///
+    /// `query` is usually a [ListAuditLogsQuery].
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn get<Q>(&self, query: &Q) -> Result<ListAuditLogsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query("/organization/audit_logs", &query)
+            .get_with_query("/organization/audit_logs", &query, &self.request_options)
             .await
     }
+
+    /// Streams all the events matching `query`, e.g. of a time range, newest first, fetching
+    /// pages as needed. `after` of `query` is used as the starting cursor.
+    ///
+    /// ```no_run
+    /// use async_openai::{
+    ///     types::{AuditLogEvent, ListAuditLogsQueryArgs},
+    ///     Client,
+    /// };
+    /// use futures::TryStreamExt;
+    ///
+    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+    /// let query = ListAuditLogsQueryArgs::default()
+    ///     .effective_at_gte(1_735_689_600u64)
+    ///     .effective_at_lt(1_735_776_000u64)
+    ///     .limit(100u32)
+    ///     .build()?;
+    /// let client = Client::new();
+    /// let logs = client.audit_logs().stream(&query);
+    /// futures::pin_mut!(logs);
+    /// while let Some(log) = logs.try_next().await? {
+    ///     if let AuditLogEvent::LoginFailed(failed) = log.event() {
+    ///         println!("{:?}: {}", log.effective_at, failed.error_message);
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream(
+        &self,
+        query: &ListAuditLogsQuery,
+    ) -> impl Stream<Item = Result<AuditLog, OpenAIError>> + 'c {
+        let client = self.client;
+        let request_options = self.request_options.clone();
+        // `None` once the last page was fetched
+        stream::try_unfold(Some(query.clone()), move |query| {
+            let request_options = request_options.clone();
+            async move {
+                let Some(mut query) = query else {
+                    return Ok::<_, OpenAIError>(None);
+                };
+                let page: ListAuditLogsResponse = client
+                    .get_with_query("/organization/audit_logs", &query, &request_options)
+                    .await?;
+                let (logs, next) = page.into_page();
+                let next = next.map(|after| {
+                    query.after = Some(after);
+                    query
+                });
+                Ok(Some((
+                    stream::iter(logs.into_iter().map(Ok::<_, OpenAIError>)),
+                    next,
+                )))
+            }
+        })
+        .try_flatten()
+    }
 }
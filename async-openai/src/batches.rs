@@ -3,6 +3,8 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    poll::{poll_until_done, PollOptions},
+    request_options::{impl_request_options, RequestOptions},
     types::{Batch, BatchRequest, ListBatchesResponse},
     Client,
 };
@@ -12,32 +14,52 @@ use crate::{
 /// Related guide: [Batch](https://platform.openai.com/docs/guides/batch)
 pub struct Batches<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Batches);
+crate::util::impl_api_group_debug!(Batches, "/batches");
+
 impl<'c, C: Config> Batches<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates and executes a batch from an uploaded file of requests
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: BatchRequest) -> Result<Batch, OpenAIError> {
-        self.client.post("/batches", request).await
+        self.client
+            .post("/batches", request, &self.request_options)
+            .await
     }
 
     /// List your organization's batches.
+    ///
+    /// `query` is usually a [ListBatchesQuery](crate::types::ListBatchesQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListBatchesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
-        self.client.get_with_query("/batches", &query).await
+        self.client
+            .get_with_query("/batches", &query, &self.request_options)
+            .await
     }
 
     /// Retrieves a batch.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, batch_id: &str) -> Result<Batch, OpenAIError> {
-        self.client.get(&format!("/batches/{batch_id}")).await
+        self.client
+            .get(&format!("/batches/{batch_id}"), &self.request_options)
+            .await
+    }
+
+    /// Polls a batch until it is `completed`, `failed`, `expired` or `cancelled`.
+    pub async fn wait(&self, batch_id: &str, options: &PollOptions) -> Result<Batch, OpenAIError> {
+        poll_until_done(options, || self.retrieve(batch_id)).await
     }
 
     /// Cancels an in-progress batch. The batch will be in status `cancelling` for up to 10 minutes, before changing to `cancelled`, where it will have partial results (if any) available in the output file.
@@ -47,6 +69,7 @@ impl<'c, C: Config> Batches<'c, C> {
             .post(
                 &format!("/batches/{batch_id}/cancel"),
                 serde_json::json!({}),
+                &self.request_options,
             )
             .await
     }
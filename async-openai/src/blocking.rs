@@ -0,0 +1,362 @@
+//! A synchronous client for programs without an async runtime, such as CLI tools and build
+//! scripts, enabled by the `blocking` feature.
+//!
+//! [Client] drives the calls of an async [crate::Client] on a runtime of its own, like
+//! `reqwest::blocking` does. It must not be created, used or dropped from within an async
+//! runtime: use the async client there.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     blocking::Client,
+//!     types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs},
+//! };
+//!
+//! # fn main() -> Result<(), async_openai::error::OpenAIError> {
+//! let client = Client::new();
+//! let request = CreateChatCompletionRequestArgs::default()
+//!     .model("gpt-4o-mini")
+//!     .messages([ChatCompletionRequestUserMessage::from("Write a haiku about Rust.").into()])
+//!     .build()?;
+//!
+//! let response = client.chat().create(request.clone())?;
+//!
+//! // streamed responses are iterators
+//! for chunk in client.chat().create_stream(request)? {
+//!     for choice in chunk?.choices {
+//!         print!("{}", choice.delta.content.unwrap_or_default());
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use std::{future::Future, pin::Pin, sync::Arc};
+
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
+use serde::Serialize;
+use tokio::runtime::Runtime;
+
+use crate::{
+    config::{Config, OpenAIConfig},
+    error::OpenAIError,
+    request_options::RequestOptions,
+    types::{
+        CreateChatCompletionRequest, CreateChatCompletionResponse,
+        CreateChatCompletionStreamResponse, CreateCompletionRequest, CreateCompletionResponse,
+        CreateEmbeddingRequest, CreateEmbeddingResponse, CreateFileRequest, CreateImageEditRequest,
+        CreateImageRequest, CreateImageVariationRequest, CreateSpeechRequest, CreateSpeechResponse,
+        CreateTranscriptionRequest, CreateTranscriptionResponseJson, CreateTranslationRequest,
+        CreateTranslationResponseJson, DeleteFileResponse, ImagesResponse, ListFilesResponse,
+        OpenAIFile,
+    },
+};
+
+/// Synchronous counterpart of [crate::Client], for the chat, completions, embeddings, images,
+/// audio and files groups.
+#[derive(Debug, Clone)]
+pub struct Client<C: Config> {
+    inner: crate::Client<C>,
+    runtime: Arc<Runtime>,
+}
+
+impl Client<OpenAIConfig> {
+    /// Client with default [OpenAIConfig].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the runtime of the client cannot be created, or when called from within
+    /// an async runtime.
+    pub fn new() -> Self {
+        Self::from_async(crate::Client::new())
+    }
+}
+
+impl Default for Client<OpenAIConfig> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: Config> Client<C> {
+    /// Client with [OpenAIConfig] or [crate::config::AzureConfig], panicking like [Client::new].
+    pub fn with_config(config: C) -> Self {
+        Self::from_async(crate::Client::with_config(config))
+    }
+
+    /// Client making the calls of `client`, with its configuration, retries and options,
+    /// panicking like [Client::new].
+    pub fn from_async(client: crate::Client<C>) -> Self {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .worker_threads(1)
+            .thread_name("async-openai-blocking")
+            .enable_all()
+            .build()
+            .expect("failed to create the runtime of the blocking client");
+        Self {
+            inner: client,
+            runtime: Arc::new(runtime),
+        }
+    }
+
+    /// The async client making the calls.
+    pub fn inner(&self) -> &crate::Client<C> {
+        &self.inner
+    }
+
+    fn block_on<F: Future>(&self, future: F) -> F::Output {
+        self.runtime.block_on(future)
+    }
+
+    /// The items of `stream`, each one waited for by the runtime of this client.
+    fn iter<T>(&self, stream: Pin<Box<dyn Stream<Item = T> + Send>>) -> StreamIter<T> {
+        StreamIter {
+            stream,
+            runtime: self.runtime.clone(),
+        }
+    }
+
+    /// To call [Chat] group related APIs using this client.
+    pub fn chat(&self) -> Chat<'_, C> {
+        Chat {
+            inner: self.inner.chat(),
+            client: self,
+        }
+    }
+
+    /// To call [Completions] group related APIs using this client.
+    pub fn completions(&self) -> Completions<'_, C> {
+        Completions {
+            inner: self.inner.completions(),
+            client: self,
+        }
+    }
+
+    /// To call [Embeddings] group related APIs using this client.
+    pub fn embeddings(&self) -> Embeddings<'_, C> {
+        Embeddings {
+            inner: self.inner.embeddings(),
+            client: self,
+        }
+    }
+
+    /// To call [Images] group related APIs using this client.
+    pub fn images(&self) -> Images<'_, C> {
+        Images {
+            inner: self.inner.images(),
+            client: self,
+        }
+    }
+
+    /// To call [Audio] group related APIs using this client.
+    pub fn audio(&self) -> Audio<'_, C> {
+        Audio {
+            inner: self.inner.audio(),
+            client: self,
+        }
+    }
+
+    /// To call [Files] group related APIs using this client.
+    pub fn files(&self) -> Files<'_, C> {
+        Files {
+            inner: self.inner.files(),
+            client: self,
+        }
+    }
+}
+
+/// Iterator over a streamed response, blocking until the next item is received.
+pub struct StreamIter<T> {
+    stream: Pin<Box<dyn Stream<Item = T> + Send>>,
+    runtime: Arc<Runtime>,
+}
+
+impl<T> Iterator for StreamIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.runtime.block_on(self.stream.next())
+    }
+}
+
+impl<T> std::fmt::Debug for StreamIter<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StreamIter").finish_non_exhaustive()
+    }
+}
+
+/// Blocking API group wrapping the async group of the same name.
+macro_rules! blocking_group {
+    ($group:ident) => {
+        #[doc = concat!("Synchronous counterpart of [crate::", stringify!($group), "].")]
+        pub struct $group<'c, C: Config> {
+            inner: crate::$group<'c, C>,
+            client: &'c Client<C>,
+        }
+
+        impl<'c, C: Config> $group<'c, C> {
+            /// Send extra headers and query parameters with every call made through this API group.
+            pub fn with_options(self, options: RequestOptions) -> Self {
+                Self {
+                    inner: self.inner.with_options(options),
+                    client: self.client,
+                }
+            }
+        }
+
+        impl<C: Config> std::fmt::Debug for $group<'_, C> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                std::fmt::Debug::fmt(&self.inner, f)
+            }
+        }
+    };
+}
+
+blocking_group!(Chat);
+blocking_group!(Completions);
+blocking_group!(Embeddings);
+blocking_group!(Images);
+blocking_group!(Audio);
+blocking_group!(Files);
+
+impl<C: Config> Chat<'_, C> {
+    /// Creates a model response for the given chat conversation, see [crate::Chat::create].
+    pub fn create(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<CreateChatCompletionResponse, OpenAIError> {
+        self.client.block_on(self.inner.create(request))
+    }
+
+    /// Creates a streamed chat completion, its chunks iterated as they are received.
+    pub fn create_stream(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<StreamIter<Result<CreateChatCompletionStreamResponse, OpenAIError>>, OpenAIError>
+    {
+        let stream = self.client.block_on(self.inner.create_stream(request))?;
+        Ok(self.client.iter(stream))
+    }
+}
+
+impl<C: Config> Completions<'_, C> {
+    /// Creates a completion for the provided prompt and parameters.
+    pub fn create(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> Result<CreateCompletionResponse, OpenAIError> {
+        self.client.block_on(self.inner.create(request))
+    }
+
+    /// Creates a streamed completion, its chunks iterated as they are received.
+    pub fn create_stream(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> Result<StreamIter<Result<CreateCompletionResponse, OpenAIError>>, OpenAIError> {
+        let stream = self.client.block_on(self.inner.create_stream(request))?;
+        Ok(self.client.iter(stream))
+    }
+}
+
+impl<C: Config> Embeddings<'_, C> {
+    /// Creates an embedding vector representing the input text.
+    pub fn create(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        self.client.block_on(self.inner.create(request))
+    }
+}
+
+impl<C: Config> Images<'_, C> {
+    /// Creates an image given a prompt.
+    pub fn create(&self, request: CreateImageRequest) -> Result<ImagesResponse, OpenAIError> {
+        self.client.block_on(self.inner.create(request))
+    }
+
+    /// Creates an edited or extended image given an original image and a prompt.
+    pub fn create_edit(
+        &self,
+        request: CreateImageEditRequest,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        self.client.block_on(self.inner.create_edit(request))
+    }
+
+    /// Creates a variation of a given image.
+    pub fn create_variation(
+        &self,
+        request: CreateImageVariationRequest,
+    ) -> Result<ImagesResponse, OpenAIError> {
+        self.client.block_on(self.inner.create_variation(request))
+    }
+}
+
+impl<C: Config> Audio<'_, C> {
+    /// Transcribes audio into the input language.
+    pub fn transcribe(
+        &self,
+        request: CreateTranscriptionRequest,
+    ) -> Result<CreateTranscriptionResponseJson, OpenAIError> {
+        self.client.block_on(self.inner.transcribe(request))
+    }
+
+    /// Translates audio into English.
+    pub fn translate(
+        &self,
+        request: CreateTranslationRequest,
+    ) -> Result<CreateTranslationResponseJson, OpenAIError> {
+        self.client.block_on(self.inner.translate(request))
+    }
+
+    /// Generates audio from the input text.
+    pub fn speech(
+        &self,
+        request: CreateSpeechRequest,
+    ) -> Result<CreateSpeechResponse, OpenAIError> {
+        self.client.block_on(self.inner.speech(request))
+    }
+}
+
+impl<C: Config> Files<'_, C> {
+    /// Upload a file that can be used across various endpoints.
+    pub fn create(&self, request: CreateFileRequest) -> Result<OpenAIFile, OpenAIError> {
+        self.client.block_on(self.inner.create(request))
+    }
+
+    /// Returns a list of files that belong to the user's organization.
+    ///
+    /// `query` is usually a [ListFilesQuery](crate::types::ListFilesQuery).
+    pub fn list<Q>(&self, query: &Q) -> Result<ListFilesResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client.block_on(self.inner.list(query))
+    }
+
+    /// Returns information about a specific file.
+    pub fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
+        self.client.block_on(self.inner.retrieve(file_id))
+    }
+
+    /// Delete a file.
+    pub fn delete(&self, file_id: &str) -> Result<DeleteFileResponse, OpenAIError> {
+        self.client.block_on(self.inner.delete(file_id))
+    }
+
+    /// Returns the contents of the specified file.
+    pub fn content(&self, file_id: &str) -> Result<Bytes, OpenAIError> {
+        self.client.block_on(self.inner.content(file_id))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stream_iter() {
+        let client = Client::new();
+        let stream = Box::pin(futures::stream::iter([1, 2, 3]));
+        assert_eq!(client.iter(stream).collect::<Vec<_>>(), [1, 2, 3]);
+        assert!(format!("{:?}", client.chat()).contains("/chat/completions"));
+    }
+}
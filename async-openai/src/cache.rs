@@ -0,0 +1,258 @@
+//! Caching of the responses of deterministic requests, embeddings and chat completions with
+//! `temperature: 0`, to not pay twice for the same response.
+//!
+//! A [ResponseCache] set with [crate::Client::with_response_cache] is looked up before sending
+//! such requests, by a key hashing the url, the query, the headers of the request options and the
+//! body of the request, and stores the body of their successful responses. [LruCache] keeps them
+//! in memory, other backends such as Redis or a disk implement [ResponseCache].
+//!
+//! ```
+//! use async_openai::{cache::LruCache, Client};
+//!
+//! let client = Client::new().with_response_cache(LruCache::new(10_000));
+//! ```
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    sync::{Arc, Mutex},
+};
+
+use bytes::Bytes;
+use futures::future::BoxFuture;
+use md5::{Digest, Md5};
+use reqwest::header::HeaderMap;
+use serde_json::Value;
+
+/// Storage of response bodies by request key.
+pub trait ResponseCache: Send + Sync {
+    /// Body of the response cached for `key`, if any.
+    fn get(&self, key: String) -> BoxFuture<'_, Option<Bytes>>;
+
+    /// Cache `response` for `key`.
+    fn put(&self, key: String, response: Bytes) -> BoxFuture<'_, ()>;
+}
+
+impl<T: ResponseCache + ?Sized> ResponseCache for Arc<T> {
+    fn get(&self, key: String) -> BoxFuture<'_, Option<Bytes>> {
+        self.as_ref().get(key)
+    }
+
+    fn put(&self, key: String, response: Bytes) -> BoxFuture<'_, ()> {
+        self.as_ref().put(key, response)
+    }
+}
+
+/// In-memory [ResponseCache] of the `capacity` most recently used responses.
+pub struct LruCache {
+    capacity: usize,
+    state: Mutex<Lru>,
+}
+
+#[derive(Default)]
+struct Lru {
+    /// Response and last use of each key.
+    entries: HashMap<String, (Bytes, u64)>,
+    /// Keys by last use.
+    uses: BTreeMap<u64, String>,
+    clock: u64,
+}
+
+impl Lru {
+    fn touch(&mut self, key: &str) -> Option<Bytes> {
+        self.clock += 1;
+        let (response, used) = self.entries.get_mut(key)?;
+        self.uses.remove(used);
+        *used = self.clock;
+        self.uses.insert(self.clock, key.to_string());
+        Some(response.clone())
+    }
+}
+
+impl LruCache {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            state: Default::default(),
+        }
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn clear(&self) {
+        *self.lock() = Lru::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Lru> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl ResponseCache for LruCache {
+    fn get(&self, key: String) -> BoxFuture<'_, Option<Bytes>> {
+        let response = self.lock().touch(&key);
+        Box::pin(async move { response })
+    }
+
+    fn put(&self, key: String, response: Bytes) -> BoxFuture<'_, ()> {
+        if self.capacity > 0 {
+            let mut state = self.lock();
+            if state.touch(&key).is_none() {
+                let clock = state.clock;
+                state.uses.insert(clock, key.clone());
+            }
+            let clock = state.clock;
+            state.entries.insert(key, (response, clock));
+            while state.entries.len() > self.capacity {
+                let Some((_, oldest)) = state.uses.pop_first() else {
+                    break;
+                };
+                state.entries.remove(&oldest);
+            }
+        }
+        Box::pin(async {})
+    }
+}
+
+impl fmt::Debug for LruCache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LruCache")
+            .field("capacity", &self.capacity)
+            .field("len", &self.len())
+            .finish()
+    }
+}
+
+/// Response cache of a [crate::Client].
+#[derive(Clone)]
+pub(crate) struct Cache(Arc<dyn ResponseCache>);
+
+impl Cache {
+    pub(crate) fn new(cache: Arc<dyn ResponseCache>) -> Self {
+        Self(cache)
+    }
+
+    pub(crate) async fn get(&self, key: &str) -> Option<Bytes> {
+        self.0.get(key.to_string()).await
+    }
+
+    pub(crate) async fn put(&self, key: String, response: Bytes) {
+        self.0.put(key, response).await
+    }
+}
+
+impl fmt::Debug for Cache {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Cache").finish_non_exhaustive()
+    }
+}
+
+/// Key of a request to `url` with the `query`, the `headers` of its request options and the
+/// JSON `body`, stable across processes and versions.
+pub(crate) fn key<'q>(
+    url: &str,
+    query: impl IntoIterator<Item = (&'q str, &'q str)>,
+    headers: &HeaderMap,
+    body: &[u8],
+) -> String {
+    let mut md5 = Md5::new();
+    let mut query = query.into_iter().peekable();
+    match (query.peek().is_some(), reqwest::Url::parse(url)) {
+        (true, Ok(mut url)) => {
+            url.query_pairs_mut().extend_pairs(query);
+            md5.update(url.as_str().as_bytes());
+        }
+        _ => md5.update(url.as_bytes()),
+    }
+    let mut headers: Vec<_> = headers.iter().collect();
+    headers.sort_by_key(|(name, _)| name.as_str());
+    for (name, value) in headers {
+        md5.update(b"\n");
+        md5.update(name.as_str().as_bytes());
+        md5.update(b": ");
+        md5.update(value.as_bytes());
+    }
+    md5.update(b"\n");
+    md5.update(body);
+    format!("{:x}", md5.finalize())
+}
+
+/// Whether a request always gets the same response, so it can be cached.
+pub(crate) type Deterministic = fn(&Value) -> bool;
+
+/// Embeddings requests, whose responses depend on their input only.
+pub(crate) fn always(_: &Value) -> bool {
+    true
+}
+
+/// Chat completion requests with `temperature: 0`, which are not streamed.
+pub(crate) fn zero_temperature(request: &Value) -> bool {
+    request.get("temperature").and_then(Value::as_f64) == Some(0.0)
+        && request.get("stream").and_then(Value::as_bool) != Some(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{transport::mock::MockTransport, types::CreateEmbeddingRequestArgs};
+
+    #[tokio::test]
+    async fn test_cached_embeddings() {
+        let transport = MockTransport::body(
+            r#"{"object": "list", "model": "text-embedding-3-small",
+                "data": [{"object": "embedding", "index": 0, "embedding": [0.5]}],
+                "usage": {"prompt_tokens": 1, "total_tokens": 1}}"#,
+        );
+        let client = crate::Client::new()
+            .with_transport(transport.clone())
+            .with_response_cache(LruCache::new(10));
+        let request = |input: &str| {
+            CreateEmbeddingRequestArgs::default()
+                .model("text-embedding-3-small")
+                .input(input)
+                .build()
+                .unwrap()
+        };
+        for input in ["a", "a", "b"] {
+            let response = client.embeddings().create(request(input)).await.unwrap();
+            assert_eq!(response.data[0].embedding, [0.5]);
+        }
+        assert_eq!(transport.len(), 2);
+
+        // the query and headers of request options are part of the key
+        let embeddings = client.embeddings().query("api-version", "2024-10-21");
+        embeddings.create(request("a")).await.unwrap();
+        embeddings.create(request("a")).await.unwrap();
+        assert_eq!(transport.len(), 3);
+        let embeddings = client
+            .embeddings()
+            .header("OpenAI-Project", "proj_abc")
+            .unwrap();
+        embeddings.create(request("a")).await.unwrap();
+        assert_eq!(transport.len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_lru_cache() {
+        let cache = LruCache::new(2);
+        cache.put("a".into(), "1".into()).await;
+        cache.put("b".into(), "2".into()).await;
+        assert_eq!(cache.get("a".into()).await, Some("1".into()));
+        // "b" is the least recently used
+        cache.put("c".into(), "3".into()).await;
+        assert_eq!(cache.get("b".into()).await, None);
+        assert_eq!(cache.len(), 2);
+
+        cache.put("a".into(), "4".into()).await;
+        assert_eq!(cache.get("a".into()).await, Some("4".into()));
+        assert_eq!(cache.get("c".into()).await, Some("3".into()));
+    }
+}
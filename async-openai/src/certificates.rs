@@ -0,0 +1,149 @@
+use futures::Stream;
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::list_stream,
+    request_options::{impl_request_options, RequestOptions},
+    types::{
+        Certificate, CertificateListResponse, DeleteCertificateResponse, ModifyCertificateRequest,
+        ToggleCertificatesRequest, UploadCertificateRequest,
+    },
+    Client,
+};
+
+/// Manage the certificates of an organization, used for mutual TLS with the API. Uploaded
+/// certificates are activated for the organization or for some of its projects, see
+/// [ProjectCertificates](crate::ProjectCertificates).
+pub struct Certificates<'c, C: Config> {
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(Certificates);
+crate::util::impl_api_group_debug!(Certificates, "/organization/certificates");
+
+impl<'c, C: Config> Certificates<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// List the certificates uploaded to the organization.
+    ///
+    /// `query` is usually a [ListCertificatesQuery](crate::types::ListCertificatesQuery).
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<CertificateListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/organization/certificates", &query, &self.request_options)
+            .await
+    }
+
+    /// Streams all certificates of the organization, fetching pages as needed.
+    ///
+    /// `query` takes the same parameters as [Self::list], `after` is used as the starting cursor.
+    pub fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> impl Stream<Item = Result<Certificate, OpenAIError>> + 'c
+    where
+        Q: Serialize + ?Sized,
+    {
+        list_stream::<_, _, CertificateListResponse>(
+            self.client,
+            "/organization/certificates".into(),
+            query,
+            self.request_options.clone(),
+        )
+    }
+
+    /// Upload a certificate to the organization. It is not active until activated for the
+    /// organization or a project.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn upload(
+        &self,
+        request: UploadCertificateRequest,
+    ) -> Result<Certificate, OpenAIError> {
+        self.client
+            .post("/organization/certificates", request, &self.request_options)
+            .await
+    }
+
+    /// Get a certificate uploaded to the organization.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve(&self, certificate_id: &str) -> Result<Certificate, OpenAIError> {
+        self.client
+            .get(
+                format!("/organization/certificates/{certificate_id}").as_str(),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Modify a certificate. Only its name can be changed.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn modify(
+        &self,
+        certificate_id: &str,
+        request: ModifyCertificateRequest,
+    ) -> Result<Certificate, OpenAIError> {
+        self.client
+            .post(
+                format!("/organization/certificates/{certificate_id}").as_str(),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Delete a certificate from the organization. It must be inactive for the organization
+    /// and all its projects.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(
+        &self,
+        certificate_id: &str,
+    ) -> Result<DeleteCertificateResponse, OpenAIError> {
+        self.client
+            .delete(
+                format!("/organization/certificates/{certificate_id}").as_str(),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Activate certificates for the organization, returning the certificates activated.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn activate(
+        &self,
+        request: ToggleCertificatesRequest,
+    ) -> Result<CertificateListResponse, OpenAIError> {
+        self.client
+            .post(
+                "/organization/certificates/activate",
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Deactivate certificates for the organization, returning the certificates deactivated.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn deactivate(
+        &self,
+        request: ToggleCertificatesRequest,
+    ) -> Result<CertificateListResponse, OpenAIError> {
+        self.client
+            .post(
+                "/organization/certificates/deactivate",
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+}
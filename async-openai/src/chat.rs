@@ -1,8 +1,19 @@
+use std::sync::Arc;
+
+#[cfg(not(feature = "byot"))]
+use futures::StreamExt;
+
+#[cfg(not(feature = "byot"))]
+use crate::{postprocess::PostprocessContext, types::ResponseFormat};
+
 use crate::{
     config::Config,
     error::OpenAIError,
+    postprocess::ResponsePostprocessor,
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse,
+        ChatCompletionResponseStream, ContextChatResponse, ContextDocument, ContextStrategy,
+        CreateChatCompletionRequest, CreateChatCompletionResponse,
     },
     Client,
 };
@@ -12,11 +23,33 @@ use crate::{
 /// Related guide: [Chat completions](https://platform.openai.com//docs/guides/text-generation)
 pub struct Chat<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
+    postprocessors: Vec<Arc<dyn ResponsePostprocessor>>,
 }
 
+impl_request_options!(Chat);
+crate::util::impl_api_group_debug!(Chat, "/chat/completions");
+
 impl<'c, C: Config> Chat<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+            postprocessors: Vec::new(),
+        }
+    }
+
+    /// Transform the content of each choice returned by [Chat::create], after the
+    /// postprocessors added before. Streamed deltas are not processed.
+    ///
+    /// With the `byot` feature, responses are returned as received: the postprocessors are
+    /// not applied, by [Chat::create] or by `create_byot`.
+    pub fn with_postprocessor<P: ResponsePostprocessor + 'static>(
+        mut self,
+        postprocessor: P,
+    ) -> Self {
+        self.postprocessors.push(Arc::new(postprocessor));
+        self
     }
 
     /// Creates a model response for the given chat conversation. Learn more in
@@ -35,24 +68,100 @@ impl<'c, C: Config> Chat<'c, C> {
     ///
     /// [refer to the reasoning guide](https://platform.openai.com/docs/guides/reasoning).
     ///
+    /// Requests using the deprecated `functions` / `function_call` fields are sent as `tools` / `tool_choice`,
+    /// and the first tool call of each choice is returned in `function_call`.
+    ///
     /// byot: You must ensure "stream: false" in serialized `request`
     #[crate::byot(
         T0 = serde::Serialize,
         R = serde::de::DeserializeOwned
     )]
+    #[allow(unused_mut)]
     pub async fn create(
         &self,
-        request: CreateChatCompletionRequest,
+        mut request: CreateChatCompletionRequest,
     ) -> Result<CreateChatCompletionResponse, OpenAIError> {
         #[cfg(not(feature = "byot"))]
-        {
+        let legacy_functions = {
             if request.stream.is_some() && request.stream.unwrap() {
                 return Err(OpenAIError::InvalidArgument(
                     "When stream is true, use Chat::create_stream".into(),
                 ));
             }
+            if request.stream_options.is_some() {
+                return Err(OpenAIError::InvalidArgument(
+                    "stream_options is only allowed with Chat::create_stream".into(),
+                ));
+            }
+
+            let legacy_functions = request.expects_legacy_function_call();
+            if request.uses_legacy_functions() {
+                request.upgrade_legacy_functions();
+            }
+            legacy_functions
+        };
+        #[cfg(not(feature = "byot"))]
+        let json = matches!(
+            request.response_format,
+            Some(ResponseFormat::JsonObject | ResponseFormat::JsonSchema { .. })
+        );
+
+        let mut response = self
+            .client
+            .post_cached(
+                "/chat/completions",
+                request,
+                &self.request_options,
+                crate::cache::zero_temperature,
+                false,
+            )
+            .await?;
+
+        #[cfg(not(feature = "byot"))]
+        {
+            if legacy_functions {
+                CreateChatCompletionResponse::downgrade_to_legacy_function_call(&mut response);
+            }
+            self.postprocess(&mut response, json);
+        }
+
+        Ok(response)
+    }
+
+    #[cfg(not(feature = "byot"))]
+    fn postprocess(&self, response: &mut CreateChatCompletionResponse, json: bool) {
+        if self.postprocessors.is_empty() {
+            return;
         }
-        self.client.post("/chat/completions", request).await
+        let context = PostprocessContext {
+            json,
+            model: response.model.clone(),
+        };
+        for choice in &mut response.choices {
+            if let Some(content) = choice.message.content.take() {
+                choice.message.content = Some(
+                    self.postprocessors
+                        .iter()
+                        .fold(content, |content, p| p.process(content, &context)),
+                );
+            }
+        }
+    }
+
+    /// Creates a model response after injecting retrieved `documents` in the messages of `request`
+    /// as configured by `strategy`, see [ContextStrategy::apply].
+    pub async fn create_with_context(
+        &self,
+        mut request: CreateChatCompletionRequest,
+        documents: &[ContextDocument],
+        strategy: &ContextStrategy,
+    ) -> Result<ContextChatResponse, OpenAIError> {
+        let documents = strategy.apply(&mut request, documents)?;
+        let response = self.create(request).await?;
+        Ok(ContextChatResponse {
+            response,
+            documents,
+        })
     }
 
     /// Creates a completion for the chat message
@@ -61,6 +170,8 @@ impl<'c, C: Config> Chat<'c, C> {
     ///
     /// [ChatCompletionResponseStream] is a parsed SSE stream until a \[DONE\] is received from server.
     ///
+    /// Requests using the deprecated `functions` / `function_call` fields are handled as in [Chat::create].
+    ///
     /// byot: You must ensure "stream: true" in serialized `request`
     #[crate::byot(
         T0 = serde::Serialize,
@@ -83,6 +194,35 @@ impl<'c, C: Config> Chat<'c, C> {
 
             request.stream = Some(true);
         }
-        Ok(self.client.post_stream("/chat/completions", request).await)
+
+        #[cfg(not(feature = "byot"))]
+        let legacy_functions = {
+            let legacy_functions = request.expects_legacy_function_call();
+            if request.uses_legacy_functions() {
+                request.upgrade_legacy_functions();
+            }
+            legacy_functions
+        };
+
+        let stream = self
+            .client
+            .post_stream("/chat/completions", request, &self.request_options)
+            .await;
+
+        #[cfg(not(feature = "byot"))]
+        {
+            if legacy_functions {
+                return Ok(Box::pin(stream.map(|chunk| {
+                    chunk.map(|mut chunk| {
+                        crate::types::CreateChatCompletionStreamResponse::downgrade_to_legacy_function_call(
+                            &mut chunk,
+                        );
+                        chunk
+                    })
+                })));
+            }
+        }
+
+        Ok(stream)
     }
 }
@@ -1,20 +1,53 @@
-use std::pin::Pin;
+use std::{
+    collections::HashMap,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
 
 use bytes::Bytes;
 use futures::{stream::StreamExt, Stream};
-use reqwest::multipart::Form;
+use reqwest::{
+    header::{HeaderMap, HeaderValue},
+    multipart::Form,
+};
 use reqwest_eventsource::{Event, EventSource, RequestBuilderExt};
 use serde::{de::DeserializeOwned, Serialize};
+use tracing::Instrument;
 
+#[cfg(feature = "replay")]
+use crate::replay::Replay;
 use crate::{
+    cache::{self, Cache, Deterministic, ResponseCache},
     config::{Config, OpenAIConfig},
-    error::{map_deserialization_error, ApiError, OpenAIError, WrappedError},
+    connection::ConnectionOptions,
+    credentials::{CredentialProvider, Credentials, ReauthHook},
+    error::{map_status_error, ApiError, OpenAIError, UploadError},
+    failover::{EndpointStatus, Failover, Fallback, ServedBy, DEFAULT_COOLDOWN},
     file::Files,
+    hedge::{self, HedgePolicy},
     image::Images,
+    inspect::{RequestInspector, SentRequest},
     moderation::Moderations,
+    observability::Observation,
+    parse::{self, ParseMode},
+    propagation::{Propagation, TracePropagator},
+    quota::{CallQuota, TenantQuotas},
+    rate_limit::{estimate_tokens, RateLimiter, RatePermit},
+    reconnect::{ReconnectPolicy, StreamState},
+    request_options::{CancellationToken, RequestOptions, IDEMPOTENCY_KEY},
+    shutdown::{InFlight, Lifecycle},
     traits::AsyncTryFrom,
-    Assistants, Audio, AuditLogs, Batches, Chat, Completions, Embeddings, FineTuning, Invites,
-    Models, Projects, Responses, Threads, Tokenize, Uploads, Users, VectorStores,
+    transport::{HttpClient, Transport},
+    types::ModelCapabilities,
+    usage::{UsageRecorder, UsageStats},
+    util::UploadedFiles,
+    Assistants, Audio, AuditLogs, Batches, Certificates, Chat, Completions, Conversations, Costs,
+    Embeddings, FineTuning, Invites, Models, Projects, Raw, Responses, Threads, Tokenize, Uploads,
+    Users, VectorStores,
 };
 
 #[derive(Debug, Clone, Default)]
@@ -24,6 +57,25 @@ pub struct Client<C: Config> {
     http_client: reqwest::Client,
     config: C,
     backoff: backoff::ExponentialBackoff,
+    model_capabilities: Arc<Mutex<HashMap<(String, String), ModelCapabilities>>>,
+    credentials: Option<Credentials>,
+    reauth_hook: Option<ReauthHook>,
+    reconnect: Option<ReconnectPolicy>,
+    propagation: Option<Propagation>,
+    rate_limiter: Option<RateLimiter>,
+    failover: Option<Arc<Failover>>,
+    fallbacks: Vec<Fallback>,
+    cache: Option<Cache>,
+    hedging: Option<HedgePolicy>,
+    transport: Option<Transport>,
+    #[cfg(feature = "replay")]
+    replay: Option<Replay>,
+    lifecycle: Lifecycle,
+    usage: Option<UsageRecorder>,
+    inspector: Option<RequestInspector>,
+    idempotency_keys: bool,
+    parse_mode: ParseMode,
+    tenant_quotas: Option<TenantQuotas>,
 }
 
 impl Client<OpenAIConfig> {
@@ -42,8 +94,27 @@ impl<C: Config> Client<C> {
     ) -> Self {
         Self {
             http_client,
+            failover: Failover::from_config(&config, DEFAULT_COOLDOWN).map(Arc::new),
             config,
             backoff,
+            model_capabilities: Default::default(),
+            credentials: None,
+            reauth_hook: None,
+            reconnect: None,
+            propagation: None,
+            rate_limiter: None,
+            fallbacks: Vec::new(),
+            cache: None,
+            hedging: None,
+            transport: None,
+            #[cfg(feature = "replay")]
+            replay: None,
+            lifecycle: Default::default(),
+            usage: None,
+            inspector: None,
+            idempotency_keys: false,
+            parse_mode: Default::default(),
+            tenant_quotas: None,
         }
     }
 
@@ -51,8 +122,27 @@ impl<C: Config> Client<C> {
     pub fn with_config(config: C) -> Self {
         Self {
             http_client: reqwest::Client::new(),
+            failover: Failover::from_config(&config, DEFAULT_COOLDOWN).map(Arc::new),
             config,
             backoff: Default::default(),
+            model_capabilities: Default::default(),
+            credentials: None,
+            reauth_hook: None,
+            reconnect: None,
+            propagation: None,
+            rate_limiter: None,
+            fallbacks: Vec::new(),
+            cache: None,
+            hedging: None,
+            transport: None,
+            #[cfg(feature = "replay")]
+            replay: None,
+            lifecycle: Default::default(),
+            usage: None,
+            inspector: None,
+            idempotency_keys: false,
+            parse_mode: Default::default(),
+            tenant_quotas: None,
         }
     }
 
@@ -64,12 +154,185 @@ impl<C: Config> Client<C> {
         self
     }
 
+    /// Send requests, except streaming ones, with `transport` instead of the HTTP client.
+    pub fn with_transport<T: HttpClient + 'static>(mut self, transport: T) -> Self {
+        self.transport = Some(Transport::new(Arc::new(transport)));
+        self
+    }
+
+    /// Record the exchanges of the client to fixture files or serve its responses from them,
+    /// streams included, see [crate::replay].
+    #[cfg(feature = "replay")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "replay")))]
+    pub fn with_replay(mut self, replay: Replay) -> Self {
+        self.replay = Some(replay);
+        self
+    }
+
+    /// Stop accepting requests, which fail with [OpenAIError::ShutDown], and wait for the requests
+    /// and streams in flight to finish. Those still running after `timeout` are cancelled:
+    /// requests fail with [OpenAIError::ShutDown], streams end, and [OpenAIError::Timeout] is
+    /// returned.
+    ///
+    /// Shuts down the clones of this client too.
+    pub async fn shutdown(&self, timeout: Duration) -> Result<(), OpenAIError> {
+        match self.lifecycle.shutdown(timeout).await {
+            0 => Ok(()),
+            cancelled => Err(OpenAIError::Timeout(format!(
+                "{cancelled} requests in flight cancelled after {timeout:?}"
+            ))),
+        }
+    }
+
+    /// Replace the HTTP client with one built from [ConnectionOptions].
+    pub fn with_connection_options(
+        mut self,
+        options: &ConnectionOptions,
+    ) -> Result<Self, OpenAIError> {
+        self.http_client = options.build_http_client()?;
+        Ok(self)
+    }
+
     /// Exponential backoff for retrying [rate limited](https://platform.openai.com/docs/guides/rate-limits) requests.
     pub fn with_backoff(mut self, backoff: backoff::ExponentialBackoff) -> Self {
         self.backoff = backoff;
         self
     }
 
+    /// Fetch credentials from `provider` instead of using the API key of the config.
+    ///
+    /// A request rejected with `401 Unauthorized` is retried once after [CredentialProvider::refresh].
+    /// Streaming requests use the current credentials but are not retried.
+    pub fn with_credential_provider<P: CredentialProvider + 'static>(
+        mut self,
+        provider: P,
+    ) -> Self {
+        let mut credentials = Credentials::new(Arc::new(provider));
+        if let Some(hook) = &self.reauth_hook {
+            credentials.set_reauth_hook(hook.clone());
+        }
+        self.credentials = Some(credentials);
+        self
+    }
+
+    /// Called with the `401 Unauthorized` error before credentials are refreshed,
+    /// has no effect without [Client::with_credential_provider], before or after it.
+    pub fn with_reauth_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&ApiError) + Send + Sync + 'static,
+    {
+        let hook = ReauthHook::new(hook);
+        if let Some(credentials) = &mut self.credentials {
+            credentials.set_reauth_hook(hook.clone());
+        }
+        self.reauth_hook = Some(hook);
+        self
+    }
+
+    /// Reconnect streaming calls whose connection dropped, streams fail on the first error otherwise.
+    pub fn with_stream_reconnect(mut self, policy: ReconnectPolicy) -> Self {
+        self.reconnect = Some(policy);
+        self
+    }
+
+    /// Add the headers of the current trace context to every request, such as the W3C
+    /// `traceparent` and `tracestate` headers of [crate::propagation::W3CTraceContext].
+    pub fn with_trace_propagator<P: TracePropagator + 'static>(mut self, propagator: P) -> Self {
+        self.propagation = Some(Propagation::new(Arc::new(propagator)));
+        self
+    }
+
+    /// Wait for the budgets of `limiter` before sending requests.
+    pub fn with_rate_limiter(mut self, limiter: RateLimiter) -> Self {
+        self.rate_limiter = Some(limiter);
+        self
+    }
+
+    /// How long a base url which failed to connect or responded with a server error is skipped,
+    /// 30 seconds by default. See [crate::config::OpenAIConfig::with_fallback_api_base].
+    pub fn with_failover_cooldown(mut self, cooldown: Duration) -> Self {
+        self.failover = Failover::from_config(&self.config, cooldown).map(Arc::new);
+        self
+    }
+
+    /// Send the requests failing to connect, rate limited or getting a server error to the
+    /// `fallbacks` in order, with their config and their model names, e.g. to another provider
+    /// serving the same models.
+    ///
+    /// Every request tries the config of the client first. Streaming requests and requests
+    /// with a streaming body, such as uploads, are only sent with the config of the client.
+    pub fn with_fallbacks(mut self, fallbacks: Vec<Fallback>) -> Self {
+        self.fallbacks = fallbacks;
+        self
+    }
+
+    /// Answer embeddings requests and chat completion requests with `temperature: 0` from
+    /// `cache`, storing their responses in it, see [crate::cache].
+    pub fn with_response_cache<R: ResponseCache + 'static>(mut self, cache: R) -> Self {
+        self.cache = Some(Cache::new(Arc::new(cache)));
+        self
+    }
+
+    /// Send a duplicate of embeddings and moderations requests slower than the delay of
+    /// `policy`, taking the first response, see [crate::hedge].
+    pub fn with_hedging(mut self, policy: HedgePolicy) -> Self {
+        self.hedging = Some(policy);
+        self
+    }
+
+    /// Accumulate the token usage of the responses to `recorder`, see [Client::usage_stats].
+    pub fn with_usage_recorder(mut self, recorder: UsageRecorder) -> Self {
+        self.usage = Some(recorder);
+        self
+    }
+
+    /// Call `inspector` with every request about to be sent, to see the headers, query and body
+    /// the API receives and where they came from, see [crate::inspect].
+    pub fn with_request_inspector<F>(mut self, inspector: F) -> Self
+    where
+        F: Fn(&SentRequest) + Send + Sync + 'static,
+    {
+        self.inspector = Some(RequestInspector::new(inspector));
+        self
+    }
+
+    /// Send a random `Idempotency-Key` header with every POST request without one, e.g.
+    /// uploads, batch or fine-tuning job creation, the same key for all the retries of a call.
+    ///
+    /// Requests with an idempotency key, generated or set with
+    /// [RequestOptions::idempotency_key], are retried after network errors like they are after
+    /// server errors. This is only safe against servers and gateways which honour
+    /// `Idempotency-Key`: others create an object for every attempt that reached them.
+    /// Streaming requests are not retried.
+    pub fn with_idempotency_keys(mut self) -> Self {
+        self.idempotency_keys = true;
+        self
+    }
+
+    /// Count the calls made for a tenant, set with [RequestOptions::tenant], against `quotas`,
+    /// failing with [OpenAIError::QuotaExceeded] before sending them once the tenant is over
+    /// its limits.
+    ///
+    /// A call counts the tokens estimated from its request until its response reports its
+    /// `usage.total_tokens`. Calls that fail, streams and responses without usage keep their
+    /// estimate counted. Retries of a call count as one request.
+    pub fn with_tenant_quotas(mut self, quotas: TenantQuotas) -> Self {
+        self.tenant_quotas = Some(quotas);
+        self
+    }
+
+    /// Deserialize responses in `mode`, e.g. [ParseMode::Strict] to reject the fields unknown
+    /// to the types of this crate in CI.
+    pub fn with_parse_mode(mut self, mode: ParseMode) -> Self {
+        self.parse_mode = mode;
+        self
+    }
+
+    /// Token usage per model recorded so far, if enabled with [Client::with_usage_recorder].
+    pub fn usage_stats(&self) -> Option<UsageStats> {
+        self.usage.as_ref().map(UsageRecorder::snapshot)
+    }
+
     // API groups
 
     /// To call [Models] group related APIs using this client.
@@ -92,6 +355,11 @@ impl<C: Config> Client<C> {
         Tokenize::new(self)
     }
 
+    /// To call any endpoint using this client, see [Raw].
+    pub fn raw(&self) -> Raw<'_, C> {
+        Raw::new(self)
+    }
+
     /// To call [Images] group related APIs using this client.
     pub fn images(&self) -> Images<C> {
         Images::new(self)
@@ -102,6 +370,17 @@ impl<C: Config> Client<C> {
         Moderations::new(self)
     }
 
+    /// To call [Realtime] group related APIs using this client.
+    #[cfg(feature = "realtime")]
+    pub fn realtime(&self) -> crate::Realtime<'_, C> {
+        crate::Realtime::new(self)
+    }
+
+    /// To call [Conversations] group related APIs using this client.
+    pub fn conversations(&self) -> Conversations<'_, C> {
+        Conversations::new(self)
+    }
+
     /// To call [Files] group related APIs using this client.
     pub fn files(&self) -> Files<C> {
         Files::new(self)
@@ -152,6 +431,11 @@ impl<C: Config> Client<C> {
         AuditLogs::new(self)
     }
 
+    /// To call [Costs] group related APIs using this client.
+    pub fn costs(&self) -> Costs<'_, C> {
+        Costs::new(self)
+    }
+
     /// To call [Invites] group related APIs using this client.
     pub fn invites(&self) -> Invites<C> {
         Invites::new(self)
@@ -167,6 +451,11 @@ impl<C: Config> Client<C> {
         Projects::new(self)
     }
 
+    /// To call [Certificates] group related APIs using this client.
+    pub fn certificates(&self) -> Certificates<'_, C> {
+        Certificates::new(self)
+    }
+
     /// To call [Responses] group related APIs using this client.
     pub fn responses(&self) -> Responses<C> {
         Responses::new(self)
@@ -176,8 +465,27 @@ impl<C: Config> Client<C> {
         &self.config
     }
 
+    /// Health of the api base and fallback api bases of the config, empty without fallbacks.
+    pub fn endpoints(&self) -> Vec<EndpointStatus> {
+        self.failover
+            .as_ref()
+            .map(|failover| failover.status())
+            .unwrap_or_default()
+    }
+
+    /// Results of [Models::probe] by api base and model, shared by clones of this client.
+    pub(crate) fn model_capabilities(
+        &self,
+    ) -> &Mutex<HashMap<(String, String), ModelCapabilities>> {
+        &self.model_capabilities
+    }
+
     /// Make a GET request to {path} and deserialize the response body
-    pub(crate) async fn get<O>(&self, path: &str) -> Result<O, OpenAIError>
+    pub(crate) async fn get<O>(
+        &self,
+        path: &str,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
     {
@@ -187,14 +495,21 @@ impl<C: Config> Client<C> {
                 .get(self.config.url(path))
                 .query(&self.config.query())
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .build()?)
         };
 
-        self.execute(request_maker).await
+        self.execute(request_maker, request_options).await
     }
 
     /// Make a GET request to {path} with given Query and deserialize the response body
-    pub(crate) async fn get_with_query<Q, O>(&self, path: &str, query: &Q) -> Result<O, OpenAIError>
+    pub(crate) async fn get_with_query<Q, O>(
+        &self,
+        path: &str,
+        query: &Q,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
         Q: Serialize + ?Sized,
@@ -206,14 +521,20 @@ impl<C: Config> Client<C> {
                 .query(&self.config.query())
                 .query(query)
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .build()?)
         };
 
-        self.execute(request_maker).await
+        self.execute(request_maker, request_options).await
     }
 
     /// Make a DELETE request to {path} and deserialize the response body
-    pub(crate) async fn delete<O>(&self, path: &str) -> Result<O, OpenAIError>
+    pub(crate) async fn delete<O>(
+        &self,
+        path: &str,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
     {
@@ -223,100 +544,381 @@ impl<C: Config> Client<C> {
                 .delete(self.config.url(path))
                 .query(&self.config.query())
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .build()?)
         };
 
-        self.execute(request_maker).await
+        self.execute(request_maker, request_options).await
     }
 
     /// Make a GET request to {path} and return the response body
-    pub(crate) async fn get_raw(&self, path: &str) -> Result<Bytes, OpenAIError> {
+    pub(crate) async fn get_raw(
+        &self,
+        path: &str,
+        request_options: &RequestOptions,
+    ) -> Result<Bytes, OpenAIError> {
+        let request_maker = || async {
+            Ok(self
+                .http_client
+                .get(self.config.url(path))
+                .query(&self.config.query())
+                .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
+                .build()?)
+        };
+
+        self.execute_raw(request_maker, request_options).await
+    }
+
+    /// Download the content at `url`, outside of the API and without its credentials
+    pub(crate) async fn download(&self, url: &str) -> Result<Bytes, OpenAIError> {
+        let request = self.http_client.get(url).build()?;
+        Ok(self
+            .transport(request)
+            .await?
+            .error_for_status()?
+            .bytes()
+            .await?)
+    }
+
+    /// Make a GET request to {path} and return the response without reading its body
+    pub(crate) async fn get_response(
+        &self,
+        path: &str,
+        request_options: &RequestOptions,
+    ) -> Result<reqwest::Response, OpenAIError> {
         let request_maker = || async {
             Ok(self
                 .http_client
                 .get(self.config.url(path))
                 .query(&self.config.query())
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .build()?)
         };
 
-        self.execute_raw(request_maker).await
+        self.execute_response(request_maker, request_options).await
+    }
+
+    /// Make a DELETE request to {path} and return the response without reading its body
+    pub(crate) async fn delete_response(
+        &self,
+        path: &str,
+        request_options: &RequestOptions,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        let request_maker = || async {
+            Ok(self
+                .http_client
+                .delete(self.config.url(path))
+                .query(&self.config.query())
+                .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
+                .build()?)
+        };
+
+        self.execute_response(request_maker, request_options).await
     }
 
     /// Make a POST request to {path} and return the response body
-    pub(crate) async fn post_raw<I>(&self, path: &str, request: I) -> Result<Bytes, OpenAIError>
+    pub(crate) async fn post_raw<I>(
+        &self,
+        path: &str,
+        request: I,
+        request_options: &RequestOptions,
+    ) -> Result<Bytes, OpenAIError>
     where
         I: Serialize,
     {
+        let url = self.request_url(path, &request);
         let request_maker = || async {
             Ok(self
                 .http_client
-                .post(self.config.url(path))
+                .post(&url)
                 .query(&self.config.query())
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .json(&request)
                 .build()?)
         };
 
-        self.execute_raw(request_maker).await
+        self.execute_raw(request_maker, request_options).await
+    }
+
+    /// Make a POST request to {path} and return the response without reading its body
+    pub(crate) async fn post_response<I>(
+        &self,
+        path: &str,
+        request: I,
+        request_options: &RequestOptions,
+    ) -> Result<reqwest::Response, OpenAIError>
+    where
+        I: Serialize,
+    {
+        let url = self.request_url(path, &request);
+        let request_maker = || async {
+            Ok(self
+                .http_client
+                .post(&url)
+                .query(&self.config.query())
+                .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
+                .json(&request)
+                .build()?)
+        };
+
+        self.execute_response(request_maker, request_options).await
     }
 
     /// Make a POST request to {path} and deserialize the response body
-    pub(crate) async fn post<I, O>(&self, path: &str, request: I) -> Result<O, OpenAIError>
+    pub(crate) async fn post<I, O>(
+        &self,
+        path: &str,
+        request: I,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         I: Serialize,
         O: DeserializeOwned,
     {
+        let url = self.request_url(path, &request);
         let request_maker = || async {
             Ok(self
                 .http_client
-                .post(self.config.url(path))
+                .post(&url)
                 .query(&self.config.query())
                 .headers(self.config.headers())
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
                 .json(&request)
                 .build()?)
         };
 
-        self.execute(request_maker).await
+        self.execute(request_maker, request_options).await
+    }
+
+    /// Make a POST request to {path} answered from the response cache, if any, when
+    /// `deterministic` holds for the JSON of `request`.
+    pub(crate) async fn post_cached<I, O>(
+        &self,
+        path: &str,
+        request: I,
+        request_options: &RequestOptions,
+        deterministic: Deterministic,
+        idempotent: bool,
+    ) -> Result<O, OpenAIError>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let post = || async {
+            if idempotent {
+                self.post_hedged(path, &request, request_options).await
+            } else {
+                self.post(path, &request, request_options).await
+            }
+        };
+        let Some(cache) = &self.cache else {
+            return post().await;
+        };
+        let body = match serde_json::to_value(&request) {
+            Ok(body) if deterministic(&body) => body.to_string(),
+            _ => return post().await,
+        };
+        let key = cache::key(
+            &self.request_url(path, &request),
+            self.config.query().into_iter().chain(
+                request_options
+                    .query_pairs()
+                    .iter()
+                    .map(|(key, value)| (key.as_str(), value.as_str())),
+            ),
+            request_options.header_map(),
+            body.as_bytes(),
+        );
+        if let Some(bytes) = cache.get(&key).await {
+            if let Ok(response) = parse::from_slice(bytes.as_ref(), self.parse_mode) {
+                tracing::debug!("{path} answered from the response cache");
+                return Ok(response);
+            }
+        }
+
+        let bytes = if idempotent {
+            self.post_raw_hedged(path, &request, request_options)
+                .await?
+        } else {
+            self.post_raw(path, &request, request_options).await?
+        };
+        let response = parse::from_slice(bytes.as_ref(), self.parse_mode)?;
+        cache.put(key, bytes).await;
+        Ok(response)
+    }
+
+    /// Make a POST request to {path} without side effects, hedged with the policy of the
+    /// client, if any, and deserialize the response body.
+    pub(crate) async fn post_hedged<I, O>(
+        &self,
+        path: &str,
+        request: I,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
+    where
+        I: Serialize,
+        O: DeserializeOwned,
+    {
+        let bytes = self
+            .post_raw_hedged(path, &request, request_options)
+            .await?;
+        parse::from_slice(bytes.as_ref(), self.parse_mode)
+    }
+
+    async fn post_raw_hedged<I>(
+        &self,
+        path: &str,
+        request: &I,
+        request_options: &RequestOptions,
+    ) -> Result<Bytes, OpenAIError>
+    where
+        I: Serialize,
+    {
+        match &self.hedging {
+            Some(policy) => {
+                hedge::hedged(policy, || self.post_raw(path, request, request_options)).await
+            }
+            None => self.post_raw(path, request, request_options).await,
+        }
     }
 
     /// POST a form at {path} and return the response body
-    pub(crate) async fn post_form_raw<F>(&self, path: &str, form: F) -> Result<Bytes, OpenAIError>
+    pub(crate) async fn post_form_raw<F>(
+        &self,
+        path: &str,
+        form: F,
+        request_options: &RequestOptions,
+    ) -> Result<Bytes, OpenAIError>
     where
         Form: AsyncTryFrom<F, Error = OpenAIError>,
         F: Clone,
     {
+        let uploaded_files = UploadedFiles::default();
         let request_maker = || async {
+            let form = uploaded_files
+                .record(<Form as AsyncTryFrom<F>>::try_from(form.clone()))
+                .await?;
             Ok(self
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
                 .headers(self.config.headers())
-                .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
+                .multipart(form)
                 .build()?)
         };
 
-        self.execute_raw(request_maker).await
+        self.execute_raw(request_maker, request_options)
+            .await
+            .map_err(|error| UploadError::map(path, uploaded_files.files(), error))
     }
 
     /// POST a form at {path} and deserialize the response body
-    pub(crate) async fn post_form<O, F>(&self, path: &str, form: F) -> Result<O, OpenAIError>
+    pub(crate) async fn post_form<O, F>(
+        &self,
+        path: &str,
+        form: F,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
         Form: AsyncTryFrom<F, Error = OpenAIError>,
         F: Clone,
     {
+        let uploaded_files = UploadedFiles::default();
         let request_maker = || async {
+            let form = uploaded_files
+                .record(<Form as AsyncTryFrom<F>>::try_from(form.clone()))
+                .await?;
             Ok(self
                 .http_client
                 .post(self.config.url(path))
                 .query(&self.config.query())
                 .headers(self.config.headers())
-                .multipart(<Form as AsyncTryFrom<F>>::try_from(form.clone()).await?)
+                .query(request_options.query_pairs())
+                .headers(request_options.header_map().clone())
+                .multipart(form)
                 .build()?)
         };
 
-        self.execute(request_maker).await
+        self.execute(request_maker, request_options)
+            .await
+            .map_err(|error| UploadError::map(path, uploaded_files.files(), error))
+    }
+
+    /// POST a form at {path} whose body can only be sent once, such as a stream, and deserialize
+    /// the response body. The request is neither retried nor sent again with refreshed credentials.
+    pub(crate) async fn post_form_once<O, F>(
+        &self,
+        path: &str,
+        form: F,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
+    where
+        O: DeserializeOwned,
+        Form: AsyncTryFrom<F, Error = OpenAIError>,
+    {
+        let uploaded_files = UploadedFiles::default();
+        let form = uploaded_files
+            .record(<Form as AsyncTryFrom<F>>::try_from(form))
+            .await?;
+        let request = self
+            .http_client
+            .post(self.config.url(path))
+            .query(&self.config.query())
+            .headers(self.config.headers())
+            .query(request_options.query_pairs())
+            .headers(request_options.header_map().clone())
+            .multipart(form)
+            .build()?;
+        CallQuota::new(self.tenant_quotas.as_ref(), request_options).acquire(&request)?;
+        let request = std::sync::Mutex::new(Some(request));
+        let request_maker = || async {
+            request.lock().unwrap().take().ok_or_else(|| {
+                OpenAIError::InvalidArgument("the request can only be sent once".into())
+            })
+        };
+
+        let in_flight = self.lifecycle.enter()?;
+        let mut observation = self.observation();
+        let result = in_flight
+            .run(async {
+                let response = self.send(&request_maker, &observation, false).await?;
+                let status = response.status();
+                if !status.is_success() {
+                    let headers = response.headers().clone();
+                    let bytes = response.bytes().await?;
+                    return Err(OpenAIError::ApiError(map_status_error(
+                        status,
+                        headers,
+                        bytes.as_ref(),
+                    )));
+                }
+                response.bytes().await.map_err(OpenAIError::Reqwest)
+            })
+            .instrument(observation.span().clone())
+            .await;
+
+        if let Ok(bytes) = &result {
+            observation.response(bytes);
+        }
+        observation.finish(result.as_ref().err());
+        let bytes =
+            result.map_err(|error| UploadError::map(path, uploaded_files.files(), error))?;
+        parse::from_slice(bytes.as_ref(), self.parse_mode)
     }
 
     /// Execute a HTTP request and retry on rate limit
@@ -324,22 +926,121 @@ impl<C: Config> Client<C> {
     /// request_maker serves one purpose: to be able to create request again
     /// to retry API call after getting rate limited. request_maker is async because
     /// reqwest::multipart::Form is created by async calls to read files for uploads.
-    async fn execute_raw<M, Fut>(&self, request_maker: M) -> Result<Bytes, OpenAIError>
+    async fn execute_raw<M, Fut>(
+        &self,
+        request_maker: M,
+        request_options: &RequestOptions,
+    ) -> Result<Bytes, OpenAIError>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let quota = CallQuota::new(self.tenant_quotas.as_ref(), request_options);
+        let request_maker = || async {
+            let request = request_maker().await?;
+            quota.acquire(&request)?;
+            Ok(request)
+        };
+        let in_flight = self.lifecycle.enter()?;
+        let mut observation = self.observation();
+        let result = in_flight
+            .run(async {
+                let response = self.retry(&request_maker, &observation).await?;
+                response.bytes().await.map_err(OpenAIError::Reqwest)
+            })
+            .instrument(observation.span().clone())
+            .await;
+
+        if let Ok(bytes) = &result {
+            observation.response(bytes);
+            quota.record(bytes);
+        }
+        observation.finish(result.as_ref().err());
+        result
+    }
+
+    /// Execute a HTTP request and retry on rate limit, returning the successful response
+    /// before its body is read so it can be streamed.
+    async fn execute_response<M, Fut>(
+        &self,
+        request_maker: M,
+        request_options: &RequestOptions,
+    ) -> Result<reqwest::Response, OpenAIError>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let quota = CallQuota::new(self.tenant_quotas.as_ref(), request_options);
+        let request_maker = || async {
+            let request = request_maker().await?;
+            quota.acquire(&request)?;
+            Ok(request)
+        };
+        let in_flight = self.lifecycle.enter()?;
+        let observation = self.observation();
+        let result = in_flight
+            .run(self.retry(&request_maker, &observation))
+            .instrument(observation.span().clone())
+            .await;
+        observation.finish(result.as_ref().err());
+        result
+    }
+
+    /// Send a HTTP request and retry on rate limit.
+    async fn retry<M, Fut>(
+        &self,
+        request_maker: &M,
+        observation: &Observation,
+    ) -> Result<reqwest::Response, OpenAIError>
     where
         M: Fn() -> Fut,
         Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
     {
-        let client = self.http_client.clone();
+        let idempotency_key = self
+            .idempotency_keys
+            .then(|| HeaderValue::from_str(&uuid_v4()).ok())
+            .flatten();
+        let idempotent = AtomicBool::new(false);
+        let (idempotency_key, idempotent_ref) = (&idempotency_key, &idempotent);
+        let keyed_request = move || async move {
+            let mut request = request_maker().await?;
+            if let Some(key) = idempotency_key {
+                if request.method() == reqwest::Method::POST {
+                    request
+                        .headers_mut()
+                        .entry(IDEMPOTENCY_KEY)
+                        .or_insert_with(|| key.clone());
+                }
+            }
+            idempotent_ref.store(
+                request.headers().contains_key(IDEMPOTENCY_KEY),
+                Ordering::Relaxed,
+            );
+            Ok(request)
+        };
 
         backoff::future::retry(self.backoff.clone(), || async {
-            let request = request_maker().await.map_err(backoff::Error::Permanent)?;
-            let response = client
-                .execute(request)
+            let response = self
+                .send(&keyed_request, observation, true)
                 .await
-                .map_err(OpenAIError::Reqwest)
-                .map_err(backoff::Error::Permanent)?;
+                .map_err(|error| {
+                    if idempotent.load(Ordering::Relaxed) && is_network_error(&error) {
+                        tracing::warn!("Network error of an idempotent request: {error}");
+                        backoff::Error::Transient {
+                            err: error,
+                            retry_after: None,
+                        }
+                    } else {
+                        backoff::Error::Permanent(error)
+                    }
+                })?;
 
             let status = response.status();
+            if status.is_success() {
+                return Ok(response);
+            }
+
+            let headers = response.headers().clone();
             let bytes = response
                 .bytes()
                 .await
@@ -348,64 +1049,353 @@ impl<C: Config> Client<C> {
 
             if status.is_server_error() {
                 // OpenAI does not guarantee server errors are returned as JSON so we cannot deserialize them.
-                let message: String = String::from_utf8_lossy(&bytes).into_owned();
-                tracing::warn!("Server error: {status} - {message}");
+                let error = map_status_error(status, headers, bytes.as_ref());
+                tracing::warn!("Server error: {status} - {}", error.message);
                 return Err(backoff::Error::Transient {
-                    err: OpenAIError::ApiError(ApiError {
-                        message,
-                        r#type: None,
-                        param: None,
-                        code: None,
-                    }),
+                    err: OpenAIError::ApiError(error),
                     retry_after: None,
                 });
             }
 
-            // Deserialize response body from either error object or actual response object
-            if !status.is_success() {
-                let wrapped_error: WrappedError = serde_json::from_slice(bytes.as_ref())
-                    .map_err(|e| map_deserialization_error(e, bytes.as_ref()))
-                    .map_err(backoff::Error::Permanent)?;
-                let error: ApiError = wrapped_error.into();
-
-                if status.as_u16() == 429
-                    // API returns 429 also when:
-                    // "You exceeded your current quota, please check your plan and billing details."
-                    && error.r#type != Some("insufficient_quota".to_string())
-                {
-                    // Rate limited retry...
-                    tracing::warn!("Rate limited: {}", error.message);
-                    return Err(backoff::Error::Transient {
-                        err: OpenAIError::ApiError(error),
-                        retry_after: None,
-                    });
-                } else {
-                    return Err(backoff::Error::Permanent(OpenAIError::ApiError(error)));
-                }
+            // Deserialize response body from error object
+            let error = map_status_error(status, headers, bytes.as_ref());
+
+            if status.as_u16() == 429
+                // API returns 429 also when:
+                // "You exceeded your current quota, please check your plan and billing details."
+                && error.r#type != Some("insufficient_quota".to_string())
+            {
+                // Rate limited retry...
+                tracing::warn!("Rate limited: {}", error.message);
+                Err(backoff::Error::Transient {
+                    err: OpenAIError::ApiError(error),
+                    retry_after: None,
+                })
+            } else {
+                Err(backoff::Error::Permanent(OpenAIError::ApiError(error)))
             }
-
-            Ok(bytes)
         })
         .await
     }
 
+    /// Send a request with the credentials of the provider, if any, and, if it was rejected
+    /// with `401 Unauthorized`, refresh them and send it again when `resend` is set.
+    async fn send<M, Fut>(
+        &self,
+        request_maker: &M,
+        observation: &Observation,
+        resend: bool,
+    ) -> Result<reqwest::Response, OpenAIError>
+    where
+        M: Fn() -> Fut,
+        Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
+    {
+        let Some(credentials) = &self.credentials else {
+            let mut request = request_maker().await?;
+            observation.request(
+                request.url().path(),
+                request.body().and_then(reqwest::Body::as_bytes),
+            );
+            let built = self.inspector.as_ref().map(|_| request.headers().clone());
+            self.propagate(request.headers_mut());
+            self.inspect(&request, built);
+            return self.execute_http(request).await;
+        };
+
+        let mut request = request_maker().await?;
+        observation.request(
+            request.url().path(),
+            request.body().and_then(reqwest::Body::as_bytes),
+        );
+        let built = self.inspector.as_ref().map(|_| request.headers().clone());
+        credentials.apply(request.headers_mut()).await?;
+        self.propagate(request.headers_mut());
+        self.inspect(&request, built);
+        let response = self.execute_http(request).await?;
+        if response.status() != reqwest::StatusCode::UNAUTHORIZED {
+            return Ok(response);
+        }
+
+        let status = response.status();
+        let headers = response.headers().clone();
+        let bytes = response.bytes().await?;
+        let error = map_status_error(status, headers, bytes.as_ref());
+        credentials.reauthenticate(&error).await?;
+        if !resend {
+            return Err(OpenAIError::ApiError(error));
+        }
+
+        let mut request = request_maker().await?;
+        let built = self.inspector.as_ref().map(|_| request.headers().clone());
+        credentials.apply(request.headers_mut()).await?;
+        self.propagate(request.headers_mut());
+        self.inspect(&request, built);
+        self.execute_http(request).await
+    }
+
+    /// Send `request` once its rate limit budget is available.
+    async fn execute_http(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        let _permit = match &self.rate_limiter {
+            Some(limiter) => Some(
+                limiter
+                    .acquire(estimate_tokens(
+                        request.body().and_then(reqwest::Body::as_bytes),
+                    ))
+                    .await,
+            ),
+            None => None,
+        };
+        let response = self.execute_fallbacks(request).await?;
+        if let Some(limiter) = &self.rate_limiter {
+            limiter.update(response.headers());
+        }
+        Ok(response)
+    }
+
+    /// Send `request` with the config, then with the fallbacks in order until one connects, is
+    /// not rate limited and does not respond with a server error.
+    async fn execute_fallbacks(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        let original = if self.fallbacks.is_empty() {
+            None
+        } else {
+            request.try_clone()
+        };
+        let mut result = match &self.failover {
+            Some(failover) => self.execute_failover(failover, request).await,
+            None => self
+                .transport(request)
+                .await
+                .map(|response| ServedBy::mark(response, self.config.api_base())),
+        };
+        let Some(original) = original else {
+            return result;
+        };
+        for fallback in &self.fallbacks {
+            let failed = match &result {
+                Ok(response) => {
+                    response.status().is_server_error()
+                        || response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+                }
+                Err(OpenAIError::Reqwest(e)) => e.is_connect() || e.is_timeout(),
+                Err(OpenAIError::Transport(_)) => true,
+                Err(_) => false,
+            };
+            let Some(request) = original.try_clone().filter(|_| failed) else {
+                break;
+            };
+            let mut request = fallback.route(request, &self.config)?;
+            self.propagate(request.headers_mut());
+            let api_base = fallback.config().api_base();
+            tracing::warn!("falling back to {api_base}");
+            result = self
+                .transport(request)
+                .await
+                .map(|response| ServedBy::mark(response, api_base));
+        }
+        result
+    }
+
+    /// Send `request` to the healthy base urls in order until one connects and does not respond
+    /// with a server error. Requests with a streaming body, such as uploads, are only sent once.
+    async fn execute_failover(
+        &self,
+        failover: &Failover,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        let order = failover.order();
+        let mut request = request;
+        for (attempt, (index, api_base)) in order.iter().enumerate() {
+            let next = if attempt + 1 < order.len() {
+                request.try_clone()
+            } else {
+                None
+            };
+
+            if let Some(url) = failover
+                .rewrite(request.url().as_str(), api_base)
+                .and_then(|url| reqwest::Url::parse(&url).ok())
+            {
+                *request.url_mut() = url;
+            }
+
+            let result = self.transport(request).await;
+            match &result {
+                Ok(response) if !response.status().is_server_error() => {
+                    failover.succeeded(*index);
+                    tracing::debug!("served by {api_base}");
+                    tracing::Span::current().record("server.address", api_base.as_str());
+                    return result.map(|response| ServedBy::mark(response, api_base));
+                }
+                Ok(_) => failover.failed(*index),
+                Err(OpenAIError::Reqwest(e)) if e.is_connect() || e.is_timeout() => {
+                    failover.failed(*index)
+                }
+                Err(OpenAIError::Transport(_)) => failover.failed(*index),
+                Err(_) => return result,
+            }
+            match next {
+                Some(next) => request = next,
+                None => return result,
+            }
+        }
+        unreachable!("the api base is always tried")
+    }
+
+    /// Send `request` with the custom transport, if any, or the HTTP client.
+    async fn transport(&self, request: reqwest::Request) -> Result<reqwest::Response, OpenAIError> {
+        #[cfg(feature = "replay")]
+        if let Some(replay) = &self.replay {
+            return replay
+                .execute(request, |request| self.send_request(request))
+                .await;
+        }
+        self.send_request(request).await
+    }
+
+    async fn send_request(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        match &self.transport {
+            Some(transport) => transport.execute(request).await,
+            None => Ok(self.http_client.execute(request).await?),
+        }
+    }
+
+    /// Events of a stream request sent through [Client::transport], for its exchange to be
+    /// recorded or replayed.
+    #[cfg(feature = "replay")]
+    async fn replayed_events(
+        &self,
+        builder: reqwest::RequestBuilder,
+    ) -> Pin<Box<dyn Stream<Item = Result<eventsource_stream::Event, OpenAIError>> + Send>> {
+        let response = match builder.build() {
+            Ok(request) => self.transport(request).await,
+            Err(e) => Err(e.into()),
+        };
+        Box::pin(crate::replay::events(response))
+    }
+
+    /// Url of `path` for a JSON `request`, routed by its `model` with [Config::model_url].
+    fn request_url<I: Serialize>(&self, path: &str, request: &I) -> String {
+        let model = serde_json::to_value(request)
+            .ok()
+            .and_then(|request| request.get("model")?.as_str().map(str::to_string));
+        match model {
+            Some(model) => self.config.model_url(path, &model),
+            None => self.config.url(path),
+        }
+    }
+
+    /// `url` on the first healthy base url with fallback api bases.
+    fn failover_url(&self, url: String) -> String {
+        match &self.failover {
+            Some(failover) => failover.url(url),
+            None => url,
+        }
+    }
+
+    /// Report `request` to the request inspector, if any, `built` being its headers before the
+    /// client added credentials and trace context.
+    fn inspect(&self, request: &reqwest::Request, built: Option<HeaderMap>) {
+        let (Some(inspector), Some(built)) = (&self.inspector, built) else {
+            return;
+        };
+        let client_headers = added_headers(&built, request.headers());
+        inspector.inspect(&SentRequest::new(
+            request,
+            &self.config.headers(),
+            &self.config.query(),
+            &client_headers,
+        ));
+    }
+
+    /// Report the streamed request of `builder` to the request inspector, if any, `headers`
+    /// being the headers of [Client::headers].
+    fn inspect_stream(&self, builder: &reqwest::RequestBuilder, headers: &HeaderMap) {
+        let Some(inspector) = &self.inspector else {
+            return;
+        };
+        let Some(Ok(request)) = builder.try_clone().map(reqwest::RequestBuilder::build) else {
+            return;
+        };
+        let config_headers = self.config.headers();
+        inspector.inspect(&SentRequest::new(
+            &request,
+            &config_headers,
+            &self.config.query(),
+            &added_headers(&config_headers, headers),
+        ));
+    }
+
+    /// Span of a request, recording the usage of its responses if enabled.
+    fn observation(&self) -> Observation {
+        Observation::new().with_usage(self.usage.clone())
+    }
+
+    /// Rate limit budget of a streaming request, held until the stream ends.
+    async fn acquire_stream<I: Serialize>(&self, request: Option<&I>) -> Option<RatePermit> {
+        let limiter = self.rate_limiter.as_ref()?;
+        let body = request.and_then(|request| serde_json::to_vec(request).ok());
+        Some(limiter.acquire(estimate_tokens(body.as_deref())).await)
+    }
+
+    /// Count a streaming request against the quotas of its tenant, if any. Its usage is not
+    /// recorded, so its estimate stays counted.
+    fn acquire_stream_quota<I: Serialize>(
+        &self,
+        request: &I,
+        request_options: &RequestOptions,
+    ) -> Result<(), OpenAIError> {
+        let (Some(quotas), Some(tenant)) = (&self.tenant_quotas, request_options.get_tenant())
+        else {
+            return Ok(());
+        };
+        let body = serde_json::to_vec(request).ok();
+        quotas.acquire(tenant, estimate_tokens(body.as_deref()))?;
+        Ok(())
+    }
+
+    /// Config headers, with the credentials of the provider and the trace headers if any.
+    async fn headers(&self) -> Result<reqwest::header::HeaderMap, OpenAIError> {
+        let mut headers = self.config.headers();
+        if let Some(credentials) = &self.credentials {
+            credentials.apply(&mut headers).await?;
+        }
+        self.propagate(&mut headers);
+        Ok(headers)
+    }
+
+    fn propagate(&self, headers: &mut reqwest::header::HeaderMap) {
+        if let Some(propagation) = &self.propagation {
+            propagation.apply(headers);
+        }
+    }
+
     /// Execute a HTTP request and retry on rate limit
     ///
     /// request_maker serves one purpose: to be able to create request again
     /// to retry API call after getting rate limited. request_maker is async because
     /// reqwest::multipart::Form is created by async calls to read files for uploads.
-    async fn execute<O, M, Fut>(&self, request_maker: M) -> Result<O, OpenAIError>
+    async fn execute<O, M, Fut>(
+        &self,
+        request_maker: M,
+        request_options: &RequestOptions,
+    ) -> Result<O, OpenAIError>
     where
         O: DeserializeOwned,
         M: Fn() -> Fut,
         Fut: core::future::Future<Output = Result<reqwest::Request, OpenAIError>>,
     {
-        let bytes = self.execute_raw(request_maker).await?;
-
-        let response: O = serde_json::from_slice(bytes.as_ref())
-            .map_err(|e| map_deserialization_error(e, bytes.as_ref()))?;
+        let bytes = self.execute_raw(request_maker, request_options).await?;
 
-        Ok(response)
+        parse::from_slice(bytes.as_ref(), self.parse_mode)
     }
 
     /// Make HTTP POST request to receive SSE
@@ -413,21 +1403,63 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         request: I,
+        request_options: &RequestOptions,
     ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
     where
         I: Serialize,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
+        let in_flight = match self.lifecycle.enter() {
+            Ok(in_flight) => in_flight,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        let headers = match self.headers().await {
+            Ok(headers) => headers,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        if let Err(e) = self.acquire_stream_quota(&request, request_options) {
+            return Box::pin(futures::stream::once(async move { Err(e) }));
+        }
+        let permit = self.acquire_stream(Some(&request)).await;
+        let observation = self.observation();
+        observation.request_json(path, &request);
+        let builder = self
             .http_client
-            .post(self.config.url(path))
+            .post(self.failover_url(self.request_url(path, &request)))
             .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
-
-        stream(event_source).await
+            .headers(headers.clone())
+            .query(request_options.query_pairs())
+            .headers(request_options.header_map().clone())
+            .json(&request);
+        self.inspect_stream(&builder, &headers);
+        #[cfg(feature = "replay")]
+        if self.replay.is_some() {
+            let parse_mode = self.parse_mode;
+            return replayed_stream(
+                self.replayed_events(builder).await,
+                move |event| {
+                    parse::from_slice(event.data.as_bytes(), parse_mode)
+                        .map_err(|error| in_stream_error(&event, error))
+                },
+                false,
+                request_options.get_cancellation_token().cloned(),
+                observation,
+                permit,
+                in_flight,
+            );
+        }
+        let event_source = builder.eventsource().unwrap();
+
+        stream(
+            event_source,
+            self.reconnect,
+            self.parse_mode,
+            request_options.get_cancellation_token().cloned(),
+            observation,
+            permit,
+            in_flight,
+        )
+        .await
     }
 
     pub(crate) async fn post_stream_mapped_raw_events<I, O>(
@@ -435,21 +1467,62 @@ impl<C: Config> Client<C> {
         path: &str,
         request: I,
         event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
+        request_options: &RequestOptions,
     ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
     where
         I: Serialize,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
+        let in_flight = match self.lifecycle.enter() {
+            Ok(in_flight) => in_flight,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        let headers = match self.headers().await {
+            Ok(headers) => headers,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        if let Err(e) = self.acquire_stream_quota(&request, request_options) {
+            return Box::pin(futures::stream::once(async move { Err(e) }));
+        }
+        let permit = self.acquire_stream(Some(&request)).await;
+        let observation = self.observation();
+        observation.request_json(path, &request);
+        let builder = self
             .http_client
-            .post(self.config.url(path))
+            .post(self.failover_url(self.request_url(path, &request)))
             .query(&self.config.query())
-            .headers(self.config.headers())
-            .json(&request)
-            .eventsource()
-            .unwrap();
-
-        stream_mapped_raw_events(event_source, event_mapper).await
+            .headers(headers.clone())
+            .query(request_options.query_pairs())
+            .headers(request_options.header_map().clone())
+            .json(&request);
+        self.inspect_stream(&builder, &headers);
+        // mappers are conversions taking no mode, see parse::with_event_mode
+        let parse_mode = self.parse_mode;
+        let event_mapper = move |event| parse::with_event_mode(parse_mode, || event_mapper(event));
+        #[cfg(feature = "replay")]
+        if self.replay.is_some() {
+            return replayed_stream(
+                self.replayed_events(builder).await,
+                event_mapper,
+                true,
+                request_options.get_cancellation_token().cloned(),
+                observation,
+                permit,
+                in_flight,
+            );
+        }
+        let event_source = builder.eventsource().unwrap();
+
+        stream_mapped_raw_events(
+            event_source,
+            event_mapper,
+            self.reconnect,
+            request_options.get_cancellation_token().cloned(),
+            observation,
+            permit,
+            in_flight,
+        )
+        .await
     }
 
     /// Make HTTP GET request to receive SSE
@@ -457,130 +1530,373 @@ impl<C: Config> Client<C> {
         &self,
         path: &str,
         query: &Q,
+        request_options: &RequestOptions,
     ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
     where
         Q: Serialize + ?Sized,
         O: DeserializeOwned + std::marker::Send + 'static,
     {
-        let event_source = self
+        let in_flight = match self.lifecycle.enter() {
+            Ok(in_flight) => in_flight,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        let headers = match self.headers().await {
+            Ok(headers) => headers,
+            Err(e) => return Box::pin(futures::stream::once(async move { Err(e) })),
+        };
+        let permit = self.acquire_stream(None::<&()>).await;
+        let observation = self.observation();
+        observation.request(path, None);
+        let builder = self
             .http_client
-            .get(self.config.url(path))
+            .get(self.failover_url(self.config.url(path)))
             .query(query)
             .query(&self.config.query())
-            .headers(self.config.headers())
-            .eventsource()
-            .unwrap();
+            .headers(headers.clone())
+            .query(request_options.query_pairs())
+            .headers(request_options.header_map().clone());
+        self.inspect_stream(&builder, &headers);
+        #[cfg(feature = "replay")]
+        if self.replay.is_some() {
+            let parse_mode = self.parse_mode;
+            return replayed_stream(
+                self.replayed_events(builder).await,
+                move |event| {
+                    parse::from_slice(event.data.as_bytes(), parse_mode)
+                        .map_err(|error| in_stream_error(&event, error))
+                },
+                false,
+                request_options.get_cancellation_token().cloned(),
+                observation,
+                permit,
+                in_flight,
+            );
+        }
+        let event_source = builder.eventsource().unwrap();
+
+        stream(
+            event_source,
+            self.reconnect,
+            self.parse_mode,
+            request_options.get_cancellation_token().cloned(),
+            observation,
+            permit,
+            in_flight,
+        )
+        .await
+    }
+}
 
-        stream(event_source).await
+/// Whether `error` is a failure to send a request or receive its response.
+fn is_network_error(error: &OpenAIError) -> bool {
+    match error {
+        OpenAIError::Reqwest(error) => {
+            error.is_connect() || error.is_timeout() || error.is_request()
+        }
+        OpenAIError::Transport(_) => true,
+        _ => false,
     }
 }
 
+/// A random version 4 UUID.
+fn uuid_v4() -> String {
+    let mut bits = rand::random::<u128>();
+    // version 4, RFC 4122 variant
+    bits = bits & !(0xf_u128 << 76) | (0x4_u128 << 76);
+    bits = bits & !(0x3_u128 << 62) | (0x2_u128 << 62);
+    let hex = format!("{bits:032x}");
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..]
+    )
+}
+
+/// Headers of `after` which are not in `before`, or with another value.
+fn added_headers(before: &HeaderMap, after: &HeaderMap) -> HeaderMap {
+    let mut added = HeaderMap::new();
+    for (name, value) in after {
+        if before.get(name) != Some(value) {
+            added.insert(name.clone(), value.clone());
+        }
+    }
+    added
+}
+
 /// Request which responds with SSE.
 /// [server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events/Using_server-sent_events#event_stream_format)
 pub(crate) async fn stream<O>(
     mut event_source: EventSource,
+    reconnect: Option<ReconnectPolicy>,
+    parse_mode: ParseMode,
+    cancellation_token: Option<CancellationToken>,
+    mut observation: Observation,
+    permit: Option<RatePermit>,
+    in_flight: InFlight,
 ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
 where
     O: DeserializeOwned + std::marker::Send + 'static,
 {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-    tokio::spawn(async move {
-        while let Some(ev) = event_source.next().await {
-            match ev {
-                Err(e) => {
-                    // First get a pretty error message from the error
-                    let pretty_err = match e {
-                        reqwest_eventsource::Error::InvalidStatusCode(status_code, response) => {
-                            "Invalid status code: ".to_string()
-                                + &status_code.to_string()
-                                + "\n"
-                                + &response.text().await.unwrap_or_default()
-                        }
-                        reqwest_eventsource::Error::InvalidContentType(header_value, response) => {
-                            "Invalid content type: ".to_string()
-                                + header_value.to_str().unwrap_or_default()
-                                + "\n"
-                                + &response.text().await.unwrap_or_default()
+    let span = observation.span().clone();
+    tokio::spawn(
+        async move {
+            let mut state = StreamState::new(&mut event_source, reconnect);
+            while let Some(ev) = next_event(
+                &mut event_source,
+                cancellation_token.as_ref(),
+                &in_flight,
+                &tx,
+            )
+            .await
+            {
+                match ev {
+                    // the server closed the stream, do not let the event source reconnect and send the request again
+                    Err(reqwest_eventsource::Error::StreamEnded) => break,
+                    Err(e) => {
+                        if state.reconnect(&e, event_source.last_event_id()) {
+                            continue;
                         }
-                        _ => e.to_string(), // The other variants don't throw away details we might need, just use the error message
-                    };
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(pretty_err))) {
-                        // rx dropped
+                        let error = state.interrupted(map_event_source_error(e).await);
+                        observation.error(&error);
+                        // rx may be dropped, the stream ends either way
+                        let _ = tx.send(Err(error));
                         break;
                     }
-                }
-                Ok(event) => match event {
-                    Event::Message(message) => {
-                        if message.data == "[DONE]" {
-                            break;
+                    Ok(event) => match event {
+                        Event::Message(message) => {
+                            if !state.receive(&message) {
+                                continue;
+                            }
+                            if message.data == "[DONE]" {
+                                break;
+                            }
+                            observation.event(&message.data);
+
+                            let response = parse::from_slice(message.data.as_bytes(), parse_mode)
+                                .map_err(|error| in_stream_error(&message, error));
+
+                            if let Err(_e) = tx.send(response) {
+                                // rx dropped
+                                break;
+                            }
                         }
-
-                        let response = match serde_json::from_str::<O>(&message.data) {
-                            Err(e) => Err(map_deserialization_error(e, message.data.as_bytes())),
-                            Ok(output) => Ok(output),
-                        };
-
-                        if let Err(_e) = tx.send(response) {
-                            // rx dropped
-                            break;
-                        }
-                    }
-                    Event::Open => continue,
-                },
+                        Event::Open => continue,
+                    },
+                }
             }
-        }
 
-        event_source.close();
-    });
+            event_source.close();
+            observation.finish(None);
+            drop(permit);
+            drop(in_flight);
+        }
+        .instrument(span),
+    );
 
     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 }
 
-pub(crate) async fn stream_mapped_raw_events<O>(
-    mut event_source: EventSource,
+/// Events of a replayed or recorded stream, mapped with `event_mapper` like
+/// [stream_mapped_raw_events] does, `[DONE]` included when `map_done`.
+///
+/// They are not reconnected: their response comes from a fixture, or was read through the
+/// transport to be recorded.
+#[cfg(feature = "replay")]
+fn replayed_stream<O>(
+    mut events: Pin<Box<dyn Stream<Item = Result<eventsource_stream::Event, OpenAIError>> + Send>>,
     event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
+    map_done: bool,
+    cancellation_token: Option<CancellationToken>,
+    mut observation: Observation,
+    permit: Option<RatePermit>,
+    in_flight: InFlight,
 ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
 where
-    O: DeserializeOwned + std::marker::Send + 'static,
+    O: Send + 'static,
 {
     let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
 
-    tokio::spawn(async move {
-        while let Some(ev) = event_source.next().await {
-            match ev {
-                Err(e) => {
-                    if let Err(_e) = tx.send(Err(OpenAIError::StreamError(e.to_string()))) {
-                        // rx dropped
+    let span = observation.span().clone();
+    tokio::spawn(
+        async move {
+            while let Some(event) =
+                next_event(&mut events, cancellation_token.as_ref(), &in_flight, &tx).await
+            {
+                let event = match event {
+                    Ok(event) => event,
+                    Err(error) => {
+                        observation.error(&error);
+                        let _ = tx.send(Err(error));
                         break;
                     }
+                };
+                let done = event.data == "[DONE]";
+                if done && !map_done {
+                    break;
+                }
+                if !done {
+                    observation.event(&event.data);
+                }
+                if tx.send(event_mapper(event)).is_err() || done {
+                    break;
                 }
-                Ok(event) => match event {
-                    Event::Message(message) => {
-                        let mut done = false;
+            }
 
-                        if message.data == "[DONE]" {
-                            done = true;
-                        }
+            observation.finish(None);
+            drop(permit);
+            drop(in_flight);
+        }
+        .instrument(span),
+    );
 
-                        let response = event_mapper(message);
+    Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
+}
 
-                        if let Err(_e) = tx.send(response) {
-                            // rx dropped
-                            break;
-                        }
+/// Next event of `events`, `None` once it ended, `cancellation_token` was cancelled, the
+/// client was shut down or the receiving stream was dropped.
+async fn next_event<S: Stream + Unpin, O>(
+    events: &mut S,
+    cancellation_token: Option<&CancellationToken>,
+    in_flight: &InFlight,
+    tx: &tokio::sync::mpsc::UnboundedSender<O>,
+) -> Option<S::Item> {
+    let cancelled = async {
+        match cancellation_token {
+            Some(token) => token.cancelled().await,
+            None => std::future::pending().await,
+        }
+    };
+    // no event once cancelled, replayed events being always ready
+    tokio::select! {
+        biased;
+        _ = cancelled => None,
+        _ = in_flight.cancelled() => None,
+        _ = tx.closed() => None,
+        event = events.next() => event,
+    }
+}
 
-                        if done {
-                            break;
+/// Converts an event source error, keeping status code, headers and body of failed responses.
+async fn map_event_source_error(e: reqwest_eventsource::Error) -> OpenAIError {
+    match e {
+        reqwest_eventsource::Error::InvalidStatusCode(status, response) => {
+            let headers = response.headers().clone();
+            let bytes = response.bytes().await.unwrap_or_default();
+            OpenAIError::ApiError(map_status_error(status, headers, bytes.as_ref()))
+        }
+        reqwest_eventsource::Error::InvalidContentType(header_value, response) => {
+            OpenAIError::StreamError(
+                "Invalid content type: ".to_string()
+                    + header_value.to_str().unwrap_or_default()
+                    + "\n"
+                    + &response.text().await.unwrap_or_default(),
+            )
+        }
+        reqwest_eventsource::Error::Transport(e) => OpenAIError::Reqwest(e),
+        // The other variants don't throw away details we might need, just use the error message
+        _ => OpenAIError::StreamError(e.to_string()),
+    }
+}
+
+/// `error` of an event which failed to deserialize, the [OpenAIError::ApiError] the event
+/// carries when it is an `error` event or an error object.
+fn in_stream_error(event: &eventsource_stream::Event, error: OpenAIError) -> OpenAIError {
+    match ApiError::from_event(&event.event, &event.data) {
+        Some(api_error) => {
+            tracing::warn!("error event in stream: {api_error}");
+            OpenAIError::ApiError(api_error)
+        }
+        None => error,
+    }
+}
+
+pub(crate) async fn stream_mapped_raw_events<O>(
+    mut event_source: EventSource,
+    event_mapper: impl Fn(eventsource_stream::Event) -> Result<O, OpenAIError> + Send + 'static,
+    reconnect: Option<ReconnectPolicy>,
+    cancellation_token: Option<CancellationToken>,
+    mut observation: Observation,
+    permit: Option<RatePermit>,
+    in_flight: InFlight,
+) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>
+where
+    O: DeserializeOwned + std::marker::Send + 'static,
+{
+    let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+
+    let span = observation.span().clone();
+    tokio::spawn(
+        async move {
+            let mut state = StreamState::new(&mut event_source, reconnect);
+            while let Some(ev) = next_event(
+                &mut event_source,
+                cancellation_token.as_ref(),
+                &in_flight,
+                &tx,
+            )
+            .await
+            {
+                match ev {
+                    // the server closed the stream, do not let the event source reconnect and send the request again
+                    Err(reqwest_eventsource::Error::StreamEnded) => break,
+                    Err(e) => {
+                        if state.reconnect(&e, event_source.last_event_id()) {
+                            continue;
                         }
+                        let error = state.interrupted(map_event_source_error(e).await);
+                        observation.error(&error);
+                        // rx may be dropped, the stream ends either way
+                        let _ = tx.send(Err(error));
+                        break;
                     }
-                    Event::Open => continue,
-                },
+                    Ok(event) => match event {
+                        Event::Message(message) => {
+                            if !state.receive(&message) {
+                                continue;
+                            }
+                            let mut done = false;
+
+                            if message.data == "[DONE]" {
+                                done = true;
+                            } else {
+                                observation.event(&message.data);
+                            }
+
+                            // only events mentioning an error can carry one
+                            let error_event =
+                                message.data.contains("error").then(|| message.clone());
+                            let response =
+                                event_mapper(message).map_err(|error| match &error_event {
+                                    Some(event) => in_stream_error(event, error),
+                                    None => error,
+                                });
+
+                            if let Err(_e) = tx.send(response) {
+                                // rx dropped
+                                break;
+                            }
+
+                            if done {
+                                break;
+                            }
+                        }
+                        Event::Open => continue,
+                    },
+                }
             }
-        }
 
-        event_source.close();
-    });
+            event_source.close();
+            observation.finish(None);
+            drop(permit);
+            drop(in_flight);
+        }
+        .instrument(span),
+    );
 
     Box::pin(tokio_stream::wrappers::UnboundedReceiverStream::new(rx))
 }
@@ -2,6 +2,7 @@ use crate::{
     client::Client,
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{CompletionResponseStream, CreateCompletionRequest, CreateCompletionResponse},
 };
 
@@ -13,11 +14,18 @@ use crate::{
 /// Related guide: [Legacy Completions](https://platform.openai.com/docs/guides/gpt/completions-api)
 pub struct Completions<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Completions);
+crate::util::impl_api_group_debug!(Completions, "/completions");
+
 impl<'c, C: Config> Completions<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates a completion for the provided prompt and parameters
@@ -39,7 +47,9 @@ impl<'c, C: Config> Completions<'c, C> {
                 ));
             }
         }
-        self.client.post("/completions", request).await
+        self.client
+            .post("/completions", request, &self.request_options)
+            .await
     }
 
     /// Creates a completion request for the provided prompt and parameters
@@ -72,6 +82,9 @@ impl<'c, C: Config> Completions<'c, C> {
 
             request.stream = Some(true);
         }
-        Ok(self.client.post_stream("/completions", request).await)
+        Ok(self
+            .client
+            .post_stream("/completions", request, &self.request_options)
+            .await)
     }
 }
@@ -1,8 +1,15 @@
 //! Client configurations: [OpenAIConfig] for OpenAI, [AzureConfig] for Azure OpenAI Service.
-use reqwest::header::{HeaderMap, AUTHORIZATION};
+//!
+//! Presets for OpenAI compatible providers: [OllamaConfig], [OpenRouterConfig], [GroqConfig],
+//! [GeminiConfig] and [MistralConfig].
+use std::collections::HashMap;
+
+use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION};
 use secrecy::{ExposeSecret, SecretString};
 use serde::Deserialize;
 
+use crate::error::OpenAIError;
+
 /// Default v1 API base url
 pub const OPENAI_API_BASE: &str = "https://api.openai.com/v1";
 /// Organization header
@@ -13,6 +20,22 @@ pub const OPENAI_PROJECT_HEADER: &str = "OpenAI-Project";
 /// Calls to the Assistants API require that you pass a Beta header
 pub const OPENAI_BETA_HEADER: &str = "OpenAI-Beta";
 
+/// Default base url of a local Ollama server
+pub const OLLAMA_API_BASE: &str = "http://localhost:11434/v1";
+/// OpenRouter API base url
+pub const OPENROUTER_API_BASE: &str = "https://openrouter.ai/api/v1";
+/// Groq API base url
+pub const GROQ_API_BASE: &str = "https://api.groq.com/openai/v1";
+/// Gemini OpenAI compatibility API base url
+pub const GEMINI_API_BASE: &str = "https://generativelanguage.googleapis.com/v1beta/openai";
+/// Mistral API base url
+pub const MISTRAL_API_BASE: &str = "https://api.mistral.ai/v1";
+
+/// Header used by OpenRouter to attribute requests to your site
+pub const OPENROUTER_REFERER_HEADER: &str = "HTTP-Referer";
+/// Header used by OpenRouter to show your app name in rankings
+pub const OPENROUTER_TITLE_HEADER: &str = "X-Title";
+
 /// [crate::Client] relies on this for every API call on OpenAI
 /// or Azure OpenAI service
 pub trait Config: Send + Sync {
@@ -23,6 +46,17 @@ pub trait Config: Send + Sync {
     fn api_base(&self) -> &str;
 
     fn api_key(&self) -> &SecretString;
+
+    /// Base urls tried in order when [Config::api_base] fails to connect or responds with a server error.
+    fn fallback_api_bases(&self) -> &[String] {
+        &[]
+    }
+
+    /// Url of `path` for a JSON request to `model`, for configs serving models at different urls
+    /// such as [AzureConfig::with_deployment_for]. Form requests always use [Config::url].
+    fn model_url(&self, path: &str, _model: &str) -> String {
+        self.url(path)
+    }
 }
 
 /// Macro to implement Config trait for pointer types with dyn objects
@@ -44,6 +78,12 @@ macro_rules! impl_config_for_ptr {
             fn api_key(&self) -> &SecretString {
                 self.as_ref().api_key()
             }
+            fn fallback_api_bases(&self) -> &[String] {
+                self.as_ref().fallback_api_bases()
+            }
+            fn model_url(&self, path: &str, model: &str) -> String {
+                self.as_ref().model_url(path, model)
+            }
         }
     };
 }
@@ -59,27 +99,42 @@ pub struct OpenAIConfig {
     api_key: SecretString,
     org_id: String,
     project_id: String,
+    fallback_api_bases: Vec<String>,
 }
 
 impl Default for OpenAIConfig {
     fn default() -> Self {
-        Self {
-            api_base: OPENAI_API_BASE.to_string(),
-            api_key: std::env::var("OPENAI_API_KEY")
-                .unwrap_or_else(|_| "".to_string())
-                .into(),
-            org_id: Default::default(),
-            project_id: Default::default(),
-        }
+        Self::from_env(|name| std::env::var(name).ok())
     }
 }
 
 impl OpenAIConfig {
-    /// Create client with default [OPENAI_API_BASE] url and default API key from OPENAI_API_KEY env var
+    /// Create config from the environment variables:
+    /// - `OPENAI_API_KEY` API key
+    /// - `OPENAI_BASE_URL` API base url, [OPENAI_API_BASE] when not set
+    /// - `OPENAI_ORG_ID` organization id sent in the [OPENAI_ORGANIZATION_HEADER]
+    /// - `OPENAI_PROJECT_ID` project id sent in the [OPENAI_PROJECT_HEADER]
     pub fn new() -> Self {
         Default::default()
     }
 
+    fn from_env(var: impl Fn(&str) -> Option<String>) -> Self {
+        // surrounding whitespace, e.g. the trailing newline of a secrets file, is not part of
+        // the values, and empty variables are the same as unset ones
+        let var = |name: &str| {
+            var(name)
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+        };
+        Self {
+            api_base: var("OPENAI_BASE_URL").unwrap_or_else(|| OPENAI_API_BASE.to_string()),
+            api_key: var("OPENAI_API_KEY").unwrap_or_default().into(),
+            org_id: var("OPENAI_ORG_ID").unwrap_or_default(),
+            project_id: var("OPENAI_PROJECT_ID").unwrap_or_default(),
+            fallback_api_bases: Default::default(),
+        }
+    }
+
     /// To use a different organization id other than default
     pub fn with_org_id<S: Into<String>>(mut self, org_id: S) -> Self {
         self.org_id = org_id.into();
@@ -104,26 +159,39 @@ impl OpenAIConfig {
         self
     }
 
+    /// Base url, e.g. of another region or gateway, to fail over to when the api base and the
+    /// previous fallbacks fail to connect or respond with a server error.
+    /// See [crate::Client::endpoints] for the health of each base url.
+    pub fn with_fallback_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+        self.fallback_api_bases.push(api_base.into());
+        self
+    }
+
     pub fn org_id(&self) -> &str {
         &self.org_id
     }
+
+    pub fn project_id(&self) -> &str {
+        &self.project_id
+    }
 }
 
 impl Config for OpenAIConfig {
     fn headers(&self) -> HeaderMap {
         let mut headers = HeaderMap::new();
-        if !self.org_id.is_empty() {
-            headers.insert(
-                OPENAI_ORGANIZATION_HEADER,
-                self.org_id.as_str().parse().unwrap(),
-            );
-        }
-
-        if !self.project_id.is_empty() {
-            headers.insert(
-                OPENAI_PROJECT_HEADER,
-                self.project_id.as_str().parse().unwrap(),
-            );
+        for (name, value) in [
+            (OPENAI_ORGANIZATION_HEADER, &self.org_id),
+            (OPENAI_PROJECT_HEADER, &self.project_id),
+        ] {
+            if value.is_empty() {
+                continue;
+            }
+            match HeaderValue::from_str(value) {
+                Ok(value) => {
+                    headers.insert(name, value);
+                }
+                Err(_) => tracing::warn!("{name} header skipped: it is not a valid header value"),
+            }
         }
 
         headers.insert(
@@ -156,6 +224,10 @@ impl Config for OpenAIConfig {
     fn query(&self) -> Vec<(&str, &str)> {
         vec![]
     }
+
+    fn fallback_api_bases(&self) -> &[String] {
+        &self.fallback_api_bases
+    }
 }
 
 /// Configuration for Azure OpenAI Service
@@ -164,6 +236,7 @@ impl Config for OpenAIConfig {
 pub struct AzureConfig {
     api_version: String,
     deployment_id: String,
+    deployments: HashMap<String, String>,
     api_base: String,
     api_key: SecretString,
 }
@@ -176,6 +249,7 @@ impl Default for AzureConfig {
                 .unwrap_or_else(|_| "".to_string())
                 .into(),
             deployment_id: Default::default(),
+            deployments: Default::default(),
             api_version: Default::default(),
         }
     }
@@ -196,6 +270,29 @@ impl AzureConfig {
         self
     }
 
+    /// Deployment serving the JSON requests to `model`, e.g. `"gpt-4o"` or
+    /// `"text-embedding-3-small"`, so one client uses several deployments. Requests to other
+    /// models and form requests, such as audio transcriptions, use the deployment id.
+    ///
+    /// ```
+    /// use async_openai::config::AzureConfig;
+    ///
+    /// let config = AzureConfig::new()
+    ///     .with_api_base("https://my-resource.openai.azure.com")
+    ///     .with_api_version("2024-10-21")
+    ///     .with_deployment_id("my-gpt4o-deploy")
+    ///     .with_deployment_for("gpt-4o", "my-gpt4o-deploy")
+    ///     .with_deployment_for("text-embedding-3-small", "my-embeddings-deploy");
+    /// ```
+    pub fn with_deployment_for<M: Into<String>, D: Into<String>>(
+        mut self,
+        model: M,
+        deployment_id: D,
+    ) -> Self {
+        self.deployments.insert(model.into(), deployment_id.into());
+        self
+    }
+
     /// To use a different API key different from default OPENAI_API_KEY env var
     pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
         self.api_key = SecretString::from(api_key.into());
@@ -225,6 +322,16 @@ impl Config for AzureConfig {
         )
     }
 
+    fn model_url(&self, path: &str, model: &str) -> String {
+        match self.deployments.get(model) {
+            Some(deployment_id) => format!(
+                "{}/openai/deployments/{}{}",
+                self.api_base, deployment_id, path
+            ),
+            None => self.url(path),
+        }
+    }
+
     fn api_base(&self) -> &str {
         &self.api_base
     }
@@ -238,6 +345,236 @@ impl Config for AzureConfig {
     }
 }
 
+/// Set the bearer `Authorization` header, skipped for an API key which is not a valid header
+/// value, e.g. with a trailing newline, the request then failing with a 401 error.
+fn bearer_auth_header(headers: &mut HeaderMap, api_key: &SecretString) {
+    match HeaderValue::from_str(&format!("Bearer {}", api_key.expose_secret())) {
+        Ok(value) => {
+            headers.insert(AUTHORIZATION, value);
+        }
+        Err(_) => tracing::warn!("API key skipped: it is not a valid header value"),
+    }
+}
+
+/// Macro to define a config for an OpenAI compatible provider, with an API key read from
+/// `env` by default, optional extra fields and, instead of a bearer `Authorization` header,
+/// the headers set by the `headers` block
+macro_rules! provider_config {
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            api_base: $api_base:expr,
+            $(env: $env:literal,)?
+        }
+        $(fields { $($field:ident: $ty:ty,)* })?
+    ) => {
+        provider_config!(
+            $(#[$meta])*
+            $name {
+                api_base: $api_base,
+                $(env: $env,)?
+            }
+            $(fields { $($field: $ty,)* })?
+            headers(&self, headers) {
+                bearer_auth_header(&mut headers, &self.api_key);
+            }
+        );
+    };
+    (
+        $(#[$meta:meta])*
+        $name:ident {
+            api_base: $api_base:expr,
+            $(env: $env:literal,)?
+        }
+        $(fields { $($field:ident: $ty:ty,)* })?
+        headers(&$this:ident, $headers:ident) $set_headers:block
+    ) => {
+        $(#[$meta])*
+        #[derive(Clone, Debug, Deserialize)]
+        #[serde(default)]
+        pub struct $name {
+            api_base: String,
+            api_key: SecretString,
+            $($($field: $ty,)*)?
+        }
+
+        impl Default for $name {
+            fn default() -> Self {
+                let api_key: Option<String> = None$(.or(std::env::var($env).ok()))?;
+                Self {
+                    api_base: $api_base.to_string(),
+                    api_key: api_key.unwrap_or_default().into(),
+                    $($($field: Default::default(),)*)?
+                }
+            }
+        }
+
+        impl $name {
+            #[doc = concat!("Create config with default [", stringify!($api_base), "] url", $(" and API key from ", $env, " env var",)?)]
+            pub fn new() -> Self {
+                Default::default()
+            }
+
+            /// To use an API key different from the default one
+            pub fn with_api_key<S: Into<String>>(mut self, api_key: S) -> Self {
+                self.api_key = SecretString::from(api_key.into());
+                self
+            }
+
+            #[doc = concat!("To use a API base url different from default [", stringify!($api_base), "]")]
+            pub fn with_api_base<S: Into<String>>(mut self, api_base: S) -> Self {
+                self.api_base = api_base.into();
+                self
+            }
+        }
+
+        impl Config for $name {
+            fn headers(&$this) -> HeaderMap {
+                let mut $headers = HeaderMap::new();
+                $set_headers
+                $headers
+            }
+
+            fn url(&self, path: &str) -> String {
+                format!("{}{}", self.api_base, path)
+            }
+
+            fn api_base(&self) -> &str {
+                &self.api_base
+            }
+
+            fn api_key(&self) -> &SecretString {
+                &self.api_key
+            }
+
+            fn query(&self) -> Vec<(&str, &str)> {
+                vec![]
+            }
+        }
+    };
+}
+
+provider_config!(
+    /// Configuration for [Groq](https://console.groq.com/docs/openai)
+    GroqConfig {
+        api_base: GROQ_API_BASE,
+        env: "GROQ_API_KEY",
+    }
+);
+
+provider_config!(
+    /// Configuration for [Gemini OpenAI compatibility](https://ai.google.dev/gemini-api/docs/openai)
+    GeminiConfig {
+        api_base: GEMINI_API_BASE,
+        env: "GEMINI_API_KEY",
+    }
+);
+
+provider_config!(
+    /// Configuration for [Mistral](https://docs.mistral.ai/api/)
+    MistralConfig {
+        api_base: MISTRAL_API_BASE,
+        env: "MISTRAL_API_KEY",
+    }
+);
+
+provider_config!(
+    /// Configuration for [Ollama](https://github.com/ollama/ollama/blob/main/docs/openai.md)
+    ///
+    /// Ollama does not require an API key, the `Authorization` header is only sent when one is
+    /// set, e.g. for servers behind an authenticating proxy.
+    OllamaConfig {
+        api_base: OLLAMA_API_BASE,
+    }
+    headers(&self, headers) {
+        if !self.api_key.expose_secret().is_empty() {
+            bearer_auth_header(&mut headers, &self.api_key);
+        }
+    }
+);
+
+provider_config!(
+    /// Configuration for [OpenRouter](https://openrouter.ai/docs/api-reference/overview)
+    OpenRouterConfig {
+        api_base: OPENROUTER_API_BASE,
+        env: "OPENROUTER_API_KEY",
+    }
+    fields {
+        http_referer: String,
+        app_title: String,
+    }
+    headers(&self, headers) {
+        bearer_auth_header(&mut headers, &self.api_key);
+        // values which are not valid header values are skipped, see the `try_with_*` setters
+        for (name, value) in [
+            (OPENROUTER_REFERER_HEADER, &self.http_referer),
+            (OPENROUTER_TITLE_HEADER, &self.app_title),
+        ] {
+            if let Some(value) = header_value(value) {
+                headers.insert(name, value);
+            }
+        }
+    }
+);
+
+impl OpenRouterConfig {
+    /// Your site url, sent as [OPENROUTER_REFERER_HEADER] for rankings on openrouter.ai
+    ///
+    /// Not sent when it is not a valid header value, see [OpenRouterConfig::try_with_http_referer].
+    pub fn with_http_referer<S: Into<String>>(mut self, http_referer: S) -> Self {
+        self.http_referer = http_referer.into();
+        self
+    }
+
+    /// Your app name, sent as [OPENROUTER_TITLE_HEADER] for rankings on openrouter.ai
+    ///
+    /// Not sent when it is not a valid header value, e.g. with non ASCII characters, see
+    /// [OpenRouterConfig::try_with_app_title].
+    pub fn with_app_title<S: Into<String>>(mut self, app_title: S) -> Self {
+        self.app_title = app_title.into();
+        self
+    }
+
+    /// Like [OpenRouterConfig::with_http_referer], failing with [OpenAIError::InvalidArgument]
+    /// when `http_referer` is not a valid header value.
+    pub fn try_with_http_referer<S: Into<String>>(
+        mut self,
+        http_referer: S,
+    ) -> Result<Self, OpenAIError> {
+        self.http_referer = checked_header_value(OPENROUTER_REFERER_HEADER, http_referer.into())?;
+        Ok(self)
+    }
+
+    /// Like [OpenRouterConfig::with_app_title], failing with [OpenAIError::InvalidArgument]
+    /// when `app_title` is not a valid header value.
+    pub fn try_with_app_title<S: Into<String>>(
+        mut self,
+        app_title: S,
+    ) -> Result<Self, OpenAIError> {
+        self.app_title = checked_header_value(OPENROUTER_TITLE_HEADER, app_title.into())?;
+        Ok(self)
+    }
+}
+
+/// Header value of a non empty `value` of visible ASCII characters.
+fn header_value(value: &str) -> Option<HeaderValue> {
+    let visible = value.bytes().all(|b| b == b' ' || b.is_ascii_graphic());
+    (visible && !value.is_empty())
+        .then(|| HeaderValue::from_str(value).ok())
+        .flatten()
+}
+
+/// `value` if it is empty or can be sent as the header `name`.
+fn checked_header_value(name: &str, value: String) -> Result<String, OpenAIError> {
+    if value.is_empty() || header_value(&value).is_some() {
+        Ok(value)
+    } else {
+        Err(OpenAIError::InvalidArgument(format!(
+            "{name} header value must be visible ASCII characters: {value:?}"
+        )))
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -261,6 +598,101 @@ mod test {
         assert!(cloned_client.config().url("").ends_with("/v1"));
     }
 
+    #[test]
+    fn test_provider_presets() {
+        let groq = GroqConfig::new().with_api_key("gsk-test");
+        assert_eq!(
+            groq.url("/chat/completions"),
+            format!("{GROQ_API_BASE}/chat/completions")
+        );
+        assert_eq!(groq.headers()[AUTHORIZATION], "Bearer gsk-test");
+        assert!(!groq.headers().contains_key(OPENAI_BETA_HEADER));
+
+        let ollama = OllamaConfig::new();
+        assert!(ollama.url("/models").starts_with(OLLAMA_API_BASE));
+        assert!(!ollama.headers().contains_key(AUTHORIZATION));
+
+        let openrouter = OpenRouterConfig::new()
+            .with_api_key("sk-or-test")
+            .with_http_referer("https://example.com")
+            .with_app_title("Example");
+        let headers = openrouter.headers();
+        assert_eq!(headers[OPENROUTER_REFERER_HEADER], "https://example.com");
+        assert_eq!(headers[OPENROUTER_TITLE_HEADER], "Example");
+        assert_eq!(headers[AUTHORIZATION], "Bearer sk-or-test");
+
+        // not a valid header value, rejected by the fallible setters and skipped otherwise
+        assert!(OpenRouterConfig::new()
+            .try_with_app_title("Caf\u{e9}")
+            .is_err());
+        assert!(OpenRouterConfig::new()
+            .try_with_http_referer("a\nb")
+            .is_err());
+        let headers = OpenRouterConfig::new()
+            .with_app_title("Caf\u{e9}")
+            .try_with_http_referer("https://example.com")
+            .unwrap()
+            .headers();
+        assert!(!headers.contains_key(OPENROUTER_TITLE_HEADER));
+        assert_eq!(headers[OPENROUTER_REFERER_HEADER], "https://example.com");
+        let openrouter: OpenRouterConfig =
+            serde_json::from_str(r#"{"app_title": "Caf\u00e9", "http_referer": "a\nb"}"#).unwrap();
+        let headers = openrouter.headers();
+        assert!(!headers.contains_key(OPENROUTER_TITLE_HEADER));
+        assert!(!headers.contains_key(OPENROUTER_REFERER_HEADER));
+    }
+
+    #[test]
+    fn test_azure_deployments() {
+        let config = AzureConfig::new()
+            .with_api_base("https://res.openai.azure.com")
+            .with_deployment_id("default")
+            .with_deployment_for("text-embedding-3-small", "embeddings");
+        assert_eq!(
+            config.model_url("/embeddings", "text-embedding-3-small"),
+            "https://res.openai.azure.com/openai/deployments/embeddings/embeddings"
+        );
+        assert_eq!(
+            config.model_url("/chat/completions", "gpt-4o"),
+            "https://res.openai.azure.com/openai/deployments/default/chat/completions"
+        );
+    }
+
+    #[test]
+    fn test_openai_config_from_env() {
+        let env = |name: &str| match name {
+            "OPENAI_API_KEY" => Some("sk-test".to_string()),
+            "OPENAI_BASE_URL" => Some("https://gateway.example.com/v1".to_string()),
+            "OPENAI_PROJECT_ID" => Some("proj_abc".to_string()),
+            "OPENAI_ORG_ID" => Some(String::new()),
+            _ => None,
+        };
+        let config = OpenAIConfig::from_env(env);
+        assert_eq!(config.api_base(), "https://gateway.example.com/v1");
+        assert_eq!(config.project_id(), "proj_abc");
+        let headers = config.headers();
+        assert_eq!(headers[OPENAI_PROJECT_HEADER], "proj_abc");
+        assert!(!headers.contains_key(OPENAI_ORGANIZATION_HEADER));
+
+        let config = OpenAIConfig::from_env(|_| None);
+        assert_eq!(config.api_base(), OPENAI_API_BASE);
+        assert!(config.api_key().expose_secret().is_empty());
+
+        // values read from secrets files are trimmed, invalid ones are not sent
+        let env = |name: &str| match name {
+            "OPENAI_API_KEY" => Some("sk-test\n".to_string()),
+            "OPENAI_ORG_ID" => Some("org-abc\n".to_string()),
+            "OPENAI_PROJECT_ID" => Some("proj\u{7f}abc".to_string()),
+            _ => None,
+        };
+        let config = OpenAIConfig::from_env(env);
+        assert_eq!(config.org_id(), "org-abc");
+        let headers = config.headers();
+        assert_eq!(headers[OPENAI_ORGANIZATION_HEADER], "org-abc");
+        assert_eq!(headers[AUTHORIZATION], "Bearer sk-test");
+        assert!(!headers.contains_key(OPENAI_PROJECT_HEADER));
+    }
+
     async fn dynamic_dispatch_compiles(client: &Client<Box<dyn Config>>) {
         let _ = client.chat().create(CreateChatCompletionRequest {
             model: "gpt-4o".to_string(),
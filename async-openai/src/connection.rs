@@ -0,0 +1,388 @@
+//! Tuning of the HTTP connections made by [crate::Client].
+use std::{fmt, time::Duration};
+
+use crate::error::OpenAIError;
+
+/// Connection settings applied to the underlying [reqwest::Client].
+///
+/// Settings left unset keep reqwest defaults, which use the proxies of the `HTTP_PROXY`,
+/// `HTTPS_PROXY` and `NO_PROXY` environment variables. TLS backend is selected at compile time
+/// with one of the `rustls` (default), `rustls-webpki-roots`, `native-tls` or
+/// `native-tls-vendored` features, or at runtime with [ConnectionOptions::with_tls_backend]
+/// when several are enabled.
+///
+/// ```
+/// use std::time::Duration;
+/// use async_openai::{connection::ConnectionOptions, Client};
+///
+/// # fn main() -> Result<(), async_openai::error::OpenAIError> {
+/// let options = ConnectionOptions::new()
+///     .with_connect_timeout(Duration::from_secs(5))
+///     .with_pool_idle_timeout(Duration::from_secs(90))
+///     .with_pool_max_idle_per_host(8)
+///     .with_tcp_keepalive(Duration::from_secs(30));
+///
+/// let client = Client::new().with_connection_options(&options)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Behind a corporate proxy intercepting TLS:
+///
+/// ```no_run
+/// use async_openai::{connection::ConnectionOptions, Client};
+///
+/// # fn main() -> Result<(), async_openai::error::OpenAIError> {
+/// let options = ConnectionOptions::new()
+///     .with_proxy("http://proxy.corp.example:3128")?
+///     .with_proxy_auth("user", "password")
+///     .with_no_proxy("localhost,.corp.example")
+///     .with_root_certificate_file("/etc/ssl/corp-ca.pem")?;
+///
+/// let client = Client::new().with_connection_options(&options)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ConnectionOptions {
+    connect_timeout: Option<Duration>,
+    timeout: Option<Duration>,
+    read_timeout: Option<Duration>,
+    pool_idle_timeout: Option<Duration>,
+    pool_max_idle_per_host: Option<usize>,
+    tcp_keepalive: Option<Duration>,
+    tcp_nodelay: Option<bool>,
+    #[cfg(feature = "http2")]
+    http2_adaptive_window: Option<bool>,
+    user_agent: Option<String>,
+    proxies: Vec<String>,
+    proxy_auth: Option<ProxyAuth>,
+    no_proxy: Option<String>,
+    without_system_proxy: bool,
+    root_certificates: Vec<Vec<u8>>,
+    built_in_root_certificates: Option<bool>,
+    tls_backend: Option<TlsBackend>,
+}
+
+/// Credentials of the proxies, redacted from [fmt::Debug].
+#[derive(Clone, PartialEq)]
+struct ProxyAuth {
+    username: String,
+    password: String,
+}
+
+impl fmt::Debug for ProxyAuth {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProxyAuth")
+            .field("username", &self.username)
+            .finish_non_exhaustive()
+    }
+}
+
+/// TLS implementation of the connections, among those enabled by the features of the crate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TlsBackend {
+    /// [rustls](https://docs.rs/rustls), with the `rustls` or `rustls-webpki-roots` features.
+    #[cfg(any(feature = "rustls", feature = "rustls-webpki-roots"))]
+    #[cfg_attr(
+        docsrs,
+        doc(cfg(any(feature = "rustls", feature = "rustls-webpki-roots")))
+    )]
+    Rustls,
+    /// The TLS library of the platform, with the `native-tls` or `native-tls-vendored` features.
+    #[cfg(feature = "native-tls")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "native-tls")))]
+    NativeTls,
+}
+
+impl ConnectionOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Timeout for establishing a connection.
+    pub fn with_connect_timeout(mut self, timeout: Duration) -> Self {
+        self.connect_timeout = Some(timeout);
+        self
+    }
+
+    /// Total timeout of a request, from connecting until the response body is read.
+    ///
+    /// This also bounds streaming responses, prefer [ConnectionOptions::with_read_timeout] for long streams.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Timeout between two reads of the response, reset after every successful read.
+    pub fn with_read_timeout(mut self, timeout: Duration) -> Self {
+        self.read_timeout = Some(timeout);
+        self
+    }
+
+    /// How long an idle connection is kept in the pool.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool_idle_timeout = Some(timeout);
+        self
+    }
+
+    /// Maximum number of idle connections kept per host.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool_max_idle_per_host = Some(max);
+        self
+    }
+
+    /// Interval of TCP keepalive probes.
+    pub fn with_tcp_keepalive(mut self, interval: Duration) -> Self {
+        self.tcp_keepalive = Some(interval);
+        self
+    }
+
+    /// Set `TCP_NODELAY` on connections.
+    pub fn with_tcp_nodelay(mut self, enabled: bool) -> Self {
+        self.tcp_nodelay = Some(enabled);
+        self
+    }
+
+    /// Size the flow control windows of HTTP/2 connections from the measured bandwidth and
+    /// latency, instead of fixed windows which slow down large responses on slow links.
+    #[cfg(feature = "http2")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "http2")))]
+    pub fn with_http2_adaptive_window(mut self, enabled: bool) -> Self {
+        self.http2_adaptive_window = Some(enabled);
+        self
+    }
+
+    /// `User-Agent` header sent with every request.
+    pub fn with_user_agent<S: Into<String>>(mut self, user_agent: S) -> Self {
+        self.user_agent = Some(user_agent.into());
+        self
+    }
+
+    /// Send all requests through the proxy at `url`, e.g. `http://proxy.example:3128`, instead
+    /// of the proxies of the environment. Can be called several times, the first proxy
+    /// matching a request is used.
+    ///
+    /// SOCKS proxies, `socks5://` and `socks5h://` urls, need the `socks` feature of reqwest,
+    /// enabled by depending on `reqwest` with it.
+    pub fn with_proxy<S: Into<String>>(mut self, url: S) -> Result<Self, OpenAIError> {
+        let url = url.into();
+        reqwest::Proxy::all(&url).map_err(|error| {
+            OpenAIError::InvalidArgument(format!("invalid proxy url {url}: {error}"))
+        })?;
+        self.proxies.push(url);
+        Ok(self)
+    }
+
+    /// Basic authentication sent to the proxies of [ConnectionOptions::with_proxy].
+    pub fn with_proxy_auth<S: Into<String>>(mut self, username: S, password: S) -> Self {
+        self.proxy_auth = Some(ProxyAuth {
+            username: username.into(),
+            password: password.into(),
+        });
+        self
+    }
+
+    /// Hosts reached without the proxies of [ConnectionOptions::with_proxy], a comma separated
+    /// list of hosts, domains such as `.corp.example`, IP addresses and CIDR blocks, like
+    /// the `NO_PROXY` environment variable.
+    pub fn with_no_proxy<S: Into<String>>(mut self, no_proxy: S) -> Self {
+        self.no_proxy = Some(no_proxy.into());
+        self
+    }
+
+    /// Ignore the proxies of the `HTTP_PROXY`, `HTTPS_PROXY` and `ALL_PROXY` environment
+    /// variables, connecting directly unless [ConnectionOptions::with_proxy] is set.
+    pub fn without_system_proxy(mut self) -> Self {
+        self.without_system_proxy = true;
+        self
+    }
+
+    /// Apply these options to an existing [reqwest::ClientBuilder].
+    pub fn apply(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        if let Some(timeout) = self.connect_timeout {
+            builder = builder.connect_timeout(timeout);
+        }
+        if let Some(timeout) = self.timeout {
+            builder = builder.timeout(timeout);
+        }
+        if let Some(timeout) = self.read_timeout {
+            builder = builder.read_timeout(timeout);
+        }
+        if let Some(timeout) = self.pool_idle_timeout {
+            builder = builder.pool_idle_timeout(timeout);
+        }
+        if let Some(max) = self.pool_max_idle_per_host {
+            builder = builder.pool_max_idle_per_host(max);
+        }
+        if let Some(interval) = self.tcp_keepalive {
+            builder = builder.tcp_keepalive(interval);
+        }
+        if let Some(enabled) = self.tcp_nodelay {
+            builder = builder.tcp_nodelay(enabled);
+        }
+        #[cfg(feature = "http2")]
+        if let Some(enabled) = self.http2_adaptive_window {
+            builder = builder.http2_adaptive_window(enabled);
+        }
+        if let Some(user_agent) = &self.user_agent {
+            builder = builder.user_agent(user_agent);
+        }
+        if self.without_system_proxy || !self.proxies.is_empty() {
+            builder = builder.no_proxy();
+        }
+        let no_proxy = self
+            .no_proxy
+            .as_deref()
+            .and_then(reqwest::NoProxy::from_string);
+        // the urls were validated when added
+        for proxy in self.proxies.iter().flat_map(reqwest::Proxy::all) {
+            let proxy = match &self.proxy_auth {
+                Some(auth) => proxy.basic_auth(&auth.username, &auth.password),
+                None => proxy,
+            };
+            builder = builder.proxy(proxy.no_proxy(no_proxy.clone()));
+        }
+        self.apply_tls(builder)
+    }
+
+    #[cfg(not(any(
+        feature = "rustls",
+        feature = "rustls-webpki-roots",
+        feature = "native-tls"
+    )))]
+    fn apply_tls(&self, builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        builder
+    }
+
+    #[cfg(any(
+        feature = "rustls",
+        feature = "rustls-webpki-roots",
+        feature = "native-tls"
+    ))]
+    fn apply_tls(&self, mut builder: reqwest::ClientBuilder) -> reqwest::ClientBuilder {
+        match self.tls_backend {
+            #[cfg(any(feature = "rustls", feature = "rustls-webpki-roots"))]
+            Some(TlsBackend::Rustls) => builder = builder.use_rustls_tls(),
+            #[cfg(feature = "native-tls")]
+            Some(TlsBackend::NativeTls) => builder = builder.use_native_tls(),
+            None => {}
+        }
+        if let Some(enabled) = self.built_in_root_certificates {
+            builder = builder.tls_built_in_root_certs(enabled);
+        }
+        // the certificates were validated when added
+        for pem in &self.root_certificates {
+            for certificate in reqwest::Certificate::from_pem_bundle(pem).unwrap_or_default() {
+                builder = builder.add_root_certificate(certificate);
+            }
+        }
+        builder
+    }
+
+    /// Build a [reqwest::Client] with these options.
+    pub fn build_http_client(&self) -> Result<reqwest::Client, OpenAIError> {
+        Ok(self.apply(reqwest::Client::builder()).build()?)
+    }
+}
+
+#[cfg(any(
+    feature = "rustls",
+    feature = "rustls-webpki-roots",
+    feature = "native-tls"
+))]
+#[cfg_attr(
+    docsrs,
+    doc(cfg(any(
+        feature = "rustls",
+        feature = "rustls-webpki-roots",
+        feature = "native-tls"
+    )))
+)]
+impl ConnectionOptions {
+    /// Use `backend` for TLS, the default backend of reqwest being native TLS when both
+    /// backends are enabled.
+    pub fn with_tls_backend(mut self, backend: TlsBackend) -> Self {
+        self.tls_backend = Some(backend);
+        self
+    }
+
+    /// Trust the root certificates of the PEM `pem`, one or more certificates, e.g. the CA of
+    /// a proxy intercepting TLS, in addition to the built-in ones.
+    pub fn with_root_certificate_pem<B: Into<Vec<u8>>>(
+        mut self,
+        pem: B,
+    ) -> Result<Self, OpenAIError> {
+        let pem = pem.into();
+        let certificates = reqwest::Certificate::from_pem_bundle(&pem).map_err(|error| {
+            OpenAIError::InvalidArgument(format!("invalid PEM certificate: {error}"))
+        })?;
+        if certificates.is_empty() {
+            return Err(OpenAIError::InvalidArgument(
+                "no certificate in the PEM".into(),
+            ));
+        }
+        self.root_certificates.push(pem);
+        Ok(self)
+    }
+
+    /// [ConnectionOptions::with_root_certificate_pem] of the PEM file at `path`.
+    pub fn with_root_certificate_file<P: AsRef<std::path::Path>>(
+        self,
+        path: P,
+    ) -> Result<Self, OpenAIError> {
+        let path = path.as_ref();
+        let pem = std::fs::read(path)
+            .map_err(|error| OpenAIError::FileReadError(format!("{}: {error}", path.display())))?;
+        self.with_root_certificate_pem(pem)
+    }
+
+    /// Trust only the root certificates of [ConnectionOptions::with_root_certificate_pem], not
+    /// the built-in ones of the TLS backend.
+    pub fn without_built_in_root_certificates(mut self) -> Self {
+        self.built_in_root_certificates = Some(false);
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_proxy_options() {
+        assert!(ConnectionOptions::new().with_proxy("not a url").is_err());
+        let options = ConnectionOptions::new()
+            .with_proxy("http://proxy.example:3128")
+            .unwrap()
+            .with_proxy_auth("user", "hunter2")
+            .with_no_proxy("localhost");
+        assert!(!format!("{options:?}").contains("hunter2"));
+        options.build_http_client().unwrap();
+    }
+
+    #[test]
+    fn test_builder_options() {
+        let options = ConnectionOptions::new()
+            .with_connect_timeout(Duration::from_secs(5))
+            .with_timeout(Duration::from_secs(60))
+            .with_tcp_nodelay(true)
+            .with_user_agent("app/1.0")
+            .with_proxy("http://proxy.example:3128")
+            .unwrap();
+        #[cfg(feature = "http2")]
+        let options = options.with_http2_adaptive_window(true);
+        // the builder only prints some of its settings
+        let builder = format!("{:?}", options.apply(reqwest::Client::builder()));
+        for setting in [
+            "connect_timeout: 5s",
+            "timeout: 60s",
+            "tcp_nodelay: true",
+            "\"user-agent\": \"app/1.0\"",
+            "proxy.example:3128",
+        ] {
+            assert!(builder.contains(setting), "{setting} not in {builder}");
+        }
+        options.build_http_client().unwrap();
+    }
+}
@@ -0,0 +1,190 @@
+use futures::Stream;
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::list_stream,
+    request_options::{impl_request_options, RequestOptions},
+    types::conversations::{
+        Conversation, ConversationItem, ConversationItemList, CreateConversationItemsRequest,
+        CreateConversationRequest, DeleteConversationResponse, UpdateConversationRequest,
+    },
+    Client,
+};
+
+/// Create and manage conversations storing the items of multi-turn interactions with the
+/// Responses API.
+///
+/// Related guide: [Conversation state](https://platform.openai.com/docs/guides/conversation-state)
+pub struct Conversations<'c, C: Config> {
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(Conversations);
+crate::util::impl_api_group_debug!(Conversations, "/conversations");
+
+impl<'c, C: Config> Conversations<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// Call [ConversationItems] group API to manage the items of the [conversation_id] conversation.
+    pub fn items(&self, conversation_id: &str) -> ConversationItems<'c, C> {
+        ConversationItems::new(self.client, conversation_id)
+            .with_options(self.request_options.clone())
+    }
+
+    /// Create a conversation, with up to 20 initial items.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create(
+        &self,
+        request: CreateConversationRequest,
+    ) -> Result<Conversation, OpenAIError> {
+        self.client
+            .post("/conversations", request, &self.request_options)
+            .await
+    }
+
+    /// Retrieves a conversation.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve(&self, conversation_id: &str) -> Result<Conversation, OpenAIError> {
+        self.client
+            .get(
+                &format!("/conversations/{conversation_id}"),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Updates the metadata of a conversation.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn update(
+        &self,
+        conversation_id: &str,
+        request: UpdateConversationRequest,
+    ) -> Result<Conversation, OpenAIError> {
+        self.client
+            .post(
+                &format!("/conversations/{conversation_id}"),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Deletes a conversation. Its items are not deleted.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(
+        &self,
+        conversation_id: &str,
+    ) -> Result<DeleteConversationResponse, OpenAIError> {
+        self.client
+            .delete(
+                &format!("/conversations/{conversation_id}"),
+                &self.request_options,
+            )
+            .await
+    }
+}
+
+/// Items of a [conversation](https://platform.openai.com/docs/api-reference/conversations).
+pub struct ConversationItems<'c, C: Config> {
+    /// The ID of the conversation the items belong to.
+    pub conversation_id: String,
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(ConversationItems);
+crate::util::impl_api_group_debug!(
+    ConversationItems,
+    "/conversations/{}/items",
+    conversation_id
+);
+
+impl<'c, C: Config> ConversationItems<'c, C> {
+    pub fn new(client: &'c Client<C>, conversation_id: &str) -> Self {
+        Self {
+            client,
+            conversation_id: conversation_id.into(),
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// Appends up to 20 items to the conversation, returning the created items.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create(
+        &self,
+        request: CreateConversationItemsRequest,
+    ) -> Result<ConversationItemList, OpenAIError> {
+        self.client
+            .post(
+                &format!("/conversations/{}/items", self.conversation_id),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Retrieves an item of the conversation.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn retrieve(&self, item_id: &str) -> Result<ConversationItem, OpenAIError> {
+        self.client
+            .get(
+                &format!("/conversations/{}/items/{item_id}", self.conversation_id),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Deletes an item of the conversation, returning the conversation.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete(&self, item_id: &str) -> Result<Conversation, OpenAIError> {
+        self.client
+            .delete(
+                &format!("/conversations/{}/items/{item_id}", self.conversation_id),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Returns a list of the items of the conversation.
+    ///
+    /// `query` is usually a [ListConversationItemsQuery](crate::types::conversations::ListConversationItemsQuery).
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<ConversationItemList, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                &format!("/conversations/{}/items", self.conversation_id),
+                &query,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Streams all the items of the conversation, fetching pages as needed.
+    ///
+    /// `query` takes the same parameters as [Self::list], `after` is used as the starting cursor.
+    pub fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> impl Stream<Item = Result<ConversationItem, OpenAIError>> + 'c
+    where
+        Q: Serialize + ?Sized,
+    {
+        list_stream::<_, _, ConversationItemList>(
+            self.client,
+            format!("/conversations/{}/items", self.conversation_id),
+            query,
+            self.request_options.clone(),
+        )
+    }
+}
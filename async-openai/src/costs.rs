@@ -0,0 +1,39 @@
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
+    types::CostsResponse,
+    Client,
+};
+
+/// Costs of the organization, aggregated over time buckets.
+pub struct Costs<'c, C: Config> {
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(Costs);
+crate::util::impl_api_group_debug!(Costs, "/organization/costs");
+
+impl<'c, C: Config> Costs<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// Get costs details for the organization.
+    /// `start_time` is required in `query`, results can be grouped by `project_id` or `line_item` with `group_by`.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<CostsResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query("/organization/costs", &query, &self.request_options)
+            .await
+    }
+}
@@ -0,0 +1,252 @@
+//! Credentials fetched at request time, for short lived tokens such as Azure AD tokens.
+//!
+//! When a [CredentialProvider] is set with [crate::Client::with_credential_provider], a request
+//! rejected with `401 Unauthorized` is sent one more time after [CredentialProvider::refresh].
+use std::{fmt, future::Future, sync::Arc};
+
+use futures::future::BoxFuture;
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION};
+use secrecy::{ExposeSecret, SecretString};
+
+use crate::error::{ApiError, OpenAIError};
+
+/// Header used by [crate::config::AzureConfig] to send the API key
+const AZURE_API_KEY_HEADER: &str = "api-key";
+
+/// Source of the credentials sent with every request, replacing the API key of the [crate::config::Config].
+pub trait CredentialProvider: Send + Sync {
+    /// Headers authenticating the next request.
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, OpenAIError>>;
+
+    /// Fetch new credentials after a request was rejected with `401 Unauthorized`.
+    fn refresh(&self) -> BoxFuture<'_, Result<(), OpenAIError>>;
+}
+
+type FetchToken =
+    dyn Fn() -> BoxFuture<'static, Result<String, OpenAIError>> + Send + Sync + 'static;
+
+/// [CredentialProvider] caching a token fetched with an async function until the API rejects it.
+///
+/// The token is sent as `Authorization: Bearer <token>` by default, which is what
+/// Azure OpenAI expects for Microsoft Entra ID (Azure AD) tokens.
+///
+/// ```no_run
+/// use async_openai::{config::AzureConfig, credentials::TokenProvider, Client};
+///
+/// # async fn fetch_azure_ad_token() -> Result<String, async_openai::error::OpenAIError> { todo!() }
+/// let provider = TokenProvider::new(|| async { fetch_azure_ad_token().await });
+///
+/// let client = Client::with_config(AzureConfig::new())
+///     .with_credential_provider(provider)
+///     .with_reauth_hook(|error| tracing::info!("refreshed token: {}", error.message));
+/// ```
+pub struct TokenProvider {
+    fetch: Arc<FetchToken>,
+    header: HeaderName,
+    bearer: bool,
+    token: tokio::sync::Mutex<Option<SecretString>>,
+}
+
+impl TokenProvider {
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<String, OpenAIError>> + Send + 'static,
+    {
+        Self {
+            fetch: Arc::new(move || Box::pin(fetch())),
+            header: AUTHORIZATION,
+            bearer: true,
+            token: Default::default(),
+        }
+    }
+
+    /// Send the token as is in `header` instead of `Authorization: Bearer`, e.g. `api-key` for rotating Azure keys.
+    pub fn with_header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self.bearer = false;
+        self
+    }
+
+    async fn fetch(&self) -> Result<SecretString, OpenAIError> {
+        Ok(SecretString::from((self.fetch)().await?))
+    }
+}
+
+impl CredentialProvider for TokenProvider {
+    fn headers(&self) -> BoxFuture<'_, Result<HeaderMap, OpenAIError>> {
+        Box::pin(async move {
+            let mut token = self.token.lock().await;
+            if token.is_none() {
+                *token = Some(self.fetch().await?);
+            }
+            let token = token.as_ref().map(|t| t.expose_secret()).unwrap_or("");
+
+            let value = if self.bearer {
+                format!("Bearer {token}")
+            } else {
+                token.to_string()
+            };
+            let mut value = HeaderValue::from_str(&value).map_err(|_| {
+                OpenAIError::InvalidArgument("token is not a valid header value".into())
+            })?;
+            value.set_sensitive(true);
+
+            let mut headers = HeaderMap::new();
+            headers.insert(self.header.clone(), value);
+            Ok(headers)
+        })
+    }
+
+    fn refresh(&self) -> BoxFuture<'_, Result<(), OpenAIError>> {
+        Box::pin(async move {
+            let mut token = self.token.lock().await;
+            *token = Some(self.fetch().await?);
+            Ok(())
+        })
+    }
+}
+
+impl fmt::Debug for TokenProvider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TokenProvider")
+            .field("header", &self.header)
+            .field("bearer", &self.bearer)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Hook of [crate::Client::with_reauth_hook].
+#[derive(Clone)]
+pub(crate) struct ReauthHook(Arc<dyn Fn(&ApiError) + Send + Sync + 'static>);
+
+impl ReauthHook {
+    pub(crate) fn new<F: Fn(&ApiError) + Send + Sync + 'static>(hook: F) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+
+impl fmt::Debug for ReauthHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ReauthHook").finish_non_exhaustive()
+    }
+}
+
+/// Provider and re-authentication hook of a [crate::Client].
+#[derive(Clone)]
+pub(crate) struct Credentials {
+    provider: Arc<dyn CredentialProvider>,
+    on_reauth: Option<ReauthHook>,
+}
+
+impl Credentials {
+    pub(crate) fn new(provider: Arc<dyn CredentialProvider>) -> Self {
+        Self {
+            provider,
+            on_reauth: None,
+        }
+    }
+
+    pub(crate) fn set_reauth_hook(&mut self, hook: ReauthHook) {
+        self.on_reauth = Some(hook);
+    }
+
+    /// Replace the credential headers of the config in `headers` with those of the provider.
+    pub(crate) async fn apply(&self, headers: &mut HeaderMap) -> Result<(), OpenAIError> {
+        let credentials = self.provider.headers().await?;
+        headers.remove(AUTHORIZATION);
+        headers.remove(AZURE_API_KEY_HEADER);
+        headers.extend(credentials);
+        Ok(())
+    }
+
+    /// Refresh credentials after `error`, a `401 Unauthorized` response.
+    pub(crate) async fn reauthenticate(&self, error: &ApiError) -> Result<(), OpenAIError> {
+        tracing::info!("Unauthorized, refreshing credentials: {}", error.message);
+        if let Some(hook) = &self.on_reauth {
+            (hook.0)(error);
+        }
+        self.provider.refresh().await
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("on_reauth", &self.on_reauth.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+    use crate::transport::mock::{MockResponse, MockTransport};
+
+    #[test]
+    fn test_token_provider_refresh() {
+        let fetched = Arc::new(AtomicU32::new(0));
+        let counter = fetched.clone();
+        let provider = TokenProvider::new(move || {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            async move { Ok(format!("token-{n}")) }
+        });
+        let credentials = Credentials::new(Arc::new(provider));
+
+        tokio_test::block_on(async {
+            let mut headers = HeaderMap::new();
+            headers.insert(AZURE_API_KEY_HEADER, HeaderValue::from_static("static"));
+            credentials.apply(&mut headers).await.unwrap();
+            credentials.apply(&mut headers).await.unwrap();
+            assert!(headers.get(AZURE_API_KEY_HEADER).is_none());
+            assert_eq!(headers[AUTHORIZATION], "Bearer token-1");
+
+            let error = ApiError::from_body("expired".into());
+            credentials.reauthenticate(&error).await.unwrap();
+            credentials.apply(&mut headers).await.unwrap();
+            assert_eq!(headers[AUTHORIZATION], "Bearer token-2");
+        });
+        assert_eq!(fetched.load(Ordering::SeqCst), 2);
+    }
+
+    /// Rejects the first request with `401 Unauthorized`, lists no models afterwards.
+    fn expiring() -> Arc<MockTransport> {
+        MockTransport::new(|_, index| {
+            Ok(match index {
+                0 => MockResponse::new(
+                    r#"{"error": {"message": "expired", "type": null, "param": null, "code": null}}"#,
+                )
+                .status(401),
+                _ => MockResponse::new(r#"{"object": "list", "data": []}"#),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_reauth_hook_order() {
+        let provider = || TokenProvider::new(|| async { Ok("token".to_string()) });
+        let hooked = Arc::new(AtomicU32::new(0));
+        let hook = |hooked: &Arc<AtomicU32>| {
+            let hooked = hooked.clone();
+            move |_: &ApiError| {
+                hooked.fetch_add(1, Ordering::SeqCst);
+            }
+        };
+
+        let before = crate::Client::new()
+            .with_transport(expiring())
+            .with_credential_provider(provider())
+            .with_reauth_hook(hook(&hooked));
+        before.models().list().await.unwrap();
+        assert_eq!(hooked.load(Ordering::SeqCst), 1);
+
+        let after = crate::Client::new()
+            .with_transport(expiring())
+            .with_reauth_hook(hook(&hooked))
+            .with_credential_provider(provider());
+        after.models().list().await.unwrap();
+        assert_eq!(hooked.load(Ordering::SeqCst), 2);
+    }
+}
@@ -1,10 +1,13 @@
 use std::path::{Path, PathBuf};
 
 use base64::{engine::general_purpose, Engine as _};
+use bytes::Bytes;
+use futures::{Stream, StreamExt};
 use rand::{distr::Alphanumeric, Rng};
 use reqwest::Url;
+use tokio::io::AsyncWriteExt;
 
-use crate::error::OpenAIError;
+use crate::{error::OpenAIError, types::DownloadProgress};
 
 fn create_paths<P: AsRef<Path>>(url: &Url, base_dir: P) -> (PathBuf, PathBuf) {
     let mut dir = PathBuf::from(base_dir.as_ref());
@@ -44,26 +47,119 @@ pub(crate) async fn download_url<P: AsRef<Path>>(
         .await
         .map_err(|e| OpenAIError::FileSaveError(format!("{}, dir: {}", e, dir.display())))?;
 
-    tokio::fs::write(
-        file_path.as_path(),
-        response.bytes().await.map_err(|e| {
-            OpenAIError::FileSaveError(format!("{}, file path: {}", e, file_path.display()))
-        })?,
-    )
-    .await
-    .map_err(|e| OpenAIError::FileSaveError(e.to_string()))?;
+    let total = response.content_length();
+    let stream = response
+        .bytes_stream()
+        .map(|chunk| chunk.map_err(OpenAIError::Reqwest));
+    save_stream(stream, file_path.as_path(), total, |_| {}).await?;
 
     Ok(file_path)
 }
 
-pub(crate) async fn save_b64<P: AsRef<Path>>(b64: &str, dir: P) -> Result<PathBuf, OpenAIError> {
+/// Write `stream` to `path` chunk by chunk, calling `progress` after every chunk.
+/// Returns the number of bytes written.
+///
+/// The chunks are written to a temporary file next to `path`, renamed to `path` once the stream
+/// ended. On error the temporary file is removed, leaving `path` as it was.
+pub(crate) async fn save_stream<S, F>(
+    stream: S,
+    path: &Path,
+    total: Option<u64>,
+    progress: F,
+) -> Result<u64, OpenAIError>
+where
+    S: Stream<Item = Result<Bytes, OpenAIError>>,
+    F: FnMut(DownloadProgress),
+{
+    let partial = partial_path(path);
+    let mut result = write_stream(stream, &partial, total, progress).await;
+    if let Ok(downloaded) = result {
+        result = tokio::fs::rename(&partial, path)
+            .await
+            .map(|_| downloaded)
+            .map_err(|e| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display())));
+    }
+    if result.is_err() {
+        let _ = tokio::fs::remove_file(&partial).await;
+    }
+    result
+}
+
+/// Hidden file next to `path` to write its content to until complete.
+fn partial_path(path: &Path) -> PathBuf {
+    let suffix: String = rand::rng()
+        .sample_iter(&Alphanumeric)
+        .take(8)
+        .map(char::from)
+        .collect();
+    let name = path
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    path.with_file_name(format!(".{name}.{suffix}.part"))
+}
+
+async fn write_stream<S, F>(
+    stream: S,
+    path: &Path,
+    total: Option<u64>,
+    mut progress: F,
+) -> Result<u64, OpenAIError>
+where
+    S: Stream<Item = Result<Bytes, OpenAIError>>,
+    F: FnMut(DownloadProgress),
+{
+    let file_error =
+        |e: std::io::Error| OpenAIError::FileSaveError(format!("{}, path: {}", e, path.display()));
+
+    let mut file = tokio::fs::File::create(path).await.map_err(file_error)?;
+    let mut downloaded = 0;
+
+    futures::pin_mut!(stream);
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk?;
+        file.write_all(&chunk).await.map_err(file_error)?;
+        downloaded += chunk.len() as u64;
+        progress(DownloadProgress { downloaded, total });
+    }
+    file.flush().await.map_err(file_error)?;
+
+    Ok(downloaded)
+}
+
+/// Download the body of `url` into memory.
+pub(crate) async fn fetch_url(url: &str) -> Result<Bytes, OpenAIError> {
+    let response = reqwest::get(url).await?;
+
+    if !response.status().is_success() {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "couldn't download file, status: {}, url: {url}",
+            response.status()
+        )));
+    }
+
+    Ok(response.bytes().await?)
+}
+
+pub(crate) fn decode_b64(b64: &str) -> Result<Bytes, OpenAIError> {
+    general_purpose::STANDARD
+        .decode(b64)
+        .map(Bytes::from)
+        .map_err(|e| OpenAIError::InvalidArgument(format!("invalid base64 data: {e}")))
+}
+
+pub(crate) async fn save_b64<P: AsRef<Path>>(
+    b64: &str,
+    dir: P,
+    extension: &str,
+) -> Result<PathBuf, OpenAIError> {
     let filename: String = rand::rng()
         .sample_iter(&Alphanumeric)
         .take(10)
         .map(char::from)
         .collect();
 
-    let filename = format!("{filename}.png");
+    let filename = format!("{filename}.{extension}");
 
     let path = PathBuf::from(dir.as_ref()).join(filename);
 
@@ -1,24 +1,34 @@
+use futures::{stream, StreamExt, TryStreamExt};
+
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{CreateBase64EmbeddingResponse, CreateEmbeddingRequest, CreateEmbeddingResponse},
+    request_options::{impl_request_options, RequestOptions},
+    types::{
+        CreateBase64EmbeddingResponse, CreateEmbeddingRequest, CreateEmbeddingResponse,
+        EmbeddingChunkOptions, EmbeddingInput, EmbeddingUsage, EncodingFormat,
+    },
     Client,
 };
 
-#[cfg(not(feature = "byot"))]
-use crate::types::EncodingFormat;
-
 /// Get a vector representation of a given input that can be easily
 /// consumed by machine learning models and algorithms.
 ///
 /// Related guide: [Embeddings](https://platform.openai.com/docs/guides/embeddings/what-are-embeddings)
 pub struct Embeddings<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Embeddings);
+crate::util::impl_api_group_debug!(Embeddings, "/embeddings");
+
 impl<'c, C: Config> Embeddings<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates an embedding vector representing the input text.
@@ -37,7 +47,15 @@ impl<'c, C: Config> Embeddings<'c, C> {
                 ));
             }
         }
-        self.client.post("/embeddings", request).await
+        self.client
+            .post_cached(
+                "/embeddings",
+                request,
+                &self.request_options,
+                crate::cache::always,
+                true,
+            )
+            .await
     }
 
     /// Creates an embedding vector representing the input text.
@@ -58,13 +76,82 @@ impl<'c, C: Config> Embeddings<'c, C> {
                 ));
             }
         }
-        self.client.post("/embeddings", request).await
+        self.client
+            .post_cached(
+                "/embeddings",
+                request,
+                &self.request_options,
+                crate::cache::always,
+                true,
+            )
+            .await
+    }
+
+    /// Creates embedding vectors for the input, in the order of the inputs.
+    ///
+    /// Embeddings are always requested in base64, which is much faster to parse than
+    /// a JSON list of floats, and decoded to `f32`.
+    pub async fn create_float(
+        &self,
+        mut request: CreateEmbeddingRequest,
+    ) -> Result<Vec<Vec<f32>>, OpenAIError> {
+        request.encoding_format = Some(EncodingFormat::Base64);
+        let response = self.create_base64(request).await?;
+
+        let mut data = response.data;
+        data.sort_by_key(|embedding| embedding.index);
+        data.iter()
+            .map(|embedding| embedding.embedding.decode())
+            .collect()
+    }
+
+    /// Creates embeddings for any number of inputs by splitting them into requests within
+    /// the endpoint limits, see [EmbeddingChunkOptions].
+    ///
+    /// Requests run concurrently, the merged response lists embeddings in input order
+    /// (with `index` relative to all inputs) and the usage of all requests combined.
+    pub async fn create_chunked<I: Into<EmbeddingInput>>(
+        &self,
+        input: I,
+        model: &str,
+        options: &EmbeddingChunkOptions,
+    ) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        let requests = options.chunk_requests(model, input.into());
+
+        let responses: Vec<CreateEmbeddingResponse> = stream::iter(requests)
+            .map(|request| async move { self.create_base64(request).await?.decode() })
+            .buffered(options.max_concurrency())
+            .try_collect()
+            .await?;
+
+        let mut merged = CreateEmbeddingResponse {
+            object: "list".into(),
+            model: model.to_string(),
+            data: vec![],
+            usage: EmbeddingUsage {
+                prompt_tokens: 0,
+                total_tokens: 0,
+            },
+        };
+        for mut response in responses {
+            let offset = merged.data.len() as u32;
+            response.data.sort_by_key(|embedding| embedding.index);
+            merged
+                .data
+                .extend(response.data.into_iter().map(|mut embedding| {
+                    embedding.index += offset;
+                    embedding
+                }));
+            merged.model = response.model;
+            merged.usage.prompt_tokens += response.usage.prompt_tokens;
+            merged.usage.total_tokens += response.usage.total_tokens;
+        }
+        Ok(merged)
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::error::OpenAIError;
     use crate::types::{CreateEmbeddingResponse, Embedding, EncodingFormat};
     use crate::{types::CreateEmbeddingRequestArgs, Client};
 
@@ -167,6 +254,8 @@ mod tests {
     #[tokio::test]
     #[cfg(not(feature = "byot"))]
     async fn test_cannot_use_base64_encoding_with_normal_create_request() {
+        use crate::error::OpenAIError;
+
         let client = Client::new();
 
         const MODEL: &str = "text-embedding-ada-002";
@@ -1,5 +1,6 @@
 //! Errors originating from API calls, parsing responses, and reading-or-writing to the file system.
 use axum::http::StatusCode;
+use reqwest::header::HeaderMap;
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, thiserror::Error)]
@@ -19,13 +20,130 @@ pub enum OpenAIError {
     /// Error on the client side when reading file from file system
     #[error("failed to read file: {0}")]
     FileReadError(String),
-    /// Error on SSE streaming
+    /// Error on SSE streaming, such as an event which is not valid UTF-8 or SSE
     #[error("stream failed: {0}")]
     StreamError(String),
     /// Error from client side validation
     /// or when builder fails to build request before making API call
     #[error("invalid args: {0}")]
     InvalidArgument(String),
+    /// Error when a `wait` helper gives up before the polled object reached a terminal status
+    #[error("timed out: {0}")]
+    Timeout(String),
+    /// Error when a tenant exceeded its client side limits, see [crate::quota::TenantQuotas]
+    #[error("{0}")]
+    QuotaExceeded(QuotaExceeded),
+    /// Error when a stream failed after its first event and could not be reconnected,
+    /// see [crate::reconnect::ReconnectPolicy]
+    #[error("{0}")]
+    StreamInterrupted(StreamInterrupted),
+    /// Error of a custom [crate::transport::HttpClient] failing to send a request
+    #[error("transport error: {0}")]
+    Transport(String),
+    /// Error when a request is made after [crate::Client::shutdown], or cancelled by it
+    #[error("client is shut down")]
+    ShutDown,
+    /// Error when an upload is rejected as too large (HTTP 413) or of an unsupported media
+    /// type (HTTP 415)
+    #[error("{0}")]
+    Upload(Box<UploadError>),
+    /// Error when a replayed request has no recorded fixture, see `replay::Replay`
+    #[error("missing fixture: {0}")]
+    MissingFixture(String),
+}
+
+impl OpenAIError {
+    /// HTTP status code of the failed API call, if any.
+    pub fn status(&self) -> Option<StatusCode> {
+        match self {
+            OpenAIError::ApiError(err) => err.status,
+            OpenAIError::Upload(err) => err.error.status,
+            OpenAIError::Reqwest(err) => err.status(),
+            _ => None,
+        }
+    }
+
+    fn api_error(&self) -> Option<&ApiError> {
+        match self {
+            OpenAIError::ApiError(err) => Some(err),
+            OpenAIError::Upload(err) => Some(&err.error),
+            _ => None,
+        }
+    }
+
+    fn has_code(&self, code: &str) -> bool {
+        self.api_error().is_some_and(|err| {
+            err.code.as_deref() == Some(code) || err.r#type.as_deref() == Some(code)
+        })
+    }
+
+    /// Request was rate limited (HTTP 429), excluding [OpenAIError::is_quota_exceeded].
+    pub fn is_rate_limit(&self) -> bool {
+        self.status() == Some(StatusCode::TOO_MANY_REQUESTS) && !self.is_quota_exceeded()
+    }
+
+    /// Billing quota of the account is exhausted, retrying will not help.
+    ///
+    /// Client side tenant limits are reported as [OpenAIError::QuotaExceeded] instead.
+    pub fn is_quota_exceeded(&self) -> bool {
+        self.has_code("insufficient_quota")
+    }
+
+    /// API key is missing, invalid or lacks permissions (HTTP 401 or 403).
+    pub fn is_auth_error(&self) -> bool {
+        matches!(
+            self.status(),
+            Some(StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+        ) || self.has_code("invalid_api_key")
+    }
+
+    /// Requested resource does not exist (HTTP 404).
+    pub fn is_not_found(&self) -> bool {
+        self.status() == Some(StatusCode::NOT_FOUND)
+    }
+
+    /// Prompt and requested completion do not fit in the model context window.
+    pub fn is_context_length_exceeded(&self) -> bool {
+        self.has_code("context_length_exceeded")
+    }
+
+    /// API failed on the server side (HTTP 5xx), or sent a `server_error` in a stream.
+    pub fn is_server_error(&self) -> bool {
+        self.status().is_some_and(|status| status.is_server_error())
+            || self
+                .api_error()
+                .is_some_and(|err| err.in_stream && err.r#type.as_deref() == Some("server_error"))
+    }
+
+    /// Why a stream, or the request starting it, failed: `None` for errors which are not
+    /// failures of the connection, the API or the events, such as invalid arguments.
+    pub fn stream_failure(&self) -> Option<StreamFailure> {
+        match self {
+            OpenAIError::Reqwest(_) | OpenAIError::Transport(_) => Some(StreamFailure::Transport),
+            OpenAIError::ApiError(err) if err.in_stream => Some(StreamFailure::InStream),
+            OpenAIError::ApiError(_) | OpenAIError::Upload(_) => Some(StreamFailure::Response),
+            OpenAIError::JSONDeserialize(..) | OpenAIError::StreamError(_) => {
+                Some(StreamFailure::Malformed)
+            }
+            OpenAIError::StreamInterrupted(interrupted) => interrupted.error.stream_failure(),
+            _ => None,
+        }
+    }
+}
+
+/// Kind of failure of a stream, see [OpenAIError::stream_failure].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StreamFailure {
+    /// The connection failed or dropped, retrying may succeed.
+    Transport,
+    /// The API rejected the request before streaming, see [OpenAIError::status].
+    Response,
+    /// The API sent an error event after the stream started, such as a `server_error` when
+    /// overloaded mid-generation.
+    InStream,
+    /// An event was not valid UTF-8, SSE or JSON of the expected type, retrying will likely
+    /// fail the same way.
+    Malformed,
 }
 
 impl axum::response::IntoResponse for OpenAIError {
@@ -36,8 +154,9 @@ impl axum::response::IntoResponse for OpenAIError {
                 .code
                 .as_ref()
                 .map(|c| StatusCode::from_bytes(c.as_bytes()));
-            let status = match try_status {
-                Some(Ok(code)) => code,
+            let status = match (err.status, try_status) {
+                (Some(status), _) => status,
+                (None, Some(Ok(code))) => code,
                 _ => StatusCode::INTERNAL_SERVER_ERROR,
             };
             return (status, axum::Json(err)).into_response();
@@ -59,6 +178,11 @@ impl axum::response::IntoResponse for OpenAIError {
                 }
             }
             OpenAIError::InvalidArgument(_) => StatusCode::BAD_REQUEST,
+            OpenAIError::Timeout(_) => StatusCode::GATEWAY_TIMEOUT,
+            OpenAIError::Transport(_) => StatusCode::BAD_GATEWAY,
+            OpenAIError::ShutDown => StatusCode::SERVICE_UNAVAILABLE,
+            OpenAIError::QuotaExceeded(_) => StatusCode::TOO_MANY_REQUESTS,
+            OpenAIError::Upload(err) => err.error.status.unwrap_or(StatusCode::BAD_REQUEST),
             _ => StatusCode::INTERNAL_SERVER_ERROR,
         };
 
@@ -66,6 +190,171 @@ impl axum::response::IntoResponse for OpenAIError {
     }
 }
 
+/// The limit of a tenant which was exceeded.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuotaLimit {
+    /// Requests per minute.
+    Requests,
+    /// Tokens per minute.
+    Tokens,
+}
+
+/// A tenant exceeded one of its limits, the request was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuotaExceeded {
+    pub tenant: String,
+    pub limit: QuotaLimit,
+    /// The configured maximum per minute.
+    pub max: u32,
+    /// How long until enough of the window frees up for the request to be accepted.
+    pub retry_after: std::time::Duration,
+}
+
+impl std::fmt::Display for QuotaExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let limit = match self.limit {
+            QuotaLimit::Requests => "requests",
+            QuotaLimit::Tokens => "tokens",
+        };
+        write!(
+            f,
+            "tenant {} exceeded {} {limit} per minute, retry after {:?}",
+            self.tenant, self.max, self.retry_after
+        )
+    }
+}
+
+/// A stream failed after some of its events were received.
+#[derive(Debug)]
+pub struct StreamInterrupted {
+    /// The error which interrupted the stream.
+    pub error: Box<OpenAIError>,
+    /// Reconnection attempts made before giving up.
+    pub attempts: u32,
+    /// Data of the events received before the interruption, in order.
+    pub events: Vec<String>,
+}
+
+impl StreamInterrupted {
+    /// The received events deserialized, e.g. into chat completion chunks to recover the partial content.
+    pub fn chunks<O: serde::de::DeserializeOwned>(&self) -> Result<Vec<O>, OpenAIError> {
+        self.events
+            .iter()
+            .filter(|data| data.as_str() != "[DONE]")
+            .map(|data| {
+                serde_json::from_str(data)
+                    .map_err(|e| map_deserialization_error(e, data.as_bytes()))
+            })
+            .collect()
+    }
+}
+
+impl std::fmt::Display for StreamInterrupted {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "stream interrupted after {} events and {} reconnection attempts: {}",
+            self.events.len(),
+            self.attempts,
+            self.error
+        )
+    }
+}
+
+/// Why an upload was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UploadErrorKind {
+    /// HTTP 413, a file or the whole request is larger than the endpoint accepts.
+    PayloadTooLarge,
+    /// HTTP 415, a file is not in one of the formats the endpoint accepts.
+    UnsupportedMediaType,
+}
+
+/// A file of a rejected upload, as it was sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UploadedFile {
+    pub file_name: String,
+    /// MIME type detected from the extension of the file name, sent as the content type of
+    /// the file part.
+    pub content_type: Option<String>,
+    /// Size in bytes, if known before sending.
+    pub size: Option<u64>,
+}
+
+/// An upload rejected with `413 Payload Too Large` or `415 Unsupported Media Type`.
+#[derive(Debug, Clone)]
+pub struct UploadError {
+    pub kind: UploadErrorKind,
+    /// Path of the endpoint, e.g. `/audio/transcriptions`.
+    pub path: String,
+    pub files: Vec<UploadedFile>,
+    /// Maximum size of a file documented for the endpoint, in bytes.
+    pub max_file_size: Option<u64>,
+    /// The error returned by the API.
+    pub error: ApiError,
+}
+
+impl UploadError {
+    /// [OpenAIError::Upload] for an `error` of a multipart request to `path` uploading `files`,
+    /// other errors are returned unchanged.
+    pub(crate) fn map(path: &str, files: Vec<UploadedFile>, error: OpenAIError) -> OpenAIError {
+        let OpenAIError::ApiError(api_error) = error else {
+            return error;
+        };
+        let kind = match api_error.status {
+            Some(StatusCode::PAYLOAD_TOO_LARGE) => UploadErrorKind::PayloadTooLarge,
+            Some(StatusCode::UNSUPPORTED_MEDIA_TYPE) => UploadErrorKind::UnsupportedMediaType,
+            _ => return OpenAIError::ApiError(api_error),
+        };
+        OpenAIError::Upload(Box::new(UploadError {
+            kind,
+            path: path.to_string(),
+            files,
+            max_file_size: max_file_size(path),
+            error: api_error,
+        }))
+    }
+}
+
+/// Maximum file size of the upload endpoints, for models whose limits do not differ.
+fn max_file_size(path: &str) -> Option<u64> {
+    const MB: u64 = 1024 * 1024;
+    if path.starts_with("/audio/") {
+        Some(25 * MB)
+    } else if path == "/files" {
+        Some(512 * MB)
+    } else if path.starts_with("/uploads/") {
+        Some(64 * MB)
+    } else if path == "/images/variations" {
+        Some(4 * MB)
+    } else {
+        None
+    }
+}
+
+impl std::fmt::Display for UploadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self.kind {
+            UploadErrorKind::PayloadTooLarge => "payload too large",
+            UploadErrorKind::UnsupportedMediaType => "unsupported media type",
+        };
+        write!(f, "upload to {} rejected, {kind}", self.path)?;
+        for file in &self.files {
+            write!(f, "; {}", file.file_name)?;
+            if let Some(content_type) = &file.content_type {
+                write!(f, " ({content_type})")?;
+            }
+            if let Some(size) = file.size {
+                write!(f, " of {size} bytes")?;
+            }
+        }
+        if let Some(max_file_size) = self.max_file_size {
+            write!(f, "; files of at most {max_file_size} bytes are accepted")?;
+        }
+        write!(f, ": {}", self.error)
+    }
+}
+
 /// OpenAI API returns error object on failure
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct ApiError {
@@ -73,6 +362,56 @@ pub struct ApiError {
     pub r#type: Option<String>,
     pub param: Option<String>,
     pub code: Option<String>,
+    /// HTTP status code of the response carrying this error.
+    #[serde(skip)]
+    pub status: Option<StatusCode>,
+    /// Headers of the response carrying this error, e.g. `retry-after` or `x-request-id`.
+    /// Boxed to keep [OpenAIError] small.
+    #[serde(skip)]
+    pub headers: Option<Box<HeaderMap>>,
+    /// Whether the error was sent as an event of a stream after it started, e.g. when the
+    /// server got overloaded mid-generation, instead of as the response to the request.
+    #[serde(skip)]
+    pub in_stream: bool,
+}
+
+impl ApiError {
+    /// Error from a response whose body is not an OpenAI error object.
+    pub(crate) fn from_body(message: String) -> Self {
+        Self {
+            message,
+            r#type: None,
+            param: None,
+            code: None,
+            status: None,
+            headers: None,
+            in_stream: false,
+        }
+    }
+
+    /// Error sent in a stream by an event of type `event` with `data`, `None` when the event
+    /// is neither an `error` event nor an error object.
+    pub(crate) fn from_event(event: &str, data: &str) -> Option<Self> {
+        let error = match serde_json::from_str::<WrappedError>(data) {
+            Ok(wrapped) => wrapped.into(),
+            Err(_) => match serde_json::from_str::<ApiErrorFlex>(data) {
+                Ok(flex) => flex.into(),
+                Err(_) if event == "error" => Self::from_body(data.to_string()),
+                Err(_) => return None,
+            },
+        };
+        Some(Self {
+            in_stream: true,
+            ..error
+        })
+    }
+
+    /// Attach the status code and headers of the response.
+    pub(crate) fn with_response(mut self, status: StatusCode, headers: HeaderMap) -> Self {
+        self.status = Some(status);
+        self.headers = Some(Box::new(headers));
+        self
+    }
 }
 
 impl std::fmt::Display for ApiError {
@@ -107,6 +446,9 @@ impl From<ApiErrorFlex> for ApiError {
             r#type: err.r#type,
             param: err.param,
             code: err.code.map(|code| code.into()),
+            status: None,
+            headers: None,
+            in_stream: false,
         }
     }
 }
@@ -149,6 +491,19 @@ pub struct WrappedError {
     error: ApiErrorFlex,
 }
 
+/// Builds an [OpenAIError::ApiError] from a non-success response, falling back to
+/// the raw body as message when it is not an OpenAI error object.
+pub(crate) fn map_status_error(status: StatusCode, headers: HeaderMap, bytes: &[u8]) -> ApiError {
+    let error = match serde_json::from_slice::<WrappedError>(bytes) {
+        Ok(wrapped) => wrapped.into(),
+        Err(_) => match serde_json::from_slice::<ApiErrorFlex>(bytes) {
+            Ok(flex) => flex.into(),
+            Err(_) => ApiError::from_body(String::from_utf8_lossy(bytes).into_owned()),
+        },
+    };
+    error.with_response(status, headers)
+}
+
 /// Attempts to parse the response body as an OpenAI error before falling back to
 /// a generic deserialization error with the full response body included for debugging.
 pub(crate) fn map_deserialization_error(err: serde_json::Error, bytes: &[u8]) -> OpenAIError {
@@ -208,4 +563,83 @@ mod tests {
         let error: ApiErrorFlex = serde_json::from_str(json).unwrap();
         assert_eq!(error.code, Some(ErrorCode::Int(429)));
     }
+
+    #[test]
+    fn test_status_classification() {
+        let body = br#"{"error": {"message": "Rate limit reached", "type": "requests", "param": null, "code": "rate_limit_exceeded"}}"#;
+        let err = OpenAIError::ApiError(map_status_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            HeaderMap::new(),
+            body,
+        ));
+        assert!(err.is_rate_limit());
+        assert!(!err.is_auth_error());
+
+        let body = br#"{"error": {"message": "You exceeded your current quota", "type": "insufficient_quota", "param": null, "code": "insufficient_quota"}}"#;
+        let err = OpenAIError::ApiError(map_status_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            HeaderMap::new(),
+            body,
+        ));
+        assert!(err.is_quota_exceeded());
+        assert!(!err.is_rate_limit());
+
+        let err = OpenAIError::ApiError(map_status_error(
+            StatusCode::BAD_GATEWAY,
+            HeaderMap::new(),
+            b"<html>bad gateway</html>",
+        ));
+        assert!(err.is_server_error());
+        assert_eq!(err.status(), Some(StatusCode::BAD_GATEWAY));
+    }
+
+    #[tokio::test]
+    async fn test_upload_error() {
+        use crate::{
+            transport::mock::{MockResponse, MockTransport},
+            types::{AudioInput, CreateTranscriptionRequest, CreateTranscriptionResponseJson},
+            Client,
+        };
+
+        let request = CreateTranscriptionRequest {
+            file: AudioInput::from_vec_u8("speech.mp3".into(), vec![0; 1024]),
+            model: "whisper-1".into(),
+            ..Default::default()
+        };
+        let client = Client::new().with_transport(MockTransport::script(vec![MockResponse::new(
+            r#"{"error":{"message":"Maximum content size limit exceeded"}}"#,
+        )
+        .status(413)]));
+        let result: Result<CreateTranscriptionResponseJson, _> =
+            client.audio().transcribe(request).await;
+        let Err(OpenAIError::Upload(error)) = result else {
+            panic!("expected an upload error, got {result:?}");
+        };
+        assert_eq!(error.kind, UploadErrorKind::PayloadTooLarge);
+        assert_eq!(error.max_file_size, Some(25 * 1024 * 1024));
+        assert_eq!(
+            error.files,
+            [UploadedFile {
+                file_name: "speech.mp3".into(),
+                content_type: Some("audio/mpeg".into()),
+                size: Some(1024),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_in_stream_error() {
+        let data = r#"{"error": {"message": "The server is overloaded", "type": "server_error", "param": null, "code": null}}"#;
+        let error = OpenAIError::ApiError(ApiError::from_event("", data).unwrap());
+        assert_eq!(error.stream_failure(), Some(StreamFailure::InStream));
+        assert!(error.is_server_error());
+
+        let error = ApiError::from_event("error", "upstream timeout").unwrap();
+        assert_eq!(error.message, "upstream timeout");
+        assert!(ApiError::from_event("message", r#"{"id": "chatcmpl-1"}"#).is_none());
+
+        let malformed =
+            map_deserialization_error(serde_json::from_str::<u8>("{").unwrap_err(), b"{");
+        assert_eq!(malformed.stream_failure(), Some(StreamFailure::Malformed));
+    }
 }
@@ -0,0 +1,422 @@
+//! Minimal valid requests for the endpoints of each API group, built with the typed builders.
+//!
+//! Enabled with the `examples` feature, meant for tests catching drift between builders and
+//! serialization, or as request templates:
+//!
+//! ```
+//! # async fn example() -> Result<(), async_openai::error::OpenAIError> {
+//! use async_openai::examples::{generate, ApiGroup};
+//!
+//! for group in ApiGroup::ALL {
+//!     for example in generate(*group).await? {
+//!         println!("{} {}", example.method, example.path);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+use bytes::Bytes;
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::{
+    error::OpenAIError,
+    traits::AsyncTryFrom,
+    types::{
+        responses::CreateResponseArgs, AudioInput, BatchCompletionWindow, BatchEndpoint,
+        BatchRequestArgs, ChatCompletionRequestUserMessageArgs, CreateAssistantRequestArgs,
+        CreateChatCompletionRequestArgs, CreateCompletionRequestArgs, CreateEmbeddingRequestArgs,
+        CreateFileRequestArgs, CreateFineTuningCheckpointPermissionRequestArgs,
+        CreateFineTuningJobRequestArgs, CreateImageEditRequestArgs, CreateImageRequestArgs,
+        CreateImageVariationRequestArgs, CreateMessageRequestArgs, CreateModerationRequestArgs,
+        CreateRunRequestArgs, CreateSpeechRequestArgs, CreateThreadAndRunRequestArgs,
+        CreateThreadRequestArgs, CreateTranscriptionRequestArgs, CreateTranslationRequestArgs,
+        CreateUploadRequestArgs, CreateVectorStoreFileBatchRequestArgs,
+        CreateVectorStoreFileRequestArgs, CreateVectorStoreRequestArgs, FileInput, FilePurpose,
+        ImageInput, InviteRequestArgs, MessageRole, ModifyAssistantRequestArgs, OrganizationRole,
+        ProjectCreateRequestArgs, ProjectUpdateRequestArgs, ProjectUserCreateRequestArgs,
+        ProjectUserRole, ProjectUserUpdateRequestArgs, SpeechModel, UpdateVectorStoreRequestArgs,
+        UploadPurpose, UserRoleUpdateRequestArgs, VectorStoreSearchRequestArgs, Voice,
+    },
+};
+
+/// API groups having endpoints which take a request body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ApiGroup {
+    Assistants,
+    Audio,
+    Batches,
+    Chat,
+    Completions,
+    Embeddings,
+    Files,
+    FineTuning,
+    Images,
+    Invites,
+    Messages,
+    Moderations,
+    ProjectUsers,
+    Projects,
+    Responses,
+    Runs,
+    Threads,
+    Uploads,
+    Users,
+    VectorStoreFileBatches,
+    VectorStoreFiles,
+    VectorStores,
+}
+
+impl ApiGroup {
+    pub const ALL: &'static [ApiGroup] = &[
+        ApiGroup::Assistants,
+        ApiGroup::Audio,
+        ApiGroup::Batches,
+        ApiGroup::Chat,
+        ApiGroup::Completions,
+        ApiGroup::Embeddings,
+        ApiGroup::Files,
+        ApiGroup::FineTuning,
+        ApiGroup::Images,
+        ApiGroup::Invites,
+        ApiGroup::Messages,
+        ApiGroup::Moderations,
+        ApiGroup::ProjectUsers,
+        ApiGroup::Projects,
+        ApiGroup::Responses,
+        ApiGroup::Runs,
+        ApiGroup::Threads,
+        ApiGroup::Uploads,
+        ApiGroup::Users,
+        ApiGroup::VectorStoreFileBatches,
+        ApiGroup::VectorStoreFiles,
+        ApiGroup::VectorStores,
+    ];
+}
+
+/// A request of an endpoint, path parameters are left as `{placeholder}`.
+#[derive(Debug, Clone)]
+pub struct ExampleRequest {
+    pub method: Method,
+    pub path: &'static str,
+    /// The serialized JSON body, `None` for multipart requests which are only converted into a form.
+    pub body: Option<serde_json::Value>,
+}
+
+fn json<T: Serialize>(
+    method: Method,
+    path: &'static str,
+    request: T,
+) -> Result<ExampleRequest, OpenAIError> {
+    let body = serde_json::to_value(request)
+        .map_err(|e| OpenAIError::InvalidArgument(format!("{path}: {e}")))?;
+    Ok(ExampleRequest {
+        method,
+        path,
+        body: Some(body),
+    })
+}
+
+async fn multipart<T>(path: &'static str, request: T) -> Result<ExampleRequest, OpenAIError>
+where
+    reqwest::multipart::Form: AsyncTryFrom<T, Error = OpenAIError>,
+{
+    <reqwest::multipart::Form as AsyncTryFrom<T>>::try_from(request).await?;
+    Ok(ExampleRequest {
+        method: Method::POST,
+        path,
+        body: None,
+    })
+}
+
+fn in_memory(filename: &str) -> Bytes {
+    Bytes::from(format!("contents of {filename}"))
+}
+
+/// Minimal valid requests of the endpoints of `group` taking a request body.
+pub async fn generate(group: ApiGroup) -> Result<Vec<ExampleRequest>, OpenAIError> {
+    let examples = match group {
+        ApiGroup::Assistants => vec![
+            json(
+                Method::POST,
+                "/assistants",
+                CreateAssistantRequestArgs::default()
+                    .model("gpt-4o")
+                    .build()?,
+            )?,
+            json(
+                Method::POST,
+                "/assistants/{assistant_id}",
+                ModifyAssistantRequestArgs::default()
+                    .name("assistant")
+                    .build()?,
+            )?,
+        ],
+        ApiGroup::Audio => vec![
+            json(
+                Method::POST,
+                "/audio/speech",
+                CreateSpeechRequestArgs::default()
+                    .model(SpeechModel::Tts1)
+                    .input("Hello")
+                    .voice(Voice::Alloy)
+                    .build()?,
+            )?,
+            multipart(
+                "/audio/transcriptions",
+                CreateTranscriptionRequestArgs::default()
+                    .file(AudioInput::from_bytes(
+                        "audio.mp3".into(),
+                        in_memory("audio.mp3"),
+                    ))
+                    .model("whisper-1")
+                    .build()?,
+            )
+            .await?,
+            multipart(
+                "/audio/translations",
+                CreateTranslationRequestArgs::default()
+                    .file(AudioInput::from_bytes(
+                        "audio.mp3".into(),
+                        in_memory("audio.mp3"),
+                    ))
+                    .model("whisper-1")
+                    .build()?,
+            )
+            .await?,
+        ],
+        ApiGroup::Batches => vec![json(
+            Method::POST,
+            "/batches",
+            BatchRequestArgs::default()
+                .input_file_id("file-abc123")
+                .endpoint(BatchEndpoint::V1ChatCompletions)
+                .completion_window(BatchCompletionWindow::W24H)
+                .build()?,
+        )?],
+        ApiGroup::Chat => vec![json(
+            Method::POST,
+            "/chat/completions",
+            CreateChatCompletionRequestArgs::default()
+                .model("gpt-4o")
+                .messages([ChatCompletionRequestUserMessageArgs::default()
+                    .content("Hello")
+                    .build()?
+                    .into()])
+                .build()?,
+        )?],
+        ApiGroup::Completions => vec![json(
+            Method::POST,
+            "/completions",
+            CreateCompletionRequestArgs::default()
+                .model("gpt-3.5-turbo-instruct")
+                .prompt("Hello")
+                .build()?,
+        )?],
+        ApiGroup::Embeddings => vec![json(
+            Method::POST,
+            "/embeddings",
+            CreateEmbeddingRequestArgs::default()
+                .model("text-embedding-3-small")
+                .input("Hello")
+                .build()?,
+        )?],
+        ApiGroup::Files => vec![
+            multipart(
+                "/files",
+                CreateFileRequestArgs::default()
+                    .file(FileInput::from_bytes(
+                        "batch.jsonl".into(),
+                        in_memory("batch.jsonl"),
+                    ))
+                    .purpose(FilePurpose::Batch)
+                    .build()?,
+            )
+            .await?,
+        ],
+        ApiGroup::FineTuning => vec![
+            json(
+                Method::POST,
+                "/fine_tuning/jobs",
+                CreateFineTuningJobRequestArgs::default()
+                    .model("gpt-4o-mini")
+                    .training_file("file-abc123")
+                    .build()?,
+            )?,
+            json(
+                Method::POST,
+                "/fine_tuning/checkpoints/{fine_tuned_model_checkpoint}/permissions",
+                CreateFineTuningCheckpointPermissionRequestArgs::default()
+                    .project_ids(vec!["proj_abc123".to_string()])
+                    .build()?,
+            )?,
+        ],
+        ApiGroup::Images => vec![
+            json(
+                Method::POST,
+                "/images/generations",
+                CreateImageRequestArgs::default().prompt("A lamp").build()?,
+            )?,
+            multipart(
+                "/images/edits",
+                CreateImageEditRequestArgs::default()
+                    .image(ImageInput::from_bytes(
+                        "image.png".into(),
+                        in_memory("image.png"),
+                    ))
+                    .prompt("A lamp")
+                    .build()?,
+            )
+            .await?,
+            multipart(
+                "/images/variations",
+                CreateImageVariationRequestArgs::default()
+                    .image(ImageInput::from_bytes(
+                        "image.png".into(),
+                        in_memory("image.png"),
+                    ))
+                    .build()?,
+            )
+            .await?,
+        ],
+        ApiGroup::Invites => vec![json(
+            Method::POST,
+            "/organization/invites",
+            InviteRequestArgs::default()
+                .email("user@example.com")
+                .role(OrganizationRole::Reader)
+                .build()?,
+        )?],
+        ApiGroup::Messages => vec![json(
+            Method::POST,
+            "/threads/{thread_id}/messages",
+            CreateMessageRequestArgs::default()
+                .role(MessageRole::User)
+                .content("Hello")
+                .build()?,
+        )?],
+        ApiGroup::Moderations => vec![json(
+            Method::POST,
+            "/moderations",
+            CreateModerationRequestArgs::default()
+                .input("Hello")
+                .build()?,
+        )?],
+        ApiGroup::ProjectUsers => vec![
+            json(
+                Method::POST,
+                "/organization/projects/{project_id}/users",
+                ProjectUserCreateRequestArgs::default()
+                    .user_id("user_abc123")
+                    .role(ProjectUserRole::Member)
+                    .build()?,
+            )?,
+            json(
+                Method::POST,
+                "/organization/projects/{project_id}/users/{user_id}",
+                ProjectUserUpdateRequestArgs::default()
+                    .role(ProjectUserRole::Owner)
+                    .build()?,
+            )?,
+        ],
+        ApiGroup::Projects => vec![
+            json(
+                Method::POST,
+                "/organization/projects",
+                ProjectCreateRequestArgs::default()
+                    .name("project")
+                    .build()?,
+            )?,
+            json(
+                Method::POST,
+                "/organization/projects/{project_id}",
+                ProjectUpdateRequestArgs::default()
+                    .name("project")
+                    .build()?,
+            )?,
+        ],
+        ApiGroup::Responses => vec![json(
+            Method::POST,
+            "/responses",
+            CreateResponseArgs::default()
+                .model("gpt-4o")
+                .input("Hello")
+                .build()?,
+        )?],
+        ApiGroup::Runs => vec![json(
+            Method::POST,
+            "/threads/{thread_id}/runs",
+            CreateRunRequestArgs::default()
+                .assistant_id("asst_abc123")
+                .build()?,
+        )?],
+        ApiGroup::Threads => vec![
+            json(
+                Method::POST,
+                "/threads",
+                CreateThreadRequestArgs::default().build()?,
+            )?,
+            json(
+                Method::POST,
+                "/threads/runs",
+                CreateThreadAndRunRequestArgs::default()
+                    .assistant_id("asst_abc123")
+                    .build()?,
+            )?,
+        ],
+        ApiGroup::Uploads => vec![json(
+            Method::POST,
+            "/uploads",
+            CreateUploadRequestArgs::default()
+                .filename("batch.jsonl")
+                .purpose(UploadPurpose::Batch)
+                .bytes(1024u64)
+                .mime_type("text/jsonl")
+                .build()?,
+        )?],
+        ApiGroup::Users => vec![json(
+            Method::POST,
+            "/organization/users/{user_id}",
+            UserRoleUpdateRequestArgs::default()
+                .role(OrganizationRole::Owner)
+                .build()?,
+        )?],
+        ApiGroup::VectorStoreFileBatches => vec![json(
+            Method::POST,
+            "/vector_stores/{vector_store_id}/file_batches",
+            CreateVectorStoreFileBatchRequestArgs::default()
+                .file_ids(vec!["file-abc123".to_string()])
+                .build()?,
+        )?],
+        ApiGroup::VectorStoreFiles => vec![json(
+            Method::POST,
+            "/vector_stores/{vector_store_id}/files",
+            CreateVectorStoreFileRequestArgs::default()
+                .file_id("file-abc123")
+                .build()?,
+        )?],
+        ApiGroup::VectorStores => vec![
+            json(
+                Method::POST,
+                "/vector_stores",
+                CreateVectorStoreRequestArgs::default().build()?,
+            )?,
+            json(
+                Method::POST,
+                "/vector_stores/{vector_store_id}",
+                UpdateVectorStoreRequestArgs::default()
+                    .name("vector store")
+                    .build()?,
+            )?,
+            json(
+                Method::POST,
+                "/vector_stores/{vector_store_id}/search",
+                VectorStoreSearchRequestArgs::default()
+                    .query("Hello")
+                    .build()?,
+            )?,
+        ],
+    };
+
+    Ok(examples)
+}
@@ -0,0 +1,429 @@
+//! Failover between the api base and the fallback api bases of a [crate::config::Config], and
+//! between providers with [crate::Client::with_fallbacks].
+use std::{
+    collections::HashMap,
+    fmt,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::header::{HeaderName, ACCEPT, CONTENT_TYPE};
+
+use crate::{config::Config, error::OpenAIError, request_options::IDEMPOTENCY_KEY};
+
+/// Headers of a request kept when it is sent to a [Fallback], describing its body rather than
+/// authenticating it.
+const FORWARDED_HEADERS: [HeaderName; 3] = [CONTENT_TYPE, ACCEPT, IDEMPOTENCY_KEY];
+
+/// Default time a base url failing to connect or responding with a server error is skipped.
+pub(crate) const DEFAULT_COOLDOWN: Duration = Duration::from_secs(30);
+
+/// Health of a base url of a [crate::Client], see [crate::Client::endpoints].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    pub api_base: String,
+    /// `false` while the base url is skipped after a failure.
+    pub healthy: bool,
+    /// Requests served by this base url.
+    pub served: u64,
+    /// Requests which failed to connect or got a server error.
+    pub failures: u64,
+}
+
+/// Base url which served a response, an extension of the responses of [crate::Client], see
+/// [crate::RawResponse::api_base].
+#[derive(Debug, Clone)]
+pub(crate) struct ServedBy(pub(crate) String);
+
+impl ServedBy {
+    /// `response` marked as served by `api_base`.
+    pub(crate) fn mark(mut response: reqwest::Response, api_base: &str) -> reqwest::Response {
+        response
+            .extensions_mut()
+            .insert(ServedBy(api_base.to_string()));
+        response
+    }
+}
+
+#[derive(Debug)]
+struct Endpoint {
+    api_base: String,
+    unhealthy_until: Option<Instant>,
+    served: u64,
+    failures: u64,
+}
+
+#[derive(Debug)]
+struct Endpoints {
+    endpoints: Vec<Endpoint>,
+}
+
+/// Base urls of a client, tried in order, base urls which recently failed last.
+#[derive(Debug)]
+pub(crate) struct Failover {
+    cooldown: Duration,
+    state: Mutex<Endpoints>,
+}
+
+impl Failover {
+    /// `None` for configs without fallback api bases.
+    pub(crate) fn from_config<C: Config>(config: &C, cooldown: Duration) -> Option<Self> {
+        if config.fallback_api_bases().is_empty() {
+            return None;
+        }
+        let endpoints = std::iter::once(config.api_base())
+            .chain(config.fallback_api_bases().iter().map(String::as_str))
+            .map(|api_base| Endpoint {
+                api_base: api_base.to_string(),
+                unhealthy_until: None,
+                served: 0,
+                failures: 0,
+            })
+            .collect();
+        Some(Self {
+            cooldown,
+            state: Mutex::new(Endpoints { endpoints }),
+        })
+    }
+
+    /// Indices and base urls to try, healthy base urls first in configuration order.
+    pub(crate) fn order(&self) -> Vec<(usize, String)> {
+        let now = Instant::now();
+        let state = self.lock();
+        let (healthy, unhealthy): (Vec<_>, Vec<_>) = state
+            .endpoints
+            .iter()
+            .enumerate()
+            .map(|(index, endpoint)| (index, endpoint.api_base.clone()))
+            .partition(|(index, _)| {
+                state.endpoints[*index]
+                    .unhealthy_until
+                    .map_or(true, |until| until <= now)
+            });
+        healthy.into_iter().chain(unhealthy).collect()
+    }
+
+    /// `url` of the primary api base moved to `api_base`.
+    pub(crate) fn rewrite(&self, url: &str, api_base: &str) -> Option<String> {
+        let primary = &self.lock().endpoints[0].api_base;
+        url.strip_prefix(primary.as_str())
+            .map(|rest| format!("{api_base}{rest}"))
+    }
+
+    /// `url` of the primary api base moved to the first healthy base url.
+    pub(crate) fn url(&self, url: String) -> String {
+        match self.order().first() {
+            Some((_, api_base)) => self.rewrite(&url, api_base).unwrap_or(url),
+            None => url,
+        }
+    }
+
+    pub(crate) fn succeeded(&self, index: usize) {
+        let mut state = self.lock();
+        let endpoint = &mut state.endpoints[index];
+        endpoint.served += 1;
+        endpoint.unhealthy_until = None;
+    }
+
+    pub(crate) fn failed(&self, index: usize) {
+        let mut state = self.lock();
+        let endpoint = &mut state.endpoints[index];
+        endpoint.failures += 1;
+        endpoint.unhealthy_until = Some(Instant::now() + self.cooldown);
+        tracing::warn!(
+            "{} failed, skipping it for {:?}",
+            endpoint.api_base,
+            self.cooldown
+        );
+    }
+
+    pub(crate) fn status(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        let state = self.lock();
+        state
+            .endpoints
+            .iter()
+            .map(|endpoint| EndpointStatus {
+                api_base: endpoint.api_base.clone(),
+                healthy: endpoint.unhealthy_until.map_or(true, |until| until <= now),
+                served: endpoint.served,
+                failures: endpoint.failures,
+            })
+            .collect()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Endpoints> {
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// A provider a [crate::Client] falls back to when its config fails, see
+/// [crate::Client::with_fallbacks].
+///
+/// Requests are sent to the fallback with the headers of its config only: the credentials of
+/// the client, such as those of a [crate::credentials::CredentialProvider], and the headers
+/// of the [crate::request_options::RequestOptions], which may authenticate to a gateway of the
+/// primary provider, are not forwarded.
+///
+/// ```
+/// use async_openai::{config::OpenAIConfig, failover::Fallback, Client};
+///
+/// let openrouter = OpenAIConfig::new()
+///     .with_api_base("https://openrouter.ai/api/v1")
+///     .with_api_key("sk-or-...");
+/// let client = Client::new().with_fallbacks(vec![
+///     Fallback::new(openrouter).with_model("gpt-4o", "openai/gpt-4o"),
+/// ]);
+/// ```
+#[derive(Clone)]
+pub struct Fallback {
+    config: Arc<dyn Config>,
+    models: HashMap<String, String>,
+}
+
+impl Fallback {
+    pub fn new<C: Config + 'static>(config: C) -> Self {
+        Self {
+            config: Arc::new(config),
+            models: HashMap::new(),
+        }
+    }
+
+    /// Send the requests to `model` to the model named `name` by this provider.
+    pub fn with_model<M: Into<String>, N: Into<String>>(mut self, model: M, name: N) -> Self {
+        self.models.insert(model.into(), name.into());
+        self
+    }
+
+    pub fn config(&self) -> &dyn Config {
+        self.config.as_ref()
+    }
+
+    /// `request` built with `primary` moved to this provider: its url, the query of the config
+    /// and the model of a JSON body replaced, and its headers replaced by those of the config
+    /// except [FORWARDED_HEADERS].
+    pub(crate) fn route(
+        &self,
+        mut request: reqwest::Request,
+        primary: &dyn Config,
+    ) -> Result<reqwest::Request, OpenAIError> {
+        let mut model = None;
+        if let Some(body) = request.body().and_then(reqwest::Body::as_bytes) {
+            if let Ok(mut body) = serde_json::from_slice::<serde_json::Value>(body) {
+                model = body
+                    .get("model")
+                    .and_then(|model| model.as_str())
+                    .map(|name| {
+                        self.models
+                            .get(name)
+                            .cloned()
+                            .unwrap_or_else(|| name.to_string())
+                    });
+                if let (Some(name), Some(object)) = (&model, body.as_object_mut()) {
+                    object.insert("model".into(), name.clone().into());
+                    *request.body_mut() = Some(body.to_string().into());
+                }
+            }
+        }
+
+        let primary_query: Vec<String> = primary
+            .query()
+            .into_iter()
+            .map(|(key, _)| key.to_string())
+            .collect();
+        let url = request.url().as_str();
+        let url = url.split_once('?').map_or(url, |(url, _)| url);
+        let path = [primary.url(""), primary.api_base().to_string()]
+            .into_iter()
+            .find_map(|prefix| url.strip_prefix(prefix.as_str()).map(str::to_string))
+            .ok_or_else(|| {
+                OpenAIError::InvalidArgument(format!("cannot route {url} to a fallback provider"))
+            })?;
+        let url = match &model {
+            Some(model) => self.config.model_url(&path, model),
+            None => self.config.url(&path),
+        };
+        let mut url = reqwest::Url::parse(&url)
+            .map_err(|error| OpenAIError::InvalidArgument(format!("invalid url {url}: {error}")))?;
+        let query: Vec<(String, String)> = request
+            .url()
+            .query_pairs()
+            .filter(|(key, _)| !primary_query.iter().any(|primary| primary == key))
+            .map(|(key, value)| (key.into_owned(), value.into_owned()))
+            .collect();
+        url.query_pairs_mut()
+            .extend_pairs(query)
+            .extend_pairs(self.config.query());
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+        *request.url_mut() = url;
+
+        let forwarded = std::mem::take(request.headers_mut());
+        let headers = request.headers_mut();
+        *headers = forwarded
+            .into_iter()
+            .filter_map(|(name, value)| Some((name?, value)))
+            .filter(|(name, _)| FORWARDED_HEADERS.contains(name))
+            .collect();
+        headers.extend(self.config.headers());
+        Ok(request)
+    }
+}
+
+impl fmt::Debug for Fallback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Fallback")
+            .field("api_base", &self.config.api_base())
+            .field("models", &self.models)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::OpenAIConfig,
+        transport::mock::{MockResponse, MockTransport},
+    };
+
+    #[test]
+    fn test_failover_order() {
+        let config = OpenAIConfig::new()
+            .with_api_base("https://primary/v1")
+            .with_fallback_api_base("https://fallback/v1");
+        let failover = Failover::from_config(&config, Duration::from_secs(60)).unwrap();
+        assert_eq!(failover.order()[0].1, "https://primary/v1");
+
+        failover.failed(0);
+        failover.succeeded(1);
+        assert_eq!(failover.order()[0].1, "https://fallback/v1");
+        assert_eq!(
+            failover.url("https://primary/v1/chat/completions".into()),
+            "https://fallback/v1/chat/completions"
+        );
+        let status = failover.status();
+        assert!(!status[0].healthy && status[1].served == 1);
+
+        // recovered once its cooldown elapsed
+        let failover = Failover::from_config(&config, Duration::ZERO).unwrap();
+        failover.failed(0);
+        assert_eq!(failover.order()[0].1, "https://primary/v1");
+    }
+
+    /// Fails the requests to `primary`, echoes the others.
+    fn down() -> std::sync::Arc<MockTransport> {
+        MockTransport::new(|request, _| {
+            if request.url.host_str() == Some("primary") {
+                return Ok(MockResponse::new("").status(503));
+            }
+            Ok(MockResponse::json(serde_json::json!({
+                "url": request.url.as_str(),
+                "authorization": request.header("authorization"),
+                "model": request.json()["model"],
+                "headers": request.headers.keys().map(|name| name.as_str()).collect::<Vec<_>>(),
+            })))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fallbacks() {
+        let primary = OpenAIConfig::new()
+            .with_api_base("https://primary/v1")
+            .with_api_key("sk-primary");
+        let fallback = OpenAIConfig::new()
+            .with_api_base("https://fallback/api/v1")
+            .with_api_key("sk-fallback");
+        let client = crate::Client::with_config(primary)
+            .with_transport(down())
+            .with_fallbacks(vec![
+                // model and name of different string types
+                Fallback::new(fallback).with_model("gpt-4o", "openai/gpt-4o".to_string()),
+            ]);
+        let response = client
+            .raw()
+            .query("limit", "2")
+            .post("/chat/completions", serde_json::json!({"model": "gpt-4o"}))
+            .await
+            .unwrap();
+        assert_eq!(
+            response.api_base.as_deref(),
+            Some("https://fallback/api/v1")
+        );
+        let response: serde_json::Value = response.json().unwrap();
+        assert_eq!(
+            response,
+            serde_json::json!({
+                "url": "https://fallback/api/v1/chat/completions?limit=2",
+                "authorization": "Bearer sk-fallback",
+                "model": "openai/gpt-4o",
+                "headers": ["content-type", "authorization", "openai-beta"],
+            })
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fallback_credentials() {
+        let primary = crate::config::AzureConfig::new()
+            .with_api_base("https://primary")
+            .with_deployment_id("gpt-4o")
+            .with_api_key("azure-key");
+        let fallback = OpenAIConfig::new()
+            .with_api_base("https://fallback/v1")
+            .with_api_key("sk-fallback");
+        let provider =
+            crate::credentials::TokenProvider::new(|| async { Ok("aad-token".to_string()) });
+        let client = crate::Client::with_config(primary)
+            .with_credential_provider(provider)
+            .with_transport(down())
+            .with_fallbacks(vec![Fallback::new(fallback)]);
+        let response: serde_json::Value = client
+            .raw()
+            .header("Helicone-Auth", "Bearer sk-gateway")
+            .unwrap()
+            .post("/chat/completions", serde_json::json!({"model": "gpt-4o"}))
+            .await
+            .unwrap()
+            .json()
+            .unwrap();
+        // neither the AAD token, the Azure key nor the gateway header reach the fallback
+        assert_eq!(response["authorization"], "Bearer sk-fallback");
+        assert_eq!(response["url"], "https://fallback/v1/chat/completions");
+        assert_eq!(
+            response["headers"],
+            serde_json::json!(["content-type", "authorization", "openai-beta"])
+        );
+    }
+
+    #[tokio::test]
+    async fn test_served_by() {
+        let config = OpenAIConfig::new()
+            .with_api_base("https://primary/v1")
+            .with_fallback_api_base("https://fallback/v1")
+            .with_api_key("sk-primary");
+        let failover = crate::Client::with_config(config).with_transport(down());
+        let config = OpenAIConfig::new()
+            .with_api_base("https://direct/v1")
+            .with_api_key("sk-direct");
+        let direct = crate::Client::with_config(config).with_transport(down());
+        let body = serde_json::json!({"model": "m"});
+        let (failover, direct) = (failover.raw(), direct.raw());
+        // each response names its own base url, whatever the other requests in flight
+        let (first, second, third) = futures::join!(
+            failover.post("/embeddings", &body),
+            direct.post("/embeddings", &body),
+            failover.post("/embeddings", &body),
+        );
+        assert_eq!(
+            [first, second, third].map(|response| response.unwrap().api_base),
+            [
+                Some("https://fallback/v1".to_string()),
+                Some("https://direct/v1".to_string()),
+                Some("https://fallback/v1".to_string()),
+            ]
+        );
+    }
+}
@@ -1,21 +1,38 @@
+use std::path::Path;
+
 use bytes::Bytes;
+use futures::StreamExt;
 use serde::Serialize;
 
 use crate::{
     config::Config,
+    download::save_stream,
     error::OpenAIError,
-    types::{CreateFileRequest, DeleteFileResponse, ListFilesResponse, OpenAIFile},
+    request_options::{impl_request_options, RequestOptions},
+    traits::AsyncTryFrom,
+    types::{
+        CreateFileRequest, CreateFileRequestArgs, DeleteFileResponse, DownloadProgress,
+        FileContentStream, FileInput, FilePurpose, FileReference, InputSource, ListFilesResponse,
+        OpenAIFile, UploadPartStream,
+    },
     Client,
 };
 
 /// Files are used to upload documents that can be used with features like Assistants and Fine-tuning.
 pub struct Files<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Files);
+crate::util::impl_api_group_debug!(Files, "/files");
+
 impl<'c, C: Config> Files<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Upload a file that can be used across various endpoints. Individual files can be up to 512 MB, and the size of all files uploaded by one organization can be up to 100 GB.
@@ -33,47 +50,423 @@ impl<'c, C: Config> Files<'c, C> {
         where_clause =  "reqwest::multipart::Form: crate::traits::AsyncTryFrom<T0, Error = OpenAIError>",
     )]
     pub async fn create(&self, request: CreateFileRequest) -> Result<OpenAIFile, OpenAIError> {
-        self.client.post_form("/files", request).await
+        self.client
+            .post_form("/files", request, &self.request_options)
+            .await
+    }
+
+    /// Upload `bytes` as a file named `name`, checking first that the extension of `name` suits
+    /// `purpose`, see [FilePurpose::validate_file_name].
+    pub async fn create_from_bytes(
+        &self,
+        name: impl Into<String>,
+        bytes: impl Into<Bytes>,
+        purpose: FilePurpose,
+    ) -> Result<OpenAIFile, OpenAIError> {
+        let request = CreateFileRequestArgs::default()
+            .file(FileInput::from_bytes(name.into(), bytes.into()))
+            .purpose(purpose)
+            .build()?;
+        self.create(request).await
+    }
+
+    /// Upload `items` as a JSONL file of one line per item, such as the requests of a batch or
+    /// the examples of a fine-tuning job, named after `purpose`.
+    ///
+    /// The lines are serialized as the body is sent instead of buffered in memory, so the call
+    /// is neither retried nor sent again with refreshed credentials; an item failing to
+    /// serialize fails the upload.
+    ///
+    /// ```no_run
+    /// # async fn example() -> Result<(), async_openai::error::OpenAIError> {
+    /// use async_openai::{types::FilePurpose, Client};
+    /// use serde_json::json;
+    ///
+    /// let examples = (0..1000).map(|i| json!({
+    ///     "messages": [
+    ///         {"role": "user", "content": format!("What is {i} squared?")},
+    ///         {"role": "assistant", "content": format!("{}", i * i)},
+    ///     ]
+    /// }));
+    /// let file = Client::new()
+    ///     .files()
+    ///     .create_jsonl(examples, FilePurpose::FineTune)
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn create_jsonl<I>(
+        &self,
+        items: I,
+        purpose: FilePurpose,
+    ) -> Result<OpenAIFile, OpenAIError>
+    where
+        I: IntoIterator,
+        I::IntoIter: Send + 'static,
+        I::Item: Serialize,
+    {
+        let file_name = format!("{purpose}.jsonl");
+        purpose.validate_file_name(&file_name)?;
+        let lines = futures::stream::iter(items).map(|item| {
+            serde_json::to_vec(&item).map(|mut line| {
+                line.push(b'\n');
+                Bytes::from(line)
+            })
+        });
+        let request = CreateFileStreamRequest {
+            file: UploadPartStream::from_stream(lines),
+            file_name,
+            purpose,
+        };
+        self.client
+            .post_form_once("/files", request, &self.request_options)
+            .await
     }
 
     /// Returns a list of files that belong to the user's organization.
+    ///
+    /// `query` is usually a [ListFilesQuery](crate::types::ListFilesQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListFilesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
-        self.client.get_with_query("/files", &query).await
+        self.client
+            .get_with_query("/files", &query, &self.request_options)
+            .await
     }
 
     /// Returns information about a specific file.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, file_id: &str) -> Result<OpenAIFile, OpenAIError> {
-        self.client.get(format!("/files/{file_id}").as_str()).await
+        self.client
+            .get(format!("/files/{file_id}").as_str(), &self.request_options)
+            .await
     }
 
     /// Delete a file.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, file_id: &str) -> Result<DeleteFileResponse, OpenAIError> {
         self.client
-            .delete(format!("/files/{file_id}").as_str())
+            .delete(format!("/files/{file_id}").as_str(), &self.request_options)
             .await
     }
 
     /// Returns the contents of the specified file
     pub async fn content(&self, file_id: &str) -> Result<Bytes, OpenAIError> {
         self.client
-            .get_raw(format!("/files/{file_id}/content").as_str())
+            .get_raw(
+                format!("/files/{file_id}/content").as_str(),
+                &self.request_options,
+            )
             .await
     }
+
+    /// Returns the contents of the specified file as a stream of chunks, without buffering the whole file in memory.
+    pub async fn content_stream(&self, file_id: &str) -> Result<FileContentStream, OpenAIError> {
+        let response = self
+            .client
+            .get_response(
+                format!("/files/{file_id}/content").as_str(),
+                &self.request_options,
+            )
+            .await?;
+        Ok(Box::pin(
+            response
+                .bytes_stream()
+                .map(|chunk| chunk.map_err(OpenAIError::Reqwest)),
+        ))
+    }
+
+    /// Writes the contents of the specified file to `path`, returning the number of bytes written.
+    ///
+    /// The contents are written to a temporary file renamed to `path` once complete: a failed
+    /// download leaves `path` as it was.
+    pub async fn download_to<P: AsRef<Path>>(
+        &self,
+        file_id: &str,
+        path: P,
+    ) -> Result<u64, OpenAIError> {
+        self.download_to_with_progress(file_id, path, |_| {}).await
+    }
+
+    /// Same as [Files::download_to], calling `progress` after every chunk written.
+    pub async fn download_to_with_progress<P, F>(
+        &self,
+        file_id: &str,
+        path: P,
+        progress: F,
+    ) -> Result<u64, OpenAIError>
+    where
+        P: AsRef<Path>,
+        F: FnMut(DownloadProgress),
+    {
+        let response = self
+            .client
+            .get_response(
+                format!("/files/{file_id}/content").as_str(),
+                &self.request_options,
+            )
+            .await?;
+        let total = response.content_length();
+        let stream = response
+            .bytes_stream()
+            .map(|chunk| chunk.map_err(OpenAIError::Reqwest));
+        save_stream(stream, path.as_ref(), total, progress).await
+    }
+
+    /// ID of `file`, uploaded with `purpose` unless it is already an ID.
+    pub async fn resolve<F: Into<FileReference>>(
+        &self,
+        file: F,
+        purpose: FilePurpose,
+    ) -> Result<String, OpenAIError> {
+        let source = match file.into() {
+            FileReference::Id(file_id) => return Ok(file_id),
+            FileReference::Source(source) => source,
+            FileReference::Url(url) => InputSource::Bytes {
+                filename: url_filename(&url),
+                bytes: self.client.download(&url).await?,
+            },
+        };
+        let file: OpenAIFile = self
+            .create(CreateFileRequest {
+                file: FileInput { source },
+                purpose,
+            })
+            .await?;
+        Ok(file.id)
+    }
+
+    /// Content of `file`, downloaded unless it is a local file or in memory.
+    pub async fn resolve_input<F: Into<FileReference>>(
+        &self,
+        file: F,
+    ) -> Result<InputSource, OpenAIError> {
+        match file.into() {
+            FileReference::Source(source) => Ok(source),
+            FileReference::Url(url) => Ok(InputSource::Bytes {
+                filename: url_filename(&url),
+                bytes: self.client.download(&url).await?,
+            }),
+            FileReference::Id(file_id) => {
+                let file: OpenAIFile = self.retrieve(&file_id).await?;
+                Ok(InputSource::Bytes {
+                    filename: file.filename,
+                    bytes: self.content(&file_id).await?,
+                })
+            }
+        }
+    }
+}
+
+/// A file uploaded from a stream, see [Files::create_jsonl].
+struct CreateFileStreamRequest {
+    file: UploadPartStream,
+    file_name: String,
+    purpose: FilePurpose,
+}
+
+impl AsyncTryFrom<CreateFileStreamRequest> for reqwest::multipart::Form {
+    type Error = OpenAIError;
+
+    async fn try_from(request: CreateFileStreamRequest) -> Result<Self, Self::Error> {
+        let file_part = request.file.part(&request.file_name)?;
+        Ok(reqwest::multipart::Form::new()
+            .part("file", file_part)
+            .text("purpose", request.purpose.to_string()))
+    }
+}
+
+/// Last path segment of `url`, the API requiring a filename with an extension.
+fn url_filename(url: &str) -> String {
+    let path = url.split(['?', '#']).next().unwrap_or_default();
+    path.rsplit('/')
+        .next()
+        .filter(|name| !name.is_empty())
+        .unwrap_or("file")
+        .to_string()
 }
 
 #[cfg(test)]
 mod tests {
+    use std::path::PathBuf;
+
+    use futures::StreamExt;
+
     use crate::{
-        types::{CreateFileRequestArgs, FilePurpose},
+        transport::mock::{MockResponse, MockTransport},
+        types::{CreateFileRequestArgs, DownloadProgress, FilePurpose},
         Client,
     };
 
+    /// Answers the file content requests with `chunks`, failing after them if `fail` is set.
+    fn chunked(chunks: &'static [&'static str], fail: bool) -> std::sync::Arc<MockTransport> {
+        MockTransport::new(move |_, _| {
+            let mut body: Vec<Result<&'static str, std::io::Error>> =
+                chunks.iter().copied().map(Ok).collect();
+            if fail {
+                body.push(Err(std::io::Error::other("connection reset")));
+            }
+            Ok(MockResponse::new(reqwest::Body::wrap_stream(
+                futures::stream::iter(body),
+            )))
+        })
+    }
+
+    /// Empty directory for the files of test `name`.
+    fn download_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("async-openai-{name}-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[tokio::test]
+    async fn test_content_stream() {
+        let transport = chunked(&["hello ", "world"], false);
+        let client = Client::new().with_transport(transport.clone());
+        let chunks: Vec<_> = client
+            .files()
+            .content_stream("file-1")
+            .await
+            .unwrap()
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        assert_eq!(chunks.concat(), b"hello world");
+        assert_eq!(transport.lines(), ["GET /v1/files/file-1/content"]);
+
+        let client = Client::new().with_transport(chunked(&["hello "], true));
+        let mut stream = client.files().content_stream("file-1").await.unwrap();
+        assert_eq!(stream.next().await.unwrap().unwrap(), "hello ");
+        assert!(stream.next().await.unwrap().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_download_to_with_progress() {
+        let dir = download_dir("download");
+        let path = dir.join("data.jsonl");
+
+        let client = Client::new().with_transport(chunked(&["ab", "cde", "f"], false));
+        let mut progress = Vec::new();
+        let downloaded = client
+            .files()
+            .download_to_with_progress("file-1", &path, |p| progress.push(p))
+            .await
+            .unwrap();
+        assert_eq!(downloaded, 6);
+        assert_eq!(
+            progress,
+            [2, 5, 6].map(|downloaded| DownloadProgress {
+                downloaded,
+                total: None
+            })
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "abcdef");
+
+        // the total is known from the content length
+        let client = Client::new().with_transport(MockTransport::body("content"));
+        let mut progress = Vec::new();
+        client
+            .files()
+            .download_to_with_progress("file-1", &path, |p| progress.push(p))
+            .await
+            .unwrap();
+        assert_eq!(
+            progress.last(),
+            Some(&DownloadProgress {
+                downloaded: 7,
+                total: Some(7)
+            })
+        );
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "content");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_download_to_error() {
+        let dir = download_dir("download-error");
+        let client = Client::new().with_transport(chunked(&["ab", "cd"], true));
+
+        // no partial file is left
+        let path = dir.join("new.jsonl");
+        let mut progress = Vec::new();
+        let result = client
+            .files()
+            .download_to_with_progress("file-1", &path, |p| progress.push(p.downloaded))
+            .await;
+        assert!(result.is_err());
+        assert_eq!(progress, [2, 4]);
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 0);
+
+        // an existing file is left as it was
+        let path = dir.join("existing.jsonl");
+        std::fs::write(&path, "previous").unwrap();
+        assert!(client.files().download_to("file-1", &path).await.is_err());
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "previous");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_create_jsonl_not_resent() {
+        let transport = MockTransport::new(|_, _| {
+            Ok(
+                MockResponse::new(r#"{"error": {"message": "slow down", "type": "requests"}}"#)
+                    .status(429),
+            )
+        });
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_backoff(
+                backoff::ExponentialBackoffBuilder::new()
+                    .with_initial_interval(std::time::Duration::from_millis(1))
+                    .build(),
+            );
+
+        let items = [serde_json::json!({"id": 1}), serde_json::json!({"id": 2})];
+        let error = client
+            .files()
+            .create_jsonl(items, FilePurpose::Batch)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(error, crate::error::OpenAIError::ApiError(_)),
+            "{error}"
+        );
+        assert_eq!(error.status().map(|status| status.as_u16()), Some(429));
+        assert_eq!(transport.lines(), ["POST /v1/files"]);
+        let body = String::from_utf8_lossy(&transport.requests()[0].body).into_owned();
+        assert!(body.contains("{\"id\":1}\n{\"id\":2}\n"), "{body}");
+        assert!(body.contains("filename=\"batch.jsonl\""), "{body}");
+    }
+
+    #[test]
+    fn test_url_filename() {
+        assert_eq!(
+            super::url_filename("https://example.com/docs/report.pdf?sig=abc"),
+            "report.pdf"
+        );
+        assert_eq!(super::url_filename("https://example.com/"), "file");
+    }
+
+    #[test]
+    fn test_file_purpose_validation() {
+        let request = |name: &str, purpose: FilePurpose| {
+            CreateFileRequestArgs::default()
+                .file(name)
+                .purpose(purpose)
+                .build()
+        };
+        assert!(request("data/train.JSONL", FilePurpose::FineTune).is_ok());
+        assert!(request("photo.webp", FilePurpose::Vision).is_ok());
+        assert!(request("notes.md", FilePurpose::Assistants).is_ok());
+        assert!(request("train.json", FilePurpose::FineTune).is_err());
+        assert!(request("requests.jsonl", FilePurpose::Vision).is_err());
+    }
+
     #[tokio::test]
     async fn test_file_mod() {
         let test_file_path = "/tmp/test.jsonl";
@@ -1,25 +1,43 @@
+use std::time::Instant;
+
+use futures::{stream, Stream, TryStreamExt};
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    poll::{PollOptions, PollProgress, Pollable},
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        CreateFineTuningJobRequest, FineTuningJob, ListFineTuningJobCheckpointsResponse,
-        ListFineTuningJobEventsResponse, ListPaginatedFineTuningJobsResponse,
+        CreateFineTuningCheckpointPermissionRequest, CreateFineTuningJobRequest, Cursor,
+        DeleteFineTuningCheckpointPermissionResponse, FineTuningJob, FineTuningJobEvent,
+        ListFineTuningCheckpointPermissionResponse, ListFineTuningEventsQuery,
+        ListFineTuningJobCheckpointsResponse, ListFineTuningJobEventsResponse,
+        ListPaginatedFineTuningJobsResponse,
     },
     Client,
 };
 
+/// Number of events fetched per page by [FineTuning::stream_events].
+const EVENTS_PAGE_SIZE: u32 = 100;
+
 /// Manage fine-tuning jobs to tailor a model to your specific training data.
 ///
 /// Related guide: [Fine-tune models](https://platform.openai.com/docs/guides/fine-tuning)
 pub struct FineTuning<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(FineTuning);
+crate::util::impl_api_group_debug!(FineTuning, "/fine_tuning/jobs");
+
 impl<'c, C: Config> FineTuning<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates a job that fine-tunes a specified model from a given dataset.
@@ -32,10 +50,14 @@ impl<'c, C: Config> FineTuning<'c, C> {
         &self,
         request: CreateFineTuningJobRequest,
     ) -> Result<FineTuningJob, OpenAIError> {
-        self.client.post("/fine_tuning/jobs", request).await
+        self.client
+            .post("/fine_tuning/jobs", request, &self.request_options)
+            .await
     }
 
     /// List your organization's fine-tuning jobs
+    ///
+    /// `query` is usually a [ListFineTuningJobsQuery](crate::types::ListFineTuningJobsQuery).
     #[crate::byot(T0 = serde::Serialize, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list_paginated<Q>(
         &self,
@@ -45,7 +67,7 @@ impl<'c, C: Config> FineTuning<'c, C> {
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query("/fine_tuning/jobs", &query)
+            .get_with_query("/fine_tuning/jobs", &query, &self.request_options)
             .await
     }
 
@@ -55,7 +77,10 @@ impl<'c, C: Config> FineTuning<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
         self.client
-            .get(format!("/fine_tuning/jobs/{fine_tuning_job_id}").as_str())
+            .get(
+                format!("/fine_tuning/jobs/{fine_tuning_job_id}").as_str(),
+                &self.request_options,
+            )
             .await
     }
 
@@ -66,11 +91,38 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .post(
                 format!("/fine_tuning/jobs/{fine_tuning_job_id}/cancel").as_str(),
                 (),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Pause a running fine-tune job.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn pause(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client
+            .post(
+                format!("/fine_tuning/jobs/{fine_tuning_job_id}/pause").as_str(),
+                (),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Resume a paused fine-tune job.
+    #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn resume(&self, fine_tuning_job_id: &str) -> Result<FineTuningJob, OpenAIError> {
+        self.client
+            .post(
+                format!("/fine_tuning/jobs/{fine_tuning_job_id}/resume").as_str(),
+                (),
+                &self.request_options,
             )
             .await
     }
 
     /// Get fine-grained status updates for a fine-tune job.
+    ///
+    /// `query` is usually a [ListFineTuningEventsQuery](crate::types::ListFineTuningEventsQuery).
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list_events<Q>(
         &self,
@@ -84,10 +136,131 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .get_with_query(
                 format!("/fine_tuning/jobs/{fine_tuning_job_id}/events").as_str(),
                 &query,
+                &self.request_options,
             )
             .await
     }
 
+    /// The events of a fine-tuning job in the order they were created, the events logged so
+    /// far then the new ones as the job is polled according to `options`, until the job
+    /// succeeded, failed or was cancelled. The training metrics of `metrics` events are
+    /// parsed by [FineTuningJobEvent::metrics].
+    ///
+    /// Fails with [OpenAIError::Timeout] once `max_wait` of `options` elapsed.
+    ///
+    /// ```no_run
+    /// use async_openai::{poll::PollOptions, Client};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+    /// let client = Client::new();
+    /// let fine_tuning = client.fine_tuning();
+    /// let mut events = Box::pin(fine_tuning.stream_events("ftjob-abc123", PollOptions::new()));
+    /// while let Some(event) = events.next().await {
+    ///     let event = event?;
+    ///     match event.metrics() {
+    ///         Some(metrics) => println!("step {:?}: loss {:?}", metrics.step, metrics.train_loss),
+    ///         None => println!("{}", event.message),
+    ///     }
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn stream_events(
+        &self,
+        fine_tuning_job_id: &str,
+        options: PollOptions,
+    ) -> impl Stream<Item = Result<FineTuningJobEvent, OpenAIError>> + 'c {
+        let fine_tuning = FineTuning {
+            client: self.client,
+            request_options: self.request_options.clone(),
+        };
+        let job_id = fine_tuning_job_id.to_string();
+        let start = Instant::now();
+
+        // the ID of the last event yielded, `None` once the job is done
+        stream::try_unfold(
+            (fine_tuning, Some(None::<String>), 0),
+            move |(fine_tuning, last_id, attempt)| {
+                let job_id = job_id.clone();
+                let options = options.clone();
+                async move {
+                    let Some(last_id) = last_id else {
+                        return Ok(None);
+                    };
+                    if attempt > 0 {
+                        let elapsed = start.elapsed();
+                        if options
+                            .max_wait()
+                            .is_some_and(|max_wait| elapsed >= max_wait)
+                        {
+                            return Err(OpenAIError::Timeout(format!(
+                                "{job_id} still running after {elapsed:?}"
+                            )));
+                        }
+                        tokio::time::sleep(options.next_delay(attempt, elapsed)).await;
+                    }
+
+                    // events logged before the job is done are listed after retrieving it
+                    let job = fine_tuning.retrieve(&job_id).await?;
+                    let events = fine_tuning.new_events(&job_id, last_id.as_deref()).await?;
+                    options.tick(&PollProgress {
+                        id: job_id,
+                        status: job.poll_status(),
+                        attempt: attempt + 1,
+                        elapsed: start.elapsed(),
+                    });
+
+                    let last_id = match events.last() {
+                        Some(event) => Some(event.id.clone()),
+                        None => last_id,
+                    };
+                    let next = (!job.is_done()).then_some(last_id);
+                    Ok(Some((
+                        stream::iter(events.into_iter().map(Ok::<_, OpenAIError>)),
+                        (fine_tuning, next, attempt + 1),
+                    )))
+                }
+            },
+        )
+        .try_flatten()
+    }
+
+    /// The events created after the event `last_id`, all of them without, oldest first.
+    async fn new_events(
+        &self,
+        fine_tuning_job_id: &str,
+        last_id: Option<&str>,
+    ) -> Result<Vec<FineTuningJobEvent>, OpenAIError> {
+        let mut events = Vec::new();
+        let mut after = None;
+        loop {
+            let query = ListFineTuningEventsQuery {
+                after,
+                limit: Some(EVENTS_PAGE_SIZE),
+            };
+            // the events are listed newest first
+            let page = self.list_events(fine_tuning_job_id, &query).await?;
+            let full = page.data.len() == EVENTS_PAGE_SIZE as usize;
+            for event in page.data {
+                if Some(event.id.as_str()) == last_id {
+                    events.reverse();
+                    return Ok(events);
+                }
+                events.push(event);
+            }
+            match events.last() {
+                Some(event) if full => after = Some(Cursor::new(&event.id)),
+                _ => break,
+            }
+        }
+        events.reverse();
+        Ok(events)
+    }
+
+    /// List checkpoints for a fine-tuning job.
+    ///
+    /// `query` is usually a [ListFineTuningCheckpointsQuery](crate::types::ListFineTuningCheckpointsQuery).
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list_checkpoints<Q>(
         &self,
@@ -101,7 +274,144 @@ impl<'c, C: Config> FineTuning<'c, C> {
             .get_with_query(
                 format!("/fine_tuning/jobs/{fine_tuning_job_id}/checkpoints").as_str(),
                 &query,
+                &self.request_options,
             )
             .await
     }
+
+    /// List checkpoint permissions of a fine-tuned model checkpoint.
+    ///
+    /// This endpoint requires an [admin API key](https://platform.openai.com/docs/api-reference/administration).
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list_checkpoint_permissions<Q>(
+        &self,
+        fine_tuned_model_checkpoint: &str,
+        query: &Q,
+    ) -> Result<ListFineTuningCheckpointPermissionResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                format!("/fine_tuning/checkpoints/{fine_tuned_model_checkpoint}/permissions")
+                    .as_str(),
+                &query,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Grant projects access to a fine-tuned model checkpoint.
+    ///
+    /// This endpoint requires an [admin API key](https://platform.openai.com/docs/api-reference/administration).
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_checkpoint_permissions(
+        &self,
+        fine_tuned_model_checkpoint: &str,
+        request: CreateFineTuningCheckpointPermissionRequest,
+    ) -> Result<ListFineTuningCheckpointPermissionResponse, OpenAIError> {
+        self.client
+            .post(
+                format!("/fine_tuning/checkpoints/{fine_tuned_model_checkpoint}/permissions")
+                    .as_str(),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Revoke a project's access to a fine-tuned model checkpoint.
+    ///
+    /// This endpoint requires an [admin API key](https://platform.openai.com/docs/api-reference/administration).
+    #[crate::byot(T0 = std::fmt::Display, T1 = std::fmt::Display, R = serde::de::DeserializeOwned)]
+    pub async fn delete_checkpoint_permission(
+        &self,
+        fine_tuned_model_checkpoint: &str,
+        permission_id: &str,
+    ) -> Result<DeleteFineTuningCheckpointPermissionResponse, OpenAIError> {
+        self.client
+            .delete(
+                format!(
+                    "/fine_tuning/checkpoints/{fine_tuned_model_checkpoint}/permissions/{permission_id}"
+                )
+                .as_str(),
+                &self.request_options,
+            )
+            .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use futures::StreamExt;
+
+    use super::*;
+    use crate::transport::mock::{MockResponse, MockTransport};
+
+    fn event(id: &str, data: serde_json::Value) -> serde_json::Value {
+        serde_json::json!({
+            "id": id, "created_at": 1, "level": "info", "message": id,
+            "object": "fine_tuning.job.event", "type": "metrics", "data": data
+        })
+    }
+
+    /// A job running on the first poll, with one event per poll, and succeeded on the second.
+    fn job() -> Arc<MockTransport> {
+        let polls = AtomicU32::new(0);
+        MockTransport::new(move |request, _| {
+            let body = if request.path().ends_with("/events") {
+                let mut events = vec![event("ftevent-1", serde_json::json!({"step": 1}))];
+                if polls.load(Ordering::SeqCst) > 1 {
+                    events.insert(
+                        0,
+                        event(
+                            "ftevent-2",
+                            serde_json::json!({"step": 2, "train_loss": 0.5}),
+                        ),
+                    );
+                }
+                serde_json::json!({"object": "list", "data": events})
+            } else {
+                let status = match polls.fetch_add(1, Ordering::SeqCst) {
+                    0 => "running",
+                    _ => "succeeded",
+                };
+                serde_json::json!({
+                    "id": "ftjob-1", "created_at": 1, "error": null, "fine_tuned_model": null,
+                    "finished_at": null, "model": "gpt-4o-mini", "object": "fine_tuning.job",
+                    "organization_id": "org-1", "result_files": [], "status": status,
+                    "trained_tokens": null, "training_file": "file-1", "validation_file": null,
+                    "seed": 1,
+                    "hyperparameters": {
+                        "batch_size": 1, "learning_rate_multiplier": 1.0, "n_epochs": 1
+                    }
+                })
+            };
+            Ok(MockResponse::json(body))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_stream_events() {
+        let client = Client::new().with_transport(job());
+        let options = PollOptions::new().with_interval(Duration::from_millis(1));
+        let events: Vec<FineTuningJobEvent> = client
+            .fine_tuning()
+            .stream_events("ftjob-1", options)
+            .map(Result::unwrap)
+            .collect()
+            .await;
+        let ids: Vec<&str> = events.iter().map(|event| event.id.as_str()).collect();
+        assert_eq!(ids, ["ftevent-1", "ftevent-2"]);
+        let metrics = events[1].metrics().unwrap();
+        assert_eq!((metrics.step, metrics.train_loss), (Some(2), Some(0.5)));
+    }
 }
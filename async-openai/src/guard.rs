@@ -0,0 +1,270 @@
+//! Guards cancelling a remote run or batch when dropped before it finished,
+//! for example when the task driving it is aborted, so it does not keep consuming tokens.
+//!
+//! The cancellation is spawned on the tokio runtime of the thread dropping the guard. Dropped
+//! outside of a runtime, where `Handle::try_current` fails, a guard only logs a warning and the
+//! run or batch keeps going.
+use std::fmt;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    poll::{PollOptions, Pollable},
+    types::{Batch, BatchStatus, RunObject, RunStatus},
+    Batches, Client, Runs,
+};
+
+/// Spawn `cancel` on the current tokio runtime, if any, since `Drop` cannot await.
+fn spawn_cancel<F>(what: String, cancel: F)
+where
+    F: std::future::Future<Output = Result<(), OpenAIError>> + Send + 'static,
+{
+    match tokio::runtime::Handle::try_current() {
+        Ok(handle) => {
+            handle.spawn(async move {
+                if let Err(e) = cancel.await {
+                    tracing::warn!("failed to cancel {what} on drop: {e}");
+                }
+            });
+        }
+        Err(_) => tracing::warn!("cannot cancel {what} on drop outside of a tokio runtime"),
+    }
+}
+
+/// Cancels a run when dropped, unless it finished or [RunGuard::disarm] was called.
+///
+/// ```no_run
+/// # async fn example(run: async_openai::types::RunObject) -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{guard::RunGuard, poll::PollOptions, Client};
+///
+/// let client = Client::new();
+/// let mut guard = RunGuard::new(&client, &run);
+/// // the run is cancelled if this future is dropped before the run finished
+/// let run = guard.wait(&PollOptions::new()).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct RunGuard<C: Config + Clone + 'static> {
+    client: Client<C>,
+    thread_id: String,
+    run_id: String,
+    armed: bool,
+}
+
+impl<C: Config + Clone + 'static> RunGuard<C> {
+    /// Guard `run`, already finished runs are never cancelled.
+    pub fn new(client: &Client<C>, run: &RunObject) -> Self {
+        let mut guard = Self::from_ids(client, &run.thread_id, &run.id);
+        guard.armed = !Self::is_finished(run);
+        guard
+    }
+
+    pub fn from_ids(client: &Client<C>, thread_id: &str, run_id: &str) -> Self {
+        Self {
+            client: client.clone(),
+            thread_id: thread_id.to_string(),
+            run_id: run_id.to_string(),
+            armed: true,
+        }
+    }
+
+    pub fn thread_id(&self) -> &str {
+        &self.thread_id
+    }
+
+    pub fn run_id(&self) -> &str {
+        &self.run_id
+    }
+
+    /// Whether the run will be cancelled on drop.
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    /// Let the run continue after the guard is dropped.
+    pub fn disarm(&mut self) {
+        self.armed = false;
+    }
+
+    /// Wait with [Runs::wait], the guard is disarmed once the run finished.
+    ///
+    /// A run returned with `requires_action` keeps the guard armed.
+    pub async fn wait(&mut self, options: &PollOptions) -> Result<RunObject, OpenAIError> {
+        let run = Runs::new(&self.client, &self.thread_id)
+            .wait(&self.run_id, options)
+            .await?;
+        if Self::is_finished(&run) {
+            self.disarm();
+        }
+        Ok(run)
+    }
+
+    /// Cancel the run now.
+    pub async fn cancel(mut self) -> Result<RunObject, OpenAIError> {
+        self.disarm();
+        Runs::new(&self.client, &self.thread_id)
+            .cancel(&self.run_id)
+            .await
+    }
+
+    fn is_finished(run: &RunObject) -> bool {
+        run.is_done() && run.status != RunStatus::RequiresAction
+    }
+}
+
+impl<C: Config + Clone + 'static> Drop for RunGuard<C> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let client = self.client.clone();
+        let thread_id = std::mem::take(&mut self.thread_id);
+        let run_id = std::mem::take(&mut self.run_id);
+        spawn_cancel(format!("run {run_id}"), async move {
+            Runs::new(&client, &thread_id).cancel(&run_id).await?;
+            Ok(())
+        });
+    }
+}
+
+impl<C: Config + Clone + 'static> fmt::Debug for RunGuard<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RunGuard")
+            .field("thread_id", &self.thread_id)
+            .field("run_id", &self.run_id)
+            .field("armed", &self.armed)
+            .finish()
+    }
+}
+
+/// Cancels a batch when dropped, unless it finished or [BatchGuard::disarm] was called.
+pub struct BatchGuard<C: Config + Clone + 'static> {
+    client: Client<C>,
+    batch_id: String,
+    armed: bool,
+}
+
+impl<C: Config + Clone + 'static> BatchGuard<C> {
+    /// Guard `batch`, already finished batches are never cancelled.
+    pub fn new(client: &Client<C>, batch: &Batch) -> Self {
+        let mut guard = Self::from_id(client, &batch.id);
+        guard.armed = !batch.is_done() && batch.status != BatchStatus::Cancelling;
+        guard
+    }
+
+    pub fn from_id(client: &Client<C>, batch_id: &str) -> Self {
+        Self {
+            client: client.clone(),
+            batch_id: batch_id.to_string(),
+            armed: true,
+        }
+    }
+
+    pub fn batch_id(&self) -> &str {
+        &self.batch_id
+    }
+
+    /// Whether the batch will be cancelled on drop.
+    pub fn is_armed(&self) -> bool {
+        self.armed
+    }
+
+    /// Let the batch continue after the guard is dropped.
+    pub fn disarm(&mut self) {
+        self.armed = false;
+    }
+
+    /// Wait with [Batches::wait], the guard is disarmed once the batch finished.
+    pub async fn wait(&mut self, options: &PollOptions) -> Result<Batch, OpenAIError> {
+        let batch = Batches::new(&self.client)
+            .wait(&self.batch_id, options)
+            .await?;
+        self.disarm();
+        Ok(batch)
+    }
+
+    /// Cancel the batch now.
+    pub async fn cancel(mut self) -> Result<Batch, OpenAIError> {
+        self.disarm();
+        Batches::new(&self.client).cancel(&self.batch_id).await
+    }
+}
+
+impl<C: Config + Clone + 'static> Drop for BatchGuard<C> {
+    fn drop(&mut self) {
+        if !self.armed {
+            return;
+        }
+        let client = self.client.clone();
+        let batch_id = std::mem::take(&mut self.batch_id);
+        spawn_cancel(format!("batch {batch_id}"), async move {
+            Batches::new(&client).cancel(&batch_id).await?;
+            Ok(())
+        });
+    }
+}
+
+impl<C: Config + Clone + 'static> fmt::Debug for BatchGuard<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BatchGuard")
+            .field("batch_id", &self.batch_id)
+            .field("armed", &self.armed)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::transport::mock::MockTransport;
+
+    /// Answers every request with a completed batch.
+    fn recorder() -> Arc<MockTransport> {
+        MockTransport::body(
+            r#"{"id": "batch_1", "object": "batch", "endpoint": "/v1/chat/completions",
+                "input_file_id": "file-1", "completion_window": "24h", "status": "completed",
+                "created_at": 1714508499}"#,
+        )
+    }
+
+    #[tokio::test]
+    async fn test_cancel_on_drop() {
+        let transport = recorder();
+        let client = Client::new().with_transport(transport.clone());
+
+        // finished and disarmed guards send nothing on drop
+        let mut finished = BatchGuard::from_id(&client, "batch_1");
+        finished.wait(&PollOptions::new()).await.unwrap();
+        assert!(!finished.is_armed());
+        assert_eq!(transport.lines(), ["GET /v1/batches/batch_1"]);
+        drop(finished);
+        let mut disarmed = RunGuard::from_ids(&client, "thread_1", "run_2");
+        disarmed.disarm();
+        drop(disarmed);
+
+        drop(RunGuard::from_ids(&client, "thread_1", "run_1"));
+        drop(BatchGuard::from_id(&client, "batch_2"));
+        transport.received(3).await;
+        let mut cancelled = transport.lines().split_off(1);
+        cancelled.sort();
+        assert_eq!(
+            cancelled,
+            [
+                "POST /v1/batches/batch_2/cancel",
+                "POST /v1/threads/thread_1/runs/run_1/cancel"
+            ]
+        );
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        assert_eq!(transport.len(), 3);
+    }
+
+    #[test]
+    fn test_drop_without_runtime() {
+        let transport = recorder();
+        let client = Client::new().with_transport(transport.clone());
+        drop(RunGuard::from_ids(&client, "thread_1", "run_1"));
+        assert_eq!(transport.len(), 0);
+    }
+}
@@ -0,0 +1,115 @@
+//! Hedging of idempotent requests: when a request is slower than a threshold, a duplicate is
+//! sent and the first response wins, cutting the tail latency of the API.
+//!
+//! Only requests without side effects are hedged: embeddings and moderations. Each hedge is
+//! billed like any other request, so pick a threshold around the P95 latency of your calls.
+//!
+//! ```
+//! use std::time::Duration;
+//!
+//! use async_openai::{hedge::HedgePolicy, Client};
+//!
+//! let client = Client::new().with_hedging(HedgePolicy::new(Duration::from_millis(800)));
+//! ```
+use std::{future::Future, time::Duration};
+
+use futures::{stream::FuturesUnordered, StreamExt};
+
+use crate::error::OpenAIError;
+
+/// When to send duplicates of a slow request, see [crate::Client::with_hedging].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HedgePolicy {
+    delay: Duration,
+    max_hedges: u32,
+}
+
+impl HedgePolicy {
+    /// Send one duplicate of a request which did not complete after `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            max_hedges: 1,
+        }
+    }
+
+    /// Send up to `max_hedges` duplicates, each `delay` after the previous one.
+    pub fn with_max_hedges(mut self, max_hedges: u32) -> Self {
+        self.max_hedges = max_hedges;
+        self
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    pub fn max_hedges(&self) -> u32 {
+        self.max_hedges
+    }
+}
+
+/// Run `attempt`, and again each time the attempts in flight are slower than the delay of
+/// `policy`. The first success wins and the other attempts are cancelled by being dropped.
+/// An error is only returned when no attempt is left in flight.
+pub(crate) async fn hedged<T, F, Fut>(policy: &HedgePolicy, attempt: F) -> Result<T, OpenAIError>
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = Result<T, OpenAIError>>,
+{
+    let mut in_flight = FuturesUnordered::new();
+    in_flight.push(attempt());
+    let mut hedges = 0;
+    loop {
+        let delay = tokio::time::sleep(policy.delay);
+        tokio::select! {
+            Some(result) = in_flight.next() => match result {
+                Ok(response) => return Ok(response),
+                Err(error) if in_flight.is_empty() => return Err(error),
+                Err(error) => tracing::debug!("hedged request failed: {error}"),
+            },
+            _ = delay, if hedges < policy.max_hedges => {
+                hedges += 1;
+                tracing::debug!("hedging a request slower than {:?}", policy.delay);
+                in_flight.push(attempt());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use super::*;
+    use crate::{
+        transport::mock::{MockResponse, MockTransport},
+        types::CreateModerationRequestArgs,
+    };
+
+    #[tokio::test]
+    async fn test_hedged_moderation() {
+        // answers its first request after a second and the others immediately
+        let transport = MockTransport::new(|_, index| {
+            let response = MockResponse::new(
+                r#"{"id": "modr-1", "model": "omni-moderation-latest", "results": []}"#,
+            );
+            Ok(match index {
+                0 => response.delay(Duration::from_secs(1)),
+                _ => response,
+            })
+        });
+        let client = crate::Client::new()
+            .with_transport(transport.clone())
+            .with_hedging(HedgePolicy::new(Duration::from_millis(50)));
+        let request = CreateModerationRequestArgs::default()
+            .input("hello")
+            .build()
+            .unwrap();
+
+        let start = Instant::now();
+        let response = client.moderations().create(request).await.unwrap();
+        assert_eq!(response.id, "modr-1");
+        assert!(start.elapsed() < Duration::from_millis(500));
+        assert_eq!(transport.len(), 2);
+    }
+}
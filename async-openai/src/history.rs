@@ -0,0 +1,435 @@
+//! Chat histories kept within a token budget, dropping or summarizing their oldest turns.
+//!
+//! ```
+//! # tokio_test::block_on(async {
+//! use async_openai::{
+//!     history::ChatHistory,
+//!     types::{ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage},
+//! };
+//!
+//! let mut history = ChatHistory::new(30);
+//! history.push(ChatCompletionRequestSystemMessage::from("You are terse."));
+//! for question in ["What is Rust?", "Who created it?", "When was 1.0 released?"] {
+//!     history.push(ChatCompletionRequestUserMessage::from(question));
+//!     history.fit().await?;
+//! }
+//! // the system message and the last turn are always kept
+//! assert_eq!(history.messages().len(), 2);
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+use std::{fmt, sync::Arc};
+
+use futures::future::BoxFuture;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    transcript::TranscriptFormat,
+    types::{
+        estimate_tokens, ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestUserMessage, CreateChatCompletionRequest,
+        CreateChatCompletionResponse, TokenCounter,
+    },
+    Client,
+};
+
+/// What to do with the oldest turns of a [ChatHistory] over its budget.
+pub trait CompactionStrategy: Send + Sync {
+    /// Messages replacing `turns`, e.g. nothing to drop them or a summary of them.
+    ///
+    /// `turns` starts with the replacement of the previous compaction, if any.
+    fn compact<'a>(
+        &'a self,
+        turns: &'a [ChatCompletionRequestMessage],
+    ) -> BoxFuture<'a, Result<Vec<ChatCompletionRequestMessage>, OpenAIError>>;
+}
+
+impl<S: CompactionStrategy + ?Sized> CompactionStrategy for Arc<S> {
+    fn compact<'a>(
+        &'a self,
+        turns: &'a [ChatCompletionRequestMessage],
+    ) -> BoxFuture<'a, Result<Vec<ChatCompletionRequestMessage>, OpenAIError>> {
+        (**self).compact(turns)
+    }
+}
+
+/// [CompactionStrategy] dropping the oldest turns.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DropOldest;
+
+impl CompactionStrategy for DropOldest {
+    fn compact<'a>(
+        &'a self,
+        _: &'a [ChatCompletionRequestMessage],
+    ) -> BoxFuture<'a, Result<Vec<ChatCompletionRequestMessage>, OpenAIError>> {
+        Box::pin(async { Ok(Vec::new()) })
+    }
+}
+
+/// [CompactionStrategy] replacing the oldest turns by a system message summarizing them,
+/// written by `model`.
+#[derive(Debug, Clone)]
+pub struct Summarize<C: Config> {
+    client: Client<C>,
+    model: String,
+    instructions: String,
+}
+
+impl<C: Config> Summarize<C> {
+    pub fn new<S: Into<String>>(client: Client<C>, model: S) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            instructions: "Summarize the conversation above in a few sentences, keeping the \
+                facts, decisions and open questions needed to continue it."
+                .into(),
+        }
+    }
+
+    /// Instructions sent to the model after the turns to summarize.
+    pub fn with_instructions<S: Into<String>>(mut self, instructions: S) -> Self {
+        self.instructions = instructions.into();
+        self
+    }
+}
+
+impl<C: Config> CompactionStrategy for Summarize<C> {
+    fn compact<'a>(
+        &'a self,
+        turns: &'a [ChatCompletionRequestMessage],
+    ) -> BoxFuture<'a, Result<Vec<ChatCompletionRequestMessage>, OpenAIError>> {
+        Box::pin(async move {
+            // a transcript, as the turns may start with tool messages without their call
+            let transcript = TranscriptFormat::Markdown.export(turns)?;
+            let request = CreateChatCompletionRequest {
+                model: self.model.clone(),
+                messages: vec![
+                    ChatCompletionRequestUserMessage::from(transcript).into(),
+                    ChatCompletionRequestUserMessage::from(self.instructions.as_str()).into(),
+                ],
+                ..Default::default()
+            };
+            let response = self.client.chat().create(request).await?;
+            let summary = response
+                .choices
+                .into_iter()
+                .next()
+                .and_then(|choice| choice.message.content)
+                .unwrap_or_default();
+            Ok(vec![ChatCompletionRequestSystemMessage::from(format!(
+                "Summary of the earlier conversation: {summary}"
+            ))
+            .into()])
+        })
+    }
+}
+
+/// Messages of a chat session kept within a token budget.
+///
+/// The leading system and developer messages and the last turn are always kept. When
+/// [ChatHistory::fit] finds the history over budget, the oldest turns, each a user message
+/// with the assistant and tool messages answering it, are replaced by the [CompactionStrategy],
+/// [DropOldest] by default. Tokens are counted on the JSON of the messages with
+/// [estimate_tokens] unless another counter is set with [ChatHistory::with_token_counter].
+#[derive(Clone)]
+pub struct ChatHistory {
+    messages: Vec<ChatCompletionRequestMessage>,
+    compacted: Vec<ChatCompletionRequestMessage>,
+    max_tokens: usize,
+    count_tokens: TokenCounter,
+    strategy: Arc<dyn CompactionStrategy>,
+}
+
+impl fmt::Debug for ChatHistory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChatHistory")
+            .field("messages", &self.messages)
+            .field("compacted", &self.compacted)
+            .field("max_tokens", &self.max_tokens)
+            .finish_non_exhaustive()
+    }
+}
+
+impl ChatHistory {
+    /// Empty history of at most `max_tokens`, dropping its oldest turns.
+    pub fn new(max_tokens: usize) -> Self {
+        Self {
+            messages: Vec::new(),
+            compacted: Vec::new(),
+            max_tokens,
+            count_tokens: Arc::new(estimate_tokens),
+            strategy: Arc::new(DropOldest),
+        }
+    }
+
+    pub fn with_strategy<S: CompactionStrategy + 'static>(mut self, strategy: S) -> Self {
+        self.strategy = Arc::new(strategy);
+        self
+    }
+
+    /// Count tokens with the tokenizer of the model instead of [estimate_tokens].
+    pub fn with_token_counter<F>(mut self, count_tokens: F) -> Self
+    where
+        F: Fn(&str) -> usize + Send + Sync + 'static,
+    {
+        self.count_tokens = Arc::new(count_tokens);
+        self
+    }
+
+    pub fn push<M: Into<ChatCompletionRequestMessage>>(&mut self, message: M) {
+        self.messages.push(message.into());
+    }
+
+    /// Append the message of the first choice of `response`, if any.
+    pub fn push_response(&mut self, response: &CreateChatCompletionResponse) {
+        if let Some(choice) = response.choices.first() {
+            self.push(choice.message.clone());
+        }
+    }
+
+    /// The messages to send: the leading system and developer messages, the replacement of
+    /// the compacted turns, then the following turns.
+    pub fn messages(&self) -> Vec<ChatCompletionRequestMessage> {
+        let pinned = self.pinned();
+        let mut messages = self.messages[..pinned].to_vec();
+        messages.extend(self.compacted.iter().cloned());
+        messages.extend(self.messages[pinned..].iter().cloned());
+        messages
+    }
+
+    /// Estimated tokens of [ChatHistory::messages].
+    pub fn tokens(&self) -> usize {
+        self.compacted
+            .iter()
+            .chain(&self.messages)
+            .map(|message| self.message_tokens(message))
+            .sum()
+    }
+
+    pub fn max_tokens(&self) -> usize {
+        self.max_tokens
+    }
+
+    pub fn clear(&mut self) {
+        self.messages.clear();
+        self.compacted.clear();
+    }
+
+    /// Compact the oldest turns until the history fits in its budget, or only the leading
+    /// system and developer messages and the last turn are left.
+    pub async fn fit(&mut self) -> Result<(), OpenAIError> {
+        loop {
+            let over = self.tokens().saturating_sub(self.max_tokens);
+            if over == 0 {
+                return Ok(());
+            }
+            let pinned = self.pinned();
+            // turns end before a user message, the last turn is kept
+            let mut freed: usize = self
+                .compacted
+                .iter()
+                .map(|message| self.message_tokens(message))
+                .sum();
+            let mut end = None;
+            for (index, message) in self.messages.iter().enumerate().skip(pinned) {
+                if index > pinned && matches!(message, ChatCompletionRequestMessage::User(_)) {
+                    end = Some(index);
+                    if freed >= over {
+                        break;
+                    }
+                }
+                freed += self.message_tokens(message);
+            }
+            let Some(end) = end else {
+                return Ok(());
+            };
+
+            let mut turns = std::mem::take(&mut self.compacted);
+            turns.extend(self.messages.drain(pinned..end));
+            self.compacted = self.strategy.compact(&turns).await?;
+        }
+    }
+
+    /// Number of leading system and developer messages.
+    fn pinned(&self) -> usize {
+        self.messages
+            .iter()
+            .take_while(|message| {
+                matches!(
+                    message,
+                    ChatCompletionRequestMessage::System(_)
+                        | ChatCompletionRequestMessage::Developer(_)
+                )
+            })
+            .count()
+    }
+
+    fn message_tokens(&self, message: &ChatCompletionRequestMessage) -> usize {
+        serde_json::to_string(message)
+            .map(|json| (self.count_tokens)(&json))
+            .unwrap_or_default()
+    }
+}
+
+impl From<ChatHistory> for Vec<ChatCompletionRequestMessage> {
+    fn from(history: ChatHistory) -> Self {
+        history.messages()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessage,
+        ChatCompletionRequestToolMessage, ChatCompletionToolType, CreateChatCompletionRequestArgs,
+        FunctionCall,
+    };
+
+    /// Replaces the turns by a message counting them.
+    struct Count;
+
+    impl CompactionStrategy for Count {
+        fn compact<'a>(
+            &'a self,
+            turns: &'a [ChatCompletionRequestMessage],
+        ) -> BoxFuture<'a, Result<Vec<ChatCompletionRequestMessage>, OpenAIError>> {
+            let summary = format!("{} messages", turns.len());
+            Box::pin(async move {
+                Ok(vec![
+                    ChatCompletionRequestSystemMessage::from(summary).into()
+                ])
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_chat_history() {
+        // one token per message
+        let mut history = ChatHistory::new(4)
+            .with_token_counter(|_| 1)
+            .with_strategy(Count);
+        history.push(ChatCompletionRequestSystemMessage::from("system"));
+        for turn in ["a", "b", "c"] {
+            history.push(ChatCompletionRequestUserMessage::from(turn));
+            history.push(ChatCompletionRequestAssistantMessage::from(turn));
+        }
+        assert_eq!(history.tokens(), 7);
+
+        history.fit().await.unwrap();
+        let messages: Vec<ChatCompletionRequestMessage> = history.clone().into();
+        assert_eq!(
+            messages,
+            vec![
+                ChatCompletionRequestSystemMessage::from("system").into(),
+                ChatCompletionRequestSystemMessage::from("4 messages").into(),
+                ChatCompletionRequestUserMessage::from("c").into(),
+                ChatCompletionRequestAssistantMessage::from("c").into(),
+            ]
+        );
+
+        history.push(ChatCompletionRequestUserMessage::from("d"));
+        history.fit().await.unwrap();
+        // the previous summary is compacted along with the turn of "c"
+        assert_eq!(
+            history.messages()[1],
+            ChatCompletionRequestSystemMessage::from("3 messages").into()
+        );
+        assert_eq!(history.tokens(), 3);
+    }
+
+    /// Assistant message calling the tool `id` then the tool message answering it.
+    fn tool_call(id: &str) -> [ChatCompletionRequestMessage; 2] {
+        [
+            ChatCompletionRequestAssistantMessage {
+                tool_calls: Some(vec![ChatCompletionMessageToolCall {
+                    id: id.into(),
+                    r#type: ChatCompletionToolType::Function,
+                    function: FunctionCall {
+                        name: "get_weather".into(),
+                        arguments: "{}".into(),
+                    },
+                }]),
+                ..Default::default()
+            }
+            .into(),
+            ChatCompletionRequestToolMessage {
+                content: "sunny".into(),
+                tool_call_id: id.into(),
+            }
+            .into(),
+        ]
+    }
+
+    /// The messages of `history` pass the validation of the tool call ids of chat requests.
+    fn assert_valid(history: &ChatHistory) {
+        CreateChatCompletionRequestArgs::default()
+            .model("m")
+            .messages(history.messages())
+            .build()
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_fit_tool_call_turns() {
+        // one token per message, counted by a closure capturing its state
+        let weight = 1;
+        let mut history = ChatHistory::new(5).with_token_counter(move |_| weight);
+        history.push(ChatCompletionRequestSystemMessage::from("system"));
+        for turn in ["a", "b", "c"] {
+            history.push(ChatCompletionRequestUserMessage::from(turn));
+            for message in tool_call(&format!("call_{turn}")) {
+                history.push(message);
+            }
+            history.push(ChatCompletionRequestAssistantMessage::from(turn));
+        }
+        assert_valid(&history);
+        assert_eq!(history.tokens(), 13);
+
+        history.fit().await.unwrap();
+        // whole turns are dropped, the tool calls with their answers
+        let messages = history.messages();
+        assert_eq!(messages.len(), 5);
+        assert_eq!(
+            messages[1],
+            ChatCompletionRequestUserMessage::from("c").into()
+        );
+        assert_eq!(&messages[2..4], &tool_call("call_c"));
+        assert_valid(&history);
+    }
+
+    #[tokio::test]
+    async fn test_fit_leading_tool_calls() {
+        // a history resumed in the middle of a turn, starting with a tool call
+        let mut history = ChatHistory::new(3).with_token_counter(|_| 1);
+        for message in tool_call("call_1") {
+            history.push(message);
+        }
+        history.push(ChatCompletionRequestAssistantMessage::from("sunny"));
+        history.push(ChatCompletionRequestUserMessage::from("and tomorrow?"));
+        for message in tool_call("call_2") {
+            history.push(message);
+        }
+        assert_valid(&history);
+
+        history.fit().await.unwrap();
+        let messages = history.messages();
+        assert_eq!(
+            messages[0],
+            ChatCompletionRequestUserMessage::from("and tomorrow?").into()
+        );
+        assert_eq!(&messages[1..], &tool_call("call_2"));
+        assert_valid(&history);
+
+        // the last turn is kept over budget rather than split
+        let mut history = ChatHistory::new(1).with_token_counter(|_| 1);
+        history.push(ChatCompletionRequestUserMessage::from("weather?"));
+        for message in tool_call("call_1") {
+            history.push(message);
+        }
+        history.fit().await.unwrap();
+        assert_eq!(history.messages().len(), 3);
+        assert_valid(&history);
+    }
+}
@@ -1,8 +1,10 @@
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        CreateImageEditRequest, CreateImageRequest, CreateImageVariationRequest, ImagesResponse,
+        CreateImageEditRequest, CreateImageRequest, CreateImageVariationRequest, ImageGenStream,
+        ImagesResponse,
     },
     Client,
 };
@@ -12,17 +14,59 @@ use crate::{
 /// Related guide: [Image generation](https://platform.openai.com/docs/guides/images)
 pub struct Images<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Images);
+crate::util::impl_api_group_debug!(Images, "/images");
+
 impl<'c, C: Config> Images<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates an image given a prompt.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: CreateImageRequest) -> Result<ImagesResponse, OpenAIError> {
-        self.client.post("/images/generations", request).await
+        self.client
+            .post("/images/generations", request, &self.request_options)
+            .await
+    }
+
+    /// Creates an image given a prompt, streaming partial images as they are generated.
+    /// Only supported for `gpt-image-1`, the number of partial images is set with `partial_images`.
+    ///
+    /// [ImageGenStream] is a parsed SSE stream ending with the final image.
+    ///
+    /// byot: You must ensure "stream: true" in serialized `request`
+    #[crate::byot(
+        T0 = serde::Serialize,
+        R = serde::de::DeserializeOwned,
+        stream = "true",
+        where_clause = "R: std::marker::Send + 'static"
+    )]
+    #[allow(unused_mut)]
+    pub async fn create_stream(
+        &self,
+        mut request: CreateImageRequest,
+    ) -> Result<ImageGenStream, OpenAIError> {
+        #[cfg(not(feature = "byot"))]
+        {
+            if matches!(request.stream, Some(false)) {
+                return Err(OpenAIError::InvalidArgument(
+                    "When stream is false, use Images::create".into(),
+                ));
+            }
+            request.stream = Some(true);
+        }
+
+        Ok(self
+            .client
+            .post_stream("/images/generations", request, &self.request_options)
+            .await)
     }
 
     /// Creates an edited or extended image given an original image and a prompt.
@@ -35,19 +79,33 @@ impl<'c, C: Config> Images<'c, C> {
         &self,
         request: CreateImageEditRequest,
     ) -> Result<ImagesResponse, OpenAIError> {
-        self.client.post_form("/images/edits", request).await
+        self.client
+            .post_form("/images/edits", request, &self.request_options)
+            .await
     }
 
     /// Creates a variation of a given image.
+    ///
+    /// Only `dall-e-2` creates variations: requests without a model are sent to `dall-e-2`, and
+    /// other models are rejected with [OpenAIError::InvalidArgument] before sending the image.
     #[crate::byot(
         T0 = Clone,
         R = serde::de::DeserializeOwned,
         where_clause =  "reqwest::multipart::Form: crate::traits::AsyncTryFrom<T0, Error = OpenAIError>",
     )]
+    #[allow(unused_mut)]
     pub async fn create_variation(
         &self,
-        request: CreateImageVariationRequest,
+        mut request: CreateImageVariationRequest,
     ) -> Result<ImagesResponse, OpenAIError> {
-        self.client.post_form("/images/variations", request).await
+        #[cfg(not(feature = "byot"))]
+        request
+            .model
+            .get_or_insert(crate::types::ImageModel::DallE2)
+            .check_variations()?;
+
+        self.client
+            .post_form("/images/variations", request, &self.request_options)
+            .await
     }
 }
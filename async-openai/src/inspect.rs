@@ -0,0 +1,345 @@
+//! Inspect the requests a [crate::Client] sends, to answer "why did the API see this parameter?".
+//!
+//! [crate::Client::with_request_inspector] receives every request as sent: its headers and
+//! query parameters with where they came from, and its JSON body, which
+//! [SentRequest::diff] compares with the request built by the caller to highlight the fields
+//! injected by the API group, such as `stream: true` for streamed calls.
+//!
+//! ```
+//! use async_openai::{types::CreateCompletionRequestArgs, Client};
+//!
+//! # fn main() -> Result<(), async_openai::error::OpenAIError> {
+//! let request = CreateCompletionRequestArgs::default()
+//!     .model("gpt-3.5-turbo-instruct")
+//!     .prompt("Tell me a joke")
+//!     .build()?;
+//!
+//! let built = request.clone();
+//! let client = Client::new().with_request_inspector(move |sent| {
+//!     println!("{sent}");
+//!     println!("{}", sent.diff(&built));
+//! });
+//! # Ok(())
+//! # }
+//! ```
+use std::{fmt, sync::Arc};
+
+use reqwest::header::HeaderMap;
+use serde::Serialize;
+
+/// Headers whose values are not shown.
+const REDACTED_HEADERS: &[&str] = &[
+    "authorization",
+    "api-key",
+    "x-api-key",
+    "openai-organization",
+];
+
+/// Where a header or query parameter of a [SentRequest] came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParamSource {
+    /// The [crate::config::Config] of the client, e.g. the Azure `api-version`.
+    Config,
+    /// The client itself: credentials of a [crate::credentials::CredentialProvider] or trace
+    /// context of a [crate::propagation::TracePropagator].
+    Client,
+    /// The call: its [crate::request_options::RequestOptions], the parameters of the API group
+    /// method, or the HTTP client, e.g. `content-type`.
+    Request,
+}
+
+impl fmt::Display for ParamSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ParamSource::Config => "config",
+            ParamSource::Client => "client",
+            ParamSource::Request => "request",
+        })
+    }
+}
+
+/// A header or query parameter of a [SentRequest].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentParam {
+    pub name: String,
+    /// The value, `<redacted>` for credentials.
+    pub value: String,
+    pub source: ParamSource,
+}
+
+/// A request as sent to the API.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SentRequest {
+    pub method: String,
+    /// The url without its query.
+    pub url: String,
+    pub query: Vec<SentParam>,
+    pub headers: Vec<SentParam>,
+    /// The JSON body, `None` for requests without a body, or with a form or streamed body.
+    pub body: Option<serde_json::Value>,
+}
+
+impl SentRequest {
+    /// `request` once built by the API group and `client_headers` added by the client.
+    pub(crate) fn new(
+        request: &reqwest::Request,
+        config_headers: &HeaderMap,
+        config_query: &[(&str, &str)],
+        client_headers: &HeaderMap,
+    ) -> Self {
+        let mut url = request.url().clone();
+        let query = url
+            .query_pairs()
+            .map(|(name, value)| {
+                let source = if config_query.iter().any(|(n, v)| *n == name && *v == value) {
+                    ParamSource::Config
+                } else {
+                    ParamSource::Request
+                };
+                SentParam {
+                    name: name.into_owned(),
+                    value: value.into_owned(),
+                    source,
+                }
+            })
+            .collect();
+        url.set_query(None);
+
+        let headers = request
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                let source = if client_headers.get(name) == Some(value) {
+                    ParamSource::Client
+                } else if config_headers.get(name) == Some(value) {
+                    ParamSource::Config
+                } else {
+                    ParamSource::Request
+                };
+                let value = if REDACTED_HEADERS.contains(&name.as_str()) {
+                    "<redacted>".to_string()
+                } else {
+                    String::from_utf8_lossy(value.as_bytes()).into_owned()
+                };
+                SentParam {
+                    name: name.to_string(),
+                    value,
+                    source,
+                }
+            })
+            .collect();
+
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .and_then(|body| serde_json::from_slice(body).ok());
+
+        Self {
+            method: request.method().to_string(),
+            url: url.to_string(),
+            query,
+            headers,
+            body,
+        }
+    }
+
+    /// Changes of the body from `request`, usually the request built by the caller.
+    pub fn diff<T: Serialize>(&self, request: &T) -> RequestDiff {
+        let before = serde_json::to_value(request).unwrap_or(serde_json::Value::Null);
+        let after = self.body.clone().unwrap_or(serde_json::Value::Null);
+        RequestDiff::between(&before, &after)
+    }
+}
+
+impl fmt::Display for SentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} {}", self.method, self.url)?;
+        for param in &self.query {
+            writeln!(f, "?{}={} ({})", param.name, param.value, param.source)?;
+        }
+        for header in &self.headers {
+            writeln!(f, "{}: {} ({})", header.name, header.value, header.source)?;
+        }
+        if let Some(body) = &self.body {
+            let body = serde_json::to_string_pretty(body).map_err(|_| fmt::Error)?;
+            writeln!(f, "\n{body}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Callback of [crate::Client::with_request_inspector].
+#[derive(Clone)]
+pub(crate) struct RequestInspector(Arc<dyn Fn(&SentRequest) + Send + Sync>);
+
+impl RequestInspector {
+    pub(crate) fn new<F: Fn(&SentRequest) + Send + Sync + 'static>(inspector: F) -> Self {
+        Self(Arc::new(inspector))
+    }
+
+    pub(crate) fn inspect(&self, request: &SentRequest) {
+        (self.0)(request)
+    }
+}
+
+impl fmt::Debug for RequestInspector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestInspector").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Changed,
+    Removed,
+}
+
+/// A field of a request body which differs between two versions of the request.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FieldChange {
+    /// [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) of the field, e.g. `/messages/0/role`.
+    pub path: String,
+    pub kind: ChangeKind,
+    pub before: Option<serde_json::Value>,
+    pub after: Option<serde_json::Value>,
+}
+
+/// Fields added, changed or removed between two versions of a request body.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct RequestDiff {
+    pub changes: Vec<FieldChange>,
+}
+
+impl RequestDiff {
+    /// Changes made to `before` to get `after`, objects and arrays compared field by field.
+    pub fn between(before: &serde_json::Value, after: &serde_json::Value) -> Self {
+        let mut diff = Self::default();
+        diff.compare(String::new(), before, after);
+        diff
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Changes of fields which are not in `before`, such as defaults injected in `after`.
+    pub fn added(&self) -> impl Iterator<Item = &FieldChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.kind == ChangeKind::Added)
+    }
+
+    fn compare(&mut self, path: String, before: &serde_json::Value, after: &serde_json::Value) {
+        use serde_json::Value;
+
+        match (before, after) {
+            (Value::Object(before), Value::Object(after)) => {
+                for (key, value) in before {
+                    let path = format!("{path}/{}", escape(key));
+                    match after.get(key) {
+                        Some(after) => self.compare(path, value, after),
+                        None => self.push(path, ChangeKind::Removed, Some(value), None),
+                    }
+                }
+                for (key, value) in after {
+                    if !before.contains_key(key) {
+                        let path = format!("{path}/{}", escape(key));
+                        self.push(path, ChangeKind::Added, None, Some(value));
+                    }
+                }
+            }
+            (Value::Array(before), Value::Array(after)) => {
+                for (index, value) in before.iter().enumerate() {
+                    let path = format!("{path}/{index}");
+                    match after.get(index) {
+                        Some(after) => self.compare(path, value, after),
+                        None => self.push(path, ChangeKind::Removed, Some(value), None),
+                    }
+                }
+                for (index, value) in after.iter().enumerate().skip(before.len()) {
+                    self.push(
+                        format!("{path}/{index}"),
+                        ChangeKind::Added,
+                        None,
+                        Some(value),
+                    );
+                }
+            }
+            (before, after) if before != after => {
+                self.push(path, ChangeKind::Changed, Some(before), Some(after))
+            }
+            _ => {}
+        }
+    }
+
+    fn push(
+        &mut self,
+        path: String,
+        kind: ChangeKind,
+        before: Option<&serde_json::Value>,
+        after: Option<&serde_json::Value>,
+    ) {
+        self.changes.push(FieldChange {
+            path,
+            kind,
+            before: before.cloned(),
+            after: after.cloned(),
+        });
+    }
+}
+
+/// Escape `~` and `/` of a JSON pointer segment.
+pub(crate) fn escape(key: &str) -> String {
+    key.replace('~', "~0").replace('/', "~1")
+}
+
+impl fmt::Display for RequestDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            let value = |value: &Option<serde_json::Value>| {
+                value.as_ref().map(ToString::to_string).unwrap_or_default()
+            };
+            match change.kind {
+                ChangeKind::Added => writeln!(f, "+ {}: {}", change.path, value(&change.after))?,
+                ChangeKind::Removed => writeln!(f, "- {}: {}", change.path, value(&change.before))?,
+                ChangeKind::Changed => writeln!(
+                    f,
+                    "~ {}: {} -> {}",
+                    change.path,
+                    value(&change.before),
+                    value(&change.after)
+                )?,
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_request_diff() {
+        let before = serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hi"}],
+            "temperature": 1.0,
+            "user": "u/1"
+        });
+        let after = serde_json::json!({
+            "model": "gpt-4o",
+            "messages": [{"role": "user", "content": "Hi"}, {"role": "user", "content": "Bye"}],
+            "temperature": 0.5,
+            "stream": true
+        });
+        let diff = RequestDiff::between(&before, &after);
+        assert_eq!(
+            diff.to_string(),
+            "+ /messages/1: {\"content\":\"Bye\",\"role\":\"user\"}\n~ /temperature: 1.0 -> 0.5\n- /user: \"u/1\"\n+ /stream: true\n"
+        );
+        assert_eq!(diff.added().count(), 2);
+        assert!(RequestDiff::between(&before, &before).is_empty());
+    }
+}
@@ -0,0 +1,459 @@
+//! Incremental parsing of the JSON streamed by JSON mode and structured outputs, to render
+//! structured results while they are generated.
+//!
+//! [JsonStreamParser] consumes the text deltas of a response and reports each value of the
+//! JSON document as soon as it is complete, along with a [JsonStreamParser::snapshot] of the
+//! document so far.
+//!
+//! ```
+//! use async_openai::json_stream::{JsonStreamEvent, JsonStreamParser};
+//! use serde_json::json;
+//!
+//! let mut parser = JsonStreamParser::new();
+//! let mut completed = Vec::new();
+//! for delta in [r#"{"title": "Du"#, r#"ne", "tags": ["sci"#, r#"-fi"]"#, "}"] {
+//!     for event in parser.push(delta) {
+//!         if let JsonStreamEvent::FieldCompleted { path, .. } = event {
+//!             completed.push(path);
+//!         }
+//!     }
+//!     println!("{:?}", parser.snapshot());
+//! }
+//! assert_eq!(completed, ["/title", "/tags/0", "/tags"]);
+//! assert_eq!(parser.finish()?, json!({"title": "Dune", "tags": ["sci-fi"]}));
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! ```
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use crate::{
+    error::OpenAIError,
+    types::{ChatCompletionResponseStream, CreateChatCompletionStreamResponse},
+};
+
+/// An event of [JsonStreamParser::push].
+#[derive(Debug, Clone, PartialEq)]
+pub enum JsonStreamEvent {
+    /// The value at `path`, a [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901) such as
+    /// `/tags/0`, is complete. Objects and arrays are reported after their members.
+    FieldCompleted { path: String, value: Value },
+    /// The whole document is complete.
+    Completed(Value),
+}
+
+/// A segment of the path of the value being parsed.
+#[derive(Debug, Clone)]
+enum Segment {
+    Key(String),
+    Index(usize),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Expect {
+    Key,
+    Colon,
+    Value,
+    /// A comma or the end of the container.
+    Next,
+}
+
+/// An object or array being parsed.
+#[derive(Debug, Clone)]
+struct Frame {
+    /// Byte offset of the `{` or `[` in the text.
+    start: usize,
+    /// Key of the current member of an object, `None` for an array.
+    key: Option<Option<String>>,
+    index: usize,
+    expect: Expect,
+}
+
+impl Frame {
+    fn segment(&self) -> Option<Segment> {
+        match &self.key {
+            Some(key) => key.clone().map(Segment::Key),
+            None => Some(Segment::Index(self.index)),
+        }
+    }
+}
+
+/// The string, number or literal being parsed.
+#[derive(Debug, Clone, Copy)]
+enum Token {
+    String {
+        start: usize,
+        is_key: bool,
+        escaped: bool,
+    },
+    Scalar {
+        start: usize,
+    },
+}
+
+/// Parser of a JSON document received in pieces, e.g. the content deltas of a streamed chat
+/// completion with a JSON `response_format`.
+///
+/// Text before the first `{` or `[`, such as a markdown code fence, and after the end of the
+/// document is ignored. Malformed JSON is not reported by [JsonStreamParser::push] but by
+/// [JsonStreamParser::finish].
+#[derive(Debug, Clone, Default)]
+pub struct JsonStreamParser {
+    text: String,
+    /// Byte offset of the document in `text`, once started.
+    root: Option<usize>,
+    /// Byte offset after the end of the document in `text`, once complete.
+    end: Option<usize>,
+    stack: Vec<Frame>,
+    token: Option<Token>,
+    snapshot: Option<Value>,
+}
+
+impl JsonStreamParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Parse a piece of the document, returning the values it completes.
+    pub fn push(&mut self, delta: &str) -> Vec<JsonStreamEvent> {
+        let base = self.text.len();
+        self.text.push_str(delta);
+        let mut events = Vec::new();
+        for (offset, c) in delta.char_indices() {
+            if self.end.is_some() {
+                break;
+            }
+            self.next_char(base + offset, c, &mut events);
+        }
+        events
+    }
+
+    /// Parse the content delta of the first choice of `chunk`.
+    pub fn push_chunk(
+        &mut self,
+        chunk: &CreateChatCompletionStreamResponse,
+    ) -> Vec<JsonStreamEvent> {
+        chunk
+            .choices
+            .iter()
+            .find(|choice| choice.index == 0)
+            .and_then(|choice| choice.delta.content.as_deref())
+            .map(|delta| self.push(delta))
+            .unwrap_or_default()
+    }
+
+    /// The text received so far.
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// Whether the document is complete.
+    pub fn is_complete(&self) -> bool {
+        self.end.is_some()
+    }
+
+    /// The document parsed so far, with open objects and arrays closed and the string being
+    /// received truncated. Numbers and literals are only included once complete.
+    pub fn snapshot(&self) -> Option<Value> {
+        let mut snapshot = self.snapshot.clone()?;
+        if let Some(Token::String {
+            start,
+            is_key: false,
+            ..
+        }) = self.token
+        {
+            let path = self.path();
+            set(
+                &mut snapshot,
+                &path,
+                Value::String(partial_string(&self.text[start + 1..])),
+            );
+        }
+        Some(snapshot)
+    }
+
+    /// The complete document.
+    pub fn finish(self) -> Result<Value, OpenAIError> {
+        self.parse()
+    }
+
+    /// The complete document deserialized to `T`.
+    pub fn parse<T: DeserializeOwned>(&self) -> Result<T, OpenAIError> {
+        let text = match self.root {
+            Some(root) => &self.text[root..self.end.unwrap_or(self.text.len())],
+            None => &self.text,
+        };
+        serde_json::from_str(text)
+            .map_err(|error| OpenAIError::JSONDeserialize(error, self.text.clone()))
+    }
+
+    fn next_char(&mut self, offset: usize, c: char, events: &mut Vec<JsonStreamEvent>) {
+        match self.token {
+            Some(Token::String {
+                start,
+                is_key,
+                escaped,
+            }) => {
+                if escaped {
+                    self.token = Some(Token::String {
+                        start,
+                        is_key,
+                        escaped: false,
+                    });
+                } else if c == '\\' {
+                    self.token = Some(Token::String {
+                        start,
+                        is_key,
+                        escaped: true,
+                    });
+                } else if c == '"' {
+                    self.token = None;
+                    let value = serde_json::from_str::<Value>(&self.text[start..=offset]).ok();
+                    match (is_key, value) {
+                        (true, Some(Value::String(key))) => {
+                            if let Some(frame) = self.stack.last_mut() {
+                                frame.key = Some(Some(key));
+                                frame.expect = Expect::Colon;
+                            }
+                        }
+                        (false, Some(value)) => self.complete(value, events),
+                        _ => {}
+                    }
+                }
+                return;
+            }
+            Some(Token::Scalar { start }) => {
+                if !(c.is_whitespace() || matches!(c, ',' | '}' | ']')) {
+                    return;
+                }
+                self.token = None;
+                if let Ok(value) = serde_json::from_str::<Value>(&self.text[start..offset]) {
+                    self.complete(value, events);
+                }
+            }
+            None => {}
+        }
+
+        if self.root.is_none() {
+            if matches!(c, '{' | '[') {
+                self.root = Some(offset);
+                self.snapshot = Some(Value::Null);
+            } else {
+                return;
+            }
+        }
+
+        match c {
+            _ if c.is_whitespace() => {}
+            '{' | '[' => {
+                let path = self.path();
+                if let Some(snapshot) = &mut self.snapshot {
+                    let value = if c == '{' {
+                        Value::Object(Default::default())
+                    } else {
+                        Value::Array(Vec::new())
+                    };
+                    set(snapshot, &path, value);
+                }
+                self.stack.push(Frame {
+                    start: offset,
+                    key: (c == '{').then_some(None),
+                    index: 0,
+                    expect: if c == '{' { Expect::Key } else { Expect::Value },
+                });
+            }
+            '}' | ']' => {
+                if let Some(frame) = self.stack.pop() {
+                    if self.stack.is_empty() {
+                        self.end = Some(offset + 1);
+                    }
+                    if let Ok(value) = serde_json::from_str(&self.text[frame.start..=offset]) {
+                        self.complete(value, events);
+                    }
+                }
+            }
+            '"' => {
+                let is_key =
+                    matches!(self.stack.last(), Some(frame) if frame.expect == Expect::Key);
+                self.token = Some(Token::String {
+                    start: offset,
+                    is_key,
+                    escaped: false,
+                });
+            }
+            ':' => {
+                if let Some(frame) = self.stack.last_mut() {
+                    frame.expect = Expect::Value;
+                }
+            }
+            ',' => {
+                if let Some(frame) = self.stack.last_mut() {
+                    match &mut frame.key {
+                        Some(key) => {
+                            *key = None;
+                            frame.expect = Expect::Key;
+                        }
+                        None => {
+                            frame.index += 1;
+                            frame.expect = Expect::Value;
+                        }
+                    }
+                }
+            }
+            _ => self.token = Some(Token::Scalar { start: offset }),
+        }
+    }
+
+    /// Report `value`, complete at the current path.
+    fn complete(&mut self, value: Value, events: &mut Vec<JsonStreamEvent>) {
+        if let Some(frame) = self.stack.last_mut() {
+            frame.expect = Expect::Next;
+        }
+        let path = self.path();
+        if let Some(snapshot) = &mut self.snapshot {
+            set(snapshot, &path, value.clone());
+        }
+        if self.stack.is_empty() {
+            events.push(JsonStreamEvent::Completed(value));
+        } else {
+            events.push(JsonStreamEvent::FieldCompleted {
+                path: pointer(&path),
+                value,
+            });
+        }
+    }
+
+    fn path(&self) -> Vec<Segment> {
+        self.stack.iter().filter_map(Frame::segment).collect()
+    }
+}
+
+/// Events of the JSON document streamed in the content of the first choice of `stream`,
+/// see [JsonStreamParser].
+pub fn json_events(
+    stream: ChatCompletionResponseStream,
+) -> Pin<Box<dyn Stream<Item = Result<JsonStreamEvent, OpenAIError>> + Send>> {
+    let mut parser = JsonStreamParser::new();
+    Box::pin(stream.flat_map(move |chunk| {
+        let events = match chunk {
+            Ok(chunk) => parser.push_chunk(&chunk).into_iter().map(Ok).collect(),
+            Err(error) => vec![Err(error)],
+        };
+        futures::stream::iter(events)
+    }))
+}
+
+/// Store `value` at `path` of `root`.
+fn set(root: &mut Value, path: &[Segment], value: Value) {
+    let Some((last, parents)) = path.split_last() else {
+        *root = value;
+        return;
+    };
+    let mut target = root;
+    for segment in parents {
+        let next = match (segment, target) {
+            (Segment::Key(key), Value::Object(object)) => object.get_mut(key),
+            (Segment::Index(index), Value::Array(array)) => array.get_mut(*index),
+            _ => None,
+        };
+        match next {
+            Some(next) => target = next,
+            None => return,
+        }
+    }
+    match (last, target) {
+        (Segment::Key(key), Value::Object(object)) => {
+            object.insert(key.clone(), value);
+        }
+        (Segment::Index(index), Value::Array(array)) => {
+            if *index < array.len() {
+                array[*index] = value;
+            } else {
+                array.push(value);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// JSON pointer of `path`.
+fn pointer(path: &[Segment]) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            Segment::Key(key) => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+            Segment::Index(index) => format!("/{index}"),
+        })
+        .collect()
+}
+
+/// The decoded part of the string whose escaped content starts `raw`.
+fn partial_string(raw: &str) -> String {
+    // drop an escape sequence cut short, at most `\uXXXX`
+    let mut end = raw.len();
+    loop {
+        if let Ok(value) = serde_json::from_str(&format!("\"{}\"", &raw[..end])) {
+            return value;
+        }
+        match raw[..end].char_indices().next_back() {
+            Some((offset, _)) if raw.len() - offset <= 6 => end = offset,
+            _ => return String::new(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_json_stream_parser() {
+        let text = "```json\n{\"name\": \"Ada \\u00e9\", \"age\": 36, \"langs\": [{\"n\": \"en\"}, true], \"x\": null}\n```";
+        let mut parser = JsonStreamParser::new();
+        let mut events = Vec::new();
+        let mut snapshots = Vec::new();
+        for (offset, c) in text.char_indices() {
+            events.extend(parser.push(&text[offset..offset + c.len_utf8()]));
+            snapshots.push(parser.snapshot());
+        }
+        let paths: Vec<_> = events
+            .iter()
+            .map(|event| match event {
+                JsonStreamEvent::FieldCompleted { path, .. } => path.as_str(),
+                JsonStreamEvent::Completed(_) => "",
+            })
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                "/name",
+                "/age",
+                "/langs/0/n",
+                "/langs/0",
+                "/langs/1",
+                "/langs",
+                "/x",
+                ""
+            ]
+        );
+        assert!(snapshots.contains(&Some(json!({"name": "Ad"}))));
+        assert!(snapshots.contains(&Some(json!({"name": "Ada "}))));
+        assert!(snapshots.contains(&Some(json!({"name": "Ada é", "age": 36, "langs": [{}]}))));
+
+        let expected = json!({"name": "Ada é", "age": 36, "langs": [{"n": "en"}, true], "x": null});
+        assert_eq!(
+            events.last(),
+            Some(&JsonStreamEvent::Completed(expected.clone()))
+        );
+        assert!(parser.is_complete());
+        assert_eq!(parser.finish().unwrap(), expected);
+
+        let mut parser = JsonStreamParser::new();
+        parser.push("{\"a\": [1, 2");
+        assert_eq!(parser.snapshot(), Some(json!({"a": [1]})));
+        assert!(parser.finish().is_err());
+    }
+}
@@ -25,6 +25,53 @@
 //! let client = Client::new().with_http_client(http_client);
 //! ```
 //!
+//! ## Connection options
+//!
+//! Timeouts, connection pooling and TCP settings can be tuned without building a reqwest client by hand,
+//! see [connection::ConnectionOptions].
+//!
+//! The TLS backend is chosen with one of these features:
+//! - `rustls` (default): rustls with native root certificates.
+//! - `rustls-webpki-roots`: rustls with the root certificates bundled by `webpki-roots`.
+//! - `native-tls`: the platform TLS library (OpenSSL on Linux).
+//! - `native-tls-vendored`: like `native-tls`, but OpenSSL is compiled and statically linked.
+//!
+//! When using a `native-tls` feature, disable default features to avoid also compiling rustls.
+//!
+//! Connections use HTTP/1.1 unless the `http2` feature is enabled, which also adds the HTTP/2
+//! settings of [connection::ConnectionOptions].
+//!
+//! ## Observability
+//!
+//! With the `observability` feature every API call is wrapped in an `INFO` level `gen_ai` span
+//! following the OpenTelemetry [semantic conventions for generative AI](https://opentelemetry.io/docs/specs/semconv/gen-ai/gen-ai-spans/):
+//! operation, request and response model, token usage and finish reasons, along with
+//! `duration_ms`, `time_to_first_token_ms` for streamed responses, and `error.type` of failed calls.
+//! Export them with `tracing-opentelemetry`, and see [propagation] to send the trace context to the API.
+//!
+//! ## Blocking client
+//!
+//! The `blocking` feature adds `blocking::Client`, a synchronous client for programs without an
+//! async runtime, covering the chat, completions, embeddings, images, audio and files groups.
+//!
+//! ## Structured outputs
+//!
+//! The `schemars` feature adds `types::ResponseFormat::json_schema_for`, a strict response format
+//! generated from a type deriving `schemars::JsonSchema` (version 0.8), see [types::strict_schema]
+//! for the changes strict mode requires. Function tools are defined the same way with
+//! `types::FunctionObject::from_fn`, or `#[derive(traits::OpenAiTool)]` on their arguments.
+//!
+//! ## Tower
+//!
+//! The `tower` feature adds `service::OpenAIService`, a `tower::Service` sending the requests of
+//! a client, to compose existing middleware such as timeouts or concurrency limits around it.
+//!
+//! ## Record and replay
+//!
+//! The `replay` feature adds `Client::with_replay`, recording the requests and responses of a
+//! client to fixture files, streams included, and serving them back without network to make
+//! integration tests deterministic, see `replay::Replay`.
+//!
 //!
 //! ## Making requests
 //!
@@ -56,6 +103,11 @@
 //! # });
 //!```
 //!
+//! The client, its API groups and the request and response types implement `Debug`, with the
+//! values of credentials and request option headers left out. Streams, such as
+//! [types::ChatCompletionResponseStream], are type aliases of boxed `dyn Stream` trait
+//! objects and do not implement `Debug`: debug the items they yield instead.
+//!
 //! ## Bring Your Own Types
 //!
 //! To use custom types for inputs and outputs, enable `byot` feature which provides additional generic methods with same name and `_byot` suffix.
@@ -140,36 +192,83 @@ pub(crate) use async_openai_macros::byot;
 #[cfg(not(feature = "byot"))]
 pub(crate) use async_openai_macros::byot_passthrough as byot;
 
+pub mod assistant_events;
 mod assistants;
 mod audio;
 mod audit_logs;
 mod batches;
+#[cfg(feature = "blocking")]
+#[cfg_attr(docsrs, doc(cfg(feature = "blocking")))]
+pub mod blocking;
+pub mod cache;
+mod certificates;
 mod chat;
 mod client;
 mod completion;
 pub mod config;
+pub mod connection;
+mod conversations;
+mod costs;
+pub mod credentials;
 mod download;
 mod embedding;
 pub mod error;
+#[cfg(feature = "examples")]
+#[cfg_attr(docsrs, doc(cfg(feature = "examples")))]
+pub mod examples;
+pub mod failover;
 mod file;
 mod fine_tuning;
+pub mod guard;
+pub mod hedge;
+pub mod history;
 mod image;
+pub mod inspect;
 mod invites;
+pub mod json_stream;
 mod messages;
 mod model;
 mod moderation;
+mod observability;
+mod pagination;
+pub mod parse;
+pub mod persist;
+pub mod poll;
+pub mod postprocess;
+pub mod pricing;
 mod project_api_keys;
+mod project_certificates;
 mod project_service_accounts;
 mod project_users;
 mod projects;
+pub mod prompts;
+pub mod propagation;
+pub mod quota;
+pub mod rate_limit;
+mod raw;
+#[cfg(feature = "realtime")]
+mod realtime;
+pub mod reconnect;
+pub mod registry;
+#[cfg(feature = "replay")]
+#[cfg_attr(docsrs, doc(cfg(feature = "replay")))]
+pub mod replay;
+pub mod request_options;
 mod responses;
 mod runs;
+#[cfg(feature = "tower")]
+#[cfg_attr(docsrs, doc(cfg(feature = "tower")))]
+pub mod service;
+mod shutdown;
 mod steps;
 mod threads;
 mod tokenize;
 pub mod traits;
+pub mod transcript;
+pub mod transport;
 pub mod types;
 mod uploads;
+pub mod usage;
 mod users;
 mod util;
 mod vector_store_file_batches;
@@ -180,9 +279,12 @@ pub use assistants::Assistants;
 pub use audio::Audio;
 pub use audit_logs::AuditLogs;
 pub use batches::Batches;
+pub use certificates::Certificates;
 pub use chat::Chat;
 pub use client::Client;
 pub use completion::Completions;
+pub use conversations::{ConversationItems, Conversations};
+pub use costs::Costs;
 pub use embedding::Embeddings;
 pub use file::Files;
 pub use fine_tuning::FineTuning;
@@ -192,9 +294,13 @@ pub use messages::Messages;
 pub use model::Models;
 pub use moderation::Moderations;
 pub use project_api_keys::ProjectAPIKeys;
+pub use project_certificates::ProjectCertificates;
 pub use project_service_accounts::ProjectServiceAccounts;
 pub use project_users::ProjectUsers;
 pub use projects::Projects;
+pub use raw::{Raw, RawResponse};
+#[cfg(feature = "realtime")]
+pub use realtime::Realtime;
 pub use responses::Responses;
 pub use runs::Runs;
 pub use steps::Steps;
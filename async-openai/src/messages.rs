@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
         CreateMessageRequest, DeleteMessageResponse, ListMessagesResponse, MessageObject,
         ModifyMessageRequest,
@@ -15,13 +16,18 @@ pub struct Messages<'c, C: Config> {
     ///  The ID of the [thread](https://platform.openai.com/docs/api-reference/threads) to create a message for.
     pub thread_id: String,
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Messages);
+crate::util::impl_api_group_debug!(Messages, "/threads/{}/messages", thread_id);
+
 impl<'c, C: Config> Messages<'c, C> {
     pub fn new(client: &'c Client<C>, thread_id: &str) -> Self {
         Self {
             client,
             thread_id: thread_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
@@ -32,7 +38,11 @@ impl<'c, C: Config> Messages<'c, C> {
         request: CreateMessageRequest,
     ) -> Result<MessageObject, OpenAIError> {
         self.client
-            .post(&format!("/threads/{}/messages", self.thread_id), request)
+            .post(
+                &format!("/threads/{}/messages", self.thread_id),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -40,10 +50,10 @@ impl<'c, C: Config> Messages<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, message_id: &str) -> Result<MessageObject, OpenAIError> {
         self.client
-            .get(&format!(
-                "/threads/{}/messages/{message_id}",
-                self.thread_id
-            ))
+            .get(
+                &format!("/threads/{}/messages/{message_id}", self.thread_id),
+                &self.request_options,
+            )
             .await
     }
 
@@ -58,28 +68,35 @@ impl<'c, C: Config> Messages<'c, C> {
             .post(
                 &format!("/threads/{}/messages/{message_id}", self.thread_id),
                 request,
+                &self.request_options,
             )
             .await
     }
 
     /// Returns a list of messages for a given thread.
+    ///
+    /// `query` is usually a [ListMessagesQuery](crate::types::ListMessagesQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListMessagesResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query(&format!("/threads/{}/messages", self.thread_id), &query)
+            .get_with_query(
+                &format!("/threads/{}/messages", self.thread_id),
+                &query,
+                &self.request_options,
+            )
             .await
     }
 
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, message_id: &str) -> Result<DeleteMessageResponse, OpenAIError> {
         self.client
-            .delete(&format!(
-                "/threads/{}/messages/{message_id}",
-                self.thread_id
-            ))
+            .delete(
+                &format!("/threads/{}/messages/{message_id}", self.thread_id),
+                &self.request_options,
+            )
             .await
     }
 }
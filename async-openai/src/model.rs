@@ -1,41 +1,291 @@
+use reqwest::StatusCode;
+
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{DeleteModelResponse, ListModelResponse, Model},
-    Client,
+    request_options::{impl_request_options, RequestOptions},
+    types::{
+        ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestMessageContentPartText,
+        ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
+        ChatCompletionTool, ChatCompletionToolType, CreateChatCompletionRequest,
+        DeleteModelResponse, FunctionObject, ImageUrl, ListModelResponse, Model, ModelCapabilities,
+        ResponseFormat,
+    },
+    Chat, Client,
 };
 
+/// 1x1 transparent PNG sent by [Models::probe] to detect vision support.
+const PROBE_IMAGE: &str = "data:image/png;base64,iVBORw0KGgoAAAANSUhEUgAAAAEAAAABCAYAAAAfFcSJAAAADUlEQVR42mNkYPhfDwAChwGA60e6kgAAAABJRU5ErkJggg==";
+
 /// List and describe the various models available in the API.
 /// You can refer to the [Models](https://platform.openai.com/docs/models) documentation to understand what
 /// models are available and the differences between them.
 pub struct Models<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Models);
+crate::util::impl_api_group_debug!(Models, "/models");
+
 impl<'c, C: Config> Models<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Lists the currently available models, and provides basic information
     /// about each one such as the owner and availability.
     #[crate::byot(R = serde::de::DeserializeOwned)]
     pub async fn list(&self) -> Result<ListModelResponse, OpenAIError> {
-        self.client.get("/models").await
+        self.client.get("/models", &self.request_options).await
     }
 
     /// Retrieves a model instance, providing basic information about the model
     /// such as the owner and permissioning.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, id: &str) -> Result<Model, OpenAIError> {
-        self.client.get(format!("/models/{id}").as_str()).await
+        self.client
+            .get(format!("/models/{id}").as_str(), &self.request_options)
+            .await
     }
 
     /// Delete a fine-tuned model. You must have the Owner role in your organization.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, model: &str) -> Result<DeleteModelResponse, OpenAIError> {
         self.client
-            .delete(format!("/models/{model}").as_str())
+            .delete(format!("/models/{model}").as_str(), &self.request_options)
+            .await
+    }
+
+    /// Lists the available models matching `predicate`.
+    pub async fn find<P>(&self, predicate: P) -> Result<Vec<Model>, OpenAIError>
+    where
+        P: Fn(&Model) -> bool,
+    {
+        let response = self.list().await?;
+        Ok(response
+            .data
+            .into_iter()
+            .filter(|model| predicate(model))
+            .collect())
+    }
+
+    /// Detects which chat completion features `model` supports by sending tiny requests,
+    /// useful with OpenAI compatible backends which do not document them.
+    ///
+    /// A feature is considered unsupported when its request is rejected with a client error.
+    /// Backends rejecting `max_completion_tokens` are probed with `max_tokens` instead.
+    /// Results are cached on the client by api base and model, so each model of a backend is
+    /// probed at most once. Probing is billed like any other request.
+    pub async fn probe(&self, model: &str) -> Result<ModelCapabilities, OpenAIError> {
+        let key = (
+            self.client.config().api_base().to_string(),
+            model.to_string(),
+        );
+        if let Some(capabilities) = self.cached_capabilities(&key) {
+            return Ok(capabilities);
+        }
+
+        // Fails for an unknown model or invalid credentials before probing any feature
+        let legacy = match self
+            .probe_request(Self::probe_base_request(model, false))
+            .await
+        {
+            Ok(()) => false,
+            Err(err) if rejects_max_completion_tokens(&err) => {
+                self.probe_request(Self::probe_base_request(model, true))
+                    .await?;
+                true
+            }
+            Err(err) => return Err(err),
+        };
+
+        let mut request = Self::probe_base_request(model, legacy);
+        request.tools = Some(vec![ChatCompletionTool {
+            r#type: ChatCompletionToolType::Function,
+            function: FunctionObject {
+                name: "ping".into(),
+                description: Some("Replies to a ping".into()),
+                parameters: Some(serde_json::json!({"type": "object", "properties": {}})),
+                strict: None,
+            },
+        }]);
+        let tools = self.probe_feature(request).await?;
+
+        let mut request = Self::probe_base_request(model, legacy);
+        request.messages = vec![ChatCompletionRequestUserMessage::from(
+            ChatCompletionRequestUserMessageContent::from(vec![
+                ChatCompletionRequestMessageContentPartText::from("Reply with OK").into(),
+                ChatCompletionRequestMessageContentPartImage {
+                    image_url: ImageUrl {
+                        url: PROBE_IMAGE.into(),
+                        detail: None,
+                    },
+                }
+                .into(),
+            ]),
+        )
+        .into()];
+        let vision = self.probe_feature(request).await?;
+
+        let mut request = Self::probe_base_request(model, legacy);
+        request.messages =
+            vec![
+                ChatCompletionRequestUserMessage::from("Reply with the JSON {\"ok\": true}").into(),
+            ];
+        request.response_format = Some(ResponseFormat::JsonObject);
+        let json_mode = self.probe_feature(request).await?;
+
+        let capabilities = ModelCapabilities {
+            tools,
+            vision,
+            json_mode,
+        };
+        self.client
+            .model_capabilities()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner())
+            .insert(key, capabilities);
+        Ok(capabilities)
+    }
+
+    fn cached_capabilities(&self, key: &(String, String)) -> Option<ModelCapabilities> {
+        let cache = self
+            .client
+            .model_capabilities()
+            .lock()
+            .unwrap_or_else(|p| p.into_inner());
+        cache.get(key).copied()
+    }
+
+    /// Smallest request to `model`, limited with the deprecated `max_tokens` when `legacy`.
+    #[allow(deprecated)]
+    fn probe_base_request(model: &str, legacy: bool) -> CreateChatCompletionRequest {
+        CreateChatCompletionRequest {
+            model: model.to_string(),
+            messages: vec![ChatCompletionRequestUserMessage::from("Reply with OK").into()],
+            max_completion_tokens: (!legacy).then_some(16),
+            max_tokens: legacy.then_some(16),
+            ..Default::default()
+        }
+    }
+
+    async fn probe_request(&self, request: CreateChatCompletionRequest) -> Result<(), OpenAIError> {
+        Chat::new(self.client)
+            .with_options(self.request_options.clone())
+            .create(request)
             .await
+            .map(|_| ())
+    }
+
+    /// `Ok(false)` when the request is rejected because of the probed feature.
+    async fn probe_feature(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> Result<bool, OpenAIError> {
+        match self.probe_request(request).await {
+            Ok(()) => Ok(true),
+            Err(err) => match err.status() {
+                Some(
+                    StatusCode::UNAUTHORIZED
+                    | StatusCode::FORBIDDEN
+                    | StatusCode::TOO_MANY_REQUESTS,
+                ) => Err(err),
+                Some(status) if status.is_client_error() => Ok(false),
+                _ => Err(err),
+            },
+        }
+    }
+}
+
+/// Whether `err` rejects the `max_completion_tokens` parameter, unknown to older backends.
+fn rejects_max_completion_tokens(err: &OpenAIError) -> bool {
+    match err {
+        OpenAIError::ApiError(err) => {
+            err.status == Some(StatusCode::BAD_REQUEST)
+                && (err.param.as_deref() == Some("max_completion_tokens")
+                    || err.message.contains("max_completion_tokens"))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use serde_json::Value;
+
+    use super::*;
+    use crate::transport::mock::{MockResponse, MockTransport};
+
+    /// Answers chat completions, rejecting with `(status, param)` the requests matched by
+    /// `rejected`.
+    fn backend(rejected: fn(&Value) -> Option<(u16, &'static str)>) -> Arc<MockTransport> {
+        MockTransport::new(move |request, _| {
+            Ok(match rejected(&request.json()) {
+                Some((status, param)) => MockResponse::json(serde_json::json!({"error": {
+                    "message": format!("Unrecognized request argument supplied: {param}"),
+                    "type": "invalid_request_error",
+                    "param": param,
+                    "code": null,
+                }}))
+                .status(status),
+                None => MockResponse::new(
+                    r#"{"id":"1","object":"chat.completion","created":1,"model":"m","choices":[{"index":0,"message":{"role":"assistant","content":"OK"},"finish_reason":"stop"}]}"#,
+                ),
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_probe() {
+        let backend = backend(|body| match body["model"].as_str() {
+            Some("missing") => Some((404, "model")),
+            _ => body.get("tools").map(|_| (400, "tools")),
+        });
+        let client = Client::new().with_transport(backend.clone());
+
+        let capabilities = client.models().probe("m").await.unwrap();
+        assert_eq!(
+            capabilities,
+            ModelCapabilities {
+                tools: false,
+                vision: true,
+                json_mode: true,
+            }
+        );
+        assert_eq!(backend.len(), 4);
+
+        // cached
+        client.models().probe("m").await.unwrap();
+        assert_eq!(backend.len(), 4);
+
+        // the base request fails before probing any feature
+        let error = client.models().probe("missing").await.unwrap_err();
+        assert_eq!(error.status(), Some(StatusCode::NOT_FOUND));
+        assert_eq!(backend.len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_probe_max_tokens() {
+        let backend = backend(|body| {
+            body.get("max_completion_tokens")
+                .map(|_| (400, "max_completion_tokens"))
+        });
+        let client = Client::new().with_transport(backend.clone());
+
+        let capabilities = client.models().probe("m").await.unwrap();
+        assert!(capabilities.tools && capabilities.vision && capabilities.json_mode);
+        let requests = backend.requests();
+        assert_eq!(requests.len(), 5);
+        for request in &requests[1..] {
+            let request = request.json();
+            assert_eq!(request["max_tokens"], 16);
+            assert!(request.get("max_completion_tokens").is_none());
+        }
     }
 }
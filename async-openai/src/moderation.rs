@@ -1,6 +1,7 @@
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{CreateModerationRequest, CreateModerationResponse},
     Client,
 };
@@ -10,11 +11,18 @@ use crate::{
 /// Related guide: [Moderations](https://platform.openai.com/docs/guides/moderation)
 pub struct Moderations<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Moderations);
+crate::util::impl_api_group_debug!(Moderations, "/moderations");
+
 impl<'c, C: Config> Moderations<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Classifies if text and/or image inputs are potentially harmful. Learn
@@ -24,6 +32,8 @@ impl<'c, C: Config> Moderations<'c, C> {
         &self,
         request: CreateModerationRequest,
     ) -> Result<CreateModerationResponse, OpenAIError> {
-        self.client.post("/moderations", request).await
+        self.client
+            .post_hedged("/moderations", request, &self.request_options)
+            .await
     }
 }
@@ -0,0 +1,292 @@
+//! `tracing` spans of API calls following the OpenTelemetry semantic conventions for
+//! generative AI, recorded when the `observability` feature is enabled.
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+use tracing::Span;
+
+use crate::{error::OpenAIError, usage::UsageRecorder};
+
+/// Span of an API call, and the time it started at.
+pub(crate) struct Observation {
+    span: Span,
+    started: Instant,
+    first_event: bool,
+    finish_reasons: Vec<String>,
+    usage: Option<UsageRecorder>,
+}
+
+impl Observation {
+    /// A new `gen_ai` span, disabled without the `observability` feature.
+    pub(crate) fn new() -> Self {
+        #[cfg(feature = "observability")]
+        let span = {
+            use tracing::field::Empty;
+            tracing::info_span!(
+                "gen_ai",
+                otel.name = Empty,
+                otel.kind = "client",
+                gen_ai.system = "openai",
+                gen_ai.operation.name = Empty,
+                server.address = Empty,
+                gen_ai.request.model = Empty,
+                gen_ai.request.max_tokens = Empty,
+                gen_ai.request.temperature = Empty,
+                gen_ai.request.top_p = Empty,
+                gen_ai.response.id = Empty,
+                gen_ai.response.model = Empty,
+                gen_ai.response.finish_reasons = Empty,
+                gen_ai.usage.input_tokens = Empty,
+                gen_ai.usage.output_tokens = Empty,
+                time_to_first_token_ms = Empty,
+                duration_ms = Empty,
+                error.type = Empty,
+            )
+        };
+        #[cfg(not(feature = "observability"))]
+        let span = Span::none();
+
+        Self {
+            span,
+            started: Instant::now(),
+            first_event: true,
+            finish_reasons: Vec::new(),
+            usage: None,
+        }
+    }
+
+    /// Record the usage of model responses to `usage` too.
+    pub(crate) fn with_usage(mut self, usage: Option<UsageRecorder>) -> Self {
+        self.usage = usage;
+        self
+    }
+
+    pub(crate) fn span(&self) -> &Span {
+        &self.span
+    }
+
+    /// Record the operation and request attributes of a request at `path` with a JSON `body`.
+    pub(crate) fn request(&self, path: &str, body: Option<&[u8]>) {
+        if self.span.is_disabled() {
+            return;
+        }
+        let operation = operation(path);
+        self.span.record("gen_ai.operation.name", operation);
+
+        let request = body
+            .and_then(|body| serde_json::from_slice::<RequestSummary>(body).ok())
+            .unwrap_or_default();
+        match &request.model {
+            Some(model) => {
+                self.span
+                    .record("otel.name", format!("{operation} {model}"));
+                self.span.record("gen_ai.request.model", model.as_str());
+            }
+            None => {
+                self.span.record("otel.name", operation);
+            }
+        }
+        if let Some(max_tokens) = request
+            .max_completion_tokens
+            .or(request.max_output_tokens)
+            .or(request.max_tokens)
+        {
+            self.span.record("gen_ai.request.max_tokens", max_tokens);
+        }
+        if let Some(temperature) = request.temperature {
+            self.span.record("gen_ai.request.temperature", temperature);
+        }
+        if let Some(top_p) = request.top_p {
+            self.span.record("gen_ai.request.top_p", top_p);
+        }
+    }
+
+    /// [Observation::request] with the body serialized from `request`.
+    pub(crate) fn request_json<I: Serialize>(&self, path: &str, request: &I) {
+        if self.span.is_disabled() {
+            return;
+        }
+        self.request(path, serde_json::to_vec(request).ok().as_deref());
+    }
+
+    /// Record the response attributes of a JSON response, of a model response only.
+    pub(crate) fn response(&mut self, body: &[u8]) {
+        if self.span.is_disabled() && self.usage.is_none() {
+            return;
+        }
+        let Ok(response) = serde_json::from_slice::<ResponseSummary>(body) else {
+            return;
+        };
+        // events of the Responses API wrap the response
+        let response = match response.response {
+            Some(response) => *response,
+            None => response,
+        };
+        let Some(model) = &response.model else {
+            return;
+        };
+
+        self.span.record("gen_ai.response.model", model.as_str());
+        if let Some(id) = &response.id {
+            self.span.record("gen_ai.response.id", id.as_str());
+        }
+        if let (Some(recorder), Some(usage)) = (&self.usage, &response.usage) {
+            let prompt_tokens = usage.prompt_tokens.unwrap_or_default();
+            let completion_tokens = usage.completion_tokens.unwrap_or_default();
+            let total_tokens = usage
+                .total_tokens
+                .unwrap_or(prompt_tokens + completion_tokens);
+            recorder.record(model, prompt_tokens, completion_tokens, total_tokens);
+        }
+        if self.span.is_disabled() {
+            return;
+        }
+        if let Some(usage) = &response.usage {
+            if let Some(input_tokens) = usage.prompt_tokens {
+                self.span.record("gen_ai.usage.input_tokens", input_tokens);
+            }
+            if let Some(output_tokens) = usage.completion_tokens {
+                self.span
+                    .record("gen_ai.usage.output_tokens", output_tokens);
+            }
+        }
+        let finish_reasons = response
+            .choices
+            .into_iter()
+            .filter_map(|choice| choice.finish_reason);
+        self.finish_reasons.extend(finish_reasons);
+        if !self.finish_reasons.is_empty() {
+            self.span.record(
+                "gen_ai.response.finish_reasons",
+                tracing::field::debug(&self.finish_reasons),
+            );
+        }
+    }
+
+    /// Record an event of a streamed response, the first one recording the time to first token.
+    pub(crate) fn event(&mut self, data: &str) {
+        if self.span.is_disabled() && self.usage.is_none() {
+            return;
+        }
+        if self.first_event && !self.span.is_disabled() {
+            self.first_event = false;
+            self.span.record(
+                "time_to_first_token_ms",
+                self.started.elapsed().as_millis() as u64,
+            );
+        }
+        self.response(data.as_bytes());
+    }
+
+    /// Record the duration of the call and the type of its error, if any.
+    pub(crate) fn finish(&self, error: Option<&OpenAIError>) {
+        if self.span.is_disabled() {
+            return;
+        }
+        self.span
+            .record("duration_ms", self.started.elapsed().as_millis() as u64);
+        if let Some(error) = error {
+            self.error(error);
+        }
+    }
+
+    /// Record the type of the error ending a streamed response.
+    pub(crate) fn error(&self, error: &OpenAIError) {
+        if self.span.is_disabled() {
+            return;
+        }
+        self.span.record("error.type", error_type(error));
+    }
+}
+
+/// `gen_ai.operation.name` of a request at `path`, the path for non model requests.
+fn operation(path: &str) -> &str {
+    if path.ends_with("/chat/completions") || path.ends_with("/responses") {
+        "chat"
+    } else if path.ends_with("/completions") {
+        "text_completion"
+    } else if path.ends_with("/embeddings") {
+        "embeddings"
+    } else {
+        path
+    }
+}
+
+fn error_type(error: &OpenAIError) -> String {
+    match error {
+        OpenAIError::ApiError(error) => error
+            .r#type
+            .clone()
+            .or_else(|| error.status.map(|status| status.as_u16().to_string()))
+            .unwrap_or_else(|| "api_error".into()),
+        OpenAIError::Reqwest(_) | OpenAIError::Transport(_) => "http".into(),
+        OpenAIError::JSONDeserialize(..) => "deserialization".into(),
+        OpenAIError::FileSaveError(_) | OpenAIError::FileReadError(_) => "file".into(),
+        OpenAIError::StreamError(_) | OpenAIError::StreamInterrupted(_) => "stream".into(),
+        OpenAIError::InvalidArgument(_) => "invalid_argument".into(),
+        OpenAIError::Timeout(_) => "timeout".into(),
+        OpenAIError::QuotaExceeded(_) => "quota_exceeded".into(),
+        OpenAIError::ShutDown => "shut_down".into(),
+        OpenAIError::MissingFixture(_) => "missing_fixture".into(),
+        OpenAIError::Upload(error) => error
+            .error
+            .status
+            .map_or_else(|| "upload".into(), |status| status.as_u16().to_string()),
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct RequestSummary {
+    model: Option<String>,
+    max_tokens: Option<u64>,
+    max_completion_tokens: Option<u64>,
+    max_output_tokens: Option<u64>,
+    temperature: Option<f64>,
+    top_p: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseSummary {
+    id: Option<String>,
+    model: Option<String>,
+    usage: Option<UsageSummary>,
+    #[serde(default)]
+    choices: Vec<ChoiceSummary>,
+    response: Option<Box<ResponseSummary>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct UsageSummary {
+    #[serde(alias = "input_tokens")]
+    prompt_tokens: Option<u64>,
+    #[serde(alias = "output_tokens")]
+    completion_tokens: Option<u64>,
+    total_tokens: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChoiceSummary {
+    finish_reason: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_response_summary() {
+        assert_eq!(operation("/v1/chat/completions"), "chat");
+        assert_eq!(operation("/v1/completions"), "text_completion");
+        assert_eq!(operation("/v1/files"), "/v1/files");
+
+        let event = r#"{"type":"response.completed","response":{"id":"resp_1","model":"gpt-4o","usage":{"input_tokens":5,"output_tokens":7}}}"#;
+        let summary: ResponseSummary = serde_json::from_str(event).unwrap();
+        let response = summary.response.unwrap();
+        assert_eq!(response.model.as_deref(), Some("gpt-4o"));
+        let usage = response.usage.unwrap();
+        assert_eq!(
+            (usage.prompt_tokens, usage.completion_tokens),
+            (Some(5), Some(7))
+        );
+    }
+}
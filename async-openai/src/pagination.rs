@@ -0,0 +1,114 @@
+//! Streams over cursor paginated list endpoints.
+use futures::{stream, Stream, TryStreamExt};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    config::Config, error::OpenAIError, request_options::RequestOptions, types::Cursor, Client,
+};
+
+/// A page of a list endpoint paginated with an `after` cursor.
+pub(crate) trait CursorPage {
+    type Item;
+
+    /// Items of this page and the cursor of the next page, `None` on the last page.
+    fn into_page(self) -> (Vec<Self::Item>, Option<Cursor>);
+}
+
+macro_rules! impl_cursor_page {
+    ($list:ty, $item:ty) => {
+        impl $crate::pagination::CursorPage for $list {
+            type Item = $item;
+
+            fn into_page(self) -> (Vec<Self::Item>, Option<$crate::types::Cursor>) {
+                let next = if self.has_more {
+                    Some(self.last_id.into())
+                } else {
+                    None
+                };
+                (self.data, next)
+            }
+        }
+    };
+}
+
+pub(crate) use impl_cursor_page;
+
+/// `query` serialized to JSON with `after` set to the given cursor.
+fn query_after(
+    query: &serde_json::Value,
+    after: Option<&Cursor>,
+) -> Result<serde_json::Value, OpenAIError> {
+    let mut query = match query {
+        serde_json::Value::Null => serde_json::Map::new(),
+        serde_json::Value::Object(map) => map.clone(),
+        _ => {
+            return Err(OpenAIError::InvalidArgument(
+                "list query must serialize to a map".into(),
+            ))
+        }
+    };
+    if let Some(after) = after {
+        query.insert("after".into(), after.as_str().into());
+    }
+    Ok(serde_json::Value::Object(query))
+}
+
+/// Stream of all items of the list at `path`, fetching the next page once the current one is consumed.
+///
+/// `after` set in `query` is used as the starting cursor.
+pub(crate) fn list_stream<'c, C, Q, P>(
+    client: &'c Client<C>,
+    path: String,
+    query: &Q,
+    request_options: RequestOptions,
+) -> impl Stream<Item = Result<P::Item, OpenAIError>> + 'c
+where
+    C: Config,
+    Q: Serialize + ?Sized,
+    P: CursorPage + DeserializeOwned + 'c,
+    P::Item: 'c,
+{
+    let query = serde_json::to_value(query).map_err(|e| e.to_string());
+
+    // `None` once the last page was fetched, `Some(after)` otherwise
+    stream::try_unfold(Some(None::<Cursor>), move |cursor| {
+        let path = path.clone();
+        let request_options = request_options.clone();
+        let query = query.clone();
+        async move {
+            let Some(after) = cursor else {
+                return Ok::<_, OpenAIError>(None);
+            };
+            let query = query.map_err(OpenAIError::InvalidArgument)?;
+            let query = query_after(&query, after.as_ref())?;
+            let page: P = client
+                .get_with_query(&path, &query, &request_options)
+                .await?;
+            let (items, next) = page.into_page();
+            Ok(Some((
+                stream::iter(items.into_iter().map(Ok::<_, OpenAIError>)),
+                next.map(Some),
+            )))
+        }
+    })
+    .try_flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_query_after() {
+        let query = serde_json::json!({"limit": 10});
+        assert_eq!(
+            query_after(&query, Some(&Cursor::new("key_1"))).unwrap(),
+            serde_json::json!({"limit": 10, "after": "key_1"})
+        );
+        assert_eq!(
+            query_after(&serde_json::Value::Null, None).unwrap(),
+            serde_json::json!({})
+        );
+        assert!(query_after(&serde_json::json!([1]), None).is_err());
+    }
+}
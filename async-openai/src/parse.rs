@@ -0,0 +1,262 @@
+//! How the responses of the API are deserialized, see [crate::Client::with_parse_mode].
+//!
+//! Responses are parsed leniently by default: fields unknown to the types of this crate are
+//! ignored, so that the fields added to the API do not break existing programs.
+//! [ParseMode::Strict] rejects them instead, to catch the drift of the API from these types in
+//! CI, e.g. when testing a library against the API.
+//!
+//! In both modes, a response which cannot be deserialized fails with an
+//! [OpenAIError::JSONDeserialize] naming the [JSON pointer](https://www.rfc-editor.org/rfc/rfc6901)
+//! of the failing value, e.g. `/choices/0/message/role: unknown variant ...`.
+use std::cell::{Cell, RefCell};
+
+use serde::de::{self, DeserializeOwned};
+use serde_path_to_error::Segment;
+
+use crate::{error::map_deserialization_error, error::OpenAIError, inspect::escape};
+
+/// Whether responses with unknown fields are rejected.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ParseMode {
+    /// Ignore the fields unknown to the types of this crate.
+    #[default]
+    Lossy,
+    /// Fail with the pointers of the fields unknown to the types of this crate.
+    ///
+    /// Fields of values which serde buffers before deserializing them, the values of
+    /// flattened fields, untagged and internally tagged enums, are not checked.
+    ///
+    /// With the `extra-fields` feature, the fields collected in the `extra` fields of the
+    /// types are unknown fields too.
+    Strict,
+}
+
+thread_local! {
+    /// Mode of the events deserialized by the event mappers of streams, see [with_event_mode].
+    static EVENT_MODE: Cell<ParseMode> = const { Cell::new(ParseMode::Lossy) };
+    /// Keys of the extra fields of the value being deserialized in [ParseMode::Strict], `None`
+    /// in [ParseMode::Lossy].
+    static EXTRA_FIELDS: RefCell<Option<Vec<String>>> = const { RefCell::new(None) };
+}
+
+/// Map a stream event with `map` in `mode`.
+///
+/// Event mappers are `TryFrom<eventsource_stream::Event>` conversions, which take no mode, and
+/// read it with [event_mode] instead.
+pub(crate) fn with_event_mode<T>(mode: ParseMode, map: impl FnOnce() -> T) -> T {
+    struct Restore(ParseMode);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            EVENT_MODE.set(self.0);
+        }
+    }
+
+    let _restore = Restore(EVENT_MODE.replace(mode));
+    map()
+}
+
+/// The mode of the event being mapped, [ParseMode::Lossy] outside of [with_event_mode].
+pub(crate) fn event_mode() -> ParseMode {
+    EVENT_MODE.get()
+}
+
+/// Deserialize the response body `bytes` in `mode`.
+pub(crate) fn from_slice<O: DeserializeOwned>(
+    bytes: &[u8],
+    mode: ParseMode,
+) -> Result<O, OpenAIError> {
+    let result = match mode {
+        ParseMode::Lossy => serde_json::from_slice(bytes).map_err(|error| {
+            // parse again to find where, only when failing
+            match error.is_data() {
+                true => tracked::<O>(bytes, false).err().unwrap_or(error),
+                false => error,
+            }
+        }),
+        ParseMode::Strict => tracked(bytes, true),
+    };
+    result.map_err(|error| map_deserialization_error(error, bytes))
+}
+
+/// Deserialize `bytes` tracking the pointers of values, to name the failing or unknown ones.
+fn tracked<O: DeserializeOwned>(bytes: &[u8], strict: bool) -> Result<O, serde_json::Error> {
+    struct Restore(Option<Vec<String>>);
+
+    impl Drop for Restore {
+        fn drop(&mut self) {
+            EXTRA_FIELDS.set(self.0.take());
+        }
+    }
+
+    let _restore = Restore(EXTRA_FIELDS.replace(strict.then(Vec::new)));
+    let mut unknown = Vec::new();
+    let mut ignore = |path: serde_ignored::Path| unknown.push(ignored_pointer(&path));
+    let mut deserializer = serde_json::Deserializer::from_slice(bytes);
+    let result = match strict {
+        true => serde_path_to_error::deserialize(serde_ignored::Deserializer::new(
+            &mut deserializer,
+            &mut ignore,
+        )),
+        false => serde_path_to_error::deserialize(&mut deserializer),
+    };
+    let value = result.map_err(|error| {
+        let path = pointer(error.path());
+        let extra = EXTRA_FIELDS.with_borrow_mut(|keys| keys.as_mut().map(std::mem::take));
+        if let Some(keys) = extra.filter(|keys| !keys.is_empty()) {
+            let keys: Vec<_> = keys.iter().map(|key| format!("{path}/{key}")).collect();
+            return de::Error::custom(format!("unknown fields {}", keys.join(", ")));
+        }
+        let error = error.into_inner();
+        match error.is_data() && !path.is_empty() {
+            true => de::Error::custom(format!("{path}: {error}")),
+            false => error,
+        }
+    })?;
+    deserializer.end()?;
+
+    if !unknown.is_empty() {
+        return Err(de::Error::custom(format!(
+            "unknown fields {}",
+            unknown.join(", ")
+        )));
+    }
+    Ok(value)
+}
+
+/// Deserialize the `extra` fields of the types, failing in [ParseMode::Strict] when there are
+/// any, to name them as unknown fields.
+#[cfg(feature = "extra-fields")]
+pub(crate) fn extra_fields<'de, D: de::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<crate::types::ExtraFields, D::Error> {
+    let extra: crate::types::ExtraFields = serde::Deserialize::deserialize(deserializer)?;
+    let strict = EXTRA_FIELDS.with_borrow_mut(|keys| match keys {
+        Some(keys) if !extra.is_empty() => {
+            let mut extra: Vec<_> = extra.keys().map(|key| escape(key)).collect();
+            extra.sort();
+            keys.extend(extra);
+            true
+        }
+        _ => false,
+    });
+    match strict {
+        true => Err(de::Error::custom("unknown fields")),
+        false => Ok(extra),
+    }
+}
+
+/// JSON pointer of the value at `path`.
+fn pointer(path: &serde_path_to_error::Path) -> String {
+    path.iter()
+        .map(|segment| match segment {
+            Segment::Seq { index } => format!("/{index}"),
+            Segment::Map { key } => format!("/{}", escape(key)),
+            // the variants of externally tagged enums are the keys of their values
+            Segment::Enum { variant } => format!("/{}", escape(variant)),
+            Segment::Unknown => "/?".to_string(),
+        })
+        .collect()
+}
+
+/// JSON pointer of the ignored value at `path`.
+fn ignored_pointer(path: &serde_ignored::Path) -> String {
+    match path {
+        serde_ignored::Path::Root => String::new(),
+        serde_ignored::Path::Seq { parent, index } => {
+            format!("{}/{index}", ignored_pointer(parent))
+        }
+        serde_ignored::Path::Map { parent, key } => {
+            format!("{}/{}", ignored_pointer(parent), escape(key))
+        }
+        // options and newtypes are not levels of the JSON
+        serde_ignored::Path::Some { parent }
+        | serde_ignored::Path::NewtypeStruct { parent }
+        | serde_ignored::Path::NewtypeVariant { parent } => ignored_pointer(parent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        AssistantStreamEvent, CreateChatCompletionResponse, CreateEmbeddingResponse,
+    };
+
+    #[test]
+    fn test_parse_mode() {
+        let body = br#"{
+            "object": "list",
+            "model": "text-embedding-3-small",
+            "data": [{"object": "embedding", "index": 0, "embedding": [0.5], "norm": 1.0}],
+            "usage": {"prompt_tokens": 1, "total_tokens": 1}
+        }"#;
+        assert!(from_slice::<CreateEmbeddingResponse>(body, ParseMode::Lossy).is_ok());
+        let error = from_slice::<CreateEmbeddingResponse>(body, ParseMode::Strict).unwrap_err();
+        assert!(error.to_string().contains("unknown fields /data/0/norm"));
+
+        let body = br#"{"object": "list", "model": "m", "data": [{"object": "embedding"}]}"#;
+        let error = from_slice::<CreateEmbeddingResponse>(body, ParseMode::Lossy).unwrap_err();
+        assert!(error.to_string().contains("/data/0: missing field `index`"));
+    }
+
+    #[test]
+    fn test_buffered_value_pointer() {
+        #[derive(Debug, serde::Deserialize)]
+        #[allow(dead_code)]
+        struct Outer {
+            items: Vec<Item>,
+        }
+
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(untagged)]
+        #[allow(dead_code)]
+        enum Item {
+            Text(String),
+            Number { value: u32 },
+        }
+
+        // serde buffers untagged values, the pointer names the value which failed as a whole
+        let body = br#"{"items": ["a", {"value": "b"}]}"#;
+        let error = from_slice::<Outer>(body, ParseMode::Lossy).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("/items/1: data did not match any variant of untagged enum Item"));
+    }
+
+    #[test]
+    fn test_event_mode() {
+        assert_eq!(event_mode(), ParseMode::Lossy);
+        let mode = with_event_mode(ParseMode::Strict, event_mode);
+        assert_eq!(mode, ParseMode::Strict);
+        assert_eq!(event_mode(), ParseMode::Lossy);
+
+        let event = eventsource_stream::Event {
+            event: "thread.created".into(),
+            data: r#"{"id": "thread_1", "object": "thread", "created_at": 1, "tool_resources": null, "metadata": null, "vector": 1}"#.into(),
+            id: String::new(),
+            retry: None,
+        };
+        assert!(AssistantStreamEvent::try_from(event.clone()).is_ok());
+        let error = with_event_mode(ParseMode::Strict, || AssistantStreamEvent::try_from(event))
+            .unwrap_err();
+        assert!(error.to_string().contains("unknown fields /vector"));
+    }
+
+    #[test]
+    fn test_extra_fields() {
+        let body = br#"{
+            "id": "1", "object": "chat.completion", "created": 1, "model": "m",
+            "choices": [{
+                "index": 0, "finish_reason": "stop", "provider": "p",
+                "message": {"role": "assistant", "content": "OK"}
+            }]
+        }"#;
+        assert!(from_slice::<CreateChatCompletionResponse>(body, ParseMode::Lossy).is_ok());
+        let error =
+            from_slice::<CreateChatCompletionResponse>(body, ParseMode::Strict).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("unknown fields /choices/0/provider"));
+    }
+}
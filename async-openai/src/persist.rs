@@ -0,0 +1,258 @@
+//! Persistence of streamed responses to [JSON Lines](https://jsonlines.org) files while they are
+//! consumed, to debug or replay production streams.
+use std::{
+    path::{Path, PathBuf},
+    pin::Pin,
+    time::{Instant, SystemTime, UNIX_EPOCH},
+};
+
+use futures::{Stream, StreamExt};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio::{io::AsyncWriteExt, sync::mpsc::UnboundedReceiver};
+
+use crate::error::{map_deserialization_error, OpenAIError};
+
+/// A line of a file written by [PersistStreamExt::persist_to].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PersistedEvent<O> {
+    /// Unix timestamp in milliseconds of when the event was received.
+    pub timestamp_ms: u64,
+    /// Milliseconds between [PersistStreamExt::persist_to] and the event.
+    pub elapsed_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event: Option<O>,
+    /// Error message of a failed event, the message of an [OpenAIError::StreamError] without
+    /// its prefix.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Persistence of the events of streams, such as [crate::types::ChatCompletionResponseStream].
+///
+/// ```no_run
+/// use async_openai::{persist::PersistStreamExt, types::CreateChatCompletionRequestArgs, Client};
+/// use futures::StreamExt;
+///
+/// # tokio_test::block_on(async {
+/// let client = Client::new();
+/// let request = CreateChatCompletionRequestArgs::default()
+///     .model("gpt-4o-mini")
+///     .messages([async_openai::types::ChatCompletionRequestUserMessage::from("Hello!").into()])
+///     .build()?;
+///
+/// let mut stream = client
+///     .chat()
+///     .create_stream(request)
+///     .await?
+///     .persist_to("chat-stream.jsonl");
+/// while let Some(chunk) = stream.next().await {
+///     // ...
+/// }
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// # });
+/// ```
+pub trait PersistStreamExt<O>:
+    Stream<Item = Result<O, OpenAIError>> + Send + Sized + 'static
+where
+    O: Serialize + Send + 'static,
+{
+    /// Append each event, or error, received from this stream as a [PersistedEvent] line to
+    /// the file at `path`, created if needed, passing events through.
+    ///
+    /// Lines are written in the background by a task spawned on the tokio runtime polling the
+    /// stream, when it is first polled; a failure to write is logged and stops the persistence
+    /// but not the stream.
+    fn persist_to<P: AsRef<Path>>(
+        self,
+        path: P,
+    ) -> Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>> {
+        let path = path.as_ref().to_path_buf();
+        let started = Instant::now();
+        Box::pin(
+            futures::stream::once(async move {
+                let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+                tokio::spawn(write_lines(path, rx));
+                self.inspect(move |item| {
+                    // the writer only stops on IO errors, which it already logged
+                    let _ = tx.send(line(item, started));
+                })
+            })
+            .flatten(),
+        )
+    }
+}
+
+impl<S, O> PersistStreamExt<O> for S
+where
+    S: Stream<Item = Result<O, OpenAIError>> + Send + 'static,
+    O: Serialize + Send + 'static,
+{
+}
+
+fn line<O: Serialize>(item: &Result<O, OpenAIError>, started: Instant) -> String {
+    let timestamp_ms = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|since| since.as_millis() as u64)
+        .unwrap_or_default();
+    let elapsed_ms = started.elapsed().as_millis() as u64;
+    let error = |error: String| PersistedEvent::<()> {
+        timestamp_ms,
+        elapsed_ms,
+        event: None,
+        error: Some(error),
+    };
+
+    let line = match item {
+        Ok(event) => serde_json::to_string(&PersistedEvent {
+            timestamp_ms,
+            elapsed_ms,
+            event: Some(event),
+            error: None,
+        })
+        .or_else(|e| serde_json::to_string(&error(format!("cannot serialize event: {e}")))),
+        // replayed as a stream error, which adds its prefix again
+        Err(OpenAIError::StreamError(message)) => serde_json::to_string(&error(message.clone())),
+        Err(e) => serde_json::to_string(&error(e.to_string())),
+    };
+    // serializing the error record cannot fail
+    line.unwrap_or_default() + "\n"
+}
+
+async fn write_lines(path: PathBuf, mut lines: UnboundedReceiver<String>) {
+    let file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await;
+    let mut file = match file {
+        Ok(file) => file,
+        Err(e) => {
+            tracing::warn!("cannot persist stream to {}: {e}", path.display());
+            return;
+        }
+    };
+    while let Some(line) = lines.recv().await {
+        if let Err(e) = file.write_all(line.as_bytes()).await {
+            tracing::warn!("cannot persist stream to {}: {e}", path.display());
+            return;
+        }
+    }
+    if let Err(e) = file.flush().await {
+        tracing::warn!("cannot persist stream to {}: {e}", path.display());
+    }
+}
+
+/// Read the lines of a file written by [PersistStreamExt::persist_to].
+pub async fn read<O, P>(path: P) -> Result<Vec<PersistedEvent<O>>, OpenAIError>
+where
+    O: DeserializeOwned,
+    P: AsRef<Path>,
+{
+    let content = tokio::fs::read_to_string(path.as_ref())
+        .await
+        .map_err(|e| {
+            OpenAIError::FileReadError(format!("cannot read {}: {e}", path.as_ref().display()))
+        })?;
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| map_deserialization_error(e, line.as_bytes()))
+        })
+        .collect()
+}
+
+/// Stream the events of a file written by [PersistStreamExt::persist_to],
+/// errors being replayed as [OpenAIError::StreamError].
+pub async fn replay<O, P>(
+    path: P,
+) -> Result<Pin<Box<dyn Stream<Item = Result<O, OpenAIError>> + Send>>, OpenAIError>
+where
+    O: DeserializeOwned + Send + 'static,
+    P: AsRef<Path>,
+{
+    let events = read::<O, P>(path).await?;
+    Ok(Box::pin(futures::stream::iter(
+        events
+            .into_iter()
+            .filter_map(|record| match (record.event, record.error) {
+                (Some(event), _) => Some(Ok(event)),
+                (None, Some(error)) => Some(Err(OpenAIError::StreamError(error))),
+                (None, None) => None,
+            }),
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_persist_and_replay() {
+        let path = std::env::temp_dir().join(format!("persist-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let events = vec![
+            Ok(serde_json::json!({"delta": "Hel"})),
+            Ok(serde_json::json!({"delta": "lo"})),
+            Err(OpenAIError::StreamError("connection reset".into())),
+        ];
+        let stream = futures::stream::iter(events).persist_to(&path);
+        let received: Vec<_> = stream.collect().await;
+        assert_eq!(received.len(), 3);
+
+        // the writer task finishes once the stream is dropped
+        let mut persisted = Vec::new();
+        for _ in 0..100 {
+            persisted = read::<serde_json::Value, _>(&path)
+                .await
+                .unwrap_or_default();
+            if persisted.len() == 3 {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(persisted.len(), 3);
+        assert_eq!(persisted[2].error.as_deref(), Some("connection reset"));
+
+        let replayed: Vec<_> = replay::<serde_json::Value, _>(&path)
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(replayed[1].as_ref().unwrap()["delta"], "lo");
+        let error = replayed[2].as_ref().unwrap_err();
+        assert_eq!(error.to_string(), "stream failed: connection reset");
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_persist_to_outside_runtime() {
+        let path = std::env::temp_dir().join(format!("persist-lazy-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        // the writer is spawned once the stream is polled within a runtime
+        let stream =
+            futures::stream::iter(vec![Ok(serde_json::json!({"delta": "Hi"}))]).persist_to(&path);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .unwrap();
+        let persisted = runtime.block_on(async {
+            assert_eq!(stream.collect::<Vec<_>>().await.len(), 1);
+            for _ in 0..100 {
+                let persisted = read::<serde_json::Value, _>(&path)
+                    .await
+                    .unwrap_or_default();
+                if !persisted.is_empty() {
+                    return persisted;
+                }
+                tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            }
+            Vec::new()
+        });
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].event.as_ref().unwrap()["delta"], "Hi");
+        std::fs::remove_file(&path).unwrap();
+    }
+}
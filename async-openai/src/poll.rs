@@ -0,0 +1,431 @@
+//! Options shared by the `wait` helpers that poll long running objects
+//! (runs, batches, vector store files and file batches) until they settle.
+//!
+//! Uploads have no `wait` helper: an Upload stays `pending` until the client calls
+//! [crate::Uploads::complete] or [crate::Uploads::cancel], which return the settled Upload, or
+//! it expires, so polling it would only wait for the expiry.
+use std::{
+    fmt,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use rand::Rng;
+use serde::Serialize;
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        Batch, BatchStatus, FineTuningJob, FineTuningJobStatus, RunObject, RunStatus,
+        VectorStoreFileBatchObject, VectorStoreFileBatchStatus, VectorStoreFileObject,
+        VectorStoreFileStatus,
+    },
+};
+
+/// Default delay between two consecutive polls.
+pub const DEFAULT_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Progress reported to [PollOptions::with_on_tick] after every poll.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PollProgress {
+    /// Id of the object being polled.
+    pub id: String,
+    /// Status of the object as returned by the API, e.g. `in_progress`.
+    pub status: String,
+    /// Number of polls made so far, starting at 1.
+    pub attempt: u32,
+    /// Time elapsed since the first poll.
+    pub elapsed: Duration,
+}
+
+type OnTick = Arc<dyn Fn(&PollProgress) + Send + Sync>;
+
+/// Controls how `wait` helpers poll an object until it reaches a terminal status.
+///
+/// ```
+/// use std::time::Duration;
+/// use async_openai::poll::PollOptions;
+///
+/// let options = PollOptions::new()
+///     .with_interval(Duration::from_millis(500))
+///     .with_max_wait(Duration::from_secs(60))
+///     .with_jitter(Duration::from_millis(100))
+///     .with_backoff(2.0, Duration::from_secs(8))
+///     .with_on_tick(|progress| println!("{}: {}", progress.id, progress.status));
+/// ```
+#[derive(Clone)]
+pub struct PollOptions {
+    interval: Duration,
+    max_wait: Option<Duration>,
+    jitter: Duration,
+    backoff: f64,
+    max_interval: Option<Duration>,
+    on_tick: Option<OnTick>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            interval: DEFAULT_POLL_INTERVAL,
+            max_wait: None,
+            jitter: Duration::ZERO,
+            backoff: 1.0,
+            max_interval: None,
+            on_tick: None,
+        }
+    }
+}
+
+impl fmt::Debug for PollOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollOptions")
+            .field("interval", &self.interval)
+            .field("max_wait", &self.max_wait)
+            .field("jitter", &self.jitter)
+            .field("backoff", &self.backoff)
+            .field("max_interval", &self.max_interval)
+            .field(
+                "on_tick",
+                &self.on_tick.as_ref().map(|_| "Fn(&PollProgress)"),
+            )
+            .finish()
+    }
+}
+
+impl PollOptions {
+    /// Poll every [DEFAULT_POLL_INTERVAL] without a time limit.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Delay between two consecutive polls.
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Give up with [OpenAIError::Timeout] once this much time has elapsed without reaching a terminal status.
+    pub fn with_max_wait(mut self, max_wait: Duration) -> Self {
+        self.max_wait = Some(max_wait);
+        self
+    }
+
+    /// Add a random delay between zero and `jitter` to every interval.
+    pub fn with_jitter(mut self, jitter: Duration) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Multiply the interval by `multiplier` after every poll, up to `max_interval`.
+    pub fn with_backoff(mut self, multiplier: f64, max_interval: Duration) -> Self {
+        self.backoff = multiplier.max(1.0);
+        self.max_interval = Some(max_interval);
+        self
+    }
+
+    /// Callback invoked after every poll with the latest status.
+    pub fn with_on_tick<F>(mut self, on_tick: F) -> Self
+    where
+        F: Fn(&PollProgress) + Send + Sync + 'static,
+    {
+        self.on_tick = Some(Arc::new(on_tick));
+        self
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    pub fn max_wait(&self) -> Option<Duration> {
+        self.max_wait
+    }
+
+    pub fn jitter(&self) -> Duration {
+        self.jitter
+    }
+
+    /// Options giving up once `elapsed` reaches `max_wait`, for a wait which already
+    /// spent `elapsed` before polling.
+    pub(crate) fn remaining(&self, elapsed: Duration) -> Self {
+        let mut options = self.clone();
+        options.max_wait = self
+            .max_wait
+            .map(|max_wait| max_wait.saturating_sub(elapsed));
+        options
+    }
+
+    /// Report `progress` to the callback of [PollOptions::with_on_tick].
+    pub(crate) fn tick(&self, progress: &PollProgress) {
+        if let Some(on_tick) = &self.on_tick {
+            on_tick(progress);
+        }
+    }
+
+    /// Interval of the `attempt`th poll with backoff and jitter applied, capped so a sleep
+    /// never overshoots `max_wait`.
+    pub(crate) fn next_delay(&self, attempt: u32, elapsed: Duration) -> Duration {
+        let mut delay = self.interval;
+        if self.backoff > 1.0 {
+            let backoff = self.backoff.powi(attempt.saturating_sub(1) as i32);
+            let max_interval = self.max_interval.unwrap_or(Duration::MAX);
+            delay = Duration::try_from_secs_f64(delay.as_secs_f64() * backoff)
+                .unwrap_or(max_interval)
+                .min(max_interval);
+        }
+        if !self.jitter.is_zero() {
+            delay += self.jitter.mul_f64(rand::rng().random_range(0.0..1.0));
+        }
+        match self.max_wait {
+            Some(max_wait) => delay.min(max_wait.saturating_sub(elapsed)),
+            None => delay,
+        }
+    }
+}
+
+/// Objects which can be polled by [poll_until_done].
+pub(crate) trait Pollable {
+    fn poll_id(&self) -> &str;
+    fn poll_status(&self) -> String;
+    fn is_done(&self) -> bool;
+}
+
+/// String representation of a status enum as sent over the wire.
+fn status_label<S: Serialize>(status: &S) -> String {
+    match serde_json::to_value(status) {
+        Ok(serde_json::Value::String(s)) => s,
+        _ => String::new(),
+    }
+}
+
+impl Pollable for RunObject {
+    fn poll_id(&self) -> &str {
+        &self.id
+    }
+
+    fn poll_status(&self) -> String {
+        status_label(&self.status)
+    }
+
+    /// `requires_action` is treated as done because the run cannot progress without the caller.
+    fn is_done(&self) -> bool {
+        !matches!(
+            self.status,
+            RunStatus::Queued | RunStatus::InProgress | RunStatus::Cancelling
+        )
+    }
+}
+
+impl Pollable for Batch {
+    fn poll_id(&self) -> &str {
+        &self.id
+    }
+
+    fn poll_status(&self) -> String {
+        status_label(&self.status)
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(
+            self.status,
+            BatchStatus::Completed
+                | BatchStatus::Failed
+                | BatchStatus::Expired
+                | BatchStatus::Cancelled
+        )
+    }
+}
+
+impl Pollable for FineTuningJob {
+    fn poll_id(&self) -> &str {
+        &self.id
+    }
+
+    fn poll_status(&self) -> String {
+        status_label(&self.status)
+    }
+
+    fn is_done(&self) -> bool {
+        matches!(
+            self.status,
+            FineTuningJobStatus::Succeeded
+                | FineTuningJobStatus::Failed
+                | FineTuningJobStatus::Cancelled
+        )
+    }
+}
+
+impl Pollable for VectorStoreFileBatchObject {
+    fn poll_id(&self) -> &str {
+        &self.id
+    }
+
+    fn poll_status(&self) -> String {
+        status_label(&self.status)
+    }
+
+    fn is_done(&self) -> bool {
+        self.status != VectorStoreFileBatchStatus::InProgress
+    }
+}
+
+impl Pollable for VectorStoreFileObject {
+    fn poll_id(&self) -> &str {
+        &self.id
+    }
+
+    fn poll_status(&self) -> String {
+        status_label(&self.status)
+    }
+
+    fn is_done(&self) -> bool {
+        self.status != VectorStoreFileStatus::InProgress
+    }
+}
+
+/// Repeatedly calls `fetch` until the returned object is done or `max_wait` elapses.
+pub(crate) async fn poll_until_done<T, F, Fut>(
+    options: &PollOptions,
+    mut fetch: F,
+) -> Result<T, OpenAIError>
+where
+    T: Pollable,
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, OpenAIError>>,
+{
+    let start = Instant::now();
+    let mut attempt = 0;
+
+    loop {
+        let object = fetch().await?;
+        attempt += 1;
+        let elapsed = start.elapsed();
+
+        options.tick(&PollProgress {
+            id: object.poll_id().to_string(),
+            status: object.poll_status(),
+            attempt,
+            elapsed,
+        });
+
+        if object.is_done() {
+            return Ok(object);
+        }
+
+        if let Some(max_wait) = options.max_wait {
+            if elapsed >= max_wait {
+                return Err(OpenAIError::Timeout(format!(
+                    "{} still {} after {:?}",
+                    object.poll_id(),
+                    object.poll_status(),
+                    elapsed
+                )));
+            }
+        }
+
+        tokio::time::sleep(options.next_delay(attempt, elapsed)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    struct Job {
+        id: String,
+        done: bool,
+    }
+
+    impl Pollable for Job {
+        fn poll_id(&self) -> &str {
+            &self.id
+        }
+
+        fn poll_status(&self) -> String {
+            if self.done {
+                "completed"
+            } else {
+                "in_progress"
+            }
+            .to_string()
+        }
+
+        fn is_done(&self) -> bool {
+            self.done
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_done_reports_progress() {
+        let ticks = Arc::new(AtomicU32::new(0));
+        let counter = ticks.clone();
+        let options = PollOptions::new()
+            .with_interval(Duration::from_millis(1))
+            .with_on_tick(move |_| {
+                counter.fetch_add(1, Ordering::SeqCst);
+            });
+
+        let mut calls = 0;
+        let job = poll_until_done(&options, || {
+            calls += 1;
+            let done = calls == 3;
+            async move {
+                Ok(Job {
+                    id: "job_1".into(),
+                    done,
+                })
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(job.poll_status(), "completed");
+        assert_eq!(ticks.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_poll_until_done_times_out() {
+        let options = PollOptions::new()
+            .with_interval(Duration::from_millis(5))
+            .with_max_wait(Duration::from_millis(20));
+
+        let result = poll_until_done(&options, || async {
+            Ok(Job {
+                id: "job_1".into(),
+                done: false,
+            })
+        })
+        .await;
+
+        assert!(matches!(result, Err(OpenAIError::Timeout(_))));
+    }
+
+    #[test]
+    fn test_next_delay_backoff() {
+        let options = PollOptions::new()
+            .with_backoff(2.0, Duration::from_secs(5))
+            .with_max_wait(Duration::from_secs(60));
+        assert_eq!(
+            options.next_delay(1, Duration::ZERO),
+            Duration::from_secs(1)
+        );
+        assert_eq!(
+            options.next_delay(3, Duration::ZERO),
+            Duration::from_secs(4)
+        );
+        assert_eq!(
+            options.next_delay(10, Duration::ZERO),
+            Duration::from_secs(5)
+        );
+        assert_eq!(
+            options.next_delay(10, Duration::from_secs(58)),
+            Duration::from_secs(2)
+        );
+    }
+
+    #[test]
+    fn test_status_label() {
+        assert_eq!(status_label(&RunStatus::RequiresAction), "requires_action");
+    }
+}
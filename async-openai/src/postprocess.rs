@@ -0,0 +1,135 @@
+//! Post-processing of the text content of chat completions, see [crate::Chat::with_postprocessor].
+//!
+//! Responses are not post-processed with the `byot` feature.
+
+/// What a [ResponsePostprocessor] knows about the response being processed.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct PostprocessContext {
+    /// The request asked for JSON with a `json_object` or `json_schema` response format.
+    pub json: bool,
+    /// Model which generated the response.
+    pub model: String,
+}
+
+/// Transforms the content of each choice of a chat completion before it is returned.
+///
+/// Closures taking the content and a [PostprocessContext] implement this trait.
+///
+/// ```
+/// use async_openai::{
+///     postprocess::{NormalizeQuotes, PostprocessContext, StripCodeFences, TrimWhitespace},
+///     Client,
+/// };
+///
+/// let client = Client::new();
+/// let chat = client
+///     .chat()
+///     .with_postprocessor(StripCodeFences::new())
+///     .with_postprocessor(NormalizeQuotes)
+///     .with_postprocessor(TrimWhitespace)
+///     .with_postprocessor(|content: String, _: &PostprocessContext| content.replace("\r\n", "\n"));
+/// ```
+pub trait ResponsePostprocessor: Send + Sync {
+    fn process(&self, content: String, context: &PostprocessContext) -> String;
+}
+
+impl<F> ResponsePostprocessor for F
+where
+    F: Fn(String, &PostprocessContext) -> String + Send + Sync,
+{
+    fn process(&self, content: String, context: &PostprocessContext) -> String {
+        self(content, context)
+    }
+}
+
+/// Removes a code fence, such as ` ```json `, wrapping the whole content.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct StripCodeFences {
+    always: bool,
+}
+
+impl StripCodeFences {
+    /// Strip fences of JSON responses only.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Strip fences whatever the response format.
+    pub fn always() -> Self {
+        Self { always: true }
+    }
+}
+
+impl ResponsePostprocessor for StripCodeFences {
+    fn process(&self, content: String, context: &PostprocessContext) -> String {
+        if !self.always && !context.json {
+            return content;
+        }
+        let trimmed = content.trim();
+        let Some(inner) = trimmed
+            .strip_prefix("```")
+            .and_then(|rest| rest.strip_suffix("```"))
+        else {
+            return content;
+        };
+        // drop the info string, e.g. `json`, on the opening line
+        match inner.split_once('\n') {
+            Some((_, body)) => body.trim_end().to_string(),
+            None => content,
+        }
+    }
+}
+
+/// Replaces typographic quotes with ASCII quotes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NormalizeQuotes;
+
+impl ResponsePostprocessor for NormalizeQuotes {
+    fn process(&self, content: String, _context: &PostprocessContext) -> String {
+        content
+            .chars()
+            .map(|c| match c {
+                '\u{2018}' | '\u{2019}' | '\u{201A}' | '\u{201B}' => '\'',
+                '\u{201C}' | '\u{201D}' | '\u{201E}' | '\u{201F}' => '"',
+                c => c,
+            })
+            .collect()
+    }
+}
+
+/// Removes leading and trailing whitespace.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TrimWhitespace;
+
+impl ResponsePostprocessor for TrimWhitespace {
+    fn process(&self, content: String, _context: &PostprocessContext) -> String {
+        content.trim().to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_postprocessors() {
+        let json = PostprocessContext {
+            json: true,
+            ..Default::default()
+        };
+        let fenced = "```json\n{\"a\": 1}\n```\n".to_string();
+        assert_eq!(
+            StripCodeFences::new().process(fenced.clone(), &json),
+            "{\"a\": 1}"
+        );
+        assert_eq!(
+            StripCodeFences::new().process(fenced.clone(), &PostprocessContext::default()),
+            fenced
+        );
+        assert_eq!(
+            NormalizeQuotes.process("\u{201C}it\u{2019}s\u{201D}".into(), &json),
+            "\"it's\""
+        );
+    }
+}
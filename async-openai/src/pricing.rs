@@ -0,0 +1,417 @@
+//! Cost estimation of API usage from per-token model prices.
+//!
+//! A [PriceTable] starts with the list prices in USD of common models at the time of this
+//! release; prices change, so override them with [PriceTable::set_price] from your own source of
+//! truth. Audio, image and tool call charges are not accounted for.
+//!
+//! ```
+//! use async_openai::{
+//!     pricing::{CostAccumulator, ModelPrice, PriceTable, TokenUsage},
+//!     types::CompletionUsage,
+//! };
+//!
+//! let prices = PriceTable::new().with_price("my-fine-tune", ModelPrice::per_million(3.0, 12.0));
+//! let usage = CompletionUsage {
+//!     prompt_tokens: 1_000,
+//!     completion_tokens: 500,
+//!     total_tokens: 1_500,
+//!     prompt_tokens_details: None,
+//!     completion_tokens_details: None,
+//! };
+//! # #[cfg(not(feature = "decimal"))]
+//! assert_eq!(prices.cost("my-fine-tune", &usage), Some(0.009));
+//!
+//! let spend = CostAccumulator::new(prices);
+//! spend.record_tagged("summaries", "gpt-4o-mini-2024-07-18", &usage);
+//! spend.record_tagged("search", "text-embedding-3-small", TokenUsage::input(20_000));
+//! assert_eq!(spend.by_tag()["summaries"].requests, 1);
+//! ```
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, RwLock},
+};
+
+use crate::types::{
+    responses, Amount, CompletionUsage, EmbeddingUsage, ImageGenUsage, RunCompletionUsage,
+    RunStepCompletionUsage,
+};
+
+/// List prices in USD per million input, cached input and output tokens.
+const PRICES: &[(&str, f64, Option<f64>, f64)] = &[
+    ("gpt-4.1", 2.0, Some(0.5), 8.0),
+    ("gpt-4.1-mini", 0.4, Some(0.1), 1.6),
+    ("gpt-4.1-nano", 0.1, Some(0.025), 0.4),
+    ("gpt-4o", 2.5, Some(1.25), 10.0),
+    ("gpt-4o-mini", 0.15, Some(0.075), 0.6),
+    ("gpt-4-turbo", 10.0, None, 30.0),
+    ("gpt-4", 30.0, None, 60.0),
+    ("gpt-3.5-turbo", 0.5, None, 1.5),
+    ("o1", 15.0, Some(7.5), 60.0),
+    ("o1-mini", 1.1, Some(0.55), 4.4),
+    ("o3", 2.0, Some(0.5), 8.0),
+    ("o3-mini", 1.1, Some(0.55), 4.4),
+    ("o4-mini", 1.1, Some(0.275), 4.4),
+    ("text-embedding-3-small", 0.02, None, 0.0),
+    ("text-embedding-3-large", 0.13, None, 0.0),
+    ("text-embedding-ada-002", 0.1, None, 0.0),
+];
+
+#[cfg(not(feature = "decimal"))]
+fn amount(value: f64) -> Amount {
+    value
+}
+
+#[cfg(feature = "decimal")]
+fn amount(value: f64) -> Amount {
+    Amount::try_from(value).unwrap_or_default()
+}
+
+/// Tokens billed for a request, converted from the usage of any response.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TokenUsage {
+    /// Input tokens, cached tokens included.
+    pub input_tokens: u32,
+    /// Input tokens read from the prompt cache, billed at the cached input price.
+    pub cached_input_tokens: u32,
+    /// Output tokens, reasoning tokens included.
+    pub output_tokens: u32,
+}
+
+impl TokenUsage {
+    /// Usage of `input_tokens` without output, as for embeddings.
+    pub fn input(input_tokens: u32) -> Self {
+        Self {
+            input_tokens,
+            ..Default::default()
+        }
+    }
+}
+
+impl From<&CompletionUsage> for TokenUsage {
+    fn from(usage: &CompletionUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            cached_input_tokens: usage.cached_tokens(),
+            output_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+impl From<&responses::Usage> for TokenUsage {
+    fn from(usage: &responses::Usage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: usage.input_tokens_details.cached_tokens.unwrap_or(0),
+            output_tokens: usage.output_tokens,
+        }
+    }
+}
+
+impl From<&EmbeddingUsage> for TokenUsage {
+    fn from(usage: &EmbeddingUsage) -> Self {
+        Self::input(usage.prompt_tokens)
+    }
+}
+
+impl From<&ImageGenUsage> for TokenUsage {
+    fn from(usage: &ImageGenUsage) -> Self {
+        Self {
+            input_tokens: usage.input_tokens,
+            cached_input_tokens: 0,
+            output_tokens: usage.output_tokens,
+        }
+    }
+}
+
+impl From<&RunCompletionUsage> for TokenUsage {
+    fn from(usage: &RunCompletionUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            cached_input_tokens: 0,
+            output_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+impl From<&RunStepCompletionUsage> for TokenUsage {
+    fn from(usage: &RunStepCompletionUsage) -> Self {
+        Self {
+            input_tokens: usage.prompt_tokens,
+            cached_input_tokens: 0,
+            output_tokens: usage.completion_tokens,
+        }
+    }
+}
+
+/// Prices of a model per million tokens.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ModelPrice {
+    input: Amount,
+    cached_input: Option<Amount>,
+    output: Amount,
+}
+
+impl ModelPrice {
+    /// Price per million input and output tokens.
+    pub fn per_million(input: f64, output: f64) -> Self {
+        Self {
+            input: amount(input),
+            cached_input: None,
+            output: amount(output),
+        }
+    }
+
+    /// Price per million cached input tokens, billed as input tokens otherwise.
+    pub fn with_cached_input(mut self, cached_input: f64) -> Self {
+        self.cached_input = Some(amount(cached_input));
+        self
+    }
+
+    pub fn input(&self) -> Amount {
+        self.input
+    }
+
+    pub fn cached_input(&self) -> Amount {
+        self.cached_input.unwrap_or(self.input)
+    }
+
+    pub fn output(&self) -> Amount {
+        self.output
+    }
+
+    /// Cost of `usage` at these prices.
+    pub fn cost(&self, usage: impl Into<TokenUsage>) -> Amount {
+        let usage = usage.into();
+        let cached = usage.cached_input_tokens.min(usage.input_tokens);
+        let million = Amount::from(1_000_000u32);
+        (Amount::from(usage.input_tokens - cached) * self.input
+            + Amount::from(cached) * self.cached_input()
+            + Amount::from(usage.output_tokens) * self.output)
+            / million
+    }
+}
+
+/// Prices of models, looked up by the longest model name prefixing the model of a response
+/// so that dated snapshots such as `gpt-4o-2024-08-06` use the price of `gpt-4o`.
+///
+/// Clones share the same prices.
+#[derive(Debug, Clone)]
+pub struct PriceTable {
+    prices: Arc<RwLock<HashMap<String, ModelPrice>>>,
+}
+
+impl Default for PriceTable {
+    fn default() -> Self {
+        let prices = PRICES
+            .iter()
+            .map(|(model, input, cached_input, output)| {
+                let mut price = ModelPrice::per_million(*input, *output);
+                if let Some(cached_input) = cached_input {
+                    price = price.with_cached_input(*cached_input);
+                }
+                (model.to_string(), price)
+            })
+            .collect();
+        Self {
+            prices: Arc::new(RwLock::new(prices)),
+        }
+    }
+}
+
+impl PriceTable {
+    /// Table with the built-in list prices.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Table without prices.
+    pub fn empty() -> Self {
+        Self {
+            prices: Default::default(),
+        }
+    }
+
+    pub fn with_price<S: Into<String>>(self, model: S, price: ModelPrice) -> Self {
+        self.set_price(model, price);
+        self
+    }
+
+    /// Set the price of `model` and its snapshots, taking effect immediately.
+    pub fn set_price<S: Into<String>>(&self, model: S, price: ModelPrice) {
+        self.write().insert(model.into(), price);
+    }
+
+    pub fn remove_price(&self, model: &str) {
+        self.write().remove(model);
+    }
+
+    /// Price of `model`, `None` for unknown models.
+    pub fn price(&self, model: &str) -> Option<ModelPrice> {
+        let prices = self
+            .prices
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(price) = prices.get(model) {
+            return Some(*price);
+        }
+        prices
+            .iter()
+            .filter(|(name, _)| {
+                model
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, price)| *price)
+    }
+
+    /// Cost of `usage` by `model`, `None` for unknown models.
+    pub fn cost(&self, model: &str, usage: impl Into<TokenUsage>) -> Option<Amount> {
+        Some(self.price(model)?.cost(usage))
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, ModelPrice>> {
+        self.prices
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Usage and cost summed by a [CostAccumulator].
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct CostSummary {
+    /// Cost of the requests of models with a price.
+    pub cost: Amount,
+    pub requests: u64,
+    /// Requests of models without price, not counted in `cost`.
+    pub unpriced_requests: u64,
+    pub input_tokens: u64,
+    pub cached_input_tokens: u64,
+    pub output_tokens: u64,
+}
+
+impl CostSummary {
+    fn add(&mut self, usage: TokenUsage, cost: Option<Amount>) {
+        self.requests += 1;
+        match cost {
+            Some(cost) => self.cost += cost,
+            None => self.unpriced_requests += 1,
+        }
+        self.input_tokens += usage.input_tokens as u64;
+        self.cached_input_tokens += usage.cached_input_tokens as u64;
+        self.output_tokens += usage.output_tokens as u64;
+    }
+}
+
+#[derive(Debug, Default)]
+struct Spend {
+    total: CostSummary,
+    by_model: HashMap<String, CostSummary>,
+    by_tag: HashMap<String, CostSummary>,
+}
+
+/// Sums the cost of many responses or streams, in total, by model and by tag such as a feature name.
+///
+/// Clones share the same totals.
+#[derive(Debug, Clone, Default)]
+pub struct CostAccumulator {
+    prices: PriceTable,
+    spend: Arc<Mutex<Spend>>,
+}
+
+impl CostAccumulator {
+    pub fn new(prices: PriceTable) -> Self {
+        Self {
+            prices,
+            spend: Default::default(),
+        }
+    }
+
+    pub fn prices(&self) -> &PriceTable {
+        &self.prices
+    }
+
+    /// Add the usage of a response of `model`, returns its cost if the model has a price.
+    ///
+    /// The usage of a stream is in its last chunk when requested with `include_usage`.
+    pub fn record(&self, model: &str, usage: impl Into<TokenUsage>) -> Option<Amount> {
+        self.add(None, model, usage.into())
+    }
+
+    /// [CostAccumulator::record], also summed under `tag`.
+    pub fn record_tagged(
+        &self,
+        tag: &str,
+        model: &str,
+        usage: impl Into<TokenUsage>,
+    ) -> Option<Amount> {
+        self.add(Some(tag), model, usage.into())
+    }
+
+    pub fn total(&self) -> CostSummary {
+        self.lock().total
+    }
+
+    pub fn by_model(&self) -> HashMap<String, CostSummary> {
+        self.lock().by_model.clone()
+    }
+
+    pub fn by_tag(&self) -> HashMap<String, CostSummary> {
+        self.lock().by_tag.clone()
+    }
+
+    /// Forget the recorded usage.
+    pub fn reset(&self) {
+        *self.lock() = Default::default();
+    }
+
+    fn add(&self, tag: Option<&str>, model: &str, usage: TokenUsage) -> Option<Amount> {
+        let cost = self.prices.cost(model, usage);
+        let mut spend = self.lock();
+        spend.total.add(usage, cost);
+        spend
+            .by_model
+            .entry(model.to_string())
+            .or_default()
+            .add(usage, cost);
+        if let Some(tag) = tag {
+            spend
+                .by_tag
+                .entry(tag.to_string())
+                .or_default()
+                .add(usage, cost);
+        }
+        cost
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Spend> {
+        // totals stay usable even if a thread panicked while holding the lock
+        self.spend
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price_lookup_and_cached_tokens() {
+        let prices = PriceTable::new();
+        assert_eq!(
+            prices.price("gpt-4o-mini-2024-07-18"),
+            prices.price("gpt-4o-mini")
+        );
+        assert_eq!(prices.price("gpt-4o-2024-08-06"), prices.price("gpt-4o"));
+        assert!(prices.price("gpt-4ox").is_none());
+
+        let usage = TokenUsage {
+            input_tokens: 2_000_000,
+            cached_input_tokens: 1_000_000,
+            output_tokens: 1_000_000,
+        };
+        // 1M uncached at 2.5, 1M cached at 1.25 and 1M output at 10
+        assert_eq!(prices.cost("gpt-4o", usage), Some(amount(13.75)));
+    }
+}
@@ -1,8 +1,11 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::list_stream,
+    request_options::{impl_request_options, RequestOptions},
     types::{ProjectApiKey, ProjectApiKeyDeleteResponse, ProjectApiKeyListResponse},
     Client,
 };
@@ -12,17 +15,28 @@ use crate::{
 pub struct ProjectAPIKeys<'c, C: Config> {
     client: &'c Client<C>,
     pub project_id: String,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(ProjectAPIKeys);
+crate::util::impl_api_group_debug!(
+    ProjectAPIKeys,
+    "/organization/projects/{}/api_keys",
+    project_id
+);
+
 impl<'c, C: Config> ProjectAPIKeys<'c, C> {
     pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
         Self {
             client,
             project_id: project_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
     /// Returns a list of API keys in the project.
+    ///
+    /// `query` is usually a [ListProjectApiKeysQuery](crate::types::ListProjectApiKeysQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectApiKeyListResponse, OpenAIError>
     where
@@ -32,10 +46,29 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
             .get_with_query(
                 format!("/organization/projects/{}/api_keys", self.project_id).as_str(),
                 &query,
+                &self.request_options,
             )
             .await
     }
 
+    /// Streams all API keys of the project, fetching pages as needed.
+    ///
+    /// `query` takes the same parameters as [Self::list], `after` is used as the starting cursor.
+    pub fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> impl Stream<Item = Result<ProjectApiKey, OpenAIError>> + 'c
+    where
+        Q: Serialize + ?Sized,
+    {
+        list_stream::<_, _, ProjectApiKeyListResponse>(
+            self.client,
+            format!("/organization/projects/{}/api_keys", self.project_id),
+            query,
+            self.request_options.clone(),
+        )
+    }
+
     /// Retrieves an API key in the project.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, api_key: &str) -> Result<ProjectApiKey, OpenAIError> {
@@ -46,6 +79,7 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
                     self.project_id
                 )
                 .as_str(),
+                &self.request_options,
             )
             .await
     }
@@ -60,6 +94,7 @@ impl<'c, C: Config> ProjectAPIKeys<'c, C> {
                     self.project_id
                 )
                 .as_str(),
+                &self.request_options,
             )
             .await
     }
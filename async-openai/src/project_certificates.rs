@@ -0,0 +1,109 @@
+use futures::Stream;
+use serde::Serialize;
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    pagination::list_stream,
+    request_options::{impl_request_options, RequestOptions},
+    types::{Certificate, CertificateListResponse, ToggleCertificatesRequest},
+    Client,
+};
+
+/// Manage the certificates active for a given project, among the certificates uploaded to
+/// its organization with [Certificates](crate::Certificates).
+pub struct ProjectCertificates<'c, C: Config> {
+    client: &'c Client<C>,
+    pub project_id: String,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(ProjectCertificates);
+crate::util::impl_api_group_debug!(
+    ProjectCertificates,
+    "/organization/projects/{}/certificates",
+    project_id
+);
+
+impl<'c, C: Config> ProjectCertificates<'c, C> {
+    pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
+        Self {
+            client,
+            project_id: project_id.into(),
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// List the certificates of the project.
+    ///
+    /// `query` is usually a [ListCertificatesQuery](crate::types::ListCertificatesQuery).
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn list<Q>(&self, query: &Q) -> Result<CertificateListResponse, OpenAIError>
+    where
+        Q: Serialize + ?Sized,
+    {
+        self.client
+            .get_with_query(
+                format!("/organization/projects/{}/certificates", self.project_id).as_str(),
+                &query,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Streams all certificates of the project, fetching pages as needed.
+    ///
+    /// `query` takes the same parameters as [Self::list], `after` is used as the starting cursor.
+    pub fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> impl Stream<Item = Result<Certificate, OpenAIError>> + 'c
+    where
+        Q: Serialize + ?Sized,
+    {
+        list_stream::<_, _, CertificateListResponse>(
+            self.client,
+            format!("/organization/projects/{}/certificates", self.project_id),
+            query,
+            self.request_options.clone(),
+        )
+    }
+
+    /// Activate certificates for the project, returning the certificates activated.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn activate(
+        &self,
+        request: ToggleCertificatesRequest,
+    ) -> Result<CertificateListResponse, OpenAIError> {
+        self.client
+            .post(
+                format!(
+                    "/organization/projects/{}/certificates/activate",
+                    self.project_id
+                )
+                .as_str(),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Deactivate certificates for the project, returning the certificates deactivated.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn deactivate(
+        &self,
+        request: ToggleCertificatesRequest,
+    ) -> Result<CertificateListResponse, OpenAIError> {
+        self.client
+            .post(
+                format!(
+                    "/organization/projects/{}/certificates/deactivate",
+                    self.project_id
+                )
+                .as_str(),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+}
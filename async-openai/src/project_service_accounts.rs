@@ -1,8 +1,11 @@
+use futures::Stream;
 use serde::Serialize;
 
 use crate::{
     config::Config,
     error::OpenAIError,
+    pagination::list_stream,
+    request_options::{impl_request_options, RequestOptions},
     types::{
         ProjectServiceAccount, ProjectServiceAccountCreateRequest,
         ProjectServiceAccountCreateResponse, ProjectServiceAccountDeleteResponse,
@@ -18,17 +21,28 @@ use crate::{
 pub struct ProjectServiceAccounts<'c, C: Config> {
     client: &'c Client<C>,
     pub project_id: String,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(ProjectServiceAccounts);
+crate::util::impl_api_group_debug!(
+    ProjectServiceAccounts,
+    "/organization/projects/{}/service_accounts",
+    project_id
+);
+
 impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
     pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
         Self {
             client,
             project_id: project_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
     /// Returns a list of service accounts in the project.
+    ///
+    /// `query` is usually a [ListProjectServiceAccountsQuery](crate::types::ListProjectServiceAccountsQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectServiceAccountListResponse, OpenAIError>
     where
@@ -42,10 +56,32 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
                 )
                 .as_str(),
                 &query,
+                &self.request_options,
             )
             .await
     }
 
+    /// Streams all service accounts of the project, fetching pages as needed.
+    ///
+    /// `query` takes the same parameters as [Self::list], `after` is used as the starting cursor.
+    pub fn list_stream<Q>(
+        &self,
+        query: &Q,
+    ) -> impl Stream<Item = Result<ProjectServiceAccount, OpenAIError>> + 'c
+    where
+        Q: Serialize + ?Sized,
+    {
+        list_stream::<_, _, ProjectServiceAccountListResponse>(
+            self.client,
+            format!(
+                "/organization/projects/{}/service_accounts",
+                self.project_id
+            ),
+            query,
+            self.request_options.clone(),
+        )
+    }
+
     /// Creates a new service account in the project. This also returns an unredacted API key for the service account.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(
@@ -60,6 +96,7 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
                 )
                 .as_str(),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -77,6 +114,7 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
                     self.project_id
                 )
                 .as_str(),
+                &self.request_options,
             )
             .await
     }
@@ -94,6 +132,7 @@ impl<'c, C: Config> ProjectServiceAccounts<'c, C> {
                     self.project_id
                 )
                 .as_str(),
+                &self.request_options,
             )
             .await
     }
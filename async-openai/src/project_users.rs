@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
         ProjectUser, ProjectUserCreateRequest, ProjectUserDeleteResponse, ProjectUserListResponse,
         ProjectUserUpdateRequest,
@@ -15,17 +16,24 @@ use crate::{
 pub struct ProjectUsers<'c, C: Config> {
     client: &'c Client<C>,
     pub project_id: String,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(ProjectUsers);
+crate::util::impl_api_group_debug!(ProjectUsers, "/organization/projects/{}/users", project_id);
+
 impl<'c, C: Config> ProjectUsers<'c, C> {
     pub fn new(client: &'c Client<C>, project_id: &str) -> Self {
         Self {
             client,
             project_id: project_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
     /// Returns a list of users in the project.
+    ///
+    /// `query` is usually a [ListProjectUsersQuery](crate::types::ListProjectUsersQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectUserListResponse, OpenAIError>
     where
@@ -35,6 +43,7 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
             .get_with_query(
                 format!("/organization/projects/{}/users", self.project_id).as_str(),
                 &query,
+                &self.request_options,
             )
             .await
     }
@@ -49,6 +58,7 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
             .post(
                 format!("/organization/projects/{}/users", self.project_id).as_str(),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -57,7 +67,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, user_id: &str) -> Result<ProjectUser, OpenAIError> {
         self.client
-            .get(format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str())
+            .get(
+                format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str(),
+                &self.request_options,
+            )
             .await
     }
 
@@ -72,6 +85,7 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
             .post(
                 format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str(),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -80,7 +94,10 @@ impl<'c, C: Config> ProjectUsers<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, user_id: &str) -> Result<ProjectUserDeleteResponse, OpenAIError> {
         self.client
-            .delete(format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str())
+            .delete(
+                format!("/organization/projects/{}/users/{user_id}", self.project_id).as_str(),
+                &self.request_options,
+            )
             .await
     }
 }
@@ -4,58 +4,79 @@ use crate::{
     config::Config,
     error::OpenAIError,
     project_api_keys::ProjectAPIKeys,
+    request_options::{impl_request_options, RequestOptions},
     types::{Project, ProjectCreateRequest, ProjectListResponse, ProjectUpdateRequest},
-    Client, ProjectServiceAccounts, ProjectUsers,
+    Client, ProjectCertificates, ProjectServiceAccounts, ProjectUsers,
 };
 
 /// Manage the projects within an organization includes creation, updating, and archiving or projects.
 /// The Default project cannot be modified or archived.
 pub struct Projects<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Projects);
+crate::util::impl_api_group_debug!(Projects, "/organization/projects");
+
 impl<'c, C: Config> Projects<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     // call [ProjectUsers] group APIs
     pub fn users(&self, project_id: &str) -> ProjectUsers<C> {
-        ProjectUsers::new(self.client, project_id)
+        ProjectUsers::new(self.client, project_id).with_options(self.request_options.clone())
     }
 
     // call [ProjectServiceAccounts] group APIs
     pub fn service_accounts(&self, project_id: &str) -> ProjectServiceAccounts<C> {
         ProjectServiceAccounts::new(self.client, project_id)
+            .with_options(self.request_options.clone())
     }
 
     // call [ProjectAPIKeys] group APIs
     pub fn api_keys(&self, project_id: &str) -> ProjectAPIKeys<C> {
-        ProjectAPIKeys::new(self.client, project_id)
+        ProjectAPIKeys::new(self.client, project_id).with_options(self.request_options.clone())
+    }
+
+    // call [ProjectCertificates] group APIs
+    pub fn certificates(&self, project_id: &str) -> ProjectCertificates<'_, C> {
+        ProjectCertificates::new(self.client, project_id).with_options(self.request_options.clone())
     }
 
     /// Returns a list of projects.
+    ///
+    /// `query` is usually a [ListProjectsQuery](crate::types::ListProjectsQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ProjectListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query("/organization/projects", &query)
+            .get_with_query("/organization/projects", &query, &self.request_options)
             .await
     }
 
     /// Create a new project in the organization. Projects can be created and archived, but cannot be deleted.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: ProjectCreateRequest) -> Result<Project, OpenAIError> {
-        self.client.post("/organization/projects", request).await
+        self.client
+            .post("/organization/projects", request, &self.request_options)
+            .await
     }
 
     /// Retrieves a project.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, project_id: String) -> Result<Project, OpenAIError> {
         self.client
-            .get(format!("/organization/projects/{project_id}").as_str())
+            .get(
+                format!("/organization/projects/{project_id}").as_str(),
+                &self.request_options,
+            )
             .await
     }
 
@@ -70,6 +91,7 @@ impl<'c, C: Config> Projects<'c, C> {
             .post(
                 format!("/organization/projects/{project_id}").as_str(),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -81,6 +103,7 @@ impl<'c, C: Config> Projects<'c, C> {
             .post(
                 format!("/organization/projects/{project_id}/archive").as_str(),
                 (),
+                &self.request_options,
             )
             .await
     }
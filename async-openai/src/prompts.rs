@@ -0,0 +1,264 @@
+//! Prompt templates with `{variable}` substitution, rendered into chat messages.
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionRequestAssistantMessage, ChatCompletionRequestDeveloperMessage,
+        ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs,
+    },
+};
+
+/// A prompt of system, developer, user and assistant sections, each rendered into a message.
+///
+/// In the text of a section, `{name}` is replaced by the value of the variable `name`, where
+/// the name is made of ASCII letters, digits and underscores and does not start with a digit.
+/// `{{` and `}}` are a literal `{` and `}`, and other braces, e.g. of JSON examples, are kept
+/// as they are. Values are inserted verbatim: braces in a value are never substituted.
+///
+/// ```
+/// use async_openai::{prompts::PromptTemplate, types::CreateChatCompletionRequestArgs};
+///
+/// let template = PromptTemplate::new()
+///     .system("You translate to {language}. Answer with {{\"text\": ...}}.")
+///     .user("{text}");
+/// assert_eq!(template.variables(), ["language", "text"]);
+///
+/// let request = CreateChatCompletionRequestArgs::from_template(
+///     &template,
+///     [("language", "French"), ("text", "Hello {world}")],
+/// )?
+/// .model("gpt-4o-mini")
+/// .build()?;
+/// assert_eq!(request.messages.len(), 2);
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PromptTemplate {
+    sections: Vec<Section>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Section {
+    role: SectionRole,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum SectionRole {
+    System,
+    Developer,
+    User,
+    Assistant,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Text(String),
+    Variable(String),
+}
+
+impl PromptTemplate {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a system section.
+    pub fn system(self, template: impl AsRef<str>) -> Self {
+        self.section(SectionRole::System, template.as_ref())
+    }
+
+    /// Append a developer section, the system instructions of reasoning models.
+    pub fn developer(self, template: impl AsRef<str>) -> Self {
+        self.section(SectionRole::Developer, template.as_ref())
+    }
+
+    /// Append a user section.
+    pub fn user(self, template: impl AsRef<str>) -> Self {
+        self.section(SectionRole::User, template.as_ref())
+    }
+
+    /// Append an assistant section, e.g. of a few-shot example.
+    pub fn assistant(self, template: impl AsRef<str>) -> Self {
+        self.section(SectionRole::Assistant, template.as_ref())
+    }
+
+    /// Append the sections of `other`, e.g. to share a system prompt between templates.
+    pub fn extend(mut self, other: &PromptTemplate) -> Self {
+        self.sections.extend(other.sections.iter().cloned());
+        self
+    }
+
+    fn section(mut self, role: SectionRole, template: &str) -> Self {
+        self.sections.push(Section {
+            role,
+            segments: parse(template),
+        });
+        self
+    }
+
+    /// Names of the variables of the template, sorted and without duplicates.
+    pub fn variables(&self) -> Vec<&str> {
+        self.variable_names().into_iter().collect()
+    }
+
+    fn variable_names(&self) -> BTreeSet<&str> {
+        self.sections
+            .iter()
+            .flat_map(|section| &section.segments)
+            .filter_map(|segment| match segment {
+                Segment::Variable(name) => Some(name.as_str()),
+                Segment::Text(_) => None,
+            })
+            .collect()
+    }
+
+    /// Messages of the sections with the variables replaced by their value in `vars`.
+    ///
+    /// Fails with [OpenAIError::InvalidArgument] naming the variables missing from `vars`;
+    /// variables not in the template are ignored.
+    pub fn render<K, V>(
+        &self,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Vec<ChatCompletionRequestMessage>, OpenAIError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+    {
+        let vars: HashMap<String, String> = vars
+            .into_iter()
+            .map(|(name, value)| (name.as_ref().to_string(), value.to_string()))
+            .collect();
+
+        let missing: Vec<&str> = self
+            .variable_names()
+            .into_iter()
+            .filter(|name| !vars.contains_key(*name))
+            .collect();
+        if !missing.is_empty() {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "missing prompt template variables: {}",
+                missing.join(", ")
+            )));
+        }
+
+        Ok(self
+            .sections
+            .iter()
+            .map(|section| {
+                let text: String = section
+                    .segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Text(text) => text.as_str(),
+                        Segment::Variable(name) => vars[name].as_str(),
+                    })
+                    .collect();
+                match section.role {
+                    SectionRole::System => ChatCompletionRequestSystemMessage::from(text).into(),
+                    SectionRole::Developer => {
+                        ChatCompletionRequestDeveloperMessage::from(text).into()
+                    }
+                    SectionRole::User => ChatCompletionRequestUserMessage::from(text).into(),
+                    SectionRole::Assistant => {
+                        ChatCompletionRequestAssistantMessage::from(text).into()
+                    }
+                }
+            })
+            .collect())
+    }
+}
+
+/// Split `template` into text and variables.
+fn parse(template: &str) -> Vec<Segment> {
+    let mut segments = Vec::new();
+    let mut text = String::new();
+    let mut rest = template;
+    while let Some(index) = rest.find(['{', '}']) {
+        text.push_str(&rest[..index]);
+        let brace = &rest[index..index + 1];
+        rest = &rest[index + 1..];
+        if rest.starts_with(brace) {
+            // escaped brace
+            text.push_str(brace);
+            rest = &rest[1..];
+        } else if let Some(name) = (brace == "{")
+            .then(|| rest.split_once('}'))
+            .flatten()
+            .map(|(name, _)| name)
+            .filter(|name| is_variable_name(name))
+        {
+            if !text.is_empty() {
+                segments.push(Segment::Text(std::mem::take(&mut text)));
+            }
+            segments.push(Segment::Variable(name.to_string()));
+            rest = &rest[name.len() + 1..];
+        } else {
+            text.push_str(brace);
+        }
+    }
+    text.push_str(rest);
+    if !text.is_empty() {
+        segments.push(Segment::Text(text));
+    }
+    segments
+}
+
+fn is_variable_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_ascii_alphabetic() || first == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+impl CreateChatCompletionRequestArgs {
+    /// Builder with the messages of `template` rendered with `vars`, see [PromptTemplate::render].
+    pub fn from_template<K, V>(
+        template: &PromptTemplate,
+        vars: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<Self, OpenAIError>
+    where
+        K: AsRef<str>,
+        V: ToString,
+    {
+        let mut args = Self::default();
+        args.messages(template.render(vars)?);
+        Ok(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_prompt_template() {
+        let template = PromptTemplate::new()
+            .developer("Reply as {{\"answer\": ...}} in {language}, e.g. { \"answer\": 1 }.")
+            .user("{question} {not a variable} {}")
+            .assistant("{language}");
+        assert_eq!(template.variables(), ["language", "question"]);
+
+        let messages = template
+            .render([("language", "JSON"), ("question", "{language}?")])
+            .unwrap();
+        assert_eq!(
+            messages,
+            [
+                ChatCompletionRequestDeveloperMessage::from(
+                    "Reply as {\"answer\": ...} in JSON, e.g. { \"answer\": 1 }."
+                )
+                .into(),
+                ChatCompletionRequestUserMessage::from("{language}? {not a variable} {}").into(),
+                ChatCompletionRequestAssistantMessage::from("JSON").into(),
+            ]
+        );
+
+        let error = template.render([("question", "?")]).unwrap_err();
+        assert!(
+            matches!(error, OpenAIError::InvalidArgument(message) if message.ends_with(": language"))
+        );
+    }
+}
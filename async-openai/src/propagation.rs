@@ -0,0 +1,263 @@
+//! Propagation of the trace context of the application to the API, so that traces of a
+//! gateway or proxy in front of the API correlate with the spans of the application.
+//!
+//! A [TracePropagator] set with [crate::Client::with_trace_propagator] adds its headers to every
+//! request, headers set with [crate::request_options::RequestOptions] taking precedence.
+use std::{fmt, sync::Arc};
+
+use reqwest::header::{HeaderMap, HeaderValue};
+
+/// Header of the W3C trace context carrying the trace id, parent span id and flags.
+pub const TRACEPARENT: &str = "traceparent";
+/// Header of the W3C trace context carrying vendor specific trace state.
+pub const TRACESTATE: &str = "tracestate";
+
+/// Adds the headers of the current trace context to outgoing requests.
+///
+/// Closures taking the headers of the request implement this trait, for custom header schemes:
+///
+/// ```
+/// use async_openai::Client;
+/// use reqwest::header::{HeaderMap, HeaderValue};
+///
+/// let client = Client::new().with_trace_propagator(|headers: &mut HeaderMap| {
+///     if let Some(span_id) = tracing::Span::current().id() {
+///         let value = format!("{:016x}", span_id.into_u64());
+///         headers.insert("x-request-span", HeaderValue::from_str(&value).unwrap());
+///     }
+/// });
+/// ```
+pub trait TracePropagator: Send + Sync {
+    /// Insert the headers of the current trace context in `headers`.
+    fn inject(&self, headers: &mut HeaderMap);
+}
+
+impl<F> TracePropagator for F
+where
+    F: Fn(&mut HeaderMap) + Send + Sync,
+{
+    fn inject(&self, headers: &mut HeaderMap) {
+        self(headers)
+    }
+}
+
+/// A [W3C trace context](https://www.w3.org/TR/trace-context/).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceContext {
+    trace_id: u128,
+    span_id: u64,
+    sampled: bool,
+    trace_state: Option<String>,
+}
+
+impl TraceContext {
+    /// Sampled context of the span `span_id` in the trace `trace_id`.
+    pub fn new(trace_id: u128, span_id: u64) -> Self {
+        Self {
+            trace_id,
+            span_id,
+            sampled: true,
+            trace_state: None,
+        }
+    }
+
+    pub fn with_sampled(mut self, sampled: bool) -> Self {
+        self.sampled = sampled;
+        self
+    }
+
+    /// Vendor specific `tracestate`, e.g. `vendor1=value1,vendor2=value2`.
+    pub fn with_trace_state<S: Into<String>>(mut self, trace_state: S) -> Self {
+        let trace_state = trace_state.into();
+        self.trace_state = (!trace_state.is_empty()).then_some(trace_state);
+        self
+    }
+
+    /// Parse the values of the `traceparent` and `tracestate` headers,
+    /// `None` if `traceparent` is not a valid version `00` header.
+    pub fn parse(traceparent: &str, tracestate: Option<&str>) -> Option<Self> {
+        let mut parts = traceparent.trim().split('-');
+        let (Some("00"), Some(trace_id), Some(span_id), Some(flags), None) = (
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+            parts.next(),
+        ) else {
+            return None;
+        };
+        if trace_id.len() != 32 || span_id.len() != 16 || flags.len() != 2 {
+            return None;
+        }
+        let trace_id = u128::from_str_radix(trace_id, 16).ok()?;
+        let span_id = u64::from_str_radix(span_id, 16).ok()?;
+        let flags = u8::from_str_radix(flags, 16).ok()?;
+        // all zero ids are invalid
+        if trace_id == 0 || span_id == 0 {
+            return None;
+        }
+
+        let context = Self::new(trace_id, span_id).with_sampled(flags & 1 == 1);
+        Some(match tracestate {
+            Some(tracestate) => context.with_trace_state(tracestate.trim()),
+            None => context,
+        })
+    }
+
+    pub fn trace_id(&self) -> u128 {
+        self.trace_id
+    }
+
+    pub fn span_id(&self) -> u64 {
+        self.span_id
+    }
+
+    pub fn sampled(&self) -> bool {
+        self.sampled
+    }
+
+    pub fn trace_state(&self) -> Option<&str> {
+        self.trace_state.as_deref()
+    }
+
+    /// Value of the `traceparent` header.
+    pub fn traceparent(&self) -> String {
+        format!(
+            "00-{:032x}-{:016x}-{:02x}",
+            self.trace_id, self.span_id, self.sampled as u8
+        )
+    }
+
+    /// Insert the `traceparent` and `tracestate` headers in `headers`.
+    pub fn inject(&self, headers: &mut HeaderMap) {
+        if let Ok(value) = HeaderValue::from_str(&self.traceparent()) {
+            headers.insert(TRACEPARENT, value);
+        }
+        match self
+            .trace_state
+            .as_deref()
+            .and_then(|state| HeaderValue::from_str(state).ok())
+        {
+            Some(value) => headers.insert(TRACESTATE, value),
+            None => headers.remove(TRACESTATE),
+        };
+    }
+}
+
+type CurrentContext = dyn Fn() -> Option<TraceContext> + Send + Sync + 'static;
+
+/// [TracePropagator] sending the W3C `traceparent` and `tracestate` headers of the
+/// [TraceContext] returned by a function, no headers being sent when it returns `None`.
+///
+/// With OpenTelemetry and `tracing-opentelemetry`, the context of the current span is:
+///
+/// ```ignore
+/// use async_openai::{
+///     propagation::{TraceContext, W3CTraceContext},
+///     Client,
+/// };
+/// use opentelemetry::trace::TraceContextExt;
+/// use tracing_opentelemetry::OpenTelemetrySpanExt;
+///
+/// let client = Client::new().with_trace_propagator(W3CTraceContext::new(|| {
+///     let context = tracing::Span::current().context();
+///     let span = context.span();
+///     let span_context = span.span_context();
+///     span_context.is_valid().then(|| {
+///         TraceContext::new(
+///             u128::from_be_bytes(span_context.trace_id().to_bytes()),
+///             u64::from_be_bytes(span_context.span_id().to_bytes()),
+///         )
+///         .with_sampled(span_context.is_sampled())
+///         .with_trace_state(span_context.trace_state().header())
+///     })
+/// }));
+/// ```
+#[derive(Clone)]
+pub struct W3CTraceContext {
+    current: Arc<CurrentContext>,
+}
+
+impl W3CTraceContext {
+    pub fn new<F>(current: F) -> Self
+    where
+        F: Fn() -> Option<TraceContext> + Send + Sync + 'static,
+    {
+        Self {
+            current: Arc::new(current),
+        }
+    }
+}
+
+impl TracePropagator for W3CTraceContext {
+    fn inject(&self, headers: &mut HeaderMap) {
+        if let Some(context) = (self.current)() {
+            context.inject(headers);
+        }
+    }
+}
+
+impl fmt::Debug for W3CTraceContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("W3CTraceContext").finish_non_exhaustive()
+    }
+}
+
+/// Trace propagator of a [crate::Client].
+#[derive(Clone)]
+pub(crate) struct Propagation(Arc<dyn TracePropagator>);
+
+impl Propagation {
+    pub(crate) fn new(propagator: Arc<dyn TracePropagator>) -> Self {
+        Self(propagator)
+    }
+
+    /// Add the trace headers to `headers`, keeping the headers already set.
+    pub(crate) fn apply(&self, headers: &mut HeaderMap) {
+        let mut trace = HeaderMap::new();
+        self.0.inject(&mut trace);
+        for (name, value) in trace.iter() {
+            if !headers.contains_key(name) {
+                headers.insert(name.clone(), value.clone());
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Propagation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Propagation").finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trace_context_headers() {
+        let traceparent = "00-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01";
+        let context = TraceContext::parse(traceparent, Some("congo=t61rcWkgMzE")).unwrap();
+        assert_eq!(context.traceparent(), traceparent);
+        assert!(context.sampled());
+        assert!(TraceContext::parse(
+            "00-00000000000000000000000000000000-00f067aa0ba902b7-01",
+            None
+        )
+        .is_none());
+        assert!(TraceContext::parse(
+            "01-4bf92f3577b34da6a3ce929d0e0e4736-00f067aa0ba902b7-01",
+            None
+        )
+        .is_none());
+
+        let propagation = Propagation::new(Arc::new(W3CTraceContext::new(move || {
+            Some(context.clone())
+        })));
+        let mut headers = HeaderMap::new();
+        headers.insert(TRACESTATE, HeaderValue::from_static("set=by-caller"));
+        propagation.apply(&mut headers);
+        assert_eq!(headers[TRACEPARENT], traceparent);
+        assert_eq!(headers[TRACESTATE], "set=by-caller");
+    }
+}
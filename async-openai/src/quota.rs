@@ -0,0 +1,637 @@
+//! Client side per-tenant request and token limits, for services sharing one API key between tenants.
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use serde::Deserialize;
+
+use crate::{
+    error::{OpenAIError, QuotaExceeded, QuotaLimit},
+    request_options::RequestOptions,
+    types::CompletionUsage,
+};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+/// Default of [TenantQuotas::with_max_tenants].
+const MAX_TENANTS: usize = 10_000;
+
+/// Requests and tokens per minute allowed for a tenant, unset limits are not enforced.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantLimits {
+    rpm: Option<u32>,
+    tpm: Option<u32>,
+}
+
+impl TenantLimits {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum requests per minute.
+    pub fn with_rpm(mut self, rpm: u32) -> Self {
+        self.rpm = Some(rpm);
+        self
+    }
+
+    /// Maximum tokens per minute, counting the estimates of the accepted requests until their
+    /// usage is recorded.
+    pub fn with_tpm(mut self, tpm: u32) -> Self {
+        self.tpm = Some(tpm);
+        self
+    }
+
+    pub fn rpm(&self) -> Option<u32> {
+        self.rpm
+    }
+
+    pub fn tpm(&self) -> Option<u32> {
+        self.tpm
+    }
+}
+
+/// Usage of a tenant since it was first seen, or last forgotten for being idle, see
+/// [TenantQuotas::with_max_tenants].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct TenantUsage {
+    /// Requests accepted by [TenantQuotas::acquire].
+    pub requests: u64,
+    /// Tokens recorded with [QuotaPermit::record] or [TenantQuotas::record_usage], and the
+    /// estimates of the requests whose usage was not recorded.
+    pub total_tokens: u64,
+}
+
+/// Tokens counted in the window at `at`, reserved by the permit `permit` until it is recorded.
+#[derive(Debug)]
+struct Tokens {
+    at: Instant,
+    tokens: u32,
+    permit: Option<u64>,
+}
+
+#[derive(Debug, Default)]
+struct TenantState {
+    limits: Option<TenantLimits>,
+    requests: VecDeque<Instant>,
+    tokens: VecDeque<Tokens>,
+    usage: TenantUsage,
+}
+
+#[derive(Debug, Default)]
+struct Tenants {
+    states: HashMap<String, TenantState>,
+    next_permit: u64,
+}
+
+impl TenantState {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .requests
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= WINDOW)
+        {
+            self.requests.pop_front();
+        }
+        while self
+            .tokens
+            .front()
+            .is_some_and(|tokens| now.duration_since(tokens.at) >= WINDOW)
+        {
+            self.tokens.pop_front();
+        }
+    }
+
+    /// Nothing in the window and no limits of its own, so forgetting it changes no decision.
+    fn is_idle(&self) -> bool {
+        self.limits.is_none() && self.requests.is_empty() && self.tokens.is_empty()
+    }
+
+    fn tokens_in_window(&self) -> u64 {
+        self.tokens.iter().map(|tokens| tokens.tokens as u64).sum()
+    }
+
+    /// Time until the window used `excess` fewer tokens.
+    fn tokens_retry_after(&self, now: Instant, excess: u64) -> Duration {
+        let mut freed = 0;
+        for tokens in &self.tokens {
+            freed += tokens.tokens as u64;
+            if freed >= excess {
+                return WINDOW.saturating_sub(now.duration_since(tokens.at));
+            }
+        }
+        WINDOW
+    }
+}
+
+impl Tenants {
+    /// State of `tenant`, forgetting the idle tenants first if `max_tenants` are tracked.
+    fn state(&mut self, tenant: &str, now: Instant, max_tenants: usize) -> &mut TenantState {
+        if !self.states.contains_key(tenant) && self.states.len() >= max_tenants {
+            self.states.retain(|_, state| {
+                state.prune(now);
+                !state.is_idle()
+            });
+        }
+        let state = self.states.entry(tenant.to_string()).or_default();
+        state.prune(now);
+        state
+    }
+}
+
+/// Tracks usage of each tenant over a sliding one minute window and rejects requests
+/// exceeding their limits with [OpenAIError::QuotaExceeded] before they are sent.
+///
+/// Set on a client with [crate::Client::with_tenant_quotas], the calls made with
+/// [RequestOptions::tenant] are counted, and their usage recorded, by the client. Otherwise
+/// [TenantQuotas::acquire] a permit before each request and record its usage on the permit.
+///
+/// Clones share the same state.
+///
+/// ```
+/// use async_openai::quota::{TenantLimits, TenantQuotas};
+///
+/// let quotas = TenantQuotas::new(TenantLimits::new().with_rpm(60).with_tpm(40_000))
+///     .with_tenant_limits("enterprise", TenantLimits::new().with_tpm(1_000_000));
+///
+/// let permit = quotas.acquire("acme", 500)?;
+/// // ... send the request, then report the tokens it actually used:
+/// permit.record(620);
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct TenantQuotas {
+    default_limits: TenantLimits,
+    max_tenants: usize,
+    tenants: Arc<Mutex<Tenants>>,
+}
+
+impl Default for TenantQuotas {
+    fn default() -> Self {
+        Self::new(TenantLimits::default())
+    }
+}
+
+impl TenantQuotas {
+    /// Quotas applying `default_limits` to tenants without their own limits.
+    pub fn new(default_limits: TenantLimits) -> Self {
+        Self {
+            default_limits,
+            max_tenants: MAX_TENANTS,
+            tenants: Default::default(),
+        }
+    }
+
+    /// Once `max_tenants` tenants are tracked, 10 000 by default, forget the tenants without
+    /// requests or tokens in the window and without limits of their own, along with their
+    /// usage, before tracking a new one.
+    pub fn with_max_tenants(mut self, max_tenants: usize) -> Self {
+        self.max_tenants = max_tenants;
+        self
+    }
+
+    /// Override the limits of `tenant`.
+    pub fn with_tenant_limits<S: Into<String>>(self, tenant: S, limits: TenantLimits) -> Self {
+        self.set_tenant_limits(tenant, limits);
+        self
+    }
+
+    /// Override the limits of `tenant`, taking effect for the next request.
+    pub fn set_tenant_limits<S: Into<String>>(&self, tenant: S, limits: TenantLimits) {
+        self.lock().states.entry(tenant.into()).or_default().limits = Some(limits);
+    }
+
+    /// Limits applying to `tenant`.
+    pub fn limits(&self, tenant: &str) -> TenantLimits {
+        self.lock()
+            .states
+            .get(tenant)
+            .and_then(|state| state.limits)
+            .unwrap_or(self.default_limits)
+    }
+
+    /// Count a request of `tenant` expected to use `estimated_tokens`, or fail with
+    /// [OpenAIError::QuotaExceeded] if it would exceed one of the tenant limits.
+    ///
+    /// The estimate is counted until the returned permit records the tokens the request actually
+    /// used, so that requests in flight are accounted for. A permit dropped without recording,
+    /// e.g. of a failed request, keeps its estimate counted.
+    ///
+    /// A request whose estimate alone exceeds the tokens per minute of the tenant could never be
+    /// accepted, it fails with [OpenAIError::InvalidArgument] instead.
+    pub fn acquire(&self, tenant: &str, estimated_tokens: u32) -> Result<QuotaPermit, OpenAIError> {
+        let now = Instant::now();
+        let mut tenants = self.lock();
+        let permit = tenants.next_permit;
+        let state = tenants.state(tenant, now, self.max_tenants);
+        let limits = state.limits.unwrap_or(self.default_limits);
+
+        if let Some(tpm) = limits.tpm.filter(|tpm| estimated_tokens > *tpm) {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "request of {estimated_tokens} estimated tokens exceeds the {tpm} tokens per minute of tenant {tenant}"
+            )));
+        }
+
+        if let Some(rpm) = limits.rpm {
+            if state.requests.len() >= rpm as usize {
+                let retry_after = state
+                    .requests
+                    .front()
+                    .map(|at| WINDOW.saturating_sub(now.duration_since(*at)))
+                    .unwrap_or(WINDOW);
+                return Err(OpenAIError::QuotaExceeded(QuotaExceeded {
+                    tenant: tenant.to_string(),
+                    limit: QuotaLimit::Requests,
+                    max: rpm,
+                    retry_after,
+                }));
+            }
+        }
+
+        if let Some(tpm) = limits.tpm {
+            let used = state.tokens_in_window() + estimated_tokens as u64;
+            if used > tpm as u64 {
+                return Err(OpenAIError::QuotaExceeded(QuotaExceeded {
+                    tenant: tenant.to_string(),
+                    limit: QuotaLimit::Tokens,
+                    max: tpm,
+                    retry_after: state.tokens_retry_after(now, used - tpm as u64),
+                }));
+            }
+        }
+
+        state.requests.push_back(now);
+        state.tokens.push_back(Tokens {
+            at: now,
+            tokens: estimated_tokens,
+            permit: Some(permit),
+        });
+        state.usage.requests += 1;
+        state.usage.total_tokens += estimated_tokens as u64;
+        tenants.next_permit += 1;
+        Ok(QuotaPermit {
+            quotas: self.clone(),
+            tenant: tenant.to_string(),
+            id: permit,
+            estimated_tokens,
+        })
+    }
+
+    /// Report tokens used by `tenant` outside of a [QuotaPermit], e.g. by requests sent by
+    /// another service.
+    pub fn record_usage(&self, tenant: &str, tokens: u32) {
+        let now = Instant::now();
+        let mut tenants = self.lock();
+        let state = tenants.state(tenant, now, self.max_tenants);
+        state.tokens.push_back(Tokens {
+            at: now,
+            tokens,
+            permit: None,
+        });
+        state.usage.total_tokens += tokens as u64;
+    }
+
+    /// Report the usage of a chat or completion response of `tenant` outside of a [QuotaPermit].
+    pub fn record_completion_usage(&self, tenant: &str, usage: &CompletionUsage) {
+        self.record_usage(tenant, usage.total_tokens);
+    }
+
+    /// Usage of `tenant`.
+    pub fn usage(&self, tenant: &str) -> TenantUsage {
+        self.lock()
+            .states
+            .get(tenant)
+            .map(|state| state.usage)
+            .unwrap_or_default()
+    }
+
+    /// Forget the usage and limits of `tenant`.
+    pub fn remove_tenant(&self, tenant: &str) {
+        self.lock().states.remove(tenant);
+    }
+
+    /// Number of tracked tenants.
+    pub fn tenants(&self) -> usize {
+        self.lock().states.len()
+    }
+
+    /// Replace the estimate of `permit` by the `tokens` the request used, counted from now.
+    fn record(&self, permit: &QuotaPermit, tokens: u32) {
+        let now = Instant::now();
+        let mut tenants = self.lock();
+        let state = tenants.state(&permit.tenant, now, self.max_tenants);
+        state
+            .tokens
+            .retain(|reserved| reserved.permit != Some(permit.id));
+        state.tokens.push_back(Tokens {
+            at: now,
+            tokens,
+            permit: None,
+        });
+        state.usage.total_tokens = (state.usage.total_tokens + tokens as u64)
+            .saturating_sub(permit.estimated_tokens as u64);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Tenants> {
+        // usage accounting stays consistent even if a thread panicked while holding the lock
+        self.tenants
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// Request of a tenant accepted by [TenantQuotas::acquire], counting its estimated tokens
+/// until it records the tokens the request actually used.
+#[derive(Debug)]
+pub struct QuotaPermit {
+    quotas: TenantQuotas,
+    tenant: String,
+    id: u64,
+    estimated_tokens: u32,
+}
+
+impl QuotaPermit {
+    pub fn tenant(&self) -> &str {
+        &self.tenant
+    }
+
+    pub fn estimated_tokens(&self) -> u32 {
+        self.estimated_tokens
+    }
+
+    /// Count the `tokens` the request used instead of its estimate, usually the `total_tokens`
+    /// of the response usage.
+    pub fn record(self, tokens: u32) {
+        self.quotas.record(&self, tokens);
+    }
+
+    /// Count the usage of a chat or completion response instead of the estimate.
+    pub fn record_completion_usage(self, usage: &CompletionUsage) {
+        self.record(usage.total_tokens);
+    }
+}
+
+#[derive(Deserialize)]
+struct ResponseUsage {
+    usage: Option<TotalTokens>,
+}
+
+#[derive(Deserialize)]
+struct TotalTokens {
+    total_tokens: u32,
+}
+
+/// Quota permit of a call of [crate::Client] made for a tenant, acquired when the first request
+/// of the call is built, retries counting as one request.
+pub(crate) struct CallQuota<'a> {
+    tenant: Option<(&'a TenantQuotas, &'a str)>,
+    permit: Mutex<Option<QuotaPermit>>,
+}
+
+impl<'a> CallQuota<'a> {
+    pub(crate) fn new(
+        quotas: Option<&'a TenantQuotas>,
+        request_options: &'a RequestOptions,
+    ) -> Self {
+        Self {
+            tenant: quotas.zip(request_options.get_tenant()),
+            permit: Mutex::new(None),
+        }
+    }
+
+    /// Acquire the permit of the call with the tokens estimated from `request`, once.
+    pub(crate) fn acquire(&self, request: &reqwest::Request) -> Result<(), OpenAIError> {
+        let Some((quotas, tenant)) = self.tenant else {
+            return Ok(());
+        };
+        let mut permit = self.permit.lock().unwrap_or_else(|e| e.into_inner());
+        if permit.is_none() {
+            let estimate = crate::rate_limit::estimate_tokens(
+                request.body().and_then(reqwest::Body::as_bytes),
+            );
+            *permit = Some(quotas.acquire(tenant, estimate)?);
+        }
+        Ok(())
+    }
+
+    /// Record the `usage.total_tokens` of the JSON `response`, if any, keeping the estimate
+    /// otherwise.
+    pub(crate) fn record(self, response: &[u8]) {
+        let permit = self.permit.into_inner().unwrap_or_else(|e| e.into_inner());
+        let usage = serde_json::from_slice::<ResponseUsage>(response)
+            .ok()
+            .and_then(|response| response.usage);
+        if let (Some(permit), Some(usage)) = (permit, usage) {
+            permit.record(usage.total_tokens);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tenant_limits() {
+        let quotas = TenantQuotas::new(TenantLimits::new().with_rpm(2))
+            .with_tenant_limits("small", TenantLimits::new().with_tpm(100));
+
+        quotas.acquire("acme", 0).unwrap();
+        quotas.acquire("acme", 0).unwrap();
+        let Err(OpenAIError::QuotaExceeded(exceeded)) = quotas.acquire("acme", 0) else {
+            panic!("expected rpm to be exceeded");
+        };
+        assert_eq!(exceeded.limit, QuotaLimit::Requests);
+        assert!(exceeded.retry_after <= WINDOW);
+        // other tenants are not affected
+        quotas.acquire("other", 0).unwrap();
+
+        quotas.acquire("small", 60).unwrap().record(90);
+        let Err(OpenAIError::QuotaExceeded(exceeded)) = quotas.acquire("small", 20) else {
+            panic!("expected tpm to be exceeded");
+        };
+        assert_eq!(exceeded.limit, QuotaLimit::Tokens);
+        assert_eq!(
+            quotas.usage("small"),
+            TenantUsage {
+                requests: 1,
+                total_tokens: 90
+            }
+        );
+    }
+
+    #[test]
+    fn test_in_flight_requests_count_their_estimate() {
+        let quotas = TenantQuotas::new(TenantLimits::new().with_tpm(100));
+
+        let first = quotas.acquire("acme", 60).unwrap();
+        let Err(OpenAIError::QuotaExceeded(exceeded)) = quotas.acquire("acme", 60) else {
+            panic!("expected the estimate of the request in flight to be counted");
+        };
+        assert_eq!(exceeded.limit, QuotaLimit::Tokens);
+        assert_eq!(exceeded.max, 100);
+
+        // recording replaces the estimate
+        first.record(20);
+        let second = quotas.acquire("acme", 60).unwrap();
+        assert_eq!(quotas.usage("acme").total_tokens, 80);
+
+        // a dropped permit, e.g. of a failed request, keeps its estimate
+        drop(second);
+        assert!(quotas.acquire("acme", 30).is_err());
+        assert_eq!(
+            quotas.usage("acme"),
+            TenantUsage {
+                requests: 2,
+                total_tokens: 80
+            }
+        );
+    }
+
+    #[test]
+    fn test_request_over_tpm_is_invalid() {
+        let quotas = TenantQuotas::new(TenantLimits::new().with_tpm(100));
+
+        let Err(OpenAIError::InvalidArgument(message)) = quotas.acquire("acme", 101) else {
+            panic!("expected a request over the tpm to be rejected as invalid");
+        };
+        assert!(message.contains("acme"), "{message}");
+        assert_eq!(quotas.usage("acme"), TenantUsage::default());
+        quotas.acquire("acme", 100).unwrap();
+    }
+
+    #[test]
+    fn test_completion_usage() {
+        let quotas = TenantQuotas::default();
+        let usage = CompletionUsage {
+            prompt_tokens: 10,
+            completion_tokens: 5,
+            total_tokens: 15,
+            prompt_tokens_details: None,
+            completion_tokens_details: None,
+        };
+
+        quotas
+            .acquire("acme", 100)
+            .unwrap()
+            .record_completion_usage(&usage);
+        quotas.record_completion_usage("acme", &usage);
+        assert_eq!(
+            quotas.usage("acme"),
+            TenantUsage {
+                requests: 1,
+                total_tokens: 30
+            }
+        );
+    }
+
+    #[test]
+    fn test_idle_tenants_are_evicted() {
+        let quotas = TenantQuotas::new(TenantLimits::new().with_rpm(10))
+            .with_tenant_limits("enterprise", TenantLimits::new().with_rpm(100))
+            .with_max_tenants(3);
+
+        quotas.acquire("acme", 0).unwrap();
+        quotas.acquire("globex", 0).unwrap();
+        assert_eq!(quotas.tenants(), 3);
+
+        // tenants with requests in the window or limits of their own are kept
+        quotas.acquire("initech", 0).unwrap();
+        assert_eq!(quotas.tenants(), 4);
+        assert_eq!(quotas.usage("acme").requests, 1);
+
+        // once their window is empty, tenants without limits are forgotten
+        {
+            let mut tenants = quotas.lock();
+            for state in tenants.states.values_mut() {
+                state.requests.clear();
+                state.tokens.clear();
+            }
+        }
+        quotas.acquire("umbrella", 0).unwrap();
+        assert_eq!(quotas.tenants(), 2);
+        assert_eq!(quotas.usage("acme"), TenantUsage::default());
+        assert_eq!(quotas.limits("enterprise").rpm(), Some(100));
+    }
+
+    #[tokio::test]
+    async fn test_client_counts_calls_of_tenants() {
+        use crate::{
+            transport::mock::{MockResponse, MockTransport},
+            types::{CreateEmbeddingRequestArgs, CreateEmbeddingResponse},
+            Client,
+        };
+
+        // answers the first request only
+        let transport = MockTransport::new(|_, index| {
+            Ok(match index {
+                0 => MockResponse::json(serde_json::json!({
+                    "object": "list", "model": "text-embedding-3-small", "data": [],
+                    "usage": {"prompt_tokens": 40, "total_tokens": 40}
+                })),
+                _ => MockResponse::json(serde_json::json!({
+                    "error": {"message": "invalid input", "type": "invalid_request_error"}
+                }))
+                .status(400),
+            })
+        });
+        let quotas = TenantQuotas::new(TenantLimits::new().with_rpm(2).with_tpm(1_000));
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_tenant_quotas(quotas.clone());
+        let request = CreateEmbeddingRequestArgs::default()
+            .model("text-embedding-3-small")
+            .input("hello")
+            .build()
+            .unwrap();
+        let estimate =
+            crate::rate_limit::estimate_tokens(Some(&serde_json::to_vec(&request).unwrap()));
+
+        // the usage of the response replaces the estimate
+        let _: CreateEmbeddingResponse = client
+            .embeddings()
+            .tenant("acme")
+            .create(request.clone())
+            .await
+            .unwrap();
+        assert_eq!(quotas.usage("acme").total_tokens, 40);
+
+        // a failed call keeps its estimate
+        let error = client
+            .embeddings()
+            .tenant("acme")
+            .create(request.clone())
+            .await
+            .map(|_: CreateEmbeddingResponse| ())
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::ApiError(_)), "{error}");
+        assert_eq!(
+            quotas.usage("acme"),
+            TenantUsage {
+                requests: 2,
+                total_tokens: 40 + estimate as u64
+            }
+        );
+
+        // calls over the quota are not sent
+        let error = client
+            .embeddings()
+            .tenant("acme")
+            .create(request.clone())
+            .await
+            .map(|_: CreateEmbeddingResponse| ())
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::QuotaExceeded(_)), "{error}");
+        assert_eq!(transport.len(), 2);
+
+        // calls without a tenant are not counted
+        assert!(client
+            .embeddings()
+            .create(request)
+            .await
+            .map(|_: CreateEmbeddingResponse| ())
+            .is_err());
+        assert_eq!(quotas.tenants(), 1);
+    }
+}
@@ -0,0 +1,291 @@
+//! Client side rate limiting, so that bulk jobs wait for their budget instead of being rejected
+//! with `429 Too Many Requests`.
+use std::{
+    num::NonZeroU32,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use reqwest::header::HeaderMap;
+use serde::Deserialize;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+const WINDOW: Duration = Duration::from_secs(60);
+
+const LIMIT_REQUESTS: &str = "x-ratelimit-limit-requests";
+const LIMIT_TOKENS: &str = "x-ratelimit-limit-tokens";
+const REMAINING_REQUESTS: &str = "x-ratelimit-remaining-requests";
+const REMAINING_TOKENS: &str = "x-ratelimit-remaining-tokens";
+
+/// Requests per minute, tokens per minute and concurrent requests budgets of a [crate::Client],
+/// set with [crate::Client::with_rate_limiter].
+///
+/// Requests wait until the budgets allow them. Budgets which are not set are seeded and kept up
+/// to date from the `x-ratelimit-*` headers of the responses, so a limiter without budgets
+/// follows the limits of the API key. Tokens of a request are estimated from the size of its
+/// body and its maximum output tokens, as the API does.
+///
+/// Clones share the same budgets, to limit several clients using the same API key.
+///
+/// ```
+/// use std::num::NonZeroU32;
+///
+/// use async_openai::{rate_limit::RateLimiter, Client};
+///
+/// let limiter = RateLimiter::new()
+///     .with_rpm(NonZeroU32::new(3_000).unwrap())
+///     .with_tpm(NonZeroU32::new(1_000_000).unwrap())
+///     .with_max_concurrency(16);
+/// let client = Client::new().with_rate_limiter(limiter);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct RateLimiter {
+    state: Arc<Mutex<LimiterState>>,
+    concurrency: Option<Arc<Semaphore>>,
+}
+
+#[derive(Debug, Default)]
+struct LimiterState {
+    requests: Bucket,
+    tokens: Bucket,
+}
+
+/// Token bucket refilled continuously up to its limit over one minute.
+#[derive(Debug, Default)]
+struct Bucket {
+    /// Limit set by the user, taking precedence over the headers.
+    configured: Option<u32>,
+    /// Limit reported by the API.
+    reported: Option<u32>,
+    available: f64,
+    updated: Option<Instant>,
+}
+
+impl Bucket {
+    fn configured(limit: NonZeroU32) -> Self {
+        Self {
+            configured: Some(limit.get()),
+            available: limit.get() as f64,
+            ..Default::default()
+        }
+    }
+
+    fn limit(&self) -> Option<u32> {
+        self.configured.or(self.reported)
+    }
+
+    fn refill(&mut self, now: Instant) {
+        let Some(limit) = self.limit() else {
+            return;
+        };
+        let limit = limit as f64;
+        if let Some(updated) = self.updated {
+            let elapsed = now.duration_since(updated).as_secs_f64();
+            self.available = (self.available + elapsed * limit / WINDOW.as_secs_f64()).min(limit);
+        }
+        self.updated = Some(now);
+    }
+
+    /// Time to wait before `cost` is available, a cost above the limit waiting for a full bucket.
+    /// Limits are never 0, configured ones being [NonZeroU32] and see [Bucket::report].
+    fn wait(&self, cost: f64) -> Option<Duration> {
+        let limit = self.limit()? as f64;
+        let cost = cost.min(limit);
+        if self.available >= cost {
+            return None;
+        }
+        let seconds = (cost - self.available) * WINDOW.as_secs_f64() / limit;
+        Some(Duration::from_secs_f64(seconds))
+    }
+
+    fn take(&mut self, cost: f64) {
+        if self.limit().is_some() {
+            self.available -= cost;
+        }
+    }
+
+    /// Follow the limit and remaining budget reported by the API, ignoring a limit of 0 which
+    /// no request could wait for.
+    fn report(&mut self, limit: Option<u32>, remaining: Option<u32>, now: Instant) {
+        if let Some(limit) = limit.filter(|limit| *limit > 0) {
+            if self.limit().is_none() {
+                self.available = limit as f64;
+                self.updated = Some(now);
+            }
+            self.reported = Some(limit);
+        }
+        if let Some(remaining) = remaining {
+            self.refill(now);
+            // the API also counts requests of other clients using the key
+            self.available = self.available.min(remaining as f64);
+        }
+    }
+}
+
+/// Concurrency slot of a request, released when dropped.
+#[derive(Debug)]
+pub(crate) struct RatePermit {
+    _permit: Option<OwnedSemaphorePermit>,
+}
+
+impl RateLimiter {
+    /// A limiter following the limits reported by the API.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum requests per minute. Leave it unset for no limit, or to follow the limit of the
+    /// API key.
+    pub fn with_rpm(self, rpm: NonZeroU32) -> Self {
+        self.lock().requests = Bucket::configured(rpm);
+        self
+    }
+
+    /// Maximum tokens per minute. Leave it unset for no limit, or to follow the limit of the
+    /// API key.
+    pub fn with_tpm(self, tpm: NonZeroU32) -> Self {
+        self.lock().tokens = Bucket::configured(tpm);
+        self
+    }
+
+    /// Maximum requests in flight, streams counting until they end.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.concurrency = Some(Arc::new(Semaphore::new(max_concurrency)));
+        self
+    }
+
+    /// Requests per minute, configured or reported by the API.
+    pub fn rpm(&self) -> Option<u32> {
+        self.lock().requests.limit()
+    }
+
+    /// Tokens per minute, configured or reported by the API.
+    pub fn tpm(&self) -> Option<u32> {
+        self.lock().tokens.limit()
+    }
+
+    /// Wait for a concurrency slot and the budget of a request using `tokens`.
+    pub(crate) async fn acquire(&self, tokens: u32) -> RatePermit {
+        let permit = match &self.concurrency {
+            // the semaphore is never closed
+            Some(semaphore) => semaphore.clone().acquire_owned().await.ok(),
+            None => None,
+        };
+
+        loop {
+            let wait = {
+                let mut state = self.lock();
+                let now = Instant::now();
+                state.requests.refill(now);
+                state.tokens.refill(now);
+                let wait = state
+                    .requests
+                    .wait(1.0)
+                    .max(state.tokens.wait(tokens as f64));
+                if wait.is_none() {
+                    state.requests.take(1.0);
+                    state.tokens.take(tokens as f64);
+                }
+                wait
+            };
+            match wait {
+                Some(wait) => {
+                    tracing::debug!("rate limited, waiting {wait:?}");
+                    tokio::time::sleep(wait).await;
+                }
+                None => break,
+            }
+        }
+
+        RatePermit { _permit: permit }
+    }
+
+    /// Update the budgets from the `x-ratelimit-*` headers of a response.
+    pub(crate) fn update(&self, headers: &HeaderMap) {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.trim().parse::<u32>().ok())
+        };
+        let now = Instant::now();
+        let mut state = self.lock();
+        state
+            .requests
+            .report(header(LIMIT_REQUESTS), header(REMAINING_REQUESTS), now);
+        state
+            .tokens
+            .report(header(LIMIT_TOKENS), header(REMAINING_TOKENS), now);
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, LimiterState> {
+        // budgets stay usable even if a thread panicked while holding the lock
+        self.state
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct MaxTokens {
+    max_tokens: Option<u32>,
+    max_completion_tokens: Option<u32>,
+    max_output_tokens: Option<u32>,
+}
+
+/// Tokens counted by the API for a request with a JSON `body`: about four bytes per prompt token
+/// and the maximum output tokens.
+pub(crate) fn estimate_tokens(body: Option<&[u8]>) -> u32 {
+    let Some(body) = body else {
+        return 0;
+    };
+    let max_tokens = serde_json::from_slice::<MaxTokens>(body).unwrap_or_default();
+    let output = max_tokens
+        .max_completion_tokens
+        .or(max_tokens.max_output_tokens)
+        .or(max_tokens.max_tokens)
+        .unwrap_or(0);
+    (body.len() as u32).div_ceil(4).saturating_add(output)
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest::header::HeaderValue;
+
+    use super::*;
+
+    #[test]
+    fn test_rate_limiter_budgets() {
+        let limiter = RateLimiter::new().with_rpm(NonZeroU32::new(60).unwrap());
+        assert_eq!((limiter.rpm(), limiter.tpm()), (Some(60), None));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(LIMIT_REQUESTS, HeaderValue::from_static("10000"));
+        headers.insert(LIMIT_TOKENS, HeaderValue::from_static("2000000"));
+        headers.insert(REMAINING_TOKENS, HeaderValue::from_static("500"));
+        limiter.update(&headers);
+        // configured limits take precedence over the headers
+        assert_eq!((limiter.rpm(), limiter.tpm()), (Some(60), Some(2_000_000)));
+
+        let state = limiter.lock();
+        assert!(state.tokens.wait(400.0).is_none());
+        // 100 tokens short, refilled at 2M tokens per minute
+        let wait = state.tokens.wait(600.0).unwrap();
+        assert!(wait <= Duration::from_millis(4));
+
+        assert_eq!(
+            estimate_tokens(Some(br#"{"model":"gpt-4o","max_tokens":10}"#)),
+            19
+        );
+    }
+
+    #[test]
+    fn test_reported_zero_limit_is_ignored() {
+        let limiter = RateLimiter::new();
+        let mut headers = HeaderMap::new();
+        headers.insert(LIMIT_REQUESTS, HeaderValue::from_static("0"));
+        limiter.update(&headers);
+        assert_eq!(limiter.rpm(), None);
+        assert!(limiter.lock().requests.wait(1.0).is_none());
+    }
+}
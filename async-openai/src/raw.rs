@@ -0,0 +1,149 @@
+use bytes::Bytes;
+use reqwest::{header::HeaderMap, StatusCode};
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    failover::ServedBy,
+    request_options::{impl_request_options, RequestOptions},
+    Client,
+};
+
+/// Calls of any endpoint, including the ones this crate does not support yet, with the base
+/// url, headers, credentials and retries of the client, returning the response as is.
+///
+/// ```no_run
+/// use async_openai::Client;
+///
+/// # async fn example() -> Result<(), async_openai::error::OpenAIError> {
+/// let client = Client::new();
+/// let response = client
+///     .raw()
+///     .post("/chat/completions", serde_json::json!({"model": "gpt-4o-mini", "messages": []}))
+///     .await?;
+/// println!("{} {:?}", response.status, response.headers.get("x-request-id"));
+/// let body: serde_json::Value = response.json()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct Raw<'c, C: Config> {
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(Raw);
+crate::util::impl_api_group_debug!(Raw, "");
+
+impl<'c, C: Config> Raw<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// Make a GET request to `path`, with the query parameters of [Raw::query].
+    pub async fn get(&self, path: &str) -> Result<RawResponse, OpenAIError> {
+        let response = self
+            .client
+            .get_response(path, &self.request_options)
+            .await?;
+        RawResponse::read(response).await
+    }
+
+    /// Make a POST request to `path` with the JSON of `body`.
+    pub async fn post<I: Serialize>(
+        &self,
+        path: &str,
+        body: I,
+    ) -> Result<RawResponse, OpenAIError> {
+        let response = self
+            .client
+            .post_response(path, body, &self.request_options)
+            .await?;
+        RawResponse::read(response).await
+    }
+
+    /// Make a DELETE request to `path`.
+    pub async fn delete(&self, path: &str) -> Result<RawResponse, OpenAIError> {
+        let response = self
+            .client
+            .delete_response(path, &self.request_options)
+            .await?;
+        RawResponse::read(response).await
+    }
+}
+
+/// A successful response of [Raw], its body not deserialized.
+#[derive(Debug, Clone)]
+pub struct RawResponse {
+    pub status: StatusCode,
+    pub headers: HeaderMap,
+    pub body: Bytes,
+    /// Base url which served the request, among the api base, the fallback api bases and the
+    /// [crate::failover::Fallback]s of the client.
+    pub api_base: Option<String>,
+}
+
+impl RawResponse {
+    async fn read(response: reqwest::Response) -> Result<Self, OpenAIError> {
+        Ok(Self {
+            status: response.status(),
+            api_base: response
+                .extensions()
+                .get::<ServedBy>()
+                .map(|served_by| served_by.0.clone()),
+            headers: response.headers().clone(),
+            body: response.bytes().await?,
+        })
+    }
+
+    /// Deserialize the body from JSON.
+    pub fn json<T: DeserializeOwned>(&self) -> Result<T, OpenAIError> {
+        serde_json::from_slice(&self.body).map_err(|error| {
+            OpenAIError::JSONDeserialize(error, String::from_utf8_lossy(&self.body).into_owned())
+        })
+    }
+
+    /// The body as text, invalid UTF-8 replaced.
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transport::mock::{MockResponse, MockTransport};
+
+    /// Echoes the method and url of the request.
+    fn echo() -> std::sync::Arc<MockTransport> {
+        MockTransport::new(|request, _| {
+            Ok(MockResponse::new(format!(
+                r#"{{"method":"{}","url":"{}"}}"#,
+                request.method, request.url
+            ))
+            .status(201)
+            .header("x-request-id", "req_1"))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_raw_response() {
+        let client = Client::new().with_transport(echo());
+        let response = client
+            .raw()
+            .query("limit", "2")
+            .post("/new/endpoint", serde_json::json!({}))
+            .await
+            .unwrap();
+        assert_eq!(response.status, StatusCode::CREATED);
+        assert_eq!(response.headers["x-request-id"], "req_1");
+        let body: serde_json::Value = response.json().unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"method": "POST", "url": "https://api.openai.com/v1/new/endpoint?limit=2"})
+        );
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
+    types::realtime::{
+        CreateClientSecretRequest, CreateClientSecretResponse, CreateRealtimeSessionRequest,
+        CreateTranscriptionSessionRequest, RealtimeSession, TranscriptionSession,
+    },
+    Client,
+};
+
+/// Create ephemeral keys for clients connecting to the Realtime API, e.g. browsers using
+/// WebRTC, without exposing the API key of the backend.
+///
+/// Related guide: [Realtime API](https://platform.openai.com/docs/guides/realtime)
+pub struct Realtime<'c, C: Config> {
+    client: &'c Client<C>,
+    request_options: RequestOptions,
+}
+
+impl_request_options!(Realtime);
+crate::util::impl_api_group_debug!(Realtime, "/realtime");
+
+impl<'c, C: Config> Realtime<'c, C> {
+    pub fn new(client: &'c Client<C>) -> Self {
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
+    }
+
+    /// Create an ephemeral key for a Realtime session defaulting to `request`, which the
+    /// client can update once connected.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_session(
+        &self,
+        request: CreateRealtimeSessionRequest,
+    ) -> Result<RealtimeSession, OpenAIError> {
+        self.client
+            .post("/realtime/sessions", request, &self.request_options)
+            .await
+    }
+
+    /// Create an ephemeral key for a Realtime session transcribing the input audio only.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_transcription_session(
+        &self,
+        request: CreateTranscriptionSessionRequest,
+    ) -> Result<TranscriptionSession, OpenAIError> {
+        self.client
+            .post(
+                "/realtime/transcription_sessions",
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Create an ephemeral key for a realtime or transcription session of the generally
+    /// available Realtime API.
+    #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn create_client_secret(
+        &self,
+        request: CreateClientSecretRequest,
+    ) -> Result<CreateClientSecretResponse, OpenAIError> {
+        self.client
+            .post("/realtime/client_secrets", request, &self.request_options)
+            .await
+    }
+}
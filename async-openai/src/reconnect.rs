@@ -0,0 +1,202 @@
+//! Reconnection of streaming responses interrupted before they completed.
+use std::{collections::HashSet, time::Duration};
+
+use reqwest_eventsource::{
+    retry::{Constant, Never},
+    EventSource,
+};
+
+use crate::error::{OpenAIError, StreamInterrupted};
+
+/// How streaming calls of a [crate::Client] reconnect when the connection drops.
+///
+/// A stream is only reconnected when no event was lost: before its first event, or when the
+/// server tags events with ids so the stream can be resumed with `Last-Event-ID`, events
+/// received twice being skipped. OpenAI does not resume streams, so a stream interrupted after
+/// its first event fails with [OpenAIError::StreamInterrupted] holding the events received so far.
+///
+/// ```
+/// use std::time::Duration;
+/// use async_openai::{reconnect::ReconnectPolicy, Client};
+///
+/// let client = Client::new().with_stream_reconnect(
+///     ReconnectPolicy::new()
+///         .with_max_attempts(5)
+///         .with_delay(Duration::from_millis(500)),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReconnectPolicy {
+    max_attempts: u32,
+    delay: Duration,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            delay: Duration::from_secs(1),
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Three attempts, one second apart.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum reconnection attempts of a stream.
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+
+    /// Delay before each reconnection attempt, overridden by the `retry` field of server events.
+    pub fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = delay;
+        self
+    }
+
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+}
+
+/// Events received and reconnection attempts of a stream.
+pub(crate) struct StreamState {
+    policy: Option<ReconnectPolicy>,
+    attempts: u32,
+    events: Vec<String>,
+    ids: HashSet<String>,
+}
+
+impl StreamState {
+    /// Configure `event_source` to reconnect after errors only with `policy`, the decision to
+    /// reconnect being taken by [StreamState::reconnect].
+    pub(crate) fn new(event_source: &mut EventSource, policy: Option<ReconnectPolicy>) -> Self {
+        match &policy {
+            Some(policy) => {
+                event_source.set_retry_policy(Box::new(Constant::new(policy.delay, None)))
+            }
+            None => event_source.set_retry_policy(Box::new(Never)),
+        }
+        Self {
+            policy,
+            attempts: 0,
+            events: Vec::new(),
+            ids: HashSet::new(),
+        }
+    }
+
+    /// Record a received event, returns `false` for events already received before reconnecting.
+    pub(crate) fn receive(&mut self, event: &eventsource_stream::Event) -> bool {
+        if self.policy.is_none() {
+            return true;
+        }
+        if !event.id.is_empty() && !self.ids.insert(event.id.clone()) {
+            return false;
+        }
+        self.events.push(event.data.clone());
+        true
+    }
+
+    /// Whether the event source should reconnect after `error`.
+    pub(crate) fn reconnect(
+        &mut self,
+        error: &reqwest_eventsource::Error,
+        last_event_id: &str,
+    ) -> bool {
+        let Some(policy) = &self.policy else {
+            return false;
+        };
+        if self.attempts >= policy.max_attempts {
+            return false;
+        }
+        let retriable = match error {
+            reqwest_eventsource::Error::Transport(_)
+            | reqwest_eventsource::Error::Utf8(_)
+            | reqwest_eventsource::Error::Parser(_) => true,
+            reqwest_eventsource::Error::InvalidStatusCode(status, _) => {
+                status.is_server_error() || status.as_u16() == 429
+            }
+            _ => false,
+        };
+        // without event ids a new request starts the response over
+        let resumable = self.events.is_empty() || !last_event_id.is_empty();
+        if !(retriable && resumable) {
+            return false;
+        }
+
+        self.attempts += 1;
+        tracing::warn!(
+            "stream interrupted, reconnecting ({}/{}): {error}",
+            self.attempts,
+            policy.max_attempts
+        );
+        true
+    }
+
+    /// Error ending the stream, [OpenAIError::StreamInterrupted] once events were received.
+    pub(crate) fn interrupted(&mut self, error: OpenAIError) -> OpenAIError {
+        if self.events.is_empty() {
+            return error;
+        }
+        OpenAIError::StreamInterrupted(StreamInterrupted {
+            error: Box::new(error),
+            attempts: self.attempts,
+            events: std::mem::take(&mut self.events),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use reqwest_eventsource::RequestBuilderExt;
+
+    use super::*;
+
+    fn event(id: &str, data: &str) -> eventsource_stream::Event {
+        eventsource_stream::Event {
+            id: id.into(),
+            data: data.into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_stream_state_reconnect() {
+        let mut event_source = reqwest::Client::new()
+            .get("http://localhost")
+            .eventsource()
+            .unwrap();
+        let policy = ReconnectPolicy::new().with_max_attempts(1);
+        let mut state = StreamState::new(&mut event_source, Some(policy));
+        let dropped =
+            || reqwest_eventsource::Error::Utf8(String::from_utf8(vec![0xff]).unwrap_err());
+
+        // nothing received yet, reconnecting is safe
+        assert!(state.reconnect(&dropped(), ""));
+
+        assert!(state.receive(&event("", "{}")));
+        // no event id to resume from, and attempts are exhausted anyway
+        assert!(!state.reconnect(&dropped(), ""));
+        let OpenAIError::StreamInterrupted(interrupted) =
+            state.interrupted(OpenAIError::StreamError("dropped".into()))
+        else {
+            panic!("expected the stream to be interrupted");
+        };
+        assert_eq!(interrupted.attempts, 1);
+        assert_eq!(interrupted.chunks::<serde_json::Value>().unwrap().len(), 1);
+
+        // events received again after resuming are skipped
+        let mut state = StreamState::new(&mut event_source, Some(policy));
+        assert!(state.receive(&event("1", "{}")));
+        assert!(state.reconnect(&dropped(), "1"));
+        assert!(!state.receive(&event("1", "{}")));
+    }
+}
@@ -0,0 +1,452 @@
+//! Capabilities of known models, to validate requests before they are sent and to pick models
+//! by capability.
+//!
+//! A [ModelRegistry] starts with the limits of common models at the time of this release;
+//! register new models, such as fine-tunes or models of other providers, with
+//! [ModelRegistry::register].
+//!
+//! ```
+//! use async_openai::{
+//!     registry::{ModelInfo, ModelRegistry},
+//!     types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs},
+//! };
+//!
+//! let registry = ModelRegistry::new().with_model("my-model", ModelInfo::new(32_000, 4_096));
+//!
+//! let error = CreateChatCompletionRequestArgs::default()
+//!     .model("o1-2024-12-17")
+//!     .messages([ChatCompletionRequestUserMessage::from("Hi").into()])
+//!     .temperature(0.2)
+//!     .build_checked(&registry)
+//!     .unwrap_err();
+//! assert!(error.to_string().contains("`temperature`"));
+//!
+//! let long_context = registry.find(|info| info.tools && info.context_window >= 1_000_000);
+//! assert!(long_context.contains(&"gpt-4.1".to_string()));
+//! ```
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionModalities, ChatCompletionRequestMessage,
+        ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+        CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    },
+};
+
+/// A kind of input or output of a model.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Modality {
+    Text,
+    Image,
+    Audio,
+}
+
+/// Capabilities and limits of a model.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Maximum number of input and output tokens.
+    pub context_window: u32,
+    /// Maximum number of output tokens, reasoning tokens included.
+    pub max_output_tokens: u32,
+    pub input_modalities: Vec<Modality>,
+    pub output_modalities: Vec<Modality>,
+    /// Accepts the sampling parameters `temperature`, `top_p`, `presence_penalty`,
+    /// `frequency_penalty`, `logit_bias`, `logprobs` and `top_logprobs`.
+    pub sampling: bool,
+    /// Accepts `tools` and `functions`.
+    pub tools: bool,
+    /// Accepts system and developer messages.
+    pub system_messages: bool,
+    /// A reasoning model, which accepts `reasoning_effort` and rejects `max_tokens` in favor
+    /// of `max_completion_tokens`.
+    pub reasoning: bool,
+}
+
+impl ModelInfo {
+    /// Text model accepting sampling parameters, tools and system messages.
+    pub fn new(context_window: u32, max_output_tokens: u32) -> Self {
+        Self {
+            context_window,
+            max_output_tokens,
+            input_modalities: vec![Modality::Text],
+            output_modalities: vec![Modality::Text],
+            sampling: true,
+            tools: true,
+            system_messages: true,
+            reasoning: false,
+        }
+    }
+
+    /// Reasoning model, without sampling parameters.
+    pub fn reasoning(context_window: u32, max_output_tokens: u32) -> Self {
+        Self {
+            sampling: false,
+            reasoning: true,
+            ..Self::new(context_window, max_output_tokens)
+        }
+    }
+
+    pub fn with_input(mut self, modality: Modality) -> Self {
+        if !self.input_modalities.contains(&modality) {
+            self.input_modalities.push(modality);
+        }
+        self
+    }
+
+    pub fn with_output(mut self, modality: Modality) -> Self {
+        if !self.output_modalities.contains(&modality) {
+            self.output_modalities.push(modality);
+        }
+        self
+    }
+
+    pub fn without_tools(mut self) -> Self {
+        self.tools = false;
+        self
+    }
+
+    pub fn without_system_messages(mut self) -> Self {
+        self.system_messages = false;
+        self
+    }
+
+    pub fn accepts(&self, modality: Modality) -> bool {
+        self.input_modalities.contains(&modality)
+    }
+
+    pub fn produces(&self, modality: Modality) -> bool {
+        self.output_modalities.contains(&modality)
+    }
+
+    /// Check that `request` only uses the parameters and modalities supported by this model.
+    pub fn validate_chat(&self, request: &CreateChatCompletionRequest) -> Result<(), OpenAIError> {
+        let model = &request.model;
+        let unsupported = |parameter: &str| {
+            OpenAIError::InvalidArgument(format!("`{parameter}` is not supported by {model}"))
+        };
+
+        if !self.sampling {
+            let sampling = [
+                ("temperature", request.temperature.is_some()),
+                ("top_p", request.top_p.is_some()),
+                ("presence_penalty", request.presence_penalty.is_some()),
+                ("frequency_penalty", request.frequency_penalty.is_some()),
+                ("logit_bias", request.logit_bias.is_some()),
+                ("logprobs", request.logprobs.is_some()),
+                ("top_logprobs", request.top_logprobs.is_some()),
+            ];
+            if let Some((parameter, _)) = sampling.iter().find(|(_, set)| *set) {
+                return Err(unsupported(parameter));
+            }
+        }
+
+        #[allow(deprecated)]
+        let (max_tokens, functions) = (request.max_tokens, request.functions.is_some());
+        if self.reasoning && max_tokens.is_some() {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "`max_tokens` is not supported by {model}, use `max_completion_tokens`"
+            )));
+        }
+        if !self.reasoning && request.reasoning_effort.is_some() {
+            return Err(unsupported("reasoning_effort"));
+        }
+        if let Some(max) = request.max_completion_tokens.or(max_tokens) {
+            if max > self.max_output_tokens {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "{model} generates at most {} tokens, {max} requested",
+                    self.max_output_tokens
+                )));
+            }
+        }
+
+        if !self.tools {
+            if request.tools.is_some() {
+                return Err(unsupported("tools"));
+            }
+            if functions {
+                return Err(unsupported("functions"));
+            }
+        }
+        if !self.produces(Modality::Audio)
+            && (request.audio.is_some()
+                || request.modalities.as_ref().is_some_and(|modalities| {
+                    modalities.contains(&ChatCompletionModalities::Audio)
+                }))
+        {
+            return Err(unsupported("audio output"));
+        }
+
+        for message in &request.messages {
+            match message {
+                ChatCompletionRequestMessage::System(_)
+                | ChatCompletionRequestMessage::Developer(_)
+                    if !self.system_messages =>
+                {
+                    return Err(unsupported("system and developer messages"));
+                }
+                ChatCompletionRequestMessage::User(message) => {
+                    if let ChatCompletionRequestUserMessageContent::Array(parts) = &message.content
+                    {
+                        for part in parts {
+                            match part {
+                                ChatCompletionRequestUserMessageContentPart::ImageUrl(_)
+                                    if !self.accepts(Modality::Image) =>
+                                {
+                                    return Err(unsupported("image input"));
+                                }
+                                ChatCompletionRequestUserMessageContentPart::InputAudio(_)
+                                    if !self.accepts(Modality::Audio) =>
+                                {
+                                    return Err(unsupported("audio input"));
+                                }
+                                _ => {}
+                            }
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Capabilities of the built-in models.
+fn known_models() -> Vec<(&'static str, ModelInfo)> {
+    use Modality::{Audio, Image};
+    vec![
+        (
+            "gpt-4.1",
+            ModelInfo::new(1_047_576, 32_768).with_input(Image),
+        ),
+        (
+            "gpt-4.1-mini",
+            ModelInfo::new(1_047_576, 32_768).with_input(Image),
+        ),
+        (
+            "gpt-4.1-nano",
+            ModelInfo::new(1_047_576, 32_768).with_input(Image),
+        ),
+        ("gpt-4o", ModelInfo::new(128_000, 16_384).with_input(Image)),
+        (
+            "gpt-4o-mini",
+            ModelInfo::new(128_000, 16_384).with_input(Image),
+        ),
+        (
+            "gpt-4o-audio-preview",
+            ModelInfo::new(128_000, 16_384)
+                .with_input(Audio)
+                .with_output(Audio),
+        ),
+        (
+            "gpt-4o-mini-audio-preview",
+            ModelInfo::new(128_000, 16_384)
+                .with_input(Audio)
+                .with_output(Audio),
+        ),
+        (
+            "gpt-4-turbo",
+            ModelInfo::new(128_000, 4_096).with_input(Image),
+        ),
+        ("gpt-4", ModelInfo::new(8_192, 8_192)),
+        ("gpt-3.5-turbo", ModelInfo::new(16_385, 4_096)),
+        (
+            "o1",
+            ModelInfo::reasoning(200_000, 100_000).with_input(Image),
+        ),
+        (
+            "o1-mini",
+            ModelInfo::reasoning(128_000, 65_536)
+                .without_tools()
+                .without_system_messages(),
+        ),
+        (
+            "o1-preview",
+            ModelInfo::reasoning(128_000, 32_768)
+                .without_tools()
+                .without_system_messages(),
+        ),
+        (
+            "o3",
+            ModelInfo::reasoning(200_000, 100_000).with_input(Image),
+        ),
+        ("o3-mini", ModelInfo::reasoning(200_000, 100_000)),
+        (
+            "o4-mini",
+            ModelInfo::reasoning(200_000, 100_000).with_input(Image),
+        ),
+    ]
+}
+
+/// Capabilities of models, looked up by the longest model name prefixing the model of a request
+/// so that dated snapshots such as `gpt-4o-2024-08-06` use the capabilities of `gpt-4o`.
+///
+/// Clones share the same models.
+#[derive(Debug, Clone)]
+pub struct ModelRegistry {
+    models: Arc<RwLock<HashMap<String, ModelInfo>>>,
+}
+
+impl Default for ModelRegistry {
+    fn default() -> Self {
+        let models = known_models()
+            .into_iter()
+            .map(|(model, info)| (model.to_string(), info))
+            .collect();
+        Self {
+            models: Arc::new(RwLock::new(models)),
+        }
+    }
+}
+
+impl ModelRegistry {
+    /// Registry of the built-in models.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Registry without models.
+    pub fn empty() -> Self {
+        Self {
+            models: Default::default(),
+        }
+    }
+
+    pub fn with_model<S: Into<String>>(self, model: S, info: ModelInfo) -> Self {
+        self.register(model, info);
+        self
+    }
+
+    /// Register or replace `model` and its snapshots, taking effect immediately.
+    pub fn register<S: Into<String>>(&self, model: S, info: ModelInfo) {
+        self.write().insert(model.into(), info);
+    }
+
+    pub fn remove(&self, model: &str) {
+        self.write().remove(model);
+    }
+
+    /// Capabilities of `model`, `None` for unknown models.
+    pub fn get(&self, model: &str) -> Option<ModelInfo> {
+        let models = self.read();
+        if let Some(info) = models.get(model) {
+            return Some(info.clone());
+        }
+        models
+            .iter()
+            .filter(|(name, _)| {
+                model
+                    .strip_prefix(name.as_str())
+                    .is_some_and(|rest| rest.starts_with('-'))
+            })
+            .max_by_key(|(name, _)| name.len())
+            .map(|(_, info)| info.clone())
+    }
+
+    /// Names of the registered models matching `predicate`, sorted.
+    pub fn find<P>(&self, predicate: P) -> Vec<String>
+    where
+        P: Fn(&ModelInfo) -> bool,
+    {
+        let mut models: Vec<String> = self
+            .read()
+            .iter()
+            .filter(|(_, info)| predicate(info))
+            .map(|(name, _)| name.clone())
+            .collect();
+        models.sort();
+        models
+    }
+
+    /// Check `request` against the capabilities of its model, see [ModelInfo::validate_chat].
+    /// Requests to unknown models are not checked.
+    pub fn validate_chat(&self, request: &CreateChatCompletionRequest) -> Result<(), OpenAIError> {
+        match self.get(&request.model) {
+            Some(info) => info.validate_chat(request),
+            None => Ok(()),
+        }
+    }
+
+    fn read(&self) -> std::sync::RwLockReadGuard<'_, HashMap<String, ModelInfo>> {
+        self.models
+            .read()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
+    fn write(&self) -> std::sync::RwLockWriteGuard<'_, HashMap<String, ModelInfo>> {
+        self.models
+            .write()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl CreateChatCompletionRequestArgs {
+    /// Build the request and check it against the capabilities of its model in `registry`.
+    pub fn build_checked(
+        &self,
+        registry: &ModelRegistry,
+    ) -> Result<CreateChatCompletionRequest, OpenAIError> {
+        let request = self.build()?;
+        registry.validate_chat(&request)?;
+        Ok(request)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{
+        ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestSystemMessage,
+        ChatCompletionRequestUserMessage, ImageUrl,
+    };
+
+    #[test]
+    fn test_model_registry() {
+        let registry = ModelRegistry::new();
+        assert_eq!(
+            registry
+                .get("gpt-4o-mini-2024-07-18")
+                .map(|info| info.max_output_tokens),
+            Some(16_384)
+        );
+        assert!(registry
+            .get("o1-mini-2024-09-12")
+            .is_some_and(|info| !info.tools));
+        assert_eq!(registry.get("my-model"), None);
+
+        let image =
+            ChatCompletionRequestUserMessage::from(ChatCompletionRequestUserMessageContent::from(
+                vec![ChatCompletionRequestMessageContentPartImage {
+                    image_url: ImageUrl {
+                        url: "https://example.com/cat.png".into(),
+                        detail: None,
+                    },
+                }
+                .into()],
+            ));
+        let request = |model: &str| CreateChatCompletionRequest {
+            model: model.into(),
+            messages: vec![
+                ChatCompletionRequestSystemMessage::from("Describe images").into(),
+                image.clone().into(),
+            ],
+            ..Default::default()
+        };
+        assert!(registry.validate_chat(&request("gpt-4o")).is_ok());
+        assert!(registry.validate_chat(&request("my-model")).is_ok());
+        assert!(registry.validate_chat(&request("gpt-3.5-turbo")).is_err());
+        assert!(registry.validate_chat(&request("o1-mini")).is_err());
+
+        registry.register("my-model", ModelInfo::new(8_000, 1_000));
+        let mut too_long = request("gpt-4o");
+        too_long.model = "my-model".into();
+        too_long.messages.pop();
+        too_long.max_completion_tokens = Some(2_000);
+        assert!(registry.validate_chat(&too_long).is_err());
+    }
+}
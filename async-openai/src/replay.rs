@@ -0,0 +1,483 @@
+//! Record and replay of the HTTP exchanges of a client with fixture files, for integration tests
+//! running without network nor credentials.
+//!
+//! In [ReplayMode::Record], requests are sent as usual and each exchange is saved to a JSON file
+//! of the fixture directory, streamed responses included as their SSE transcript. In
+//! [ReplayMode::Replay], responses are read from those files and nothing is sent.
+//!
+//! Requests are matched on their method, path, query and body: the api base is not part of the
+//! match, and headers, where credentials are sent, are never saved. Sensitive response headers
+//! are dropped, see [Replay::with_redacted_header]. A request sent several times is matched with
+//! its recordings in order, the last one answering the extra calls.
+//!
+//! ```no_run
+//! use async_openai::{replay::Replay, types::CreateModerationRequestArgs, Client};
+//!
+//! # tokio_test::block_on(async {
+//! // ASYNC_OPENAI_REPLAY=record cargo test --features replay, to refresh the fixtures
+//! let client = Client::new().with_replay(Replay::from_env("tests/fixtures/moderation"));
+//! let request = CreateModerationRequestArgs::default()
+//!     .input("hello")
+//!     .build()?;
+//! let response = client.moderations().create(request).await?;
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! # });
+//! ```
+use std::{
+    collections::HashMap,
+    future::Future,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use bytes::Bytes;
+use futures::{stream, Stream, StreamExt};
+use reqwest::{
+    header::{HeaderMap, HeaderName, HeaderValue},
+    StatusCode,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    cache,
+    error::{map_status_error, OpenAIError},
+};
+
+/// Environment variable read by [Replay::from_env].
+pub const REPLAY_ENV: &str = "ASYNC_OPENAI_REPLAY";
+
+/// Response headers never saved to fixtures.
+const REDACTED_HEADERS: [&str; 6] = [
+    "set-cookie",
+    "openai-organization",
+    "openai-project",
+    "authorization",
+    "api-key",
+    "x-request-id",
+];
+
+/// Whether a [Replay] saves exchanges or serves them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayMode {
+    /// Send requests and save the exchanges, overwriting previous recordings.
+    Record,
+    /// Serve responses from the fixtures, failing requests without one.
+    Replay,
+}
+
+/// Fixture directory of a client, see [crate::Client::with_replay].
+#[derive(Debug, Clone)]
+pub struct Replay {
+    mode: ReplayMode,
+    dir: PathBuf,
+    redacted_headers: Vec<String>,
+    /// Number of exchanges of each request so far.
+    calls: Arc<Mutex<HashMap<String, usize>>>,
+}
+
+impl Replay {
+    pub fn new<P: Into<PathBuf>>(mode: ReplayMode, dir: P) -> Self {
+        Self {
+            mode,
+            dir: dir.into(),
+            redacted_headers: REDACTED_HEADERS
+                .iter()
+                .map(|name| name.to_string())
+                .collect(),
+            calls: Default::default(),
+        }
+    }
+
+    pub fn record<P: Into<PathBuf>>(dir: P) -> Self {
+        Self::new(ReplayMode::Record, dir)
+    }
+
+    pub fn play<P: Into<PathBuf>>(dir: P) -> Self {
+        Self::new(ReplayMode::Replay, dir)
+    }
+
+    /// Record when [REPLAY_ENV] is `record`, replay otherwise.
+    pub fn from_env<P: Into<PathBuf>>(dir: P) -> Self {
+        let mode = match std::env::var(REPLAY_ENV) {
+            Ok(mode) if mode.eq_ignore_ascii_case("record") => ReplayMode::Record,
+            _ => ReplayMode::Replay,
+        };
+        Self::new(mode, dir)
+    }
+
+    /// Also drop the response header `name` from the fixtures.
+    pub fn with_redacted_header<S: AsRef<str>>(mut self, name: S) -> Self {
+        self.redacted_headers
+            .push(name.as_ref().to_ascii_lowercase());
+        self
+    }
+
+    pub fn mode(&self) -> ReplayMode {
+        self.mode
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Serve `request` from its fixture, or send it with `send` and save the exchange.
+    ///
+    /// A recorded response is read to its end before being returned, streams included.
+    pub(crate) async fn execute<F, Fut>(
+        &self,
+        request: reqwest::Request,
+        send: F,
+    ) -> Result<reqwest::Response, OpenAIError>
+    where
+        F: FnOnce(reqwest::Request) -> Fut,
+        Fut: Future<Output = Result<reqwest::Response, OpenAIError>>,
+    {
+        let fixture_request = FixtureRequest::from(&request);
+        let key = fixture_request.key();
+        let call = {
+            let mut calls = self.calls.lock().unwrap_or_else(|p| p.into_inner());
+            let call = calls.entry(key.clone()).or_default();
+            *call += 1;
+            *call - 1
+        };
+
+        match self.mode {
+            ReplayMode::Replay => {
+                let fixture = self.load(&fixture_request, &key, call).await?;
+                fixture.response.into_response()
+            }
+            ReplayMode::Record => {
+                let response = send(request).await?;
+                let status = response.status();
+                let headers = response.headers().clone();
+                let body = response.bytes().await?;
+
+                let fixture = Fixture {
+                    request: fixture_request,
+                    response: FixtureResponse::new(status.as_u16(), &headers, &body, self),
+                };
+                self.save(&fixture, &key, call).await?;
+
+                let mut response = axum::http::Response::new(body);
+                *response.status_mut() = status;
+                *response.headers_mut() = headers;
+                Ok(reqwest::Response::from(response))
+            }
+        }
+    }
+
+    fn path(&self, request: &FixtureRequest, key: &str, call: usize) -> PathBuf {
+        let mut name: String = format!("{} {}", request.method, request.path)
+            .chars()
+            .take_while(|c| *c != '?')
+            .map(|c| {
+                if c.is_ascii_alphanumeric() {
+                    c.to_ascii_lowercase()
+                } else {
+                    '-'
+                }
+            })
+            .collect();
+        while name.contains("--") {
+            name = name.replace("--", "-");
+        }
+        name.truncate(64);
+        self.dir.join(format!(
+            "{}-{}-{call}.json",
+            name.trim_end_matches('-'),
+            &key[..8]
+        ))
+    }
+
+    async fn load(
+        &self,
+        request: &FixtureRequest,
+        key: &str,
+        call: usize,
+    ) -> Result<Fixture, OpenAIError> {
+        // the last recording answers the calls beyond the recorded ones
+        for call in (0..=call).rev() {
+            let path = self.path(request, key, call);
+            let bytes = match tokio::fs::read(&path).await {
+                Ok(bytes) => bytes,
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => continue,
+                Err(e) => {
+                    return Err(OpenAIError::FileReadError(format!(
+                        "cannot read fixture {}: {e}",
+                        path.display()
+                    )))
+                }
+            };
+            return serde_json::from_slice(&bytes).map_err(|e| {
+                OpenAIError::FileReadError(format!("invalid fixture {}: {e}", path.display()))
+            });
+        }
+        Err(OpenAIError::MissingFixture(format!(
+            "{} {} in {}, record it with {REPLAY_ENV}=record",
+            request.method,
+            request.path,
+            self.dir.display()
+        )))
+    }
+
+    async fn save(&self, fixture: &Fixture, key: &str, call: usize) -> Result<(), OpenAIError> {
+        let save_error = |e: &dyn std::fmt::Display| {
+            OpenAIError::FileSaveError(format!(
+                "cannot save fixture to {}: {e}",
+                self.dir.display()
+            ))
+        };
+        tokio::fs::create_dir_all(&self.dir)
+            .await
+            .map_err(|e| save_error(&e))?;
+        let json = serde_json::to_vec_pretty(fixture).map_err(|e| save_error(&e))?;
+        tokio::fs::write(self.path(&fixture.request, key, call), json)
+            .await
+            .map_err(|e| save_error(&e))
+    }
+
+    fn is_redacted(&self, name: &HeaderName) -> bool {
+        self.redacted_headers
+            .iter()
+            .any(|redacted| redacted == name.as_str())
+    }
+}
+
+/// A recorded exchange, the content of a fixture file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Fixture {
+    pub request: FixtureRequest,
+    pub response: FixtureResponse,
+}
+
+/// The parts of a request it is matched on.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureRequest {
+    pub method: String,
+    /// Path and query of the url, without the api base.
+    pub path: String,
+    /// JSON body, if any: multipart uploads are matched without their body.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub body: Option<serde_json::Value>,
+}
+
+impl FixtureRequest {
+    fn key(&self) -> String {
+        let body = self
+            .body
+            .as_ref()
+            .map(|body| body.to_string())
+            .unwrap_or_default();
+        // the path holds the query
+        cache::key(
+            &format!("{} {}", self.method, self.path),
+            [],
+            &HeaderMap::new(),
+            body.as_bytes(),
+        )
+    }
+}
+
+impl From<&reqwest::Request> for FixtureRequest {
+    fn from(request: &reqwest::Request) -> Self {
+        let url = request.url();
+        let path = match url.query() {
+            Some(query) => format!("{}?{query}", url.path()),
+            None => url.path().to_string(),
+        };
+        let body = request
+            .body()
+            .and_then(reqwest::Body::as_bytes)
+            .and_then(|bytes| serde_json::from_slice(bytes).ok());
+        Self {
+            method: request.method().to_string(),
+            path,
+            body,
+        }
+    }
+}
+
+/// A recorded response, the SSE transcript of a stream as its body.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FixtureResponse {
+    pub status: u16,
+    #[serde(default)]
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+    /// Whether `body` is base64 encoded, for binary responses such as audio.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub base64: bool,
+}
+
+impl FixtureResponse {
+    fn new(status: u16, headers: &HeaderMap, body: &[u8], replay: &Replay) -> Self {
+        let headers = headers
+            .iter()
+            .filter(|(name, _)| !replay.is_redacted(name))
+            .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+            .collect();
+        let (body, base64) = match std::str::from_utf8(body) {
+            Ok(body) => (body.to_string(), false),
+            Err(_) => (STANDARD.encode(body), true),
+        };
+        Self {
+            status,
+            headers,
+            body,
+            base64,
+        }
+    }
+
+    fn into_response(self) -> Result<reqwest::Response, OpenAIError> {
+        let invalid = |e: &dyn std::fmt::Display| {
+            OpenAIError::FileReadError(format!("invalid fixture response: {e}"))
+        };
+        let body = if self.base64 {
+            STANDARD.decode(&self.body).map_err(|e| invalid(&e))?
+        } else {
+            self.body.into_bytes()
+        };
+        let mut response = axum::http::Response::new(Bytes::from(body));
+        *response.status_mut() = StatusCode::from_u16(self.status).map_err(|e| invalid(&e))?;
+        for (name, value) in &self.headers {
+            if let (Ok(name), Ok(value)) = (
+                HeaderName::try_from(name.as_str()),
+                HeaderValue::try_from(value.as_str()),
+            ) {
+                response.headers_mut().append(name, value);
+            }
+        }
+        Ok(reqwest::Response::from(response))
+    }
+}
+
+/// Server-sent events of a replayed or recorded `response`.
+pub(crate) fn events(
+    response: Result<reqwest::Response, OpenAIError>,
+) -> impl Stream<Item = Result<eventsource_stream::Event, OpenAIError>> + Send {
+    use eventsource_stream::Eventsource;
+
+    stream::once(async move {
+        let response = response?;
+        let status = response.status();
+        if !status.is_success() {
+            let headers = response.headers().clone();
+            let bytes = response.bytes().await?;
+            return Err(OpenAIError::ApiError(map_status_error(
+                status, headers, &bytes,
+            )));
+        }
+        Ok(response
+            .bytes_stream()
+            .eventsource()
+            .map(|event| event.map_err(|e| OpenAIError::StreamError(e.to_string()))))
+    })
+    .map(|events| match events {
+        Ok(events) => events.left_stream(),
+        Err(e) => stream::once(async move { Err(e) }).right_stream(),
+    })
+    .flatten()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        transport::mock::{MockResponse, MockTransport},
+        types::{ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs},
+        Client,
+    };
+
+    /// Answers chat completions, streamed or not.
+    fn chat() -> std::sync::Arc<MockTransport> {
+        MockTransport::new(|request, _| {
+            Ok(if request.json()["stream"] == serde_json::json!(true) {
+                let chunk = r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[{"index":0,"delta":{"content":"Hi"}}]}"#;
+                MockResponse::new(format!("data: {chunk}\n\ndata: [DONE]\n\n"))
+                    .header("content-type", "text/event-stream")
+                    .header("openai-organization", "org-secret")
+            } else {
+                MockResponse::new(
+                    r#"{"id":"1","object":"chat.completion","created":1,"model":"m","choices":[{"index":0,"message":{"role":"assistant","content":"Hello"},"finish_reason":"stop"}]}"#,
+                )
+            })
+        })
+    }
+
+    #[tokio::test]
+    async fn test_record_replay() {
+        let dir = std::env::temp_dir().join(format!("async-openai-replay-{}", std::process::id()));
+        let request = CreateChatCompletionRequestArgs::default()
+            .model("m")
+            .messages([ChatCompletionRequestUserMessage::from("hi").into()])
+            .build()
+            .unwrap();
+        let mut stream_request = request.clone();
+        stream_request.stream = Some(true);
+
+        let recording = Client::new()
+            .with_transport(chat())
+            .with_replay(Replay::record(&dir));
+        let response = recording.chat().create(request.clone()).await.unwrap();
+        assert_eq!(
+            response.choices[0].message.content.as_deref(),
+            Some("Hello")
+        );
+        let chunks: Vec<_> = recording
+            .chat()
+            .create_stream(stream_request.clone())
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 1);
+
+        // no transport: the responses come from the fixtures
+        let replaying = Client::new().with_replay(Replay::play(&dir));
+        let replayed = replaying.chat().create(request.clone()).await.unwrap();
+        assert_eq!(replayed, response);
+        let mut stream = replaying
+            .chat()
+            .create_stream(stream_request.clone())
+            .await
+            .unwrap();
+        let chunk = stream.next().await.unwrap().unwrap();
+        assert_eq!(chunk.content_delta(), Some("Hi"));
+        assert!(stream.next().await.is_none());
+
+        // replayed streams are cancelled like live ones
+        let token = crate::request_options::CancellationToken::new();
+        token.cancel();
+        let mut stream = replaying
+            .chat()
+            .cancellation_token(token)
+            .create_stream(stream_request.clone())
+            .await
+            .unwrap();
+        assert!(stream.next().await.is_none());
+
+        let fixtures: Vec<_> = std::fs::read_dir(&dir).unwrap().collect();
+        assert_eq!(fixtures.len(), 2);
+        for fixture in fixtures {
+            let content = std::fs::read_to_string(fixture.unwrap().path()).unwrap();
+            assert!(!content.contains("org-secret"));
+        }
+
+        let mut other = request.clone();
+        other.model = "other".into();
+        assert!(matches!(
+            replaying.chat().create(other).await,
+            Err(OpenAIError::MissingFixture(_))
+        ));
+
+        for fixture in std::fs::read_dir(&dir).unwrap() {
+            std::fs::write(fixture.unwrap().path(), "{").unwrap();
+        }
+        assert!(matches!(
+            replaying.chat().create(request).await,
+            Err(OpenAIError::FileReadError(_))
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
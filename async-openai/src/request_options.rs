@@ -0,0 +1,208 @@
+//! Extra headers, query parameters and cancellation attached to individual API calls.
+use std::fmt;
+
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+pub use tokio_util::sync::CancellationToken;
+
+use crate::error::OpenAIError;
+
+/// Header of the key deduplicating retries of a mutating request, see
+/// [RequestOptions::idempotency_key].
+pub const IDEMPOTENCY_KEY: HeaderName = HeaderName::from_static("idempotency-key");
+
+/// Headers and query parameters sent in addition to the ones from [crate::config::Config].
+///
+/// Useful for gateways like Helicone, Cloudflare AI Gateway or LiteLLM which read
+/// per-request metadata from headers. Headers set here take precedence over config headers.
+///
+/// ```
+/// use async_openai::{request_options::RequestOptions, Client};
+///
+/// # fn main() -> Result<(), async_openai::error::OpenAIError> {
+/// let client = Client::new();
+///
+/// let options = RequestOptions::new()
+///     .header("Helicone-Property-Session", "session-1")?
+///     .query("trace", "true");
+///
+/// let chat = client.chat().with_options(options);
+/// // or directly on the API group
+/// let chat = client.chat().header("Helicone-Property-Session", "session-1")?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Its `Debug` output shows the names of the headers and query parameters but not their values,
+/// which may be credentials of a gateway.
+#[derive(Clone, Default)]
+pub struct RequestOptions {
+    headers: HeaderMap,
+    query: Vec<(String, String)>,
+    cancellation_token: Option<CancellationToken>,
+    tenant: Option<String>,
+}
+
+impl fmt::Debug for RequestOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RequestOptions")
+            .field("headers", &self.headers.keys().collect::<Vec<_>>())
+            .field(
+                "query",
+                &self.query.iter().map(|(key, _)| key).collect::<Vec<_>>(),
+            )
+            .field("cancellation_token", &self.cancellation_token)
+            .field("tenant", &self.tenant)
+            .finish()
+    }
+}
+
+impl RequestOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a header, fails if `key` or `value` is not a valid HTTP header name or value.
+    pub fn header<K, V>(mut self, key: K, value: V) -> Result<Self, OpenAIError>
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let name = HeaderName::from_bytes(key.as_ref().as_bytes())
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid header name: {e}")))?;
+        let value = HeaderValue::from_str(value.as_ref())
+            .map_err(|e| OpenAIError::InvalidArgument(format!("invalid header value: {e}")))?;
+        self.headers.insert(name, value);
+        Ok(self)
+    }
+
+    /// Add all of `headers`, replacing headers with the same name.
+    pub fn headers(mut self, headers: HeaderMap) -> Self {
+        self.headers.extend(headers);
+        self
+    }
+
+    /// Send `key` as the `Idempotency-Key` header, so that the API processes a request sent
+    /// several times only once. Use a key per call: options set on an API group apply to all
+    /// of its calls.
+    ///
+    /// A request with an idempotency key is retried after network errors too, see
+    /// [crate::Client::with_idempotency_keys].
+    pub fn idempotency_key<K: AsRef<str>>(self, key: K) -> Result<Self, OpenAIError> {
+        self.header(IDEMPOTENCY_KEY, key)
+    }
+
+    /// Add a query parameter.
+    pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+        self.query.push((key.into(), value.into()));
+        self
+    }
+
+    /// Stop streaming calls once `token` is cancelled, closing their HTTP connection.
+    /// The stream ends without error, like it does when dropped.
+    pub fn cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.cancellation_token = Some(token);
+        self
+    }
+
+    /// Count calls against the quotas of `tenant`, see [crate::Client::with_tenant_quotas].
+    pub fn tenant<S: Into<String>>(mut self, tenant: S) -> Self {
+        self.tenant = Some(tenant.into());
+        self
+    }
+
+    pub fn header_map(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    pub fn query_pairs(&self) -> &[(String, String)] {
+        &self.query
+    }
+
+    pub fn get_cancellation_token(&self) -> Option<&CancellationToken> {
+        self.cancellation_token.as_ref()
+    }
+
+    pub fn get_tenant(&self) -> Option<&str> {
+        self.tenant.as_deref()
+    }
+
+    /// Combine with `other`, values in `other` win for duplicate headers.
+    pub(crate) fn merge(&mut self, other: RequestOptions) {
+        self.headers.extend(other.headers);
+        self.query.extend(other.query);
+        if other.cancellation_token.is_some() {
+            self.cancellation_token = other.cancellation_token;
+        }
+        if other.tenant.is_some() {
+            self.tenant = other.tenant;
+        }
+    }
+}
+
+/// Macro to implement `with_options`, `header` and `query` on an API group
+/// holding a `request_options: RequestOptions` field
+macro_rules! impl_request_options {
+    ($group:ident) => {
+        impl<'c, C: $crate::config::Config> $group<'c, C> {
+            /// Send extra headers and query parameters with every call made through this API group.
+            pub fn with_options(
+                mut self,
+                options: $crate::request_options::RequestOptions,
+            ) -> Self {
+                self.request_options.merge(options);
+                self
+            }
+
+            /// Send an extra header with every call made through this API group.
+            pub fn header<K: AsRef<str>, V: AsRef<str>>(
+                mut self,
+                key: K,
+                value: V,
+            ) -> Result<Self, $crate::error::OpenAIError> {
+                self.request_options = self.request_options.header(key, value)?;
+                Ok(self)
+            }
+
+            /// Send an extra query parameter with every call made through this API group.
+            pub fn query<K: Into<String>, V: Into<String>>(mut self, key: K, value: V) -> Self {
+                self.request_options = self.request_options.query(key, value);
+                self
+            }
+
+            /// Stop streaming calls made through this API group once `token` is cancelled.
+            pub fn cancellation_token(
+                mut self,
+                token: $crate::request_options::CancellationToken,
+            ) -> Self {
+                self.request_options = self.request_options.cancellation_token(token);
+                self
+            }
+
+            /// Count calls made through this API group against the quotas of `tenant`.
+            pub fn tenant<S: Into<String>>(mut self, tenant: S) -> Self {
+                self.request_options = self.request_options.tenant(tenant);
+                self
+            }
+        }
+    };
+}
+
+pub(crate) use impl_request_options;
+
+#[cfg(test)]
+mod tests {
+    use crate::Client;
+
+    #[test]
+    fn test_debug_redacts_values() {
+        let client = Client::new();
+        let chat = client
+            .chat()
+            .header("Helicone-Auth", "Bearer sk-gateway")
+            .unwrap()
+            .query("key", "secret-key");
+        let debug = format!("{chat:?}");
+        assert!(debug.contains("helicone-auth") && debug.contains("\"key\""));
+        assert!(!debug.contains("sk-gateway") && !debug.contains("secret-key"));
+    }
+}
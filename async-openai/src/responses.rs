@@ -1,6 +1,7 @@
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::responses::{CreateResponse, Response, ResponseStream},
     Client,
 };
@@ -10,12 +11,19 @@ use crate::{
 /// Related guide: [Responses](https://platform.openai.com/docs/api-reference/responses)
 pub struct Responses<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Responses);
+crate::util::impl_api_group_debug!(Responses, "/responses");
+
 impl<'c, C: Config> Responses<'c, C> {
     /// Constructs a new Responses client.
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates a model response for the given input.
@@ -24,7 +32,9 @@ impl<'c, C: Config> Responses<'c, C> {
         R = serde::de::DeserializeOwned
     )]
     pub async fn create(&self, request: CreateResponse) -> Result<Response, OpenAIError> {
-        self.client.post("/responses", request).await
+        self.client
+            .post("/responses", request, &self.request_options)
+            .await
     }
 
     /// Creates a model response for the given input with streaming.
@@ -50,6 +60,9 @@ impl<'c, C: Config> Responses<'c, C> {
             }
             request.stream = Some(true);
         }
-        Ok(self.client.post_stream("/responses", request).await)
+        Ok(self
+            .client
+            .post_stream("/responses", request, &self.request_options)
+            .await)
     }
 }
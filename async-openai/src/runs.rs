@@ -1,12 +1,18 @@
+use std::time::{Duration, Instant};
+
+use futures::StreamExt;
 use serde::Serialize;
 
 use crate::{
+    assistant_events::{dispatch, AssistantEventHandler},
     config::Config,
     error::OpenAIError,
+    poll::{poll_until_done, PollOptions, Pollable},
+    request_options::{impl_request_options, RequestOptions},
     steps::Steps,
     types::{
         AssistantEventStream, CreateRunRequest, ListRunsResponse, ModifyRunRequest, RunObject,
-        SubmitToolOutputsRunRequest,
+        RunStatus, SubmitToolOutputsRunRequest,
     },
     Client,
 };
@@ -17,26 +23,35 @@ use crate::{
 pub struct Runs<'c, C: Config> {
     pub thread_id: String,
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Runs);
+crate::util::impl_api_group_debug!(Runs, "/threads/{}/runs", thread_id);
+
 impl<'c, C: Config> Runs<'c, C> {
     pub fn new(client: &'c Client<C>, thread_id: &str) -> Self {
         Self {
             client,
             thread_id: thread_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
     ///  [Steps] API group
     pub fn steps(&self, run_id: &str) -> Steps<C> {
-        Steps::new(self.client, &self.thread_id, run_id)
+        Steps::new(self.client, &self.thread_id, run_id).with_options(self.request_options.clone())
     }
 
     /// Create a run.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: CreateRunRequest) -> Result<RunObject, OpenAIError> {
         self.client
-            .post(&format!("/threads/{}/runs", self.thread_id), request)
+            .post(
+                &format!("/threads/{}/runs", self.thread_id),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -71,6 +86,7 @@ impl<'c, C: Config> Runs<'c, C> {
                 &format!("/threads/{}/runs", self.thread_id),
                 request,
                 TryFrom::try_from,
+                &self.request_options,
             )
             .await)
     }
@@ -79,10 +95,104 @@ impl<'c, C: Config> Runs<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, run_id: &str) -> Result<RunObject, OpenAIError> {
         self.client
-            .get(&format!("/threads/{}/runs/{run_id}", self.thread_id))
+            .get(
+                &format!("/threads/{}/runs/{run_id}", self.thread_id),
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Polls a run until it leaves the `queued`, `in_progress` and `cancelling` statuses.
+    ///
+    /// A run in `requires_action` status is returned so that tool outputs can be submitted.
+    pub async fn wait(
+        &self,
+        run_id: &str,
+        options: &PollOptions,
+    ) -> Result<RunObject, OpenAIError> {
+        poll_until_done(options, || self.retrieve(run_id)).await
+    }
+
+    /// Creates a run and waits until it leaves the `queued`, `in_progress` and `cancelling`
+    /// statuses, following its events when the API streams runs.
+    ///
+    /// When the API rejects streamed runs or answers without events, as some Azure OpenAI
+    /// deployments do, the run is polled instead with the interval and backoff of `options`.
+    pub async fn create_and_wait(
+        &self,
+        request: CreateRunRequest,
+        options: &PollOptions,
+    ) -> Result<RunObject, OpenAIError> {
+        let started = Instant::now();
+        let stream: Result<AssistantEventStream, OpenAIError> =
+            self.create_stream(request.clone()).await;
+        let run: RunObject = match follow_stream(stream, options, started).await? {
+            Some(run) => run,
+            None => self.create(request).await?,
+        };
+        self.wait_streamed(run, options, started).await
+    }
+
+    /// Submits tool outputs to a run requiring action and waits until it leaves the `queued`,
+    /// `in_progress` and `cancelling` statuses, like [Runs::create_and_wait].
+    pub async fn submit_tool_outputs_and_wait(
+        &self,
+        run_id: &str,
+        request: SubmitToolOutputsRunRequest,
+        options: &PollOptions,
+    ) -> Result<RunObject, OpenAIError> {
+        let started = Instant::now();
+        let stream: Result<AssistantEventStream, OpenAIError> = self
+            .submit_tool_outputs_stream(run_id, request.clone())
+            .await;
+        let run: RunObject = match follow_stream(stream, options, started).await? {
+            Some(run) => run,
+            None => self.submit_tool_outputs(run_id, request).await?,
+        };
+        self.wait_streamed(run, options, started).await
+    }
+
+    /// `run` if it is done, the run polled until it is done otherwise.
+    async fn wait_streamed(
+        &self,
+        run: RunObject,
+        options: &PollOptions,
+        started: Instant,
+    ) -> Result<RunObject, OpenAIError> {
+        if run.is_done() {
+            return Ok(run);
+        }
+        self.wait(&run.id, &options.remaining(started.elapsed()))
             .await
     }
 
+    /// Cancels a run and polls it until it reached a terminal status, usually `cancelled`.
+    ///
+    /// A run which already finished is returned as is, including when it finished between
+    /// being retrieved and cancelled, which the API rejects with a 400.
+    pub async fn cancel_and_wait(
+        &self,
+        run_id: &str,
+        options: &PollOptions,
+    ) -> Result<RunObject, OpenAIError> {
+        let finished = |run: &RunObject| run.is_done() && run.status != RunStatus::RequiresAction;
+        let run = self.retrieve(run_id).await?;
+        if finished(&run) {
+            return Ok(run);
+        }
+        if let Err(error) = self.cancel(run_id).await {
+            if error.status() != Some(reqwest::StatusCode::BAD_REQUEST) {
+                return Err(error);
+            }
+            let run = self.retrieve(run_id).await?;
+            return match finished(&run) {
+                true => Ok(run),
+                false => Err(error),
+            };
+        }
+        self.wait(run_id, options).await
+    }
+
     /// Modifies a run.
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn update(
@@ -94,18 +204,25 @@ impl<'c, C: Config> Runs<'c, C> {
             .post(
                 &format!("/threads/{}/runs/{run_id}", self.thread_id),
                 request,
+                &self.request_options,
             )
             .await
     }
 
     /// Returns a list of runs belonging to a thread.
+    ///
+    /// `query` is usually a [ListRunsQuery](crate::types::ListRunsQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListRunsResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query(&format!("/threads/{}/runs", self.thread_id), &query)
+            .get_with_query(
+                &format!("/threads/{}/runs", self.thread_id),
+                &query,
+                &self.request_options,
+            )
             .await
     }
 
@@ -123,6 +240,7 @@ impl<'c, C: Config> Runs<'c, C> {
                     self.thread_id
                 ),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -161,6 +279,7 @@ impl<'c, C: Config> Runs<'c, C> {
                 ),
                 request,
                 TryFrom::try_from,
+                &self.request_options,
             )
             .await)
     }
@@ -172,7 +291,237 @@ impl<'c, C: Config> Runs<'c, C> {
             .post(
                 &format!("/threads/{}/runs/{run_id}/cancel", self.thread_id),
                 (),
+                &self.request_options,
             )
             .await
     }
 }
+
+/// Ignores the events of a followed run, the run itself is returned by [dispatch].
+struct FollowRun;
+
+impl AssistantEventHandler for FollowRun {}
+
+/// Follow `stream` until its run is done, the stream ends or `max_wait` of `options` elapses.
+///
+/// Returns the run once done or as last seen when the stream ended early, `None` when the API
+/// rejected the streamed request so that no run was created or changed.
+async fn follow_stream(
+    stream: Result<AssistantEventStream, OpenAIError>,
+    options: &PollOptions,
+    started: Instant,
+) -> Result<Option<RunObject>, OpenAIError> {
+    let mut stream = match stream {
+        Ok(stream) => stream,
+        Err(error) if streaming_unsupported(&error) => return Ok(None),
+        Err(error) => return Err(error),
+    };
+    let mut last_run: Option<RunObject> = None;
+
+    let follow = async {
+        while let Some(event) = stream.next().await {
+            match event {
+                Ok(event) => {
+                    if let Some(run) = dispatch(&mut FollowRun, event) {
+                        let done = run.is_done();
+                        last_run = Some(run);
+                        if done {
+                            break;
+                        }
+                    }
+                }
+                // the run is polled from its last status
+                Err(_) if last_run.is_some() => break,
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    };
+    let max_wait = options.max_wait().unwrap_or(Duration::MAX);
+    let followed = tokio::time::timeout(max_wait.saturating_sub(started.elapsed()), follow).await;
+
+    match (followed, last_run) {
+        (Ok(Ok(())), Some(run)) => Ok(Some(run)),
+        (Ok(Ok(())), None) => Err(OpenAIError::StreamError(
+            "run stream ended without a run".into(),
+        )),
+        (Ok(Err(error)), _) => match unstreamed_run(&error) {
+            Some(run) => Ok(Some(run)),
+            None if streaming_unsupported(&error) => Ok(None),
+            None => Err(error),
+        },
+        (Err(_), run) => Err(OpenAIError::Timeout(match run {
+            Some(run) => format!(
+                "{} still {} after {:?}",
+                run.id,
+                run.poll_status(),
+                started.elapsed()
+            ),
+            None => format!("no run streamed after {:?}", started.elapsed()),
+        })),
+    }
+}
+
+/// Whether the API rejected a request because it does not stream runs.
+fn streaming_unsupported(error: &OpenAIError) -> bool {
+    let OpenAIError::ApiError(error) = error else {
+        return false;
+    };
+    match error.status.map(|status| status.as_u16()) {
+        Some(404 | 405 | 501) => true,
+        Some(400) => error.message.to_lowercase().contains("stream"),
+        _ => false,
+    }
+}
+
+/// The run of a streamed request the API answered without events, as a JSON run.
+fn unstreamed_run(error: &OpenAIError) -> Option<RunObject> {
+    let OpenAIError::StreamError(message) = error else {
+        return None;
+    };
+    let (_, body) = message
+        .strip_prefix("Invalid content type: ")?
+        .split_once('\n')?;
+    serde_json::from_str(body).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::{
+        transport::mock::{MockResponse, MockTransport},
+        types::AssistantStreamEvent,
+    };
+
+    fn run(status: &str) -> serde_json::Value {
+        serde_json::json!({
+            "id": "run_1", "object": "thread.run", "created_at": 0, "thread_id": "thread_1",
+            "assistant_id": "asst_1", "status": status, "model": "gpt-4o", "instructions": "",
+            "tools": [], "parallel_tool_calls": true
+        })
+    }
+
+    #[tokio::test]
+    async fn test_follow_stream() {
+        let events: Vec<AssistantStreamEvent> = serde_json::from_value(serde_json::json!([
+            {"event": "thread.run.created", "data": run("queued")},
+            {"event": "thread.run.in_progress", "data": run("in_progress")},
+            {"event": "thread.run.completed", "data": run("completed")},
+        ]))
+        .unwrap();
+        let stream: AssistantEventStream =
+            Box::pin(futures::stream::iter(events.into_iter().map(Ok)));
+        let followed = follow_stream(Ok(stream), &PollOptions::new(), Instant::now())
+            .await
+            .unwrap();
+        assert_eq!(followed.unwrap().status, RunStatus::Completed);
+
+        let rejected = OpenAIError::ApiError(crate::error::ApiError {
+            message: "Unrecognized request argument supplied: stream".into(),
+            r#type: None,
+            param: None,
+            code: None,
+            status: Some(reqwest::StatusCode::BAD_REQUEST),
+            headers: None,
+            in_stream: false,
+        });
+        let followed = follow_stream(Err(rejected), &PollOptions::new(), Instant::now())
+            .await
+            .unwrap();
+        assert!(followed.is_none());
+
+        let unstreamed = OpenAIError::StreamError(format!(
+            "Invalid content type: application/json\n{}",
+            run("queued")
+        ));
+        assert_eq!(
+            unstreamed_run(&unstreamed).unwrap().status,
+            RunStatus::Queued
+        );
+    }
+
+    /// Answers the requests with the scripted `(status, body)` responses, in order.
+    fn scripted(responses: Vec<(u16, serde_json::Value)>) -> Arc<MockTransport> {
+        MockTransport::script(
+            responses
+                .into_iter()
+                .map(|(status, body)| MockResponse::json(body).status(status))
+                .collect(),
+        )
+    }
+
+    fn cancel_rejected() -> serde_json::Value {
+        serde_json::json!({"error": {
+            "message": "Cannot cancel run with status 'completed'.",
+            "type": "invalid_request_error", "param": null, "code": null
+        }})
+    }
+
+    #[tokio::test]
+    async fn test_cancel_and_wait() {
+        let options = PollOptions::new().with_interval(Duration::from_millis(1));
+        let retrieve = "GET /v1/threads/thread_1/runs/run_1";
+        let cancel = "POST /v1/threads/thread_1/runs/run_1/cancel";
+
+        let backend = scripted(vec![
+            (200, run("in_progress")),
+            (200, run("cancelling")),
+            (200, run("cancelling")),
+            (200, run("cancelled")),
+        ]);
+        let client = Client::new().with_transport(backend.clone());
+        let cancelled = client
+            .threads()
+            .runs("thread_1")
+            .cancel_and_wait("run_1", &options)
+            .await
+            .unwrap();
+        assert_eq!(cancelled.status, RunStatus::Cancelled);
+        assert_eq!(backend.lines(), [retrieve, cancel, retrieve, retrieve]);
+
+        // finished before being cancelled
+        let backend = scripted(vec![(200, run("completed"))]);
+        let client = Client::new().with_transport(backend.clone());
+        let completed = client
+            .threads()
+            .runs("thread_1")
+            .cancel_and_wait("run_1", &options)
+            .await
+            .unwrap();
+        assert_eq!(completed.status, RunStatus::Completed);
+        assert_eq!(backend.lines(), [retrieve]);
+
+        // finished between being retrieved and cancelled
+        let backend = scripted(vec![
+            (200, run("in_progress")),
+            (400, cancel_rejected()),
+            (200, run("completed")),
+        ]);
+        let client = Client::new().with_transport(backend.clone());
+        let completed = client
+            .threads()
+            .runs("thread_1")
+            .cancel_and_wait("run_1", &options)
+            .await
+            .unwrap();
+        assert_eq!(completed.status, RunStatus::Completed);
+        assert_eq!(backend.lines(), [retrieve, cancel, retrieve]);
+
+        // rejected for another reason
+        let backend = scripted(vec![
+            (200, run("in_progress")),
+            (400, cancel_rejected()),
+            (200, run("in_progress")),
+        ]);
+        let client = Client::new().with_transport(backend.clone());
+        let error = client
+            .threads()
+            .runs("thread_1")
+            .cancel_and_wait("run_1", &options)
+            .await
+            .unwrap_err();
+        assert_eq!(error.status(), Some(reqwest::StatusCode::BAD_REQUEST));
+    }
+}
@@ -0,0 +1,181 @@
+//! The requests of a client as a [tower_service::Service], to compose tower middleware such as
+//! timeouts, concurrency limits, load shedding or retries around them.
+//!
+//! The service sends requests like [crate::Raw] does: with the base url, headers and
+//! credentials of the client, and its retries unless they are disabled with
+//! [crate::Client::with_backoff] in favor of a retry layer.
+//!
+//! ```no_run
+//! use async_openai::{
+//!     service::{OpenAIRequest, OpenAIService},
+//!     types::CreateEmbeddingResponse,
+//!     Client,
+//! };
+//! use tower_service::Service;
+//!
+//! # async fn example() -> Result<(), async_openai::error::OpenAIError> {
+//! let mut service = OpenAIService::new(Client::new());
+//! let request = OpenAIRequest::post(
+//!     "/embeddings",
+//!     serde_json::json!({"model": "text-embedding-3-small", "input": "hello"}),
+//! )?;
+//! let response: CreateEmbeddingResponse = service.call(request).await?.json()?;
+//! # Ok(())
+//! # }
+//! ```
+use std::task::{Context, Poll};
+
+use futures::future::BoxFuture;
+use reqwest::Method;
+use serde::Serialize;
+
+use crate::{
+    config::Config, error::OpenAIError, request_options::RequestOptions, Client, Raw, RawResponse,
+};
+
+/// A request of an [OpenAIService]: a GET, POST or DELETE of an API path.
+#[derive(Debug, Clone)]
+pub struct OpenAIRequest {
+    method: Method,
+    path: String,
+    body: Option<serde_json::Value>,
+    options: RequestOptions,
+}
+
+impl OpenAIRequest {
+    pub fn get(path: impl Into<String>) -> Self {
+        Self::new(Method::GET, path.into(), None)
+    }
+
+    /// POST of the JSON of `body`.
+    pub fn post<I: Serialize>(path: impl Into<String>, body: I) -> Result<Self, OpenAIError> {
+        let body = serde_json::to_value(body)
+            .map_err(|error| OpenAIError::InvalidArgument(error.to_string()))?;
+        Ok(Self::new(Method::POST, path.into(), Some(body)))
+    }
+
+    pub fn delete(path: impl Into<String>) -> Self {
+        Self::new(Method::DELETE, path.into(), None)
+    }
+
+    fn new(method: Method, path: String, body: Option<serde_json::Value>) -> Self {
+        Self {
+            method,
+            path,
+            body,
+            options: RequestOptions::new(),
+        }
+    }
+
+    /// Send the request with `options`, e.g. query parameters or headers.
+    pub fn with_options(mut self, options: RequestOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    pub fn method(&self) -> &Method {
+        &self.method
+    }
+
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The JSON body of a POST.
+    pub fn body(&self) -> Option<&serde_json::Value> {
+        self.body.as_ref()
+    }
+
+    pub fn options(&self) -> &RequestOptions {
+        &self.options
+    }
+}
+
+/// A [tower_service::Service] sending [OpenAIRequest]s with a client. It is always ready:
+/// limits are left to the layers around it.
+#[derive(Debug, Clone)]
+pub struct OpenAIService<C: Config> {
+    client: Client<C>,
+}
+
+impl<C: Config> OpenAIService<C> {
+    pub fn new(client: Client<C>) -> Self {
+        Self { client }
+    }
+
+    pub fn client(&self) -> &Client<C> {
+        &self.client
+    }
+}
+
+impl<C: Config> From<Client<C>> for OpenAIService<C> {
+    fn from(client: Client<C>) -> Self {
+        Self::new(client)
+    }
+}
+
+impl<C: Config + Clone + 'static> tower_service::Service<OpenAIRequest> for OpenAIService<C> {
+    type Response = RawResponse;
+    type Error = OpenAIError;
+    type Future = BoxFuture<'static, Result<RawResponse, OpenAIError>>;
+
+    fn poll_ready(&mut self, _: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: OpenAIRequest) -> Self::Future {
+        let client = self.client.clone();
+        Box::pin(async move {
+            let raw = Raw::new(&client).with_options(request.options);
+            match (request.method, request.body) {
+                (Method::POST, body) => {
+                    raw.post(&request.path, body.unwrap_or(serde_json::Value::Null))
+                        .await
+                }
+                (Method::DELETE, _) => raw.delete(&request.path).await,
+                _ => raw.get(&request.path).await,
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tower_service::Service;
+
+    use super::*;
+    use crate::transport::mock::{MockResponse, MockTransport};
+
+    /// Echoes the method and path of the request.
+    fn echo() -> std::sync::Arc<MockTransport> {
+        MockTransport::new(|request, _| {
+            Ok(MockResponse::new(format!(
+                r#"{{"method":"{}","path":"{}"}}"#,
+                request.method,
+                request.path()
+            )))
+        })
+    }
+
+    #[tokio::test]
+    async fn test_service() {
+        let mut service = OpenAIService::new(Client::new().with_transport(echo()));
+        futures::future::poll_fn(|cx| service.poll_ready(cx))
+            .await
+            .unwrap();
+
+        let request = OpenAIRequest::post("/moderations", serde_json::json!({"input": "hi"}));
+        let response = service.call(request.unwrap()).await.unwrap();
+        let body: serde_json::Value = response.json().unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({"method": "POST", "path": "/v1/moderations"})
+        );
+
+        let response = service
+            .call(OpenAIRequest::delete("/files/file-1"))
+            .await
+            .unwrap();
+        assert!(response.text().contains("DELETE"));
+    }
+}
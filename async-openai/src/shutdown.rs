@@ -0,0 +1,131 @@
+//! Requests in flight of a [crate::Client] and its clones, drained by [crate::Client::shutdown].
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+};
+
+use tokio::sync::Notify;
+use tokio_util::sync::CancellationToken;
+
+use crate::error::OpenAIError;
+
+#[derive(Debug, Default)]
+struct State {
+    closed: AtomicBool,
+    in_flight: Mutex<usize>,
+    idle: Notify,
+    cancel: CancellationToken,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Lifecycle(Arc<State>);
+
+/// A request or stream in flight, until dropped.
+#[derive(Debug)]
+pub(crate) struct InFlight(Lifecycle);
+
+impl Lifecycle {
+    /// Track a new request, failing once the client is shut down.
+    pub(crate) fn enter(&self) -> Result<InFlight, OpenAIError> {
+        if self.0.closed.load(Ordering::SeqCst) {
+            return Err(OpenAIError::ShutDown);
+        }
+        *self.lock() += 1;
+        Ok(InFlight(self.clone()))
+    }
+
+    /// Reject new requests and wait for the requests in flight, cancelling them after
+    /// `timeout`. Returns the number of cancelled requests.
+    pub(crate) async fn shutdown(&self, timeout: std::time::Duration) -> usize {
+        self.0.closed.store(true, Ordering::SeqCst);
+        if tokio::time::timeout(timeout, self.idle()).await.is_ok() {
+            return 0;
+        }
+        let cancelled = self.in_flight();
+        tracing::warn!("cancelling {cancelled} requests in flight after {timeout:?}");
+        self.0.cancel.cancel();
+        self.idle().await;
+        cancelled
+    }
+
+    fn in_flight(&self) -> usize {
+        *self.lock()
+    }
+
+    async fn idle(&self) {
+        loop {
+            let idle = self.0.idle.notified();
+            if self.in_flight() == 0 {
+                return;
+            }
+            idle.await;
+        }
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, usize> {
+        self.0
+            .in_flight
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl InFlight {
+    /// Resolves once the requests in flight are cancelled by a shutdown.
+    pub(crate) fn cancelled(&self) -> impl Future<Output = ()> + '_ {
+        self.0 .0.cancel.cancelled()
+    }
+
+    /// Run `request`, failing with [OpenAIError::ShutDown] if it is cancelled by a shutdown.
+    pub(crate) async fn run<T, F>(&self, request: F) -> Result<T, OpenAIError>
+    where
+        F: Future<Output = Result<T, OpenAIError>>,
+    {
+        tokio::select! {
+            result = request => result,
+            _ = self.cancelled() => Err(OpenAIError::ShutDown),
+        }
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        let mut in_flight = self.0.lock();
+        *in_flight -= 1;
+        if *in_flight == 0 {
+            self.0 .0.idle.notify_waiters();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_shutdown_drains_and_cancels() {
+        let lifecycle = Lifecycle::default();
+        let finished = lifecycle.enter().unwrap();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            drop(finished);
+        });
+        assert_eq!(lifecycle.shutdown(Duration::from_secs(5)).await, 0);
+        assert!(matches!(lifecycle.enter(), Err(OpenAIError::ShutDown)));
+
+        let lifecycle = Lifecycle::default();
+        let stuck = lifecycle.enter().unwrap();
+        let request = tokio::spawn(async move {
+            stuck
+                .run(std::future::pending::<Result<(), OpenAIError>>())
+                .await
+        });
+        assert_eq!(lifecycle.shutdown(Duration::from_millis(10)).await, 1);
+        assert!(matches!(request.await.unwrap(), Err(OpenAIError::ShutDown)));
+    }
+}
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{ListRunStepsResponse, RunStepObject},
     Client,
 };
@@ -12,14 +13,19 @@ pub struct Steps<'c, C: Config> {
     pub thread_id: String,
     pub run_id: String,
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Steps);
+crate::util::impl_api_group_debug!(Steps, "/threads/{}/runs/{}/steps", thread_id, run_id);
+
 impl<'c, C: Config> Steps<'c, C> {
     pub fn new(client: &'c Client<C>, thread_id: &str, run_id: &str) -> Self {
         Self {
             client,
             thread_id: thread_id.into(),
             run_id: run_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
@@ -27,14 +33,19 @@ impl<'c, C: Config> Steps<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, step_id: &str) -> Result<RunStepObject, OpenAIError> {
         self.client
-            .get(&format!(
-                "/threads/{}/runs/{}/steps/{step_id}",
-                self.thread_id, self.run_id
-            ))
+            .get(
+                &format!(
+                    "/threads/{}/runs/{}/steps/{step_id}",
+                    self.thread_id, self.run_id
+                ),
+                &self.request_options,
+            )
             .await
     }
 
     /// Returns a list of run steps belonging to a run.
+    ///
+    /// `query` is usually a [ListRunStepsQuery](crate::types::ListRunStepsQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListRunStepsResponse, OpenAIError>
     where
@@ -44,6 +55,7 @@ impl<'c, C: Config> Steps<'c, C> {
             .get_with_query(
                 &format!("/threads/{}/runs/{}/steps", self.thread_id, self.run_id),
                 &query,
+                &self.request_options,
             )
             .await
     }
@@ -1,9 +1,16 @@
+use std::future::Future;
+
+use futures::StreamExt;
+
 use crate::{
+    assistant_events::{self, AssistantEventHandler},
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        AssistantEventStream, CreateThreadAndRunRequest, CreateThreadRequest, DeleteThreadResponse,
-        ModifyThreadRequest, RunObject, ThreadObject,
+        AssistantEventStream, AssistantStreamEvent, CreateThreadAndRunRequest, CreateThreadRequest,
+        DeleteThreadResponse, ModifyThreadRequest, RunObject, RunStatus,
+        SubmitToolOutputsRunRequest, ThreadObject, ToolsOutputs,
     },
     Client, Messages, Runs,
 };
@@ -13,21 +20,28 @@ use crate::{
 /// Related guide: [Assistants](https://platform.openai.com/docs/assistants/overview)
 pub struct Threads<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Threads);
+crate::util::impl_api_group_debug!(Threads, "/threads");
+
 impl<'c, C: Config> Threads<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Call [Messages] group API to manage message in [thread_id] thread.
     pub fn messages(&self, thread_id: &str) -> Messages<C> {
-        Messages::new(self.client, thread_id)
+        Messages::new(self.client, thread_id).with_options(self.request_options.clone())
     }
 
     /// Call [Runs] group API to manage runs in [thread_id] thread.
     pub fn runs(&self, thread_id: &str) -> Runs<C> {
-        Runs::new(self.client, thread_id)
+        Runs::new(self.client, thread_id).with_options(self.request_options.clone())
     }
 
     /// Create a thread and run it in one request.
@@ -36,7 +50,9 @@ impl<'c, C: Config> Threads<'c, C> {
         &self,
         request: CreateThreadAndRunRequest,
     ) -> Result<RunObject, OpenAIError> {
-        self.client.post("/threads/runs", request).await
+        self.client
+            .post("/threads/runs", request, &self.request_options)
+            .await
     }
 
     /// Create a thread and run it in one request (streaming).
@@ -65,20 +81,64 @@ impl<'c, C: Config> Threads<'c, C> {
         }
         Ok(self
             .client
-            .post_stream_mapped_raw_events("/threads/runs", request, TryFrom::try_from)
+            .post_stream_mapped_raw_events(
+                "/threads/runs",
+                request,
+                TryFrom::try_from,
+                &self.request_options,
+            )
             .await)
     }
 
+    /// Route the events of a streamed run to `handler` until the run ends, submitting the
+    /// tool outputs returned by [AssistantEventHandler::on_requires_action] and handling the
+    /// continued stream. Returns the last run received, e.g. the completed run.
+    ///
+    /// `stream` is usually from [Threads::create_and_run_stream] or [Runs::create_stream].
+    pub async fn handle_stream<H: AssistantEventHandler + ?Sized>(
+        &self,
+        mut stream: AssistantEventStream,
+        handler: &mut H,
+    ) -> Result<Option<RunObject>, OpenAIError> {
+        let mut last_run = None;
+        loop {
+            while let Some(event) = stream.next().await {
+                if let Some(run) = assistant_events::dispatch(handler, event?) {
+                    last_run = Some(run);
+                }
+            }
+            let run = match last_run.take() {
+                Some(run) if run.status == RunStatus::RequiresAction => run,
+                run => return Ok(run),
+            };
+            let Some(tool_outputs) = handler.on_requires_action(&run).await? else {
+                return Ok(Some(run));
+            };
+            let request = SubmitToolOutputsRunRequest {
+                tool_outputs,
+                stream: Some(true),
+            };
+            stream = self
+                .runs(&run.thread_id)
+                .submit_tool_outputs_stream(&run.id, request)
+                .await?;
+        }
+    }
+
     /// Create a thread.
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: CreateThreadRequest) -> Result<ThreadObject, OpenAIError> {
-        self.client.post("/threads", request).await
+        self.client
+            .post("/threads", request, &self.request_options)
+            .await
     }
 
     /// Retrieves a thread.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, thread_id: &str) -> Result<ThreadObject, OpenAIError> {
-        self.client.get(&format!("/threads/{thread_id}")).await
+        self.client
+            .get(&format!("/threads/{thread_id}"), &self.request_options)
+            .await
     }
 
     /// Modifies a thread.
@@ -89,13 +149,133 @@ impl<'c, C: Config> Threads<'c, C> {
         request: ModifyThreadRequest,
     ) -> Result<ThreadObject, OpenAIError> {
         self.client
-            .post(&format!("/threads/{thread_id}"), request)
+            .post(
+                &format!("/threads/{thread_id}"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
     /// Delete a thread.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, thread_id: &str) -> Result<DeleteThreadResponse, OpenAIError> {
-        self.client.delete(&format!("/threads/{thread_id}")).await
+        self.client
+            .delete(&format!("/threads/{thread_id}"), &self.request_options)
+            .await
+    }
+}
+
+impl<C: Config + Clone + 'static> Threads<'_, C> {
+    /// The events of `stream` followed by the events of its run once continued: the outputs
+    /// returned by `tool_outputs` for the run requiring action are submitted and the events of
+    /// the continued stream are yielded, until the run ends or `tool_outputs` returns `None`.
+    ///
+    /// `stream` is usually from [Threads::create_and_run_stream] or [Runs::create_stream];
+    /// see [Threads::handle_stream] to handle the events with callbacks instead.
+    ///
+    /// ```no_run
+    /// use async_openai::{types::{CreateThreadAndRunRequestArgs, ToolsOutputs}, Client};
+    /// use futures::StreamExt;
+    ///
+    /// # async fn run() -> Result<(), async_openai::error::OpenAIError> {
+    /// let client = Client::new();
+    /// let threads = client.threads();
+    /// let request = CreateThreadAndRunRequestArgs::default()
+    ///     .assistant_id("asst_abc123")
+    ///     .build()?;
+    /// let stream = threads.create_and_run_stream(request).await?;
+    ///
+    /// let mut events = threads.continue_stream(stream, |run| async move {
+    ///     let tool_calls = run.required_action.map(|action| action.submit_tool_outputs.tool_calls);
+    ///     Ok(Some(
+    ///         tool_calls
+    ///             .unwrap_or_default()
+    ///             .into_iter()
+    ///             .map(|call| ToolsOutputs {
+    ///                 tool_call_id: Some(call.id),
+    ///                 output: Some("57".into()),
+    ///             })
+    ///             .collect(),
+    ///     ))
+    /// });
+    /// while let Some(event) = events.next().await {
+    ///     println!("{:?}", event?);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn continue_stream<F, Fut>(
+        &self,
+        stream: AssistantEventStream,
+        tool_outputs: F,
+    ) -> AssistantEventStream
+    where
+        F: FnMut(RunObject) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<Option<Vec<ToolsOutputs>>, OpenAIError>> + Send + 'static,
+    {
+        let continuation = Continuation {
+            client: self.client.clone(),
+            request_options: self.request_options.clone(),
+            stream: Some(stream),
+            requires_action: None,
+            tool_outputs,
+        };
+        Box::pin(futures::stream::unfold(continuation, Continuation::next))
+    }
+}
+
+/// State of [Threads::continue_stream].
+struct Continuation<C: Config, F> {
+    client: Client<C>,
+    request_options: RequestOptions,
+    /// The stream followed, `None` once the run ended or failed.
+    stream: Option<AssistantEventStream>,
+    requires_action: Option<RunObject>,
+    tool_outputs: F,
+}
+
+impl<C, F, Fut> Continuation<C, F>
+where
+    C: Config,
+    F: FnMut(RunObject) -> Fut,
+    Fut: Future<Output = Result<Option<Vec<ToolsOutputs>>, OpenAIError>>,
+{
+    async fn next(mut self) -> Option<(Result<AssistantStreamEvent, OpenAIError>, Self)> {
+        loop {
+            let stream = self.stream.as_mut()?;
+            match stream.next().await {
+                Some(Ok(event)) => {
+                    if let AssistantStreamEvent::ThreadRunRequiresAction(run) = &event {
+                        self.requires_action = Some(run.clone());
+                    }
+                    return Some((Ok(event), self));
+                }
+                Some(Err(error)) => {
+                    self.stream = None;
+                    return Some((Err(error), self));
+                }
+                None => self.stream = None,
+            }
+
+            let run = self.requires_action.take()?;
+            let tool_outputs = match (self.tool_outputs)(run.clone()).await {
+                Ok(Some(tool_outputs)) => tool_outputs,
+                Ok(None) => return None,
+                Err(error) => return Some((Err(error), self)),
+            };
+            let request = SubmitToolOutputsRunRequest {
+                tool_outputs,
+                stream: Some(true),
+            };
+            let stream = Runs::new(&self.client, &run.thread_id)
+                .with_options(self.request_options.clone())
+                .submit_tool_outputs_stream(&run.id, request)
+                .await;
+            match stream {
+                Ok(stream) => self.stream = Some(stream),
+                Err(error) => return Some((Err(error), self)),
+            }
+        }
     }
 }
@@ -1,6 +1,7 @@
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{CreateTokenizeRequest, CreateTokenizeResponse},
     Client,
 };
@@ -12,11 +13,18 @@ use crate::{
 /// Related guide: [Tokenize](https://docs.vllm.ai/en/latest/serving/openai_compatible_server.html#tokenizer-api)
 pub struct Tokenize<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Tokenize);
+crate::util::impl_api_group_debug!(Tokenize, "/tokenize");
+
 impl<'c, C: Config> Tokenize<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Create a tokenization for the given request
@@ -30,6 +38,8 @@ impl<'c, C: Config> Tokenize<'c, C> {
         &self,
         request: CreateTokenizeRequest,
     ) -> Result<CreateTokenizeResponse, OpenAIError> {
-        self.client.post("/tokenize", request).await
+        self.client
+            .post("/tokenize", request, &self.request_options)
+            .await
     }
 }
@@ -1,3 +1,98 @@
+//! Conversion traits, and object safe traits of the API groups to substitute them with mocks in tests.
+use futures::future::BoxFuture;
+
+#[cfg(feature = "schemars")]
+use crate::types::{
+    ChatCompletionMessageToolCall, ChatCompletionTool, ChatCompletionToolType, FunctionObject,
+};
+use crate::{
+    config::Config,
+    error::OpenAIError,
+    moderation::Moderations,
+    types::{
+        ChatCompletionResponseStream, CompletionResponseStream, CreateChatCompletionRequest,
+        CreateChatCompletionResponse, CreateCompletionRequest, CreateCompletionResponse,
+        CreateEmbeddingRequest, CreateEmbeddingResponse, CreateModerationRequest,
+        CreateModerationResponse, ListModelResponse, Model,
+    },
+    Chat, Completions, Embeddings, Models,
+};
+
+/// Arguments of a function tool, which the model calls with their JSON, usually implemented with
+/// `#[derive(OpenAiTool)]` with the `schemars` feature.
+///
+/// The derive macro names the tool after the type in snake case, describes it with its doc
+/// comment, and accepts `#[tool(name = "...", description = "...")]` to set them instead.
+///
+/// ```
+/// use async_openai::{traits::OpenAiTool, types::ChatCompletionMessageToolCall};
+/// use schemars::JsonSchema;
+/// use serde::Deserialize;
+///
+/// /// Get the current weather in a given location.
+/// #[derive(Deserialize, JsonSchema, OpenAiTool)]
+/// struct GetWeather {
+///     /// The city and state, e.g. San Francisco, CA
+///     location: String,
+/// }
+///
+/// let tool = GetWeather::tool()?;
+/// assert_eq!(tool.function.name, "get_weather");
+/// assert_eq!(tool.function.description.as_deref(), Some("Get the current weather in a given location."));
+///
+/// # let call: ChatCompletionMessageToolCall = serde_json::from_value(serde_json::json!({
+/// #     "id": "call_1", "type": "function",
+/// #     "function": {"name": "get_weather", "arguments": "{\"location\": \"Paris\"}"}
+/// # })).unwrap();
+/// // with a tool call of the response
+/// let arguments = GetWeather::from_tool_call(&call)?;
+/// assert_eq!(arguments.location, "Paris");
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+pub trait OpenAiTool: schemars::JsonSchema + serde::de::DeserializeOwned {
+    /// The name of the function.
+    const NAME: &'static str;
+    /// What the function does, used by the model to choose when and how to call it.
+    const DESCRIPTION: Option<&'static str> = None;
+
+    /// The strict function, see [FunctionObject::from_fn].
+    fn function() -> Result<FunctionObject, OpenAIError> {
+        FunctionObject::from_fn::<Self>(Self::NAME, Self::DESCRIPTION.unwrap_or_default())
+    }
+
+    /// The tool to send with a chat completion request.
+    fn tool() -> Result<ChatCompletionTool, OpenAIError> {
+        Ok(ChatCompletionTool {
+            r#type: ChatCompletionToolType::Function,
+            function: Self::function()?,
+        })
+    }
+
+    /// The arguments of a call, the JSON generated by the model.
+    fn from_arguments(arguments: &str) -> Result<Self, OpenAIError> {
+        serde_json::from_str(arguments)
+            .map_err(|error| OpenAIError::JSONDeserialize(error, arguments.to_string()))
+    }
+
+    /// The arguments of `call`, failing if it calls another function.
+    fn from_tool_call(call: &ChatCompletionMessageToolCall) -> Result<Self, OpenAIError> {
+        if call.function.name != Self::NAME {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "tool call `{}` is not a call of `{}`",
+                call.function.name,
+                Self::NAME
+            )));
+        }
+        Self::from_arguments(&call.function.arguments)
+    }
+}
+
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+pub use async_openai_macros::OpenAiTool;
+
 pub trait AsyncTryFrom<T>: Sized {
     /// The type returned in the event of a conversion error.
     type Error;
@@ -5,3 +100,156 @@ pub trait AsyncTryFrom<T>: Sized {
     /// Performs the conversion.
     fn try_from(value: T) -> impl std::future::Future<Output = Result<Self, Self::Error>> + Send;
 }
+
+/// Object safe interface of [Chat], for code which should be testable without network.
+///
+/// ```
+/// use async_openai::{
+///     error::OpenAIError,
+///     traits::ChatApi,
+///     types::{ChatCompletionResponseStream, CreateChatCompletionRequest, CreateChatCompletionResponse},
+///     Client,
+/// };
+/// use futures::future::BoxFuture;
+///
+/// async fn summarize(chat: &dyn ChatApi, request: CreateChatCompletionRequest) -> Result<String, OpenAIError> {
+///     let response = chat.create(request).await?;
+///     Ok(response.choices[0].message.content.clone().unwrap_or_default())
+/// }
+///
+/// struct MockChat(CreateChatCompletionResponse);
+///
+/// impl ChatApi for MockChat {
+///     fn create(&self, _: CreateChatCompletionRequest) -> BoxFuture<'_, Result<CreateChatCompletionResponse, OpenAIError>> {
+///         Box::pin(async move { Ok(self.0.clone()) })
+///     }
+///
+///     fn create_stream(&self, _: CreateChatCompletionRequest) -> BoxFuture<'_, Result<ChatCompletionResponseStream, OpenAIError>> {
+///         Box::pin(async { Err(OpenAIError::InvalidArgument("not mocked".into())) })
+///     }
+/// }
+///
+/// # async fn example(request: CreateChatCompletionRequest, canned: CreateChatCompletionResponse) -> Result<(), OpenAIError> {
+/// // in production
+/// let client = Client::new();
+/// summarize(&client.chat(), request.clone()).await?;
+/// // in tests
+/// summarize(&MockChat(canned), request).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub trait ChatApi: Send + Sync {
+    /// See [Chat::create].
+    fn create(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> BoxFuture<'_, Result<CreateChatCompletionResponse, OpenAIError>>;
+
+    /// See [Chat::create_stream].
+    fn create_stream(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> BoxFuture<'_, Result<ChatCompletionResponseStream, OpenAIError>>;
+}
+
+/// Object safe interface of [Completions].
+pub trait CompletionsApi: Send + Sync {
+    /// See [Completions::create].
+    fn create(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> BoxFuture<'_, Result<CreateCompletionResponse, OpenAIError>>;
+
+    /// See [Completions::create_stream].
+    fn create_stream(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> BoxFuture<'_, Result<CompletionResponseStream, OpenAIError>>;
+}
+
+/// Object safe interface of [Embeddings].
+pub trait EmbeddingsApi: Send + Sync {
+    /// See [Embeddings::create].
+    fn create(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> BoxFuture<'_, Result<CreateEmbeddingResponse, OpenAIError>>;
+}
+
+/// Object safe interface of [Moderations].
+pub trait ModerationsApi: Send + Sync {
+    /// See [Moderations::create].
+    fn create(
+        &self,
+        request: CreateModerationRequest,
+    ) -> BoxFuture<'_, Result<CreateModerationResponse, OpenAIError>>;
+}
+
+/// Object safe interface of [Models].
+pub trait ModelsApi: Send + Sync {
+    /// See [Models::list].
+    fn list(&self) -> BoxFuture<'_, Result<ListModelResponse, OpenAIError>>;
+
+    /// See [Models::retrieve].
+    fn retrieve<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Model, OpenAIError>>;
+}
+
+impl<C: Config> ChatApi for Chat<'_, C> {
+    fn create(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> BoxFuture<'_, Result<CreateChatCompletionResponse, OpenAIError>> {
+        Box::pin(Chat::create(self, request))
+    }
+
+    fn create_stream(
+        &self,
+        request: CreateChatCompletionRequest,
+    ) -> BoxFuture<'_, Result<ChatCompletionResponseStream, OpenAIError>> {
+        Box::pin(Chat::create_stream(self, request))
+    }
+}
+
+impl<C: Config> CompletionsApi for Completions<'_, C> {
+    fn create(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> BoxFuture<'_, Result<CreateCompletionResponse, OpenAIError>> {
+        Box::pin(Completions::create(self, request))
+    }
+
+    fn create_stream(
+        &self,
+        request: CreateCompletionRequest,
+    ) -> BoxFuture<'_, Result<CompletionResponseStream, OpenAIError>> {
+        Box::pin(Completions::create_stream(self, request))
+    }
+}
+
+impl<C: Config> EmbeddingsApi for Embeddings<'_, C> {
+    fn create(
+        &self,
+        request: CreateEmbeddingRequest,
+    ) -> BoxFuture<'_, Result<CreateEmbeddingResponse, OpenAIError>> {
+        Box::pin(Embeddings::create(self, request))
+    }
+}
+
+impl<C: Config> ModerationsApi for Moderations<'_, C> {
+    fn create(
+        &self,
+        request: CreateModerationRequest,
+    ) -> BoxFuture<'_, Result<CreateModerationResponse, OpenAIError>> {
+        Box::pin(Moderations::create(self, request))
+    }
+}
+
+impl<C: Config> ModelsApi for Models<'_, C> {
+    fn list(&self) -> BoxFuture<'_, Result<ListModelResponse, OpenAIError>> {
+        Box::pin(Models::list(self))
+    }
+
+    fn retrieve<'a>(&'a self, id: &'a str) -> BoxFuture<'a, Result<Model, OpenAIError>> {
+        Box::pin(Models::retrieve(self, id))
+    }
+}
@@ -0,0 +1,430 @@
+//! Conversion of chat message histories to and from transcript formats,
+//! to prepare fine-tuning datasets or read conversation logs.
+use std::{collections::VecDeque, fmt};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessage,
+        ChatCompletionRequestAssistantMessageContent,
+        ChatCompletionRequestAssistantMessageContentPart, ChatCompletionRequestDeveloperMessage,
+        ChatCompletionRequestDeveloperMessageContent, ChatCompletionRequestMessage,
+        ChatCompletionRequestSystemMessage, ChatCompletionRequestSystemMessageContent,
+        ChatCompletionRequestSystemMessageContentPart, ChatCompletionRequestToolMessage,
+        ChatCompletionRequestToolMessageContent, ChatCompletionRequestToolMessageContentPart,
+        ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
+        ChatCompletionRequestUserMessageContentPart,
+    },
+};
+
+/// Supported transcript formats.
+///
+/// Only [TranscriptFormat::OpenAI] is lossless, the other formats keep the text of the messages
+/// and their tool calls, dropping images, audio, names and deprecated function messages.
+///
+/// ```
+/// use async_openai::{transcript::TranscriptFormat, types::ChatCompletionRequestMessage};
+///
+/// let input = "### system\n\nYou are terse.\n\n### user\n\nHi\n\n### assistant\n\nHello.\n";
+/// let messages = TranscriptFormat::Markdown.import(input)?;
+/// assert_eq!(messages.len(), 3);
+///
+/// let sharegpt = TranscriptFormat::ShareGpt.export(&messages)?;
+/// assert!(sharegpt.starts_with(r#"{"conversations":[{"from":"system""#));
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TranscriptFormat {
+    /// `{"messages": [...]}` with messages as sent to the API, one line of a fine-tuning JSONL file.
+    /// A bare array of messages is also accepted on import.
+    OpenAI,
+    /// `### <role>` headings followed by the message text. Tool messages use `### tool <tool_call_id>`
+    /// and assistant tool calls are kept in a trailing ` ```tool_calls ` JSON code block.
+    Markdown,
+    /// `{"conversations": [{"from": ..., "value": ...}]}` with `system`, `human`, `gpt`,
+    /// `function_call` and `observation` turns.
+    ShareGpt,
+}
+
+impl fmt::Display for TranscriptFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                TranscriptFormat::OpenAI => "OpenAI",
+                TranscriptFormat::Markdown => "Markdown",
+                TranscriptFormat::ShareGpt => "ShareGPT",
+            }
+        )
+    }
+}
+
+impl TranscriptFormat {
+    /// Write `messages` in this format.
+    pub fn export(&self, messages: &[ChatCompletionRequestMessage]) -> Result<String, OpenAIError> {
+        match self {
+            TranscriptFormat::OpenAI => to_json(&OpenAITranscript {
+                messages: messages.to_vec(),
+            }),
+            TranscriptFormat::Markdown => export_markdown(messages),
+            TranscriptFormat::ShareGpt => export_sharegpt(messages),
+        }
+    }
+
+    /// Read messages written in this format.
+    pub fn import(&self, input: &str) -> Result<Vec<ChatCompletionRequestMessage>, OpenAIError> {
+        match self {
+            TranscriptFormat::OpenAI => {
+                let transcript: OpenAITranscriptInput =
+                    serde_json::from_str(input).map_err(|e| self.invalid(e))?;
+                Ok(match transcript {
+                    OpenAITranscriptInput::Object(transcript) => transcript.messages,
+                    OpenAITranscriptInput::Array(messages) => messages,
+                })
+            }
+            TranscriptFormat::Markdown => import_markdown(input),
+            TranscriptFormat::ShareGpt => import_sharegpt(input),
+        }
+    }
+
+    fn invalid<E: fmt::Display>(&self, error: E) -> OpenAIError {
+        OpenAIError::InvalidArgument(format!("invalid {self} transcript: {error}"))
+    }
+
+    fn unsupported(&self, what: &str) -> OpenAIError {
+        OpenAIError::InvalidArgument(format!("{what} cannot be written as a {self} transcript"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct OpenAITranscript {
+    messages: Vec<ChatCompletionRequestMessage>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OpenAITranscriptInput {
+    Object(OpenAITranscript),
+    Array(Vec<ChatCompletionRequestMessage>),
+}
+
+/// Role and text of a message, tool calls excluded.
+fn message_text(message: &ChatCompletionRequestMessage) -> Option<(&'static str, String)> {
+    Some(match message {
+        ChatCompletionRequestMessage::Developer(message) => (
+            "developer",
+            match &message.content {
+                ChatCompletionRequestDeveloperMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestDeveloperMessageContent::Array(parts) => {
+                    join(parts.iter().map(|part| part.text.as_str()))
+                }
+            },
+        ),
+        ChatCompletionRequestMessage::System(message) => (
+            "system",
+            match &message.content {
+                ChatCompletionRequestSystemMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestSystemMessageContent::Array(parts) => {
+                    join(parts.iter().map(|part| match part {
+                        ChatCompletionRequestSystemMessageContentPart::Text(part) => {
+                            part.text.as_str()
+                        }
+                    }))
+                }
+            },
+        ),
+        ChatCompletionRequestMessage::User(message) => (
+            "user",
+            match &message.content {
+                ChatCompletionRequestUserMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestUserMessageContent::Array(parts) => {
+                    join(parts.iter().filter_map(|part| match part {
+                        ChatCompletionRequestUserMessageContentPart::Text(part) => {
+                            Some(part.text.as_str())
+                        }
+                        _ => None,
+                    }))
+                }
+            },
+        ),
+        ChatCompletionRequestMessage::Assistant(message) => (
+            "assistant",
+            match &message.content {
+                Some(ChatCompletionRequestAssistantMessageContent::Text(text)) => text.clone(),
+                Some(ChatCompletionRequestAssistantMessageContent::Array(parts)) => {
+                    join(parts.iter().map(|part| match part {
+                        ChatCompletionRequestAssistantMessageContentPart::Text(part) => {
+                            part.text.as_str()
+                        }
+                        ChatCompletionRequestAssistantMessageContentPart::Refusal(part) => {
+                            part.refusal.as_str()
+                        }
+                    }))
+                }
+                None => message.refusal.clone().unwrap_or_default(),
+            },
+        ),
+        ChatCompletionRequestMessage::Tool(message) => (
+            "tool",
+            match &message.content {
+                ChatCompletionRequestToolMessageContent::Text(text) => text.clone(),
+                ChatCompletionRequestToolMessageContent::Array(parts) => {
+                    join(parts.iter().map(|part| match part {
+                        ChatCompletionRequestToolMessageContentPart::Text(part) => {
+                            part.text.as_str()
+                        }
+                    }))
+                }
+            },
+        ),
+        ChatCompletionRequestMessage::Function(_) => return None,
+    })
+}
+
+fn to_json<T: Serialize + ?Sized>(value: &T) -> Result<String, OpenAIError> {
+    serde_json::to_string(value).map_err(|e| OpenAIError::InvalidArgument(e.to_string()))
+}
+
+fn join<'a>(parts: impl Iterator<Item = &'a str>) -> String {
+    parts.collect::<Vec<_>>().join("\n")
+}
+
+/// Message of `role` with `text`, `None` for unknown roles.
+fn text_message(role: &str, text: String) -> Option<ChatCompletionRequestMessage> {
+    Some(match role {
+        "developer" => ChatCompletionRequestDeveloperMessage::from(text).into(),
+        "system" => ChatCompletionRequestSystemMessage::from(text).into(),
+        "user" => ChatCompletionRequestUserMessage::from(text).into(),
+        "assistant" => ChatCompletionRequestAssistantMessage::from(text).into(),
+        _ => return None,
+    })
+}
+
+const TOOL_CALLS_FENCE: &str = "```tool_calls";
+
+fn export_markdown(messages: &[ChatCompletionRequestMessage]) -> Result<String, OpenAIError> {
+    let format = TranscriptFormat::Markdown;
+    let mut output = String::new();
+    for message in messages {
+        let (role, text) =
+            message_text(message).ok_or_else(|| format.unsupported("a function message"))?;
+        match message {
+            ChatCompletionRequestMessage::Tool(tool) => {
+                output.push_str(&format!("### tool {}\n\n", tool.tool_call_id))
+            }
+            _ => output.push_str(&format!("### {role}\n\n")),
+        }
+        if !text.is_empty() {
+            output.push_str(&text);
+            output.push_str("\n\n");
+        }
+        if let ChatCompletionRequestMessage::Assistant(ChatCompletionRequestAssistantMessage {
+            tool_calls: Some(tool_calls),
+            ..
+        }) = message
+        {
+            output.push_str(TOOL_CALLS_FENCE);
+            output.push('\n');
+            output.push_str(&to_json(tool_calls)?);
+            output.push_str("\n```\n\n");
+        }
+    }
+    Ok(output)
+}
+
+fn import_markdown(input: &str) -> Result<Vec<ChatCompletionRequestMessage>, OpenAIError> {
+    let format = TranscriptFormat::Markdown;
+    // heading and body lines of each message
+    let mut sections: Vec<(&str, Vec<&str>)> = Vec::new();
+    for line in input.lines() {
+        match line.strip_prefix("### ") {
+            Some(heading) => sections.push((heading.trim(), Vec::new())),
+            None => match sections.last_mut() {
+                Some((_, body)) => body.push(line),
+                None if line.trim().is_empty() => {}
+                None => return Err(format.invalid("expected a `### <role>` heading")),
+            },
+        }
+    }
+
+    let mut messages = Vec::with_capacity(sections.len());
+    for (heading, body) in sections {
+        let mut body = body.join("\n").trim().to_string();
+        let (role, tool_call_id) = heading.split_once(' ').unwrap_or((heading, ""));
+
+        if role == "tool" {
+            if tool_call_id.is_empty() {
+                return Err(format.invalid("tool message without a tool call id"));
+            }
+            messages.push(ChatCompletionRequestToolMessage::new(tool_call_id.trim(), body).into());
+            continue;
+        }
+
+        let mut tool_calls = None;
+        if role == "assistant" {
+            if let Some(start) = body.rfind(TOOL_CALLS_FENCE) {
+                let json = body[start + TOOL_CALLS_FENCE.len()..]
+                    .trim()
+                    .strip_suffix("```")
+                    .ok_or_else(|| format.invalid("unterminated tool_calls block"))?;
+                let calls: Vec<ChatCompletionMessageToolCall> =
+                    serde_json::from_str(json).map_err(|e| format.invalid(e))?;
+                tool_calls = Some(calls);
+                body = body[..start].trim_end().to_string();
+            }
+        }
+
+        let message = match tool_calls {
+            Some(tool_calls) => ChatCompletionRequestAssistantMessage {
+                content: (!body.is_empty()).then(|| body.into()),
+                tool_calls: Some(tool_calls),
+                ..Default::default()
+            }
+            .into(),
+            None => text_message(role, body)
+                .ok_or_else(|| format.invalid(format!("unknown role `{role}`")))?,
+        };
+        messages.push(message);
+    }
+    Ok(messages)
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ShareGptTranscript {
+    conversations: Vec<ShareGptTurn>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ShareGptTurn {
+    from: String,
+    value: String,
+}
+
+/// Value of a `function_call` turn.
+#[derive(Debug, Serialize, Deserialize)]
+struct ShareGptFunctionCall {
+    name: String,
+    arguments: serde_json::Value,
+}
+
+fn export_sharegpt(messages: &[ChatCompletionRequestMessage]) -> Result<String, OpenAIError> {
+    let format = TranscriptFormat::ShareGpt;
+    let mut conversations = Vec::with_capacity(messages.len());
+    let mut turn = |from: &str, value: String| {
+        conversations.push(ShareGptTurn {
+            from: from.into(),
+            value,
+        })
+    };
+
+    for message in messages {
+        let (role, text) =
+            message_text(message).ok_or_else(|| format.unsupported("a function message"))?;
+        match message {
+            ChatCompletionRequestMessage::Assistant(assistant) => {
+                if !text.is_empty() || assistant.tool_calls.is_none() {
+                    turn("gpt", text);
+                }
+                for call in assistant.tool_calls.iter().flatten() {
+                    // arguments are kept as JSON when the model generated valid JSON
+                    let arguments = serde_json::from_str(&call.function.arguments)
+                        .unwrap_or_else(|_| call.function.arguments.clone().into());
+                    turn(
+                        "function_call",
+                        to_json(&ShareGptFunctionCall {
+                            name: call.function.name.clone(),
+                            arguments,
+                        })?,
+                    );
+                }
+            }
+            ChatCompletionRequestMessage::Tool(_) => turn("observation", text),
+            ChatCompletionRequestMessage::User(_) => turn("human", text),
+            _ => turn(role, text),
+        }
+    }
+
+    to_json(&ShareGptTranscript { conversations })
+}
+
+fn import_sharegpt(input: &str) -> Result<Vec<ChatCompletionRequestMessage>, OpenAIError> {
+    let format = TranscriptFormat::ShareGpt;
+    let transcript: ShareGptTranscript =
+        serde_json::from_str(input).map_err(|e| format.invalid(e))?;
+
+    let mut messages: Vec<ChatCompletionRequestMessage> = Vec::new();
+    // ids generated for tool calls not answered by an observation yet
+    let mut pending_calls = VecDeque::new();
+    let mut call_count = 0;
+
+    for turn in transcript.conversations {
+        let message = match turn.from.as_str() {
+            "system" | "developer" => text_message(&turn.from, turn.value),
+            "human" | "user" => text_message("user", turn.value),
+            "gpt" | "assistant" => text_message("assistant", turn.value),
+            "function_call" => {
+                let call: ShareGptFunctionCall =
+                    serde_json::from_str(&turn.value).map_err(|e| format.invalid(e))?;
+                call_count += 1;
+                let id = format!("call_{call_count}");
+                let arguments = match call.arguments {
+                    serde_json::Value::String(arguments) => arguments,
+                    arguments => arguments.to_string(),
+                };
+                let call = ChatCompletionMessageToolCall::function(&id, call.name, arguments);
+                pending_calls.push_back(id);
+
+                // consecutive calls, or a call following the text of the assistant, form one message
+                if let Some(ChatCompletionRequestMessage::Assistant(assistant)) =
+                    messages.last_mut()
+                {
+                    assistant.tool_calls.get_or_insert_with(Vec::new).push(call);
+                    continue;
+                }
+                Some(ChatCompletionRequestAssistantMessage::from(vec![call]).into())
+            }
+            "observation" | "tool" => {
+                let id = pending_calls
+                    .pop_front()
+                    .ok_or_else(|| format.invalid("observation without a function call"))?;
+                Some(ChatCompletionRequestToolMessage::new(id, turn.value).into())
+            }
+            from => return Err(format.invalid(format!("unknown turn `{from}`"))),
+        };
+        messages.extend(message);
+    }
+    Ok(messages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn conversation() -> Vec<ChatCompletionRequestMessage> {
+        let call =
+            ChatCompletionMessageToolCall::function("call_1", "get_weather", r#"{"city":"Paris"}"#);
+        vec![
+            ChatCompletionRequestSystemMessage::from("You are terse.").into(),
+            ChatCompletionRequestUserMessage::from("Weather in Paris?").into(),
+            ChatCompletionRequestAssistantMessage::from(vec![call.clone()]).into(),
+            call.respond("sunny").into(),
+            ChatCompletionRequestAssistantMessage::from("Sunny.").into(),
+        ]
+    }
+
+    #[test]
+    fn test_transcript_round_trips() {
+        let messages = conversation();
+        for format in [
+            TranscriptFormat::OpenAI,
+            TranscriptFormat::Markdown,
+            TranscriptFormat::ShareGpt,
+        ] {
+            let exported = format.export(&messages).unwrap();
+            assert_eq!(format.import(&exported).unwrap(), messages, "{format}");
+        }
+    }
+}
@@ -0,0 +1,377 @@
+//! Transport sending the HTTP requests of a [crate::Client], the [reqwest::Client] by default.
+//!
+//! A custom [HttpClient] set with [crate::Client::with_transport] sends every request except
+//! the streaming ones, which are sent by the [reqwest::Client] of the client: requests and
+//! responses stay [reqwest] types, a transport built on another library converts them, e.g. with
+//! `reqwest::Response::from(http::Response)`.
+use std::{fmt, sync::Arc};
+
+use futures::future::BoxFuture;
+
+use crate::error::OpenAIError;
+
+/// Sends HTTP requests, to plug in hyper with custom connectors, a proxy over a unix socket,
+/// middleware stacks or test doubles.
+///
+/// The [reqwest::Client] transport fails with [OpenAIError::Reqwest]. A custom transport failing
+/// to connect returns [OpenAIError::Transport], so that the client fails over to the fallback api
+/// bases of its config, if any, as it does on the connection errors of reqwest.
+///
+/// ```
+/// use async_openai::{error::OpenAIError, transport::HttpClient, Client};
+/// use futures::future::BoxFuture;
+///
+/// /// Sends requests with reqwest, logging them.
+/// struct Logged(reqwest::Client);
+///
+/// impl HttpClient for Logged {
+///     fn execute(
+///         &self,
+///         request: reqwest::Request,
+///     ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+///         Box::pin(async move {
+///             println!("{} {}", request.method(), request.url());
+///             Ok(self.0.execute(request).await?)
+///         })
+///     }
+/// }
+///
+/// let client = Client::new().with_transport(Logged(reqwest::Client::new()));
+/// ```
+pub trait HttpClient: Send + Sync {
+    /// Send `request`, returning the response once its headers are received.
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>>;
+}
+
+impl HttpClient for reqwest::Client {
+    fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+        Box::pin(async move { Ok(reqwest::Client::execute(self, request).await?) })
+    }
+}
+
+/// Custom transport of a [crate::Client].
+#[derive(Clone)]
+pub(crate) struct Transport(Arc<dyn HttpClient>);
+
+impl Transport {
+    pub(crate) fn new(http_client: Arc<dyn HttpClient>) -> Self {
+        Self(http_client)
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        request: reqwest::Request,
+    ) -> Result<reqwest::Response, OpenAIError> {
+        self.0.execute(request).await
+    }
+}
+
+impl fmt::Debug for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Transport").finish_non_exhaustive()
+    }
+}
+
+/// Configurable [HttpClient] answering the requests of the tests.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::{
+        collections::VecDeque,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use bytes::Bytes;
+    use futures::future::BoxFuture;
+    use reqwest::{header::HeaderMap, Method, Url};
+
+    use super::HttpClient;
+    use crate::error::OpenAIError;
+
+    /// Request received by a [MockTransport], with its body read.
+    #[derive(Debug, Clone)]
+    pub(crate) struct MockRequest {
+        pub(crate) method: Method,
+        pub(crate) url: Url,
+        pub(crate) headers: HeaderMap,
+        pub(crate) body: Bytes,
+    }
+
+    impl MockRequest {
+        pub(crate) fn path(&self) -> &str {
+            self.url.path()
+        }
+
+        /// Method and path of the request, such as `POST /v1/batches/batch_1/cancel`.
+        pub(crate) fn line(&self) -> String {
+            format!("{} {}", self.method, self.url.path())
+        }
+
+        pub(crate) fn header(&self, name: &str) -> Option<&str> {
+            self.headers.get(name).map(|value| value.to_str().unwrap())
+        }
+
+        /// JSON body of the request, panicking on other bodies.
+        pub(crate) fn json(&self) -> serde_json::Value {
+            serde_json::from_slice(&self.body).unwrap()
+        }
+    }
+
+    /// Response of a [MockTransport], `200 OK` unless set otherwise.
+    pub(crate) struct MockResponse {
+        status: u16,
+        headers: Vec<(&'static str, String)>,
+        body: reqwest::Body,
+        delay: Option<Duration>,
+    }
+
+    impl MockResponse {
+        pub(crate) fn new(body: impl Into<reqwest::Body>) -> Self {
+            Self {
+                status: 200,
+                headers: Vec::new(),
+                body: body.into(),
+                delay: None,
+            }
+        }
+
+        pub(crate) fn json(body: serde_json::Value) -> Self {
+            Self::new(body.to_string()).header("content-type", "application/json")
+        }
+
+        pub(crate) fn status(mut self, status: u16) -> Self {
+            self.status = status;
+            self
+        }
+
+        pub(crate) fn header(mut self, name: &'static str, value: impl Into<String>) -> Self {
+            self.headers.push((name, value.into()));
+            self
+        }
+
+        /// Answer after `delay`.
+        pub(crate) fn delay(mut self, delay: Duration) -> Self {
+            self.delay = Some(delay);
+            self
+        }
+
+        fn into_response(self) -> reqwest::Response {
+            let mut builder = axum::http::Response::builder().status(self.status);
+            for (name, value) in self.headers {
+                builder = builder.header(name, value);
+            }
+            reqwest::Response::from(builder.body(self.body).unwrap())
+        }
+    }
+
+    type Handler =
+        dyn Fn(&MockRequest, usize) -> Result<MockResponse, OpenAIError> + Send + Sync + 'static;
+
+    /// Answers the requests with a handler called with each request and its index, and records
+    /// them. Set it with `Client::with_transport(mock.clone())` to inspect the requests after.
+    pub(crate) struct MockTransport {
+        handler: Box<Handler>,
+        requests: Mutex<Vec<MockRequest>>,
+    }
+
+    impl MockTransport {
+        pub(crate) fn new(
+            handler: impl Fn(&MockRequest, usize) -> Result<MockResponse, OpenAIError>
+                + Send
+                + Sync
+                + 'static,
+        ) -> Arc<Self> {
+            Arc::new(Self {
+                handler: Box::new(handler),
+                requests: Default::default(),
+            })
+        }
+
+        /// Answers every request with `body`.
+        pub(crate) fn body(body: impl Into<String>) -> Arc<Self> {
+            let body = body.into();
+            Self::new(move |_, _| Ok(MockResponse::new(body.clone())))
+        }
+
+        /// Answers the requests with `responses`, in order, panicking once they are exhausted.
+        pub(crate) fn script(responses: Vec<MockResponse>) -> Arc<Self> {
+            let responses = Mutex::new(VecDeque::from(responses));
+            Self::new(move |request, _| {
+                Ok(responses
+                    .lock()
+                    .unwrap()
+                    .pop_front()
+                    .unwrap_or_else(|| panic!("unexpected request {}", request.line())))
+            })
+        }
+
+        pub(crate) fn requests(&self) -> Vec<MockRequest> {
+            self.requests.lock().unwrap().clone()
+        }
+
+        /// [MockRequest::line] of the requests.
+        pub(crate) fn lines(&self) -> Vec<String> {
+            self.requests().iter().map(MockRequest::line).collect()
+        }
+
+        pub(crate) fn len(&self) -> usize {
+            self.requests.lock().unwrap().len()
+        }
+
+        /// Wait for the transport to have received `count` requests, such as the ones sent by
+        /// spawned tasks.
+        pub(crate) async fn received(&self, count: usize) {
+            while self.len() < count {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+    }
+
+    impl HttpClient for Arc<MockTransport> {
+        fn execute(
+            &self,
+            mut request: reqwest::Request,
+        ) -> BoxFuture<'_, Result<reqwest::Response, OpenAIError>> {
+            Box::pin(async move {
+                let body = match request.body_mut().take() {
+                    Some(body) => match body.as_bytes() {
+                        Some(bytes) => Bytes::copy_from_slice(bytes),
+                        None => axum::body::to_bytes(axum::body::Body::new(body), usize::MAX)
+                            .await
+                            .map_err(|e| OpenAIError::Transport(e.to_string()))?,
+                    },
+                    None => Bytes::new(),
+                };
+                let request = MockRequest {
+                    method: request.method().clone(),
+                    url: request.url().clone(),
+                    headers: request.headers().clone(),
+                    body,
+                };
+                let index = {
+                    let mut requests = self.requests.lock().unwrap();
+                    requests.push(request.clone());
+                    requests.len() - 1
+                };
+                let response = (self.handler)(&request, index)?;
+                if let Some(delay) = response.delay {
+                    tokio::time::sleep(delay).await;
+                }
+                Ok(response.into_response())
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mock::{MockResponse, MockTransport};
+    use crate::{error::OpenAIError, types::Model, Client};
+
+    const EMBEDDINGS: &str =
+        r#"{"object":"list","data":[],"model":"m","usage":{"prompt_tokens":0,"total_tokens":0}}"#;
+
+    #[tokio::test]
+    async fn test_custom_transport() {
+        let transport = MockTransport::body(
+            r#"{"id":"gpt-4o","object":"model","created":0,"owned_by":"openai"}"#,
+        );
+        let client = Client::new().with_transport(transport.clone());
+
+        let model: Model = client.models().retrieve("gpt-4o").await.unwrap();
+        assert_eq!(model.id, "gpt-4o");
+        let urls: Vec<String> = transport
+            .requests()
+            .iter()
+            .map(|request| request.url.to_string())
+            .collect();
+        assert_eq!(urls, ["https://api.openai.com/v1/models/gpt-4o"]);
+    }
+
+    /// Idempotency keys of the requests received by `transport`.
+    fn idempotency_keys(transport: &MockTransport) -> Vec<Option<String>> {
+        transport
+            .requests()
+            .iter()
+            .map(|request| request.header("idempotency-key").map(str::to_string))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn test_idempotent_retry() {
+        let backoff = backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(std::time::Duration::from_millis(1))
+            .build();
+        let request = crate::types::CreateEmbeddingRequestArgs::default()
+            .model("m")
+            .input("hi")
+            .build()
+            .unwrap();
+        // fails the first request with a network error
+        let flaky = || {
+            MockTransport::new(|_, index| match index {
+                0 => Err(OpenAIError::Transport("connection reset".into())),
+                _ => Ok(MockResponse::new(EMBEDDINGS)),
+            })
+        };
+
+        let transport = flaky();
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_backoff(backoff.clone());
+        assert!(client.embeddings().create(request.clone()).await.is_err());
+        assert_eq!(idempotency_keys(&transport), [None]);
+
+        let transport = flaky();
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_backoff(backoff)
+            .with_idempotency_keys();
+        client.embeddings().create(request).await.unwrap();
+        let keys = idempotency_keys(&transport);
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], keys[1]);
+        assert_eq!(keys[0].as_ref().unwrap().len(), 36);
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_across_retries() {
+        let backoff = backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(std::time::Duration::from_millis(1))
+            .build();
+        let request = crate::types::CreateEmbeddingRequestArgs::default()
+            .model("m")
+            .input("hi")
+            .build()
+            .unwrap();
+
+        // answers 503 then 429 before succeeding
+        let transport = MockTransport::new(|_, index| {
+            Ok(match index % 3 {
+                0 => MockResponse::new(r#"{"error":{"message":"overloaded"}}"#).status(503),
+                1 => MockResponse::new(r#"{"error":{"message":"slow down","type":"requests"}}"#)
+                    .status(429),
+                _ => MockResponse::new(EMBEDDINGS),
+            })
+        });
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_backoff(backoff)
+            .with_idempotency_keys();
+        client.embeddings().create(request.clone()).await.unwrap();
+        client.embeddings().create(request).await.unwrap();
+        let keys = idempotency_keys(&transport);
+        assert_eq!(keys.len(), 6);
+        // one key for all the attempts of a call, another for the next call
+        assert!(keys[..3].iter().all(|key| key == &keys[0]));
+        assert!(keys[3..].iter().all(|key| key == &keys[3]));
+        assert_ne!(keys[0], keys[3]);
+        assert!(keys[0].is_some());
+    }
+}
@@ -1,11 +1,12 @@
-use std::collections::HashMap;
-
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::{FunctionName, FunctionObject, ResponseFormat};
+use super::{
+    Cursor, Deleted, FunctionName, FunctionObject, Metadata, ResponseFormat, SortOrder,
+    TimestampI32, VectorStoreFileBatchObject,
+};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 pub struct AssistantToolCodeInterpreterResources {
@@ -52,7 +53,7 @@ pub struct AssistantVectorStore {
     pub chunking_strategy: Option<AssistantVectorStoreChunkingStrategy>,
 
     /// Set of 16 key-value pairs that can be attached to a vector store. This can be useful for storing additional information about the vector store in a structured format. Keys can be a maximum of 64 characters long and values can be a maxium of 512 characters long.
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
@@ -85,7 +86,8 @@ pub struct AssistantObject {
     /// The object type, which is always `assistant`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the assistant was created.
-    pub created_at: i32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampI32,
     /// The name of the assistant. The maximum length is 256 characters.
     pub name: Option<String>,
     /// The description of the assistant. The maximum length is 512 characters.
@@ -100,7 +102,7 @@ pub struct AssistantObject {
     /// A set of resources that are used by the assistant's tools. The resources are specific to the type of tool. For example, the `code_interpreter` tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
     pub tool_resources: Option<AssistantToolResources>,
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
     pub temperature: Option<f32>,
     /// An alternative to sampling with temperature, called nucleus sampling, where the model considers the results of the tokens with top_p probability mass. So 0.1 means only the tokens comprising the top 10% probability mass are considered.
@@ -126,6 +128,15 @@ pub enum AssistantsApiResponseFormatOption {
     Format(ResponseFormat),
 }
 
+impl AssistantsApiResponseFormatOption {
+    pub(crate) fn validate(&self) -> Result<(), OpenAIError> {
+        match self {
+            AssistantsApiResponseFormatOption::Auto => Ok(()),
+            AssistantsApiResponseFormatOption::Format(format) => format.validate(),
+        }
+    }
+}
+
 /// Retrieval tool
 #[derive(Clone, Serialize, Debug, Default, Deserialize, PartialEq)]
 pub struct AssistantToolsFileSearch {
@@ -184,7 +195,7 @@ pub enum AssistantTools {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateAssistantRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -211,7 +222,7 @@ pub struct CreateAssistantRequest {
 
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
 
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -227,12 +238,24 @@ pub struct CreateAssistantRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
+impl CreateAssistantRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(response_format)) = &self.response_format {
+            response_format.validate()?;
+        }
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
 #[builder(name = "ModifyAssistantRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ModifyAssistantRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -259,7 +282,7 @@ pub struct ModifyAssistantRequest {
     pub tool_resources: Option<AssistantToolResources>,
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maxium of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
 
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -275,11 +298,44 @@ pub struct ModifyAssistantRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
-pub struct DeleteAssistantResponse {
-    pub id: String,
-    pub deleted: bool,
-    pub object: String,
+impl ModifyAssistantRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(response_format)) = &self.response_format {
+            response_format.validate()?;
+        }
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Confirmation of a deleted [AssistantObject].
+pub type DeleteAssistantResponse = Deleted<AssistantObject>;
+
+/// Query of [Assistants::list](crate::Assistants::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListAssistantsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListAssistantsQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
@@ -324,3 +380,12 @@ pub enum AssistantToolType {
     CodeInterpreter,
     FileSearch,
 }
+
+/// Result of [crate::Assistants::provision_file_search].
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProvisionedFileSearch {
+    /// The assistant, with the vector store attached to its `file_search` tool.
+    pub assistant: AssistantObject,
+    /// The batch indexing the files in the vector store, check its `file_counts` for failed files.
+    pub batch: VectorStoreFileBatchObject,
+}
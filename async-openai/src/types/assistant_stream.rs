@@ -1,9 +1,12 @@
 use std::pin::Pin;
 
 use futures::Stream;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
-use crate::error::{map_deserialization_error, ApiError, OpenAIError};
+use crate::{
+    error::{ApiError, OpenAIError},
+    parse,
+};
 
 use super::{
     MessageDeltaObject, MessageObject, RunObject, RunStepDeltaObject, RunStepObject, ThreadObject,
@@ -29,7 +32,7 @@ use super::{
 /// in your code. See the [Assistants API quickstart](https://platform.openai.com/docs/assistants/overview) to learn how to
 /// integrate the Assistants API with streaming.
 
-#[derive(Debug, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone)]
 #[serde(tag = "event", content = "data")]
 #[non_exhaustive]
 pub enum AssistantStreamEvent {
@@ -117,93 +120,99 @@ impl TryFrom<eventsource_stream::Event> for AssistantStreamEvent {
     type Error = OpenAIError;
     fn try_from(value: eventsource_stream::Event) -> Result<Self, Self::Error> {
         match value.event.as_str() {
-            "thread.created" => serde_json::from_str::<ThreadObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadCreated),
-            "thread.run.created" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunCreated),
-            "thread.run.queued" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunQueued),
-            "thread.run.in_progress" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunInProgress),
-            "thread.run.requires_action" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunRequiresAction),
-            "thread.run.completed" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunCompleted),
-            "thread.run.incomplete" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunIncomplete),
-            "thread.run.failed" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunFailed),
-            "thread.run.cancelling" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunCancelling),
-            "thread.run.cancelled" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunCancelled),
-            "thread.run.expired" => serde_json::from_str::<RunObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunExpired),
-            "thread.run.step.created" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunStepCreated),
+            "thread.created" => {
+                parse::from_slice::<ThreadObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadCreated)
+            }
+            "thread.run.created" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunCreated)
+            }
+            "thread.run.queued" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunQueued)
+            }
+            "thread.run.in_progress" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunInProgress)
+            }
+            "thread.run.requires_action" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunRequiresAction)
+            }
+            "thread.run.completed" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunCompleted)
+            }
+            "thread.run.incomplete" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunIncomplete)
+            }
+            "thread.run.failed" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunFailed)
+            }
+            "thread.run.cancelling" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunCancelling)
+            }
+            "thread.run.cancelled" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunCancelled)
+            }
+            "thread.run.expired" => {
+                parse::from_slice::<RunObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunExpired)
+            }
+            "thread.run.step.created" => {
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunStepCreated)
+            }
             "thread.run.step.in_progress" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadRunStepInProgress)
             }
             "thread.run.step.delta" => {
-                serde_json::from_str::<RunStepDeltaObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<RunStepDeltaObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadRunStepDelta)
             }
             "thread.run.step.completed" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadRunStepCompleted)
             }
-            "thread.run.step.failed" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunStepFailed),
+            "thread.run.step.failed" => {
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunStepFailed)
+            }
             "thread.run.step.cancelled" => {
-                serde_json::from_str::<RunStepObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadRunStepCancelled)
             }
-            "thread.run.step.expired" => serde_json::from_str::<RunStepObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadRunStepExpired),
-            "thread.message.created" => serde_json::from_str::<MessageObject>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
-                .map(AssistantStreamEvent::ThreadMessageCreated),
+            "thread.run.step.expired" => {
+                parse::from_slice::<RunStepObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadRunStepExpired)
+            }
+            "thread.message.created" => {
+                parse::from_slice::<MessageObject>(value.data.as_bytes(), parse::event_mode())
+                    .map(AssistantStreamEvent::ThreadMessageCreated)
+            }
             "thread.message.in_progress" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<MessageObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadMessageInProgress)
             }
             "thread.message.delta" => {
-                serde_json::from_str::<MessageDeltaObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<MessageDeltaObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadMessageDelta)
             }
             "thread.message.completed" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<MessageObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadMessageCompleted)
             }
             "thread.message.incomplete" => {
-                serde_json::from_str::<MessageObject>(value.data.as_str())
-                    .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+                parse::from_slice::<MessageObject>(value.data.as_bytes(), parse::event_mode())
                     .map(AssistantStreamEvent::ThreadMessageIncomplete)
             }
-            "error" => serde_json::from_str::<ApiError>(value.data.as_str())
-                .map_err(|e| map_deserialization_error(e, value.data.as_bytes()))
+            "error" => parse::from_slice::<ApiError>(value.data.as_bytes(), parse::event_mode())
                 .map(AssistantStreamEvent::ErrorEvent),
             "done" => Ok(AssistantStreamEvent::Done(value.data)),
 
@@ -1,8 +1,11 @@
+use std::{fmt, pin::Pin, sync::Arc};
+
 use bytes::Bytes;
 use derive_builder::Builder;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
-use super::InputSource;
+use super::{ChatCompletionRequestMessage, CreateChatCompletionRequest, InputSource};
 use crate::error::OpenAIError;
 
 #[derive(Debug, Default, Clone, PartialEq)]
@@ -254,3 +257,177 @@ pub struct CreateTranslationResponseVerboseJson {
 pub struct CreateSpeechResponse {
     pub bytes: Bytes,
 }
+
+/// Chunks of the audio generated by [crate::Audio::speech_stream].
+pub type SpeechResponseStream = Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>;
+
+/// Models, instructions and voice of a spoken turn with [crate::Audio::converse].
+#[derive(Clone, Debug, Builder, PartialEq)]
+#[builder(name = "ConverseOptionsArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ConverseOptions {
+    /// Model transcribing the input audio, `whisper-1` by default.
+    pub transcription_model: String,
+
+    /// Language of the input audio in ISO-639-1 format, detected when not set.
+    pub language: Option<String>,
+
+    /// Chat model replying to the transcript, `gpt-4o-mini` by default.
+    pub chat_model: String,
+
+    /// System prompt of the chat model.
+    pub instructions: Option<String>,
+
+    /// Previous turns of the conversation, sent before the transcript.
+    pub history: Vec<ChatCompletionRequestMessage>,
+
+    /// Model speaking the reply.
+    pub speech_model: SpeechModel,
+
+    pub voice: Voice,
+
+    /// Format of the output audio, `mp3` by default.
+    pub response_format: Option<SpeechResponseFormat>,
+}
+
+impl Default for ConverseOptions {
+    fn default() -> Self {
+        Self {
+            transcription_model: "whisper-1".into(),
+            language: None,
+            chat_model: "gpt-4o-mini".into(),
+            instructions: None,
+            history: Vec::new(),
+            speech_model: SpeechModel::default(),
+            voice: Voice::default(),
+            response_format: None,
+        }
+    }
+}
+
+/// A spoken turn: the transcript of the input audio, the reply of the chat model and the reply
+/// spoken as it is generated.
+pub struct ConverseResponse {
+    pub transcript: String,
+    pub reply: String,
+    pub audio: SpeechResponseStream,
+}
+
+impl fmt::Debug for ConverseResponse {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ConverseResponse")
+            .field("transcript", &self.transcript)
+            .field("reply", &self.reply)
+            .finish_non_exhaustive()
+    }
+}
+
+type TextHook = Arc<dyn Fn(&mut String) + Send + Sync>;
+type ChatRequestHook = Arc<dyn Fn(&mut CreateChatCompletionRequest) + Send + Sync>;
+
+/// Options of [crate::Audio::speech_to_speech]: the [ConverseOptions] of the turn, and hooks
+/// intercepting its intermediate results before they are sent to the next model.
+///
+/// ```
+/// use async_openai::types::{ConverseOptionsArgs, SpeechToSpeechOptions};
+///
+/// # fn main() -> Result<(), async_openai::error::OpenAIError> {
+/// let options = SpeechToSpeechOptions::new(
+///     ConverseOptionsArgs::default()
+///         .instructions("You are a helpful voice assistant, answer briefly.")
+///         .build()?,
+/// )
+/// .with_on_transcript(|transcript| println!("user: {transcript}"))
+/// .with_on_chat_request(|request| request.temperature = Some(0.2))
+/// .with_on_reply(|reply| *reply = reply.replace("**", ""));
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Default)]
+pub struct SpeechToSpeechOptions {
+    pub converse: ConverseOptions,
+    on_transcript: Option<TextHook>,
+    on_chat_request: Option<ChatRequestHook>,
+    on_reply: Option<TextHook>,
+}
+
+impl fmt::Debug for SpeechToSpeechOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpeechToSpeechOptions")
+            .field("converse", &self.converse)
+            .finish_non_exhaustive()
+    }
+}
+
+impl SpeechToSpeechOptions {
+    pub fn new(converse: ConverseOptions) -> Self {
+        Self {
+            converse,
+            ..Default::default()
+        }
+    }
+
+    /// Inspect or edit the transcript of the input audio before the chat model replies to it.
+    pub fn with_on_transcript<F: Fn(&mut String) + Send + Sync + 'static>(
+        mut self,
+        on_transcript: F,
+    ) -> Self {
+        self.on_transcript = Some(Arc::new(on_transcript));
+        self
+    }
+
+    /// Edit the chat completion request before it is sent, e.g. to set its temperature or tools.
+    pub fn with_on_chat_request<F: Fn(&mut CreateChatCompletionRequest) + Send + Sync + 'static>(
+        mut self,
+        on_chat_request: F,
+    ) -> Self {
+        self.on_chat_request = Some(Arc::new(on_chat_request));
+        self
+    }
+
+    /// Inspect or edit the reply of the chat model before it is spoken.
+    pub fn with_on_reply<F: Fn(&mut String) + Send + Sync + 'static>(
+        mut self,
+        on_reply: F,
+    ) -> Self {
+        self.on_reply = Some(Arc::new(on_reply));
+        self
+    }
+
+    pub(crate) fn transcript(&self, transcript: &mut String) {
+        if let Some(on_transcript) = &self.on_transcript {
+            on_transcript(transcript);
+        }
+    }
+
+    pub(crate) fn chat_request(&self, request: &mut CreateChatCompletionRequest) {
+        if let Some(on_chat_request) = &self.on_chat_request {
+            on_chat_request(request);
+        }
+    }
+
+    pub(crate) fn reply(&self, reply: &mut String) {
+        if let Some(on_reply) = &self.on_reply {
+            on_reply(reply);
+        }
+    }
+}
+
+impl From<ConverseOptions> for SpeechToSpeechOptions {
+    fn from(converse: ConverseOptions) -> Self {
+        Self::new(converse)
+    }
+}
+
+/// A spoken turn of [crate::Audio::speech_to_speech], with the whole audio of the reply.
+#[derive(Debug, Clone)]
+pub struct SpeechToSpeechResponse {
+    /// The transcript of the input audio, as edited by the transcript hook.
+    pub transcript: String,
+    /// The reply of the chat model, as spoken.
+    pub reply: String,
+    pub audio: Bytes,
+}
@@ -1,7 +1,14 @@
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::OpenAIError;
+
+use super::{Cursor, Timestamp};
 
 /// The event type.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub enum AuditLogEventType {
     #[serde(rename = "api_key.created")]
     ApiKeyCreated,
@@ -43,10 +50,44 @@ pub enum AuditLogEventType {
     UserUpdated,
     #[serde(rename = "user.deleted")]
     UserDeleted,
+    #[serde(rename = "certificate.created")]
+    CertificateCreated,
+    #[serde(rename = "certificate.updated")]
+    CertificateUpdated,
+    #[serde(rename = "certificate.deleted")]
+    CertificateDeleted,
+    #[serde(rename = "certificates.activated")]
+    CertificatesActivated,
+    #[serde(rename = "certificates.deactivated")]
+    CertificatesDeactivated,
+    #[serde(rename = "checkpoint_permission.created")]
+    CheckpointPermissionCreated,
+    #[serde(rename = "checkpoint_permission.deleted")]
+    CheckpointPermissionDeleted,
+    #[serde(rename = "rate_limit.updated")]
+    RateLimitUpdated,
+    #[serde(rename = "rate_limit.deleted")]
+    RateLimitDeleted,
+    /// An event type not modeled by this crate.
+    #[serde(untagged)]
+    Other(String),
+}
+
+impl AuditLogEventType {
+    /// The name of the type, e.g. `api_key.created`.
+    pub fn name(&self) -> String {
+        match self {
+            AuditLogEventType::Other(name) => name.clone(),
+            known => serde_json::to_value(known)
+                .ok()
+                .and_then(|name| name.as_str().map(str::to_string))
+                .unwrap_or_default(),
+        }
+    }
 }
 
 /// Represents a list of audit logs.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ListAuditLogsResponse {
     /// The object type, which is always `list`.
     pub object: String,
@@ -60,8 +101,10 @@ pub struct ListAuditLogsResponse {
     pub has_more: bool,
 }
 
+crate::pagination::impl_cursor_page!(ListAuditLogsResponse, AuditLog);
+
 /// The project that the action was scoped to. Absent for actions not scoped to projects.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProject {
     /// The project ID.
     pub id: String,
@@ -70,7 +113,7 @@ pub struct AuditLogProject {
 }
 
 /// The actor who performed the audit logged action.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogActor {
     /// The type of actor. Is either `session` or `api_key`.
     pub r#type: String,
@@ -81,7 +124,7 @@ pub struct AuditLogActor {
 }
 
 /// The session in which the audit logged action was performed.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogActorSession {
     /// The user who performed the audit logged action.
     pub user: AuditLogActorUser,
@@ -90,7 +133,7 @@ pub struct AuditLogActorSession {
 }
 
 /// The API Key used to perform the audit logged action.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogActorApiKey {
     /// The tracking id of the API key.
     pub id: String,
@@ -102,7 +145,7 @@ pub struct AuditLogActorApiKey {
     pub service_account: Option<AuditLogActorServiceAccount>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
 pub enum AuditLogActorApiKeyType {
     User,
@@ -110,7 +153,7 @@ pub enum AuditLogActorApiKeyType {
 }
 
 /// The user who performed the audit logged action.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogActorUser {
     /// The user id.
     pub id: String,
@@ -119,21 +162,22 @@ pub struct AuditLogActorUser {
 }
 
 /// The service account that performed the audit logged action.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogActorServiceAccount {
     /// The service account id.
     pub id: String,
 }
 
 /// A log of a user action or configuration change within this organization.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLog {
     /// The ID of this log.
     pub id: String,
     /// The event type.
     pub r#type: AuditLogEventType,
     /// The Unix timestamp (in seconds) of the event.
-    pub effective_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub effective_at: Timestamp,
     /// The project that the action was scoped to. Absent for actions not scoped to projects.
     pub project: Option<AuditLogProject>,
     /// The actor who performed the audit logged action.
@@ -192,10 +236,40 @@ pub struct AuditLog {
     /// The details for events with the type `user.deleted`.
     #[serde(rename = "user.deleted")]
     pub user_deleted: Option<AuditLogUserDeleted>,
+    /// The details for events with the type `certificate.created`.
+    #[serde(rename = "certificate.created")]
+    pub certificate_created: Option<AuditLogCertificate>,
+    /// The details for events with the type `certificate.updated`.
+    #[serde(rename = "certificate.updated")]
+    pub certificate_updated: Option<AuditLogCertificate>,
+    /// The details for events with the type `certificate.deleted`.
+    #[serde(rename = "certificate.deleted")]
+    pub certificate_deleted: Option<AuditLogCertificateDeleted>,
+    /// The details for events with the type `certificates.activated`.
+    #[serde(rename = "certificates.activated")]
+    pub certificates_activated: Option<AuditLogCertificates>,
+    /// The details for events with the type `certificates.deactivated`.
+    #[serde(rename = "certificates.deactivated")]
+    pub certificates_deactivated: Option<AuditLogCertificates>,
+    /// The details for events with the type `checkpoint_permission.created`.
+    #[serde(rename = "checkpoint_permission.created")]
+    pub checkpoint_permission_created: Option<AuditLogCheckpointPermissionCreated>,
+    /// The details for events with the type `checkpoint_permission.deleted`.
+    #[serde(rename = "checkpoint_permission.deleted")]
+    pub checkpoint_permission_deleted: Option<AuditLogCheckpointPermissionDeleted>,
+    /// The details for events with the type `rate_limit.updated`.
+    #[serde(rename = "rate_limit.updated")]
+    pub rate_limit_updated: Option<AuditLogRateLimitUpdated>,
+    /// The details for events with the type `rate_limit.deleted`.
+    #[serde(rename = "rate_limit.deleted")]
+    pub rate_limit_deleted: Option<AuditLogRateLimitDeleted>,
+    /// Fields not modeled by this crate, such as the details of newer event types, by name.
+    #[serde(flatten)]
+    pub other: HashMap<String, serde_json::Value>,
 }
 
 /// The details for events with the type `api_key.created`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogApiKeyCreated {
     /// The tracking ID of the API key.
     pub id: String,
@@ -204,14 +278,14 @@ pub struct AuditLogApiKeyCreated {
 }
 
 /// The payload used to create the API key.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogApiKeyCreatedData {
     /// A list of scopes allowed for the API key, e.g. `["api.model.request"]`.
     pub scopes: Option<Vec<String>>,
 }
 
 /// The details for events with the type `api_key.updated`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogApiKeyUpdated {
     /// The tracking ID of the API key.
     pub id: String,
@@ -220,21 +294,21 @@ pub struct AuditLogApiKeyUpdated {
 }
 
 /// The payload used to update the API key.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogApiKeyUpdatedChangesRequested {
     /// A list of scopes allowed for the API key, e.g. `["api.model.request"]`.
     pub scopes: Option<Vec<String>>,
 }
 
 /// The details for events with the type `api_key.deleted`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogApiKeyDeleted {
     /// The tracking ID of the API key.
     pub id: String,
 }
 
 /// The details for events with the type `invite.sent`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogInviteSent {
     /// The ID of the invite.
     pub id: String,
@@ -243,7 +317,7 @@ pub struct AuditLogInviteSent {
 }
 
 /// The payload used to create the invite.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogInviteSentData {
     /// The email invited to the organization.
     pub email: String,
@@ -252,21 +326,21 @@ pub struct AuditLogInviteSentData {
 }
 
 /// The details for events with the type `invite.accepted`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogInviteAccepted {
     /// The ID of the invite.
     pub id: String,
 }
 
 /// The details for events with the type `invite.deleted`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogInviteDeleted {
     /// The ID of the invite.
     pub id: String,
 }
 
 /// The details for events with the type `login.failed`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogLoginFailed {
     /// The error code of the failure.
     pub error_code: String,
@@ -275,7 +349,7 @@ pub struct AuditLogLoginFailed {
 }
 
 /// The details for events with the type `logout.failed`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogLogoutFailed {
     /// The error code of the failure.
     pub error_code: String,
@@ -284,7 +358,7 @@ pub struct AuditLogLogoutFailed {
 }
 
 /// The details for events with the type `organization.updated`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogOrganizationUpdated {
     /// The organization ID.
     pub id: String,
@@ -293,7 +367,7 @@ pub struct AuditLogOrganizationUpdated {
 }
 
 /// The payload used to update the organization settings.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogOrganizationUpdatedChangesRequested {
     /// The organization title.
     pub title: Option<String>,
@@ -306,7 +380,7 @@ pub struct AuditLogOrganizationUpdatedChangesRequested {
 }
 
 /// The organization settings.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogOrganizationUpdatedChangesRequestedSettings {
     /// Visibility of the threads page which shows messages created with the Assistants API and Playground. One of `ANY_ROLE`, `OWNERS`, or `NONE`.
     pub threads_ui_visibility: Option<String>,
@@ -315,7 +389,7 @@ pub struct AuditLogOrganizationUpdatedChangesRequestedSettings {
 }
 
 /// The details for events with the type `project.created`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProjectCreated {
     /// The project ID.
     pub id: String,
@@ -324,7 +398,7 @@ pub struct AuditLogProjectCreated {
 }
 
 /// The payload used to create the project.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProjectCreatedData {
     /// The project name.
     pub name: String,
@@ -333,7 +407,7 @@ pub struct AuditLogProjectCreatedData {
 }
 
 /// The details for events with the type `project.updated`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProjectUpdated {
     /// The project ID.
     pub id: String,
@@ -342,21 +416,21 @@ pub struct AuditLogProjectUpdated {
 }
 
 /// The payload used to update the project.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProjectUpdatedChangesRequested {
     /// The title of the project as seen on the dashboard.
     pub title: Option<String>,
 }
 
 /// The details for events with the type `project.archived`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogProjectArchived {
     /// The project ID.
     pub id: String,
 }
 
 /// The details for events with the type `service_account.created`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogServiceAccountCreated {
     /// The service account ID.
     pub id: String,
@@ -365,14 +439,14 @@ pub struct AuditLogServiceAccountCreated {
 }
 
 /// The payload used to create the service account.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogServiceAccountCreatedData {
     /// The role of the service account. Is either `owner` or `member`.
     pub role: String,
 }
 
 /// The details for events with the type `service_account.updated`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogServiceAccountUpdated {
     /// The service account ID.
     pub id: String,
@@ -381,21 +455,21 @@ pub struct AuditLogServiceAccountUpdated {
 }
 
 /// The payload used to updated the service account.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogServiceAccountUpdatedChangesRequested {
     /// The role of the service account. Is either `owner` or `member`.
     pub role: String,
 }
 
 /// The details for events with the type `service_account.deleted`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogServiceAccountDeleted {
     /// The service account ID.
     pub id: String,
 }
 
 /// The details for events with the type `user.added`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogUserAdded {
     /// The user ID.
     pub id: String,
@@ -404,14 +478,14 @@ pub struct AuditLogUserAdded {
 }
 
 /// The payload used to add the user to the project.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogUserAddedData {
     /// The role of the user. Is either `owner` or `member`.
     pub role: String,
 }
 
 /// The details for events with the type `user.updated`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogUserUpdated {
     /// The project ID.
     pub id: String,
@@ -420,15 +494,337 @@ pub struct AuditLogUserUpdated {
 }
 
 /// The payload used to update the user.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogUserUpdatedChangesRequested {
     /// The role of the user. Is either `owner` or `member`.
     pub role: String,
 }
 
 /// The details for events with the type `user.deleted`.
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct AuditLogUserDeleted {
     /// The user ID.
     pub id: String,
 }
+
+/// The details for events with the types `certificate.created` and `certificate.updated`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCertificate {
+    /// The certificate ID.
+    pub id: String,
+    /// The name of the certificate.
+    pub name: Option<String>,
+}
+
+/// The details for events with the type `certificate.deleted`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCertificateDeleted {
+    /// The certificate ID.
+    pub id: String,
+    /// The name of the certificate.
+    pub name: Option<String>,
+    /// The certificate content in PEM format.
+    pub certificate: Option<String>,
+}
+
+/// The details for events with the types `certificates.activated` and
+/// `certificates.deactivated`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCertificates {
+    pub certificates: Vec<AuditLogCertificate>,
+}
+
+/// The details for events with the type `checkpoint_permission.created`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCheckpointPermissionCreated {
+    /// The ID of the checkpoint permission.
+    pub id: String,
+    /// The payload used to create the checkpoint permission.
+    pub data: Option<AuditLogCheckpointPermissionCreatedData>,
+}
+
+/// The payload used to create the checkpoint permission.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCheckpointPermissionCreatedData {
+    /// The ID of the project that the checkpoint permission was created for.
+    pub project_id: String,
+    /// The ID of the fine-tuned model checkpoint.
+    pub fine_tuned_model_checkpoint: String,
+}
+
+/// The details for events with the type `checkpoint_permission.deleted`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogCheckpointPermissionDeleted {
+    /// The ID of the checkpoint permission.
+    pub id: String,
+}
+
+/// The details for events with the type `rate_limit.updated`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogRateLimitUpdated {
+    /// The rate limit ID.
+    pub id: String,
+    /// The payload used to update the rate limits.
+    pub changes_requested: Option<AuditLogRateLimitUpdatedChangesRequested>,
+}
+
+/// The payload used to update the rate limits.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogRateLimitUpdatedChangesRequested {
+    pub max_requests_per_1_minute: Option<u32>,
+    pub max_tokens_per_1_minute: Option<u32>,
+    pub max_images_per_1_minute: Option<u32>,
+    pub max_audio_megabytes_per_1_minute: Option<u32>,
+    pub max_requests_per_1_day: Option<u32>,
+    pub batch_1_day_max_input_tokens: Option<u32>,
+}
+
+/// The details for events with the type `rate_limit.deleted`.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct AuditLogRateLimitDeleted {
+    /// The rate limit ID.
+    pub id: String,
+}
+
+/// The type of an [AuditLog] with its details, see [AuditLog::event].
+#[derive(Debug, Clone, PartialEq)]
+pub enum AuditLogEvent {
+    ApiKeyCreated(AuditLogApiKeyCreated),
+    ApiKeyUpdated(AuditLogApiKeyUpdated),
+    ApiKeyDeleted(AuditLogApiKeyDeleted),
+    InviteSent(AuditLogInviteSent),
+    InviteAccepted(AuditLogInviteAccepted),
+    InviteDeleted(AuditLogInviteDeleted),
+    LoginSucceeded,
+    LoginFailed(AuditLogLoginFailed),
+    LogoutSucceeded,
+    LogoutFailed(AuditLogLogoutFailed),
+    OrganizationUpdated(AuditLogOrganizationUpdated),
+    ProjectCreated(AuditLogProjectCreated),
+    ProjectUpdated(AuditLogProjectUpdated),
+    ProjectArchived(AuditLogProjectArchived),
+    ServiceAccountCreated(AuditLogServiceAccountCreated),
+    ServiceAccountUpdated(AuditLogServiceAccountUpdated),
+    ServiceAccountDeleted(AuditLogServiceAccountDeleted),
+    UserAdded(AuditLogUserAdded),
+    UserUpdated(AuditLogUserUpdated),
+    UserDeleted(AuditLogUserDeleted),
+    CertificateCreated(AuditLogCertificate),
+    CertificateUpdated(AuditLogCertificate),
+    CertificateDeleted(AuditLogCertificateDeleted),
+    CertificatesActivated(AuditLogCertificates),
+    CertificatesDeactivated(AuditLogCertificates),
+    CheckpointPermissionCreated(AuditLogCheckpointPermissionCreated),
+    CheckpointPermissionDeleted(AuditLogCheckpointPermissionDeleted),
+    RateLimitUpdated(AuditLogRateLimitUpdated),
+    RateLimitDeleted(AuditLogRateLimitDeleted),
+    /// An event of a type not modeled by this crate, or without its details.
+    Other {
+        r#type: String,
+        details: Option<serde_json::Value>,
+    },
+}
+
+impl AuditLog {
+    /// The type of the event with its details, which are in the field named after the type.
+    pub fn event(&self) -> AuditLogEvent {
+        use AuditLogEventType as Type;
+        let event = match &self.r#type {
+            Type::ApiKeyCreated => self
+                .api_key_created
+                .clone()
+                .map(AuditLogEvent::ApiKeyCreated),
+            Type::ApiKeyUpdated => self
+                .api_key_updated
+                .clone()
+                .map(AuditLogEvent::ApiKeyUpdated),
+            Type::ApiKeyDeleted => self
+                .api_key_deleted
+                .clone()
+                .map(AuditLogEvent::ApiKeyDeleted),
+            Type::InviteSent => self.invite_sent.clone().map(AuditLogEvent::InviteSent),
+            Type::InviteAccepted => self
+                .invite_accepted
+                .clone()
+                .map(AuditLogEvent::InviteAccepted),
+            Type::InviteDeleted => self
+                .invite_deleted
+                .clone()
+                .map(AuditLogEvent::InviteDeleted),
+            Type::LoginSucceeded => Some(AuditLogEvent::LoginSucceeded),
+            Type::LoginFailed => self.login_failed.clone().map(AuditLogEvent::LoginFailed),
+            Type::LogoutSucceeded => Some(AuditLogEvent::LogoutSucceeded),
+            Type::LogoutFailed => self.logout_failed.clone().map(AuditLogEvent::LogoutFailed),
+            Type::OrganizationUpdated => self
+                .organization_updated
+                .clone()
+                .map(AuditLogEvent::OrganizationUpdated),
+            Type::ProjectCreated => self
+                .project_created
+                .clone()
+                .map(AuditLogEvent::ProjectCreated),
+            Type::ProjectUpdated => self
+                .project_updated
+                .clone()
+                .map(AuditLogEvent::ProjectUpdated),
+            Type::ProjectArchived => self
+                .project_archived
+                .clone()
+                .map(AuditLogEvent::ProjectArchived),
+            Type::ServiceAccountCreated => self
+                .service_account_created
+                .clone()
+                .map(AuditLogEvent::ServiceAccountCreated),
+            Type::ServiceAccountUpdated => self
+                .service_account_updated
+                .clone()
+                .map(AuditLogEvent::ServiceAccountUpdated),
+            Type::ServiceAccountDeleted => self
+                .service_account_deleted
+                .clone()
+                .map(AuditLogEvent::ServiceAccountDeleted),
+            Type::UserAdded => self.user_added.clone().map(AuditLogEvent::UserAdded),
+            Type::UserUpdated => self.user_updated.clone().map(AuditLogEvent::UserUpdated),
+            Type::UserDeleted => self.user_deleted.clone().map(AuditLogEvent::UserDeleted),
+            Type::CertificateCreated => self
+                .certificate_created
+                .clone()
+                .map(AuditLogEvent::CertificateCreated),
+            Type::CertificateUpdated => self
+                .certificate_updated
+                .clone()
+                .map(AuditLogEvent::CertificateUpdated),
+            Type::CertificateDeleted => self
+                .certificate_deleted
+                .clone()
+                .map(AuditLogEvent::CertificateDeleted),
+            Type::CertificatesActivated => self
+                .certificates_activated
+                .clone()
+                .map(AuditLogEvent::CertificatesActivated),
+            Type::CertificatesDeactivated => self
+                .certificates_deactivated
+                .clone()
+                .map(AuditLogEvent::CertificatesDeactivated),
+            Type::CheckpointPermissionCreated => self
+                .checkpoint_permission_created
+                .clone()
+                .map(AuditLogEvent::CheckpointPermissionCreated),
+            Type::CheckpointPermissionDeleted => self
+                .checkpoint_permission_deleted
+                .clone()
+                .map(AuditLogEvent::CheckpointPermissionDeleted),
+            Type::RateLimitUpdated => self
+                .rate_limit_updated
+                .clone()
+                .map(AuditLogEvent::RateLimitUpdated),
+            Type::RateLimitDeleted => self
+                .rate_limit_deleted
+                .clone()
+                .map(AuditLogEvent::RateLimitDeleted),
+            Type::Other(_) => None,
+        };
+        event.unwrap_or_else(|| {
+            let r#type = self.r#type.name();
+            AuditLogEvent::Other {
+                details: self.other.get(&r#type).cloned(),
+                r#type,
+            }
+        })
+    }
+}
+
+/// Query of [crate::AuditLogs::get] and [crate::AuditLogs::stream].
+///
+/// The `effective_at` bounds select a time range of events, in Unix seconds.
+#[derive(Debug, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListAuditLogsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListAuditLogsQuery {
+    /// Events effective after this time.
+    pub effective_at_gt: Option<u64>,
+    /// Events effective at or after this time.
+    pub effective_at_gte: Option<u64>,
+    /// Events effective before this time.
+    pub effective_at_lt: Option<u64>,
+    /// Events effective at or before this time.
+    pub effective_at_lte: Option<u64>,
+    /// Events of these projects only.
+    pub project_ids: Option<Vec<String>>,
+    /// Events of these types only.
+    pub event_types: Option<Vec<AuditLogEventType>>,
+    /// Events of these actors only: session IDs of users or API key tracking IDs.
+    pub actor_ids: Option<Vec<String>>,
+    /// Events of the users with these emails only.
+    pub actor_emails: Option<Vec<String>>,
+    /// Events on these resources only, such as the ID of an API key or a project.
+    pub resource_ids: Option<Vec<String>>,
+    /// Number of events to return, between 1 and 100, 20 by default.
+    pub limit: Option<u32>,
+    /// List the events after this audit log ID.
+    pub after: Option<Cursor>,
+    /// List the events before this audit log ID.
+    pub before: Option<Cursor>,
+}
+
+impl ListAuditLogsQuery {
+    /// Query parameters, in the bracketed form of the API for ranges and lists.
+    fn pairs(&self) -> Vec<(&'static str, String)> {
+        let mut pairs = Vec::new();
+        let bounds = [
+            ("effective_at[gt]", self.effective_at_gt),
+            ("effective_at[gte]", self.effective_at_gte),
+            ("effective_at[lt]", self.effective_at_lt),
+            ("effective_at[lte]", self.effective_at_lte),
+        ];
+        for (name, bound) in bounds {
+            if let Some(bound) = bound {
+                pairs.push((name, bound.to_string()));
+            }
+        }
+        let event_types = self
+            .event_types
+            .iter()
+            .flatten()
+            .map(AuditLogEventType::name)
+            .collect();
+        let lists = [
+            (
+                "project_ids[]",
+                self.project_ids.clone().unwrap_or_default(),
+            ),
+            ("event_types[]", event_types),
+            ("actor_ids[]", self.actor_ids.clone().unwrap_or_default()),
+            (
+                "actor_emails[]",
+                self.actor_emails.clone().unwrap_or_default(),
+            ),
+            (
+                "resource_ids[]",
+                self.resource_ids.clone().unwrap_or_default(),
+            ),
+        ];
+        for (name, values) in lists {
+            pairs.extend(values.into_iter().map(|value| (name, value)));
+        }
+        if let Some(limit) = self.limit {
+            pairs.push(("limit", limit.to_string()));
+        }
+        if let Some(after) = &self.after {
+            pairs.push(("after", after.as_str().to_string()));
+        }
+        if let Some(before) = &self.before {
+            pairs.push(("before", before.as_str().to_string()));
+        }
+        pairs
+    }
+}
+
+impl Serialize for ListAuditLogsQuery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.pairs())
+    }
+}
@@ -1,16 +1,16 @@
-use std::collections::HashMap;
-
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
+use super::{Cursor, Metadata, Timestamp};
+
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
 #[builder(name = "BatchRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct BatchRequest {
     /// The ID of an uploaded file that contains requests for the new batch.
     ///
@@ -26,7 +26,16 @@ pub struct BatchRequest {
     pub completion_window: BatchCompletionWindow,
 
     /// Optional custom metadata for the batch.
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
+}
+
+impl BatchRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Deserialize, Serialize, Default)]
@@ -66,27 +75,60 @@ pub struct Batch {
     /// The ID of the file containing the outputs of requests with errors.
     pub error_file_id: Option<String>,
     /// The Unix timestamp (in seconds) for when the batch was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The Unix timestamp (in seconds) for when the batch started processing.
-    pub in_progress_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub in_progress_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch will expire.
-    pub expires_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub expires_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch started finalizing.
-    pub finalizing_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub finalizing_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch was completed.
-    pub completed_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub completed_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch failed.
-    pub failed_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub failed_at: Option<Timestamp>,
     /// he Unix timestamp (in seconds) for when the batch expired.
-    pub expired_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub expired_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch started cancelling.
-    pub cancelling_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub cancelling_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the batch was cancelled.
-    pub cancelled_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub cancelled_at: Option<Timestamp>,
     /// The request counts for different statuses within the batch.
     pub request_counts: Option<BatchRequestCounts>,
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -186,3 +228,20 @@ pub struct BatchRequestOutput {
     ///  For requests that failed with a non-HTTP error, this will contain more information on the cause of the failure.
     pub error: Option<BatchRequestOutputError>,
 }
+
+/// Query of [Batches::list](crate::Batches::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListBatchesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListBatchesQuery {
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
@@ -0,0 +1,122 @@
+use crate::types::OpenAIError;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use super::{Cursor, Deleted, SortOrder, Timestamp};
+
+/// A certificate of the organization, used for mutual TLS with the API.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Certificate {
+    /// The object type: `certificate`, or `organization.certificate` and
+    /// `organization.project.certificate` when listed for an organization or a project.
+    pub object: String,
+    /// The identifier, which can be referenced in API endpoints
+    pub id: String,
+    /// The name of the certificate.
+    pub name: Option<String>,
+    /// The Unix timestamp (in seconds) of when the certificate was uploaded.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
+    pub certificate_details: CertificateDetails,
+    /// Whether the certificate is active at the level it was listed at, `None` when
+    /// uploaded or retrieved.
+    pub active: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CertificateDetails {
+    /// The Unix timestamp (in seconds) of when the certificate becomes valid.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub valid_at: Timestamp,
+    /// The Unix timestamp (in seconds) of when the certificate expires.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: Timestamp,
+    /// The PEM-encoded certificate, when requested.
+    pub content: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CertificateListResponse {
+    /// The object type, which is always `list`.
+    pub object: String,
+    pub data: Vec<Certificate>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+impl crate::pagination::CursorPage for CertificateListResponse {
+    type Item = Certificate;
+
+    fn into_page(self) -> (Vec<Self::Item>, Option<Cursor>) {
+        let next = if self.has_more {
+            self.last_id.map(Cursor::from)
+        } else {
+            None
+        };
+        (self.data, next)
+    }
+}
+
+/// Query of [Certificates::list](crate::Certificates::list) and
+/// [ProjectCertificates::list](crate::ProjectCertificates::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListCertificatesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListCertificatesQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// Sort order by the timestamp of creation, `desc` by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[builder(name = "UploadCertificateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct UploadCertificateRequest {
+    /// An optional name for the certificate.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[builder(default)]
+    pub name: Option<String>,
+    /// The PEM-encoded certificate.
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[builder(name = "ModifyCertificateRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ModifyCertificateRequest {
+    /// The updated name for the certificate.
+    pub name: String,
+}
+
+/// Certificates to activate or deactivate, for an organization or a project.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
+#[builder(name = "ToggleCertificatesRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ToggleCertificatesRequest {
+    /// IDs of the certificates, between 1 and 10.
+    pub certificate_ids: Vec<String>,
+}
+
+/// Confirmation of a deleted [Certificate].
+pub type DeleteCertificateResponse = Deleted<Certificate>;
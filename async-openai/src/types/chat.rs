@@ -6,6 +6,11 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
+use super::{Metadata, Timestamp};
+
+#[cfg(feature = "extra-fields")]
+use super::ExtraFields;
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum Prompt {
@@ -47,6 +52,16 @@ pub struct Choice {
     pub logprobs: Option<Logprobs>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub finish_reason: Option<CompletionFinishReason>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -103,7 +118,7 @@ pub struct CompletionUsage {
     pub completion_tokens_details: Option<CompletionTokensDetails>,
 }
 
-/// Breakdown of tokens used in a completion.
+/// Breakdown of tokens used in the prompt.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct PromptTokensDetails {
     /// Audio input tokens present in the prompt.
@@ -115,6 +130,8 @@ pub struct PromptTokensDetails {
 /// Breakdown of tokens used in a completion.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Default)]
 pub struct CompletionTokensDetails {
+    /// When using Predicted Outputs, the number of tokens in the
+    /// prediction that appeared in the completion.
     pub accepted_prediction_tokens: Option<u32>,
     /// Audio input tokens generated by the model.
     pub audio_tokens: Option<u32>,
@@ -346,6 +363,7 @@ pub struct ChatCompletionRequestAssistantMessage {
     /// [Learn more](https://platform.openai.com/docs/guides/audio).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub audio: Option<ChatCompletionRequestAssistantMessageAudio>,
+    /// The tool calls generated by the model, each answered by a following tool message with the same `tool_call_id`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_calls: Option<Vec<ChatCompletionMessageToolCall>>,
     /// Deprecated and replaced by `tool_calls`. The name and arguments of a function that should be called, as generated by the model.
@@ -364,6 +382,7 @@ pub struct ChatCompletionRequestAssistantMessage {
 pub struct ChatCompletionRequestToolMessage {
     /// The contents of the tool message.
     pub content: ChatCompletionRequestToolMessageContent,
+    /// Tool call that this message is responding to.
     pub tool_call_id: String,
 }
 
@@ -407,7 +426,8 @@ pub struct ChatCompletionResponseMessageAudio {
     /// Unique identifier for this audio response.
     pub id: String,
     /// The Unix timestamp (in seconds) for when this audio response will no longer be accessible on the server for use in multi-turn conversations.
-    pub expires_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: Timestamp,
     /// Base64 encoded audio bytes generated by the model, in the format specified in the request.
     pub data: String,
     /// Transcript of the audio generated by the model.
@@ -439,6 +459,65 @@ pub struct ChatCompletionResponseMessage {
     /// If the audio output modality is requested, this object contains data about the audio response from the model. [Learn more](https://platform.openai.com/docs/guides/audio).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub audio: Option<ChatCompletionResponseMessageAudio>,
+
+    /// Annotations for the message, when applicable, as when using the
+    /// [web search tool](https://platform.openai.com/docs/guides/tools-web-search?api-mode=chat).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<ChatCompletionMessageAnnotation>>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
+}
+
+/// An annotation of the content of an assistant message.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ChatCompletionMessageAnnotation {
+    /// A citation of a web page found by web search.
+    UrlCitation { url_citation: UrlCitation },
+    /// An annotation of a type not modeled by this crate.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl ChatCompletionMessageAnnotation {
+    /// The URL citation, if this is one.
+    pub fn url_citation(&self) -> Option<&UrlCitation> {
+        match self {
+            ChatCompletionMessageAnnotation::UrlCitation { url_citation } => Some(url_citation),
+            ChatCompletionMessageAnnotation::Other(_) => None,
+        }
+    }
+}
+
+impl ChatCompletionResponseMessage {
+    /// The URL citations of the content, e.g. of web search.
+    pub fn url_citations(&self) -> impl Iterator<Item = &UrlCitation> {
+        self.annotations
+            .iter()
+            .flatten()
+            .filter_map(ChatCompletionMessageAnnotation::url_citation)
+    }
+}
+
+/// A citation of a web page in the content of a message.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct UrlCitation {
+    /// The index of the first character of the URL citation in the message.
+    pub start_index: u32,
+    /// The index of the last character of the URL citation in the message.
+    pub end_index: u32,
+    /// The URL of the web resource.
+    pub url: String,
+    /// The title of the web resource.
+    pub title: String,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
@@ -511,6 +590,30 @@ pub struct ResponseFormatJsonSchema {
     pub strict: Option<bool>,
 }
 
+impl ResponseFormat {
+    /// Checks the `json_schema` name the same way the API does.
+    pub(crate) fn validate(&self) -> Result<(), OpenAIError> {
+        if let ResponseFormat::JsonSchema { json_schema } = self {
+            let name = &json_schema.name;
+            if name.is_empty() {
+                return Err(OpenAIError::InvalidArgument(
+                    "response_format json_schema requires a name".into(),
+                ));
+            }
+            if name.len() > 64
+                || !name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "response_format json_schema name `{name}` must be a-z, A-Z, 0-9, underscores or dashes with a maximum length of 64"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ChatCompletionToolType {
@@ -583,12 +686,16 @@ pub enum WebSearchUserLocationType {
 #[derive(Clone, Serialize, Debug, Default, Deserialize, PartialEq)]
 pub struct WebSearchLocation {
     ///  The two-letter [ISO country code](https://en.wikipedia.org/wiki/ISO_3166-1) of the user, e.g. `US`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub country: Option<String>,
     /// Free text input for the region of the user, e.g. `California`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub region: Option<String>,
     /// Free text input for the city of the user, e.g. `San Francisco`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub city: Option<String>,
     /// The [IANA timezone](https://timeapi.io/documentation/iana-timezones) of the user, e.g. `America/Los_Angeles`.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub timezone: Option<String>,
 }
 
@@ -600,16 +707,40 @@ pub struct WebSearchUserLocation {
     pub approximate: WebSearchLocation,
 }
 
+impl From<WebSearchLocation> for WebSearchUserLocation {
+    fn from(approximate: WebSearchLocation) -> Self {
+        Self {
+            r#type: WebSearchUserLocationType::Approximate,
+            approximate,
+        }
+    }
+}
+
 /// Options for the web search tool.
 #[derive(Clone, Serialize, Debug, Default, Deserialize, PartialEq)]
 pub struct WebSearchOptions {
     /// High level guidance for the amount of context window space to use for the search. One of `low`, `medium`, or `high`. `medium` is the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub search_context_size: Option<WebSearchContextSize>,
 
     /// Approximate location parameters for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub user_location: Option<WebSearchUserLocation>,
 }
 
+impl WebSearchOptions {
+    pub fn with_search_context_size(mut self, search_context_size: WebSearchContextSize) -> Self {
+        self.search_context_size = Some(search_context_size);
+        self
+    }
+
+    /// Search near the approximate `location` of the user.
+    pub fn with_user_location(mut self, location: WebSearchLocation) -> Self {
+        self.user_location = Some(location.into());
+        self
+    }
+}
+
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum ServiceTier {
@@ -679,6 +810,9 @@ pub enum ChatCompletionAudioVoice {
     Ballad,
     Coral,
     Echo,
+    Fable,
+    Nova,
+    Onyx,
     Sage,
     Shimmer,
     Verse,
@@ -696,7 +830,7 @@ pub enum ChatCompletionAudioFormat {
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 pub struct ChatCompletionAudio {
-    /// The voice the model uses to respond. Supported voices are `ash`, `ballad`, `coral`, `sage`, and `verse` (also supported but not recommended are `alloy`, `echo`, and `shimmer`; these voices are less expressive).
+    /// The voice the model uses to respond. Supported voices are `alloy`, `ash`, `ballad`, `coral`, `echo`, `fable`, `nova`, `onyx`, `sage`, `shimmer` and `verse`.
     pub voice: ChatCompletionAudioVoice,
     /// Specifies the output audio format. Must be one of `wav`, `mp3`, `flac`, `opus`, or `pcm16`.
     pub format: ChatCompletionAudioFormat,
@@ -707,7 +841,7 @@ pub struct ChatCompletionAudio {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateChatCompletionRequest {
     /// A list of messages comprising the conversation so far. Depending on the [model](https://platform.openai.com/docs/models) you use, different message types (modalities) are supported, like [text](https://platform.openai.com/docs/guides/text-generation), [images](https://platform.openai.com/docs/guides/vision), and [audio](https://platform.openai.com/docs/guides/audio).
     pub messages: Vec<ChatCompletionRequestMessage>, // min: 1
@@ -736,7 +870,7 @@ pub struct CreateChatCompletionRequest {
 
     ///  Developer-defined tags and values used for filtering completions in the [dashboard](https://platform.openai.com/chat-completions).
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<serde_json::Value>, // nullable: true
+    pub metadata: Option<Metadata>, // nullable: true
 
     /// Number between -2.0 and 2.0. Positive values penalize new tokens based on their existing frequency in the text so far, decreasing the model's likelihood to repeat the same line verbatim.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -894,6 +1028,104 @@ pub struct CreateChatCompletionRequest {
     pub functions: Option<Vec<ChatCompletionFunctions>>,
 }
 
+impl CreateChatCompletionRequestArgs {
+    /// Rejects combinations of fields that the API would refuse with a 400.
+    #[allow(deprecated)]
+    fn validate(&self) -> Result<(), OpenAIError> {
+        let has_tools = matches!(&self.tools, Some(Some(_)));
+        let has_functions = matches!(&self.functions, Some(Some(_)));
+
+        if has_tools && has_functions {
+            return Err(OpenAIError::InvalidArgument(
+                "`functions` is deprecated and cannot be combined with `tools`".into(),
+            ));
+        }
+        if matches!(&self.function_call, Some(Some(_))) && !has_functions {
+            return Err(OpenAIError::InvalidArgument(
+                "`function_call` requires `functions`, use `tool_choice` with `tools` instead"
+                    .into(),
+            ));
+        }
+        if matches!(&self.tool_choice, Some(Some(_))) && !has_tools {
+            return Err(OpenAIError::InvalidArgument(
+                "`tool_choice` is only allowed when `tools` are specified".into(),
+            ));
+        }
+        if matches!(&self.parallel_tool_calls, Some(Some(_))) && !has_tools {
+            return Err(OpenAIError::InvalidArgument(
+                "`parallel_tool_calls` is only allowed when `tools` are specified".into(),
+            ));
+        }
+        if matches!(&self.top_logprobs, Some(Some(_))) && self.logprobs != Some(Some(true)) {
+            return Err(OpenAIError::InvalidArgument(
+                "`top_logprobs` requires `logprobs` to be true".into(),
+            ));
+        }
+        if matches!(&self.stream_options, Some(Some(_))) && self.stream == Some(Some(false)) {
+            return Err(OpenAIError::InvalidArgument(
+                "`stream_options` is only allowed when `stream` is true".into(),
+            ));
+        }
+        if matches!(&self.max_tokens, Some(Some(_)))
+            && matches!(&self.max_completion_tokens, Some(Some(_)))
+        {
+            return Err(OpenAIError::InvalidArgument(
+                "`max_tokens` is deprecated and cannot be combined with `max_completion_tokens`"
+                    .into(),
+            ));
+        }
+        if matches!(&self.prediction, Some(Some(_))) {
+            if matches!(self.n, Some(Some(n)) if n > 1) {
+                return Err(OpenAIError::InvalidArgument(
+                    "`prediction` is not supported with `n` greater than 1".into(),
+                ));
+            }
+            if self.logprobs == Some(Some(true)) {
+                return Err(OpenAIError::InvalidArgument(
+                    "`prediction` is not supported with `logprobs`".into(),
+                ));
+            }
+            if matches!(&self.audio, Some(Some(_))) {
+                return Err(OpenAIError::InvalidArgument(
+                    "`prediction` is not supported with audio output".into(),
+                ));
+            }
+        }
+        if let Some(messages) = &self.messages {
+            validate_tool_call_ids(messages)?;
+        }
+        if let Some(Some(response_format)) = &self.response_format {
+            response_format.validate()?;
+        }
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Every tool message must answer a tool call of a preceding assistant message.
+fn validate_tool_call_ids(messages: &[ChatCompletionRequestMessage]) -> Result<(), OpenAIError> {
+    let mut tool_call_ids = Vec::new();
+    for message in messages {
+        match message {
+            ChatCompletionRequestMessage::Assistant(assistant) => {
+                tool_call_ids.extend(assistant.tool_calls.iter().flatten().map(|c| c.id.as_str()));
+            }
+            ChatCompletionRequestMessage::Tool(tool)
+                if !tool_call_ids.contains(&tool.tool_call_id.as_str()) =>
+            {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "tool message `{}` does not answer a tool call of a preceding assistant message",
+                    tool.tool_call_id
+                )));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
 /// Options for streaming response. Only set this when you set `stream: true`.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub struct ChatCompletionStreamOptions {
@@ -954,6 +1186,16 @@ pub struct ChatChoice {
     /// Log probability information for the choice.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logprobs: Option<ChatChoiceLogprobs>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
 }
 
 /// Represents a chat completion response returned by model, based on the provided input.
@@ -964,7 +1206,8 @@ pub struct CreateChatCompletionResponse {
     /// A list of chat completion choices. Can be more than one if `n` is greater than 1.
     pub choices: Vec<ChatChoice>,
     /// The Unix timestamp (in seconds) of when the chat completion was created.
-    pub created: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
     /// The model used for the chat completion.
     pub model: String,
     /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
@@ -979,6 +1222,16 @@ pub struct CreateChatCompletionResponse {
     /// The object type, which is always `chat.completion`.
     pub object: String,
     pub usage: Option<CompletionUsage>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
@@ -1020,6 +1273,45 @@ pub struct ChatCompletionStreamResponseDelta {
     pub role: Option<Role>,
     /// The refusal message generated by the model.
     pub refusal: Option<String>,
+    /// A chunk of the audio response, if audio output was requested with `modalities: ["audio"]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<ChatCompletionStreamResponseAudioDelta>,
+    /// Annotations of the content, such as the URL citations of web search, usually sent
+    /// with the last delta of the content.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<Vec<ChatCompletionMessageAnnotation>>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
+}
+
+/// A chunk of [ChatCompletionResponseMessageAudio]: the first chunk carries the `id`,
+/// following chunks carry `data` and `transcript` pieces and the last one `expires_at`.
+#[derive(Debug, Deserialize, Serialize, Default, Clone, PartialEq)]
+pub struct ChatCompletionStreamResponseAudioDelta {
+    /// Unique identifier for this audio response.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The Unix timestamp (in seconds) for when this audio response will no longer be accessible on the server.
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_at: Option<Timestamp>,
+    /// Base64 encoded chunk of the audio bytes.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// Chunk of the transcript of the audio.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transcript: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -1042,6 +1334,16 @@ pub struct ChatChoiceStream {
     /// Log probability information for the choice.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub logprobs: Option<ChatChoiceLogprobs>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -1053,7 +1355,8 @@ pub struct CreateChatCompletionStreamResponse {
     pub choices: Vec<ChatChoiceStream>,
 
     /// The Unix timestamp (in seconds) of when the chat completion was created. Each chunk has the same timestamp.
-    pub created: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
     /// The model to generate the completion.
     pub model: String,
     /// The service tier used for processing the request. This field is only included if the `service_tier` parameter is specified in the request.
@@ -1067,4 +1370,101 @@ pub struct CreateChatCompletionStreamResponse {
     /// An optional field that will only be present when you set `stream_options: {"include_usage": true}` in your request.
     /// When present, it contains a null value except for the last chunk which contains the token usage statistics for the entire request.
     pub usage: Option<CompletionUsage>,
+
+    /// Random characters padding the chunk to normalize payload sizes, a mitigation of
+    /// side-channel attacks on the length of the tokens.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation: Option<String>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
+}
+
+impl CreateChatCompletionStreamResponse {
+    /// Metadata carried by this chunk.
+    pub fn metadata(&self) -> StreamMetadata {
+        StreamMetadata {
+            id: self.id.clone(),
+            created: self.created,
+            model: self.model.clone(),
+            service_tier: self.service_tier.clone(),
+            system_fingerprint: self.system_fingerprint.clone(),
+            usage: self.usage.clone(),
+        }
+    }
+}
+
+/// Metadata of a streamed completion, accumulated over all of its chunks.
+///
+/// Providers do not always repeat every field on every chunk: `system_fingerprint` and
+/// `service_tier` may only be present on some chunks and `usage` only on the last one
+/// (with `stream_options: {"include_usage": true}`).
+///
+/// ```
+/// use async_openai::types::{CreateChatCompletionStreamResponse, StreamMetadata};
+///
+/// fn summarize(chunks: &[CreateChatCompletionStreamResponse]) -> StreamMetadata {
+///     StreamMetadata::from_chunks(chunks)
+/// }
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StreamMetadata {
+    /// The identifier shared by all chunks.
+    pub id: String,
+    /// The Unix timestamp (in seconds) of when the completion was created.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
+    /// The model that generated the completion.
+    pub model: String,
+    /// The service tier used for processing the request, if reported.
+    pub service_tier: Option<ServiceTierResponse>,
+    /// The backend configuration fingerprint, if reported.
+    pub system_fingerprint: Option<String>,
+    /// Token usage for the entire request, if reported.
+    pub usage: Option<CompletionUsage>,
+}
+
+impl StreamMetadata {
+    /// Accumulate the metadata of all `chunks`.
+    pub fn from_chunks<'a, I>(chunks: I) -> Self
+    where
+        I: IntoIterator<Item = &'a CreateChatCompletionStreamResponse>,
+    {
+        chunks
+            .into_iter()
+            .fold(Self::default(), |mut metadata, chunk| {
+                metadata.update(chunk.metadata());
+                metadata
+            })
+    }
+
+    /// Fold in the metadata of a subsequent chunk: `id`, `created` and `model` are kept
+    /// from the first chunk which has them, optional fields are taken from the latest chunk carrying them.
+    pub fn update(&mut self, chunk: StreamMetadata) {
+        if self.id.is_empty() {
+            self.id = chunk.id;
+        }
+        if self.created == Timestamp::default() {
+            self.created = chunk.created;
+        }
+        if self.model.is_empty() {
+            self.model = chunk.model;
+        }
+        if chunk.service_tier.is_some() {
+            self.service_tier = chunk.service_tier;
+        }
+        if chunk.system_fingerprint.is_some() {
+            self.system_fingerprint = chunk.system_fingerprint;
+        }
+        if chunk.usage.is_some() {
+            self.usage = chunk.usage;
+        }
+    }
 }
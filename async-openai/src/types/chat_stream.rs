@@ -0,0 +1,152 @@
+use std::pin::Pin;
+
+use futures::{Stream, StreamExt, TryStreamExt};
+
+use crate::error::OpenAIError;
+
+use super::{
+    ChatCompletionMessageAnnotation, ChatCompletionResponseStream, ChatCompletionStreamOptions,
+    CompletionUsage, CreateChatCompletionRequestArgs, CreateChatCompletionStreamResponse,
+    FinishReason, StreamMetadata,
+};
+
+/// Content deltas of the first choice of a stream, with the usage of the request on the item
+/// of the last chunk, see [content_with_usage].
+pub type ContentWithUsageStream =
+    Pin<Box<dyn Stream<Item = Result<(String, Option<CompletionUsage>), OpenAIError>> + Send>>;
+
+impl CreateChatCompletionRequestArgs {
+    /// Set `stream_options.include_usage`, for the usage of the request to be streamed in an
+    /// additional last chunk without choices, see [CreateChatCompletionStreamResponse::is_usage_only].
+    pub fn include_usage(&mut self, include_usage: bool) -> &mut Self {
+        self.stream_options(ChatCompletionStreamOptions { include_usage })
+    }
+}
+
+impl CreateChatCompletionStreamResponse {
+    /// Whether this is the last chunk of a stream requested with `include_usage`, which has
+    /// no choices and carries the usage of the whole request.
+    pub fn is_usage_only(&self) -> bool {
+        self.choices.is_empty() && self.usage.is_some()
+    }
+
+    /// Content delta of the first choice, if any.
+    pub fn content_delta(&self) -> Option<&str> {
+        self.choices
+            .iter()
+            .find(|choice| choice.index == 0)
+            .and_then(|choice| choice.delta.content.as_deref())
+    }
+}
+
+/// Adapt `stream` to its content deltas paired with the usage of the request, which is
+/// only present on the last item when the request was made with `include_usage`.
+///
+/// Chunks without content nor usage, such as the first chunk of the role, are skipped.
+pub fn content_with_usage(stream: ChatCompletionResponseStream) -> ContentWithUsageStream {
+    Box::pin(stream.try_filter_map(|chunk| async move {
+        let content = chunk.content_delta().unwrap_or_default().to_string();
+        Ok((!content.is_empty() || chunk.usage.is_some()).then_some((content, chunk.usage)))
+    }))
+}
+
+/// A streamed chat completion collected from its chunks.
+///
+/// ```no_run
+/// # async fn collect() -> Result<(), async_openai::error::OpenAIError> {
+/// use async_openai::{
+///     types::{
+///         ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs, StreamedChatCompletion,
+///     },
+///     Client,
+/// };
+///
+/// let request = CreateChatCompletionRequestArgs::default()
+///     .model("gpt-4o-mini")
+///     .messages([ChatCompletionRequestUserMessage::from("Write a haiku").into()])
+///     .include_usage(true)
+///     .build()?;
+/// let stream = Client::new().chat().create_stream(request).await?;
+///
+/// let completion = StreamedChatCompletion::collect(stream).await?;
+/// println!("{}", completion.content);
+/// println!("{:?}", completion.usage());
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StreamedChatCompletion {
+    /// The content of the first choice.
+    pub content: String,
+    /// The reason the first choice finished, if reported.
+    pub finish_reason: Option<FinishReason>,
+    /// Annotations of the content of the first choice, such as URL citations.
+    pub annotations: Vec<ChatCompletionMessageAnnotation>,
+    /// Metadata accumulated over the chunks, with the usage of the last chunk.
+    pub metadata: StreamMetadata,
+}
+
+impl StreamedChatCompletion {
+    /// Collect the content of `stream` along with its metadata and usage.
+    pub async fn collect(mut stream: ChatCompletionResponseStream) -> Result<Self, OpenAIError> {
+        let mut completion = Self::default();
+        while let Some(chunk) = stream.next().await {
+            completion.push(&chunk?);
+        }
+        Ok(completion)
+    }
+
+    /// Accumulate `chunk`.
+    pub fn push(&mut self, chunk: &CreateChatCompletionStreamResponse) {
+        if let Some(content) = chunk.content_delta() {
+            self.content.push_str(content);
+        }
+        if let Some(choice) = chunk.choices.iter().find(|choice| choice.index == 0) {
+            if let Some(finish_reason) = choice.finish_reason {
+                self.finish_reason = Some(finish_reason);
+            }
+            if let Some(annotations) = &choice.delta.annotations {
+                self.annotations.extend(annotations.iter().cloned());
+            }
+        }
+        self.metadata.update(chunk.metadata());
+    }
+
+    /// Token usage of the request, `None` unless it was made with `include_usage`.
+    pub fn usage(&self) -> Option<&CompletionUsage> {
+        self.metadata.usage.as_ref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunk(json: &str) -> Result<CreateChatCompletionStreamResponse, OpenAIError> {
+        Ok(serde_json::from_str(json).unwrap())
+    }
+
+    #[tokio::test]
+    async fn test_content_with_usage() {
+        let chunks = [
+            r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[{"index":0,"delta":{"role":"assistant","content":""}}],"usage":null}"#,
+            r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[{"index":0,"delta":{"content":"Hi"}}],"usage":null}"#,
+            r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[{"index":0,"delta":{},"finish_reason":"stop"}],"usage":null}"#,
+            r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[],"usage":{"prompt_tokens":3,"completion_tokens":1,"total_tokens":4}}"#,
+        ];
+        let stream = || -> ChatCompletionResponseStream {
+            Box::pin(futures::stream::iter(chunks.map(chunk)))
+        };
+
+        let items: Vec<_> = content_with_usage(stream()).try_collect().await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0], ("Hi".to_string(), None));
+        assert_eq!(items[1].1.as_ref().map(|usage| usage.total_tokens), Some(4));
+
+        let completion = StreamedChatCompletion::collect(stream()).await.unwrap();
+        assert_eq!(completion.content, "Hi");
+        assert_eq!(completion.finish_reason, Some(FinishReason::Stop));
+        assert_eq!(completion.usage().map(|usage| usage.prompt_tokens), Some(3));
+        assert!(chunk(chunks[3]).unwrap().is_usage_only());
+    }
+}
@@ -1,4 +1,7 @@
-use std::path::PathBuf;
+use std::{fmt, marker::PhantomData, path::PathBuf};
+
+#[cfg(feature = "extra-fields")]
+use std::collections::HashMap;
 
 use bytes::Bytes;
 use serde::{Deserialize, Serialize};
@@ -10,9 +13,151 @@ pub enum InputSource {
     VecU8 { filename: String, vec: Vec<u8> },
 }
 
+/// Confirmation of a deleted object of type `T`, returned by the delete endpoints.
+#[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Deleted<T> {
+    /// The ID of the deleted object.
+    pub id: String,
+    /// The object type, such as `file` or `organization.invite.deleted`.
+    pub object: String,
+    pub deleted: bool,
+    #[serde(skip)]
+    kind: PhantomData<fn() -> T>,
+}
+
+impl<T> Deleted<T> {
+    pub fn new<I: Into<String>, O: Into<String>>(id: I, object: O, deleted: bool) -> Self {
+        Self {
+            id: id.into(),
+            object: object.into(),
+            deleted,
+            kind: PhantomData,
+        }
+    }
+
+    /// Whether the API confirmed the deletion.
+    pub fn is_deleted(&self) -> bool {
+        self.deleted
+    }
+}
+
+// implemented by hand, `T` being only a marker of the deleted object type
+impl<T> fmt::Debug for Deleted<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Deleted")
+            .field("id", &self.id)
+            .field("object", &self.object)
+            .field("deleted", &self.deleted)
+            .finish()
+    }
+}
+
+impl<T> Clone for Deleted<T> {
+    fn clone(&self) -> Self {
+        Self::new(self.id.clone(), self.object.clone(), self.deleted)
+    }
+}
+
+impl<T> PartialEq for Deleted<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id && self.object == other.object && self.deleted == other.deleted
+    }
+}
+
+impl<T> Default for Deleted<T> {
+    fn default() -> Self {
+        Self::new(String::new(), String::new(), false)
+    }
+}
+
+/// Sort order of list endpoints, by the `created_at` timestamp of the objects.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    Asc,
+    #[default]
+    Desc,
+}
+
+/// A cursor of list endpoints: the ID of an object, to list the objects `after` or `before` it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq, Hash)]
+#[serde(transparent)]
+pub struct Cursor(String);
+
+impl Cursor {
+    pub fn new<S: Into<String>>(id: S) -> Self {
+        Self(id.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    pub fn into_inner(self) -> String {
+        self.0
+    }
+}
+
+impl From<String> for Cursor {
+    fn from(id: String) -> Self {
+        Self(id)
+    }
+}
+
+impl From<&str> for Cursor {
+    fn from(id: &str) -> Self {
+        Self(id.into())
+    }
+}
+
+impl From<&String> for Cursor {
+    fn from(id: &String) -> Self {
+        Self(id.clone())
+    }
+}
+
+impl AsRef<str> for Cursor {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for Cursor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum OrganizationRole {
     Owner,
     Reader,
 }
+
+/// JSON fields received from the API which are not part of a type's definition.
+#[cfg(feature = "extra-fields")]
+#[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+pub type ExtraFields = HashMap<String, serde_json::Value>;
+
+/// Unix timestamp (in seconds) of response objects, a [chrono::DateTime] in UTC with the `chrono` feature.
+#[cfg(not(feature = "chrono"))]
+pub type Timestamp = u32;
+/// Unix timestamp (in seconds) of response objects, a [chrono::DateTime] in UTC with the `chrono` feature.
+#[cfg(feature = "chrono")]
+pub type Timestamp = chrono::DateTime<chrono::Utc>;
+
+/// Same as [Timestamp], for objects whose raw timestamp is an `i32`.
+#[cfg(not(feature = "chrono"))]
+pub type TimestampI32 = i32;
+/// Same as [Timestamp], for objects whose raw timestamp is an `i32`.
+#[cfg(feature = "chrono")]
+pub type TimestampI32 = chrono::DateTime<chrono::Utc>;
+
+/// Same as [Timestamp], for objects whose raw timestamp is a `u64`.
+#[cfg(not(feature = "chrono"))]
+pub type TimestampU64 = u64;
+/// Same as [Timestamp], for objects whose raw timestamp is a `u64`.
+#[cfg(feature = "chrono")]
+pub type TimestampU64 = chrono::DateTime<chrono::Utc>;
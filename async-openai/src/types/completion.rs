@@ -6,14 +6,19 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::{ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop};
+#[cfg(feature = "extra-fields")]
+use super::ExtraFields;
+
+use super::{
+    ChatCompletionStreamOptions, Choice, CompletionUsage, Prompt, Stop, StreamMetadata, Timestamp,
+};
 
 #[derive(Clone, Serialize, Deserialize, Default, Debug, Builder, PartialEq)]
 #[builder(name = "CreateCompletionRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateCompletionRequest {
     /// ID of the model to use. You can use the [List models](https://platform.openai.com/docs/api-reference/models/list) API to see all of your available models, or see our [Model overview](https://platform.openai.com/docs/models/overview) for descriptions of them.
     pub model: String,
@@ -144,13 +149,42 @@ pub struct CreateCompletionRequest {
     pub priority: Option<i32>,
 }
 
+impl CreateCompletionRequestArgs {
+    /// Rejects combinations of fields that the API would refuse with a 400.
+    fn validate(&self) -> Result<(), OpenAIError> {
+        let stream = self.stream.flatten();
+
+        if matches!(&self.stream_options, Some(Some(_))) && stream == Some(false) {
+            return Err(OpenAIError::InvalidArgument(
+                "`stream_options` is only allowed when `stream` is true".into(),
+            ));
+        }
+        if let Some(best_of) = self.best_of.flatten() {
+            if stream == Some(true) {
+                return Err(OpenAIError::InvalidArgument(
+                    "`best_of` results cannot be streamed".into(),
+                ));
+            }
+            if let Some(n) = self.n.flatten() {
+                if best_of < n {
+                    return Err(OpenAIError::InvalidArgument(format!(
+                        "`best_of` ({best_of}) must be greater than or equal to `n` ({n})"
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct CreateCompletionResponse {
     /// A unique identifier for the completion.
     pub id: String,
     pub choices: Vec<Choice>,
     /// The Unix timestamp (in seconds) of when the completion was created.
-    pub created: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
 
     /// The model used for completion.
     pub model: String,
@@ -163,6 +197,30 @@ pub struct CreateCompletionResponse {
     /// The object type, which is always "text_completion"
     pub object: String,
     pub usage: Option<CompletionUsage>,
+
+    /// Fields not covered by this type, such as provider specific extensions.
+    #[cfg(feature = "extra-fields")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "extra-fields")))]
+    #[serde(
+        flatten,
+        deserialize_with = "crate::parse::extra_fields",
+        skip_serializing_if = "ExtraFields::is_empty"
+    )]
+    pub extra: ExtraFields,
+}
+
+impl CreateCompletionResponse {
+    /// Metadata carried by this response, or by this chunk when streaming.
+    pub fn metadata(&self) -> StreamMetadata {
+        StreamMetadata {
+            id: self.id.clone(),
+            created: self.created,
+            model: self.model.clone(),
+            service_tier: None,
+            system_fingerprint: self.system_fingerprint.clone(),
+            usage: self.usage.clone(),
+        }
+    }
 }
 
 /// Parsed server side events stream until an \[DONE\] is received from server.
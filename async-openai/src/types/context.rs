@@ -0,0 +1,508 @@
+use std::{fmt, sync::Arc};
+
+use crate::error::OpenAIError;
+
+use super::{
+    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartText,
+    ChatCompletionRequestSystemMessage, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    CreateChatCompletionRequest, CreateChatCompletionResponse,
+};
+
+/// A retrieved document injected into a chat request by [ContextStrategy].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ContextDocument {
+    pub id: Option<String>,
+    pub title: Option<String>,
+    pub text: String,
+    /// Embedding of `text`, used to rank documents with [ContextStrategy::with_query_embedding].
+    pub embedding: Option<Vec<f32>>,
+}
+
+impl ContextDocument {
+    pub fn new<S: Into<String>>(text: S) -> Self {
+        Self {
+            text: text.into(),
+            ..Default::default()
+        }
+    }
+
+    pub fn with_id<S: Into<String>>(mut self, id: S) -> Self {
+        self.id = Some(id.into());
+        self
+    }
+
+    pub fn with_title<S: Into<String>>(mut self, title: S) -> Self {
+        self.title = Some(title.into());
+        self
+    }
+
+    pub fn with_embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.embedding = Some(embedding);
+        self
+    }
+}
+
+impl From<&str> for ContextDocument {
+    fn from(text: &str) -> Self {
+        Self::new(text)
+    }
+}
+
+impl From<String> for ContextDocument {
+    fn from(text: String) -> Self {
+        Self::new(text)
+    }
+}
+
+/// Where [ContextStrategy] injects the documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ContextPlacement {
+    /// A system message following the leading system and developer messages.
+    #[default]
+    SystemMessage,
+    /// A user message right before the last user message.
+    BeforeLastUserMessage,
+    /// Prepended to the content of the last user message.
+    LastUserMessage,
+}
+
+/// How each document is written in the prompt.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CitationFormat {
+    /// `[1] Title` followed by the text, cited as `[1]`.
+    #[default]
+    Numbered,
+    /// `<document index="1" id="..." title="...">text</document>`, with the markup characters
+    /// of the text and attributes escaped.
+    Xml,
+    /// The bare text, documents separated by blank lines.
+    Plain,
+}
+
+/// Rough token count of `text` for English prose, about four characters per token.
+pub fn estimate_tokens(text: &str) -> usize {
+    text.chars().count().div_ceil(4)
+}
+
+/// Counts the tokens of a text, e.g. with the tokenizer of a model, [estimate_tokens] by default.
+pub type TokenCounter = Arc<dyn Fn(&str) -> usize + Send + Sync>;
+
+/// Budgets and formats retrieved documents injected into a chat request.
+///
+/// Documents are added in order, or by similarity to the query embedding when set, as long as
+/// they fit in the token budget; documents which do not fit are skipped.
+///
+/// ```
+/// use async_openai::types::{
+///     ChatCompletionRequestUserMessage, CitationFormat, ContextDocument, ContextStrategy,
+///     CreateChatCompletionRequestArgs,
+/// };
+///
+/// # fn main() -> Result<(), async_openai::error::OpenAIError> {
+/// let mut request = CreateChatCompletionRequestArgs::default()
+///     .model("gpt-4o-mini")
+///     .messages([ChatCompletionRequestUserMessage::from("When was the bridge built?").into()])
+///     .build()?;
+/// let documents = [ContextDocument::new("The bridge opened in 1932.").with_title("History")];
+///
+/// let strategy = ContextStrategy::new()
+///     .with_max_tokens(1_000)
+///     .with_citation_format(CitationFormat::Numbered);
+/// let included = strategy.apply(&mut request, &documents)?;
+/// assert_eq!(included, vec![0]);
+/// assert_eq!(request.messages.len(), 2);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ContextStrategy {
+    max_tokens: usize,
+    placement: ContextPlacement,
+    citation_format: CitationFormat,
+    instructions: Option<String>,
+    count_tokens: TokenCounter,
+    query_embedding: Option<Vec<f32>>,
+}
+
+impl fmt::Debug for ContextStrategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContextStrategy")
+            .field("max_tokens", &self.max_tokens)
+            .field("placement", &self.placement)
+            .field("citation_format", &self.citation_format)
+            .field("instructions", &self.instructions)
+            .field("query_embedding", &self.query_embedding)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for ContextStrategy {
+    fn default() -> Self {
+        Self {
+            max_tokens: 2_000,
+            placement: Default::default(),
+            citation_format: Default::default(),
+            instructions: None,
+            count_tokens: Arc::new(estimate_tokens),
+            query_embedding: None,
+        }
+    }
+}
+
+impl ContextStrategy {
+    /// 2000 tokens of numbered documents in a system message.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Token budget of the injected context, instructions included.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn with_placement(mut self, placement: ContextPlacement) -> Self {
+        self.placement = placement;
+        self
+    }
+
+    pub fn with_citation_format(mut self, citation_format: CitationFormat) -> Self {
+        self.citation_format = citation_format;
+        self
+    }
+
+    /// Text introducing the documents, replacing the default one of the citation format.
+    pub fn with_instructions<S: Into<String>>(mut self, instructions: S) -> Self {
+        self.instructions = Some(instructions.into());
+        self
+    }
+
+    /// Count tokens with the tokenizer of the model instead of [estimate_tokens].
+    pub fn with_token_counter<F>(mut self, count_tokens: F) -> Self
+    where
+        F: Fn(&str) -> usize + Send + Sync + 'static,
+    {
+        self.count_tokens = Arc::new(count_tokens);
+        self
+    }
+
+    /// Add the documents most similar to `embedding` first, documents without embedding last.
+    pub fn with_query_embedding(mut self, embedding: Vec<f32>) -> Self {
+        self.query_embedding = Some(embedding);
+        self
+    }
+
+    /// Inject `documents` in the messages of `request`.
+    ///
+    /// Returns the indices in `documents` of the injected documents in citation order,
+    /// the request is left untouched when none fits in the budget.
+    pub fn apply(
+        &self,
+        request: &mut CreateChatCompletionRequest,
+        documents: &[ContextDocument],
+    ) -> Result<Vec<usize>, OpenAIError> {
+        let Some((context, included)) = self.context(documents) else {
+            return Ok(Vec::new());
+        };
+        let messages = &mut request.messages;
+
+        match self.placement {
+            ContextPlacement::SystemMessage => {
+                let at = messages
+                    .iter()
+                    .take_while(|message| {
+                        matches!(
+                            message,
+                            ChatCompletionRequestMessage::System(_)
+                                | ChatCompletionRequestMessage::Developer(_)
+                        )
+                    })
+                    .count();
+                messages.insert(at, ChatCompletionRequestSystemMessage::from(context).into());
+            }
+            ContextPlacement::BeforeLastUserMessage => {
+                let at = last_user_message(messages)?;
+                messages.insert(at, ChatCompletionRequestUserMessage::from(context).into());
+            }
+            ContextPlacement::LastUserMessage => {
+                let at = last_user_message(messages)?;
+                if let ChatCompletionRequestMessage::User(message) = &mut messages[at] {
+                    match &mut message.content {
+                        ChatCompletionRequestUserMessageContent::Text(text) => {
+                            *text = format!("{context}\n\n{text}");
+                        }
+                        ChatCompletionRequestUserMessageContent::Array(parts) => parts.insert(
+                            0,
+                            ChatCompletionRequestUserMessageContentPart::Text(
+                                ChatCompletionRequestMessageContentPartText { text: context },
+                            ),
+                        ),
+                    }
+                }
+            }
+        }
+        Ok(included)
+    }
+
+    /// Text of the context and indices of the documents it includes, `None` if no document fits.
+    fn context(&self, documents: &[ContextDocument]) -> Option<(String, Vec<usize>)> {
+        let instructions = self
+            .instructions
+            .as_deref()
+            .unwrap_or(match self.citation_format {
+                CitationFormat::Numbered => {
+                    "Answer using the documents below, citing them by number like [1]."
+                }
+                CitationFormat::Xml => "Answer using the documents below, citing them by index.",
+                CitationFormat::Plain => "Answer using the documents below.",
+            });
+        let mut remaining = self
+            .max_tokens
+            .checked_sub((self.count_tokens)(instructions))?;
+
+        let mut blocks = vec![instructions.to_string()];
+        let mut included = Vec::new();
+        for index in self.order(documents) {
+            let block = self.format(included.len() + 1, &documents[index]);
+            let tokens = (self.count_tokens)(&block);
+            if tokens <= remaining {
+                remaining -= tokens;
+                blocks.push(block);
+                included.push(index);
+            }
+        }
+
+        if included.is_empty() {
+            return None;
+        }
+        Some((blocks.join("\n\n"), included))
+    }
+
+    fn order(&self, documents: &[ContextDocument]) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..documents.len()).collect();
+        if let Some(query) = &self.query_embedding {
+            let similarity = |&index: &usize| {
+                documents[index]
+                    .embedding
+                    .as_deref()
+                    .map(|embedding| cosine_similarity(query, embedding))
+                    .unwrap_or(f32::MIN)
+            };
+            // stable, documents of equal similarity keep their order
+            order.sort_by(|a, b| similarity(b).total_cmp(&similarity(a)));
+        }
+        order
+    }
+
+    fn format(&self, number: usize, document: &ContextDocument) -> String {
+        match self.citation_format {
+            CitationFormat::Numbered => match &document.title {
+                Some(title) => format!("[{number}] {title}\n{}", document.text),
+                None => format!("[{number}] {}", document.text),
+            },
+            CitationFormat::Xml => {
+                let mut attributes = format!("index=\"{number}\"");
+                if let Some(id) = &document.id {
+                    attributes.push_str(&format!(" id=\"{}\"", escape_xml(id)));
+                }
+                if let Some(title) = &document.title {
+                    attributes.push_str(&format!(" title=\"{}\"", escape_xml(title)));
+                }
+                format!(
+                    "<document {attributes}>\n{}\n</document>",
+                    escape_xml(&document.text)
+                )
+            }
+            CitationFormat::Plain => document.text.clone(),
+        }
+    }
+}
+
+fn last_user_message(messages: &[ChatCompletionRequestMessage]) -> Result<usize, OpenAIError> {
+    messages
+        .iter()
+        .rposition(|message| matches!(message, ChatCompletionRequestMessage::User(_)))
+        .ok_or_else(|| {
+            OpenAIError::InvalidArgument("context placement requires a user message".into())
+        })
+}
+
+/// `text` with the characters of XML markup replaced by their entities.
+fn escape_xml(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let dot: f32 = a.iter().zip(b).map(|(a, b)| a * b).sum();
+    let norm = |v: &[f32]| v.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norms = norm(a) * norm(b);
+    if norms == 0.0 {
+        0.0
+    } else {
+        dot / norms
+    }
+}
+
+/// Response of [crate::Chat::create_with_context].
+#[derive(Debug, Clone, PartialEq)]
+pub struct ContextChatResponse {
+    pub response: CreateChatCompletionResponse,
+    /// Indices of the injected documents in citation order, citation `[1]` being `documents[0]`.
+    pub documents: Vec<usize>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::ChatCompletionRequestDeveloperMessage;
+
+    fn request(messages: Vec<ChatCompletionRequestMessage>) -> CreateChatCompletionRequest {
+        CreateChatCompletionRequest {
+            model: "m".into(),
+            messages,
+            ..Default::default()
+        }
+    }
+
+    fn strategy() -> ContextStrategy {
+        ContextStrategy::new()
+            .with_citation_format(CitationFormat::Plain)
+            .with_instructions("Documents:")
+    }
+
+    #[test]
+    fn test_context_placement() {
+        let messages = vec![
+            ChatCompletionRequestSystemMessage::from("system").into(),
+            ChatCompletionRequestDeveloperMessage::from("developer").into(),
+            ChatCompletionRequestUserMessage::from("first").into(),
+            ChatCompletionRequestUserMessage::from("last").into(),
+        ];
+        let documents = [ContextDocument::new("doc")];
+        let context = "Documents:\n\ndoc";
+
+        let mut system = request(messages.clone());
+        strategy().apply(&mut system, &documents).unwrap();
+        assert_eq!(
+            system.messages[2],
+            ChatCompletionRequestSystemMessage::from(context).into()
+        );
+        assert_eq!(system.messages.len(), 5);
+
+        let mut before = request(messages.clone());
+        strategy()
+            .with_placement(ContextPlacement::BeforeLastUserMessage)
+            .apply(&mut before, &documents)
+            .unwrap();
+        assert_eq!(
+            before.messages[3],
+            ChatCompletionRequestUserMessage::from(context).into()
+        );
+        assert_eq!(
+            before.messages[4],
+            ChatCompletionRequestUserMessage::from("last").into()
+        );
+
+        let mut last = request(messages);
+        strategy()
+            .with_placement(ContextPlacement::LastUserMessage)
+            .apply(&mut last, &documents)
+            .unwrap();
+        assert_eq!(last.messages.len(), 4);
+        assert_eq!(
+            last.messages[3],
+            ChatCompletionRequestUserMessage::from(format!("{context}\n\nlast")).into()
+        );
+
+        let mut no_user = request(vec![ChatCompletionRequestSystemMessage::from("s").into()]);
+        let error = strategy()
+            .with_placement(ContextPlacement::LastUserMessage)
+            .apply(&mut no_user, &documents)
+            .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+    }
+
+    #[test]
+    fn test_context_budget() {
+        // one token per word, counted by a closure capturing its state
+        let counted = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counter = counted.clone();
+        let strategy = strategy()
+            .with_max_tokens(6)
+            .with_token_counter(move |text| {
+                counter.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                text.split_whitespace().count()
+            });
+        let documents = [
+            ContextDocument::new("one two three"),
+            ContextDocument::new("a b c d e f"),
+            ContextDocument::new("x y"),
+        ];
+
+        let mut request = request(vec![ChatCompletionRequestUserMessage::from("q").into()]);
+        // the second document is skipped, the third still fits
+        assert_eq!(strategy.apply(&mut request, &documents).unwrap(), [0, 2]);
+        assert_eq!(
+            request.messages[0],
+            ChatCompletionRequestSystemMessage::from("Documents:\n\none two three\n\nx y").into()
+        );
+        assert!(counted.load(std::sync::atomic::Ordering::Relaxed) > 0);
+
+        // nothing fits: the request is untouched
+        let mut untouched = request.clone();
+        untouched.messages.truncate(1);
+        let expected = untouched.clone();
+        let included = strategy
+            .with_max_tokens(2)
+            .apply(&mut untouched, &documents)
+            .unwrap();
+        assert!(included.is_empty());
+        assert_eq!(untouched, expected);
+    }
+
+    #[test]
+    fn test_context_query_embedding() {
+        let documents = [
+            ContextDocument::new("unrelated").with_embedding(vec![1.0, 0.0]),
+            ContextDocument::new("no embedding"),
+            ContextDocument::new("related").with_embedding(vec![0.1, 1.0]),
+            ContextDocument::new("closest").with_embedding(vec![0.0, 1.0]),
+        ];
+        let mut request = request(vec![ChatCompletionRequestUserMessage::from("q").into()]);
+        let included = strategy()
+            .with_query_embedding(vec![0.0, 2.0])
+            .apply(&mut request, &documents)
+            .unwrap();
+        assert_eq!(included, [3, 2, 0, 1]);
+    }
+
+    #[test]
+    fn test_context_xml_escaping() {
+        let document = ContextDocument::new("a < b && c")
+            .with_id("doc\"1")
+            .with_title("<Q&A>");
+        let mut request = request(vec![ChatCompletionRequestUserMessage::from("q").into()]);
+        strategy()
+            .with_citation_format(CitationFormat::Xml)
+            .apply(&mut request, &[document])
+            .unwrap();
+        assert_eq!(
+            request.messages[0],
+            ChatCompletionRequestSystemMessage::from(
+                "Documents:\n\n<document index=\"1\" id=\"doc&quot;1\" title=\"&lt;Q&amp;A&gt;\">\na &lt; b &amp;&amp; c\n</document>"
+            )
+            .into()
+        );
+    }
+}
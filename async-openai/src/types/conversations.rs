@@ -0,0 +1,225 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+use super::{
+    responses::{
+        CodeInterpreterCallOutput, ComputerCallOutput, FileSearchCallOutput, FunctionCall,
+        ImageGenerationCallOutput, InputFile, InputImage, InputItem, InputText,
+        LocalShellCallOutput, McpApprovalRequestOutput, McpCallOutput, McpListToolsOutput,
+        OutputStatus, OutputText, ReasoningItem, Refusal, Role, WebSearchCallOutput,
+    },
+    Cursor, Deleted, Metadata, SortOrder, TimestampU64,
+};
+
+/// A conversation storing the items of a multi-turn interaction, passed to the Responses API
+/// instead of the previous items.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Conversation {
+    /// The unique ID of the conversation.
+    pub id: String,
+    /// The object type, which is always `conversation`.
+    pub object: String,
+    /// Unix timestamp (in seconds) when the conversation was created.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampU64,
+    pub metadata: Option<Metadata>,
+}
+
+/// Confirmation of a deleted [Conversation].
+pub type DeleteConversationResponse = Deleted<Conversation>;
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Builder)]
+#[builder(name = "CreateConversationRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
+pub struct CreateConversationRequest {
+    /// Initial items of the conversation, up to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub items: Option<Vec<InputItem>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl CreateConversationRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Builder)]
+#[builder(name = "UpdateConversationRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
+pub struct UpdateConversationRequest {
+    /// Replaces the metadata of the conversation.
+    pub metadata: Metadata,
+}
+
+impl UpdateConversationRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(metadata) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Builder)]
+#[builder(name = "CreateConversationItemsRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateConversationItemsRequest {
+    /// Items to append to the conversation, up to 20.
+    pub items: Vec<InputItem>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Builder)]
+#[builder(name = "ListConversationItemsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListConversationItemsQuery {
+    /// Number of items to return, between 1 and 100, 20 by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+    /// An item ID to list the items after.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+}
+
+/// An item of a conversation: a message, a tool call or its output, or a reasoning item.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConversationItem {
+    Message(ConversationMessage),
+    FunctionCall(FunctionCall),
+    FunctionCallOutput(FunctionCallOutputItem),
+    FileSearchCall(FileSearchCallOutput),
+    WebSearchCall(WebSearchCallOutput),
+    ComputerCall(ComputerCallOutput),
+    Reasoning(ReasoningItem),
+    ImageGenerationCall(ImageGenerationCallOutput),
+    CodeInterpreterCall(CodeInterpreterCallOutput),
+    LocalShellCall(LocalShellCallOutput),
+    McpCall(McpCallOutput),
+    McpListTools(McpListToolsOutput),
+    McpApprovalRequest(McpApprovalRequestOutput),
+    /// An item of a type not modeled by this crate.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+impl ConversationItem {
+    /// ID of the item, if any.
+    pub fn id(&self) -> Option<&str> {
+        match self {
+            ConversationItem::Message(message) => Some(&message.id),
+            ConversationItem::FunctionCall(call) => Some(&call.id),
+            ConversationItem::FunctionCallOutput(output) => output.id.as_deref(),
+            ConversationItem::FileSearchCall(call) => Some(&call.id),
+            ConversationItem::WebSearchCall(call) => Some(&call.id),
+            ConversationItem::ComputerCall(call) => Some(&call.id),
+            ConversationItem::Reasoning(reasoning) => Some(&reasoning.id),
+            ConversationItem::ImageGenerationCall(call) => Some(&call.id),
+            ConversationItem::CodeInterpreterCall(call) => Some(&call.id),
+            ConversationItem::LocalShellCall(call) => Some(&call.id),
+            ConversationItem::McpCall(call) => Some(&call.id),
+            ConversationItem::McpListTools(list) => Some(&list.id),
+            ConversationItem::McpApprovalRequest(request) => Some(&request.id),
+            ConversationItem::Other(item) => item.get("id").and_then(|id| id.as_str()),
+        }
+    }
+}
+
+/// A message of a conversation, from the user, the developer or the model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationMessage {
+    /// The unique ID of the message.
+    pub id: String,
+    pub role: Role,
+    pub content: Vec<ConversationContent>,
+    pub status: Option<OutputStatus>,
+}
+
+impl ConversationMessage {
+    /// Text of the message, the text parts concatenated.
+    pub fn text(&self) -> String {
+        self.content
+            .iter()
+            .filter_map(|content| match content {
+                ConversationContent::InputText(text) => Some(text.text.as_str()),
+                ConversationContent::OutputText(text) => Some(text.text.as_str()),
+                ConversationContent::Text { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect()
+    }
+}
+
+/// A content part of a [ConversationMessage].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConversationContent {
+    InputText(InputText),
+    OutputText(OutputText),
+    Text {
+        text: String,
+    },
+    InputImage(InputImage),
+    InputFile(InputFile),
+    Refusal(Refusal),
+    /// A content part of a type not modeled by this crate.
+    #[serde(untagged)]
+    Other(serde_json::Value),
+}
+
+/// The output of a function tool call, sent back to the model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FunctionCallOutputItem {
+    /// The unique ID of the item.
+    pub id: Option<String>,
+    /// The ID of the function tool call generated by the model.
+    pub call_id: String,
+    /// A JSON string of the output of the function.
+    pub output: String,
+    pub status: Option<OutputStatus>,
+}
+
+/// A page of the items of a conversation.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ConversationItemList {
+    /// The object type, which is always `list`.
+    pub object: String,
+    pub data: Vec<ConversationItem>,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+impl crate::pagination::CursorPage for ConversationItemList {
+    type Item = ConversationItem;
+
+    fn into_page(self) -> (Vec<Self::Item>, Option<Cursor>) {
+        let next = if self.has_more {
+            self.last_id.map(Cursor::from)
+        } else {
+            None
+        };
+        (self.data, next)
+    }
+}
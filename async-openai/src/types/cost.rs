@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+
+use super::Timestamp;
+
+/// Monetary value, a [rust_decimal::Decimal] with the `decimal` feature to avoid float rounding.
+#[cfg(not(feature = "decimal"))]
+pub type Amount = f64;
+/// Monetary value, a [rust_decimal::Decimal] with the `decimal` feature to avoid float rounding.
+#[cfg(feature = "decimal")]
+pub type Amount = rust_decimal::Decimal;
+
+/// An amount of money in a given currency.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct Money {
+    /// The numeric value of the cost.
+    #[cfg_attr(feature = "decimal", serde(with = "rust_decimal::serde::float"))]
+    pub value: Amount,
+    /// Lowercase ISO-4217 currency e.g. "usd".
+    pub currency: String,
+}
+
+/// The aggregated costs details of the specific time bucket.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CostsResult {
+    /// The object type, which is always `organization.costs.result`.
+    pub object: String,
+    /// The monetary value in its associated currency.
+    pub amount: Money,
+    /// When `group_by=line_item`, this field provides the line item of the grouped costs result.
+    pub line_item: Option<String>,
+    /// When `group_by=project_id`, this field provides the project ID of the grouped costs result.
+    pub project_id: Option<String>,
+}
+
+/// Costs aggregated over a time bucket.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CostsBucket {
+    /// The object type, which is always `bucket`.
+    pub object: String,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub start_time: Timestamp,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub end_time: Timestamp,
+    pub results: Vec<CostsResult>,
+}
+
+impl CostsBucket {
+    /// Sum of the results of this bucket in `currency`.
+    pub fn total(&self, currency: &str) -> Amount {
+        self.results
+            .iter()
+            .filter(|result| result.amount.currency.eq_ignore_ascii_case(currency))
+            .map(|result| result.amount.value)
+            .sum()
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct CostsResponse {
+    /// The object type, which is always `page`.
+    pub object: String,
+    pub data: Vec<CostsBucket>,
+    pub has_more: bool,
+    /// Cursor of the next page, to be passed as `page` in the query.
+    pub next_page: Option<String>,
+}
@@ -53,6 +53,140 @@ pub struct CreateEmbeddingRequest {
     pub dimensions: Option<u32>,
 }
 
+/// Maximum number of inputs accepted by a single embeddings request.
+pub const MAX_EMBEDDING_INPUTS: usize = 2048;
+
+/// Maximum number of input tokens accepted by a single embeddings request.
+pub const MAX_EMBEDDING_REQUEST_TOKENS: usize = 300_000;
+
+/// Controls how [crate::Embeddings::create_chunked] splits inputs into requests.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmbeddingChunkOptions {
+    max_inputs: usize,
+    max_tokens: usize,
+    max_concurrency: usize,
+    dimensions: Option<u32>,
+    user: Option<String>,
+}
+
+impl Default for EmbeddingChunkOptions {
+    fn default() -> Self {
+        Self {
+            max_inputs: MAX_EMBEDDING_INPUTS,
+            max_tokens: MAX_EMBEDDING_REQUEST_TOKENS,
+            max_concurrency: 4,
+            dimensions: None,
+            user: None,
+        }
+    }
+}
+
+impl EmbeddingChunkOptions {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Maximum number of inputs per request, capped at [MAX_EMBEDDING_INPUTS].
+    pub fn with_max_inputs(mut self, max_inputs: usize) -> Self {
+        self.max_inputs = max_inputs.clamp(1, MAX_EMBEDDING_INPUTS);
+        self
+    }
+
+    /// Maximum number of tokens per request.
+    ///
+    /// Tokens of text inputs are estimated at four bytes per token, token inputs are counted exactly.
+    /// A single input larger than the budget is still sent on its own.
+    pub fn with_max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens.max(1);
+        self
+    }
+
+    /// Maximum number of requests in flight at once.
+    pub fn with_max_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Number of dimensions of the output embeddings, see [CreateEmbeddingRequest::dimensions].
+    pub fn with_dimensions(mut self, dimensions: u32) -> Self {
+        self.dimensions = Some(dimensions);
+        self
+    }
+
+    /// End-user identifier, see [CreateEmbeddingRequest::user].
+    pub fn with_user<S: Into<String>>(mut self, user: S) -> Self {
+        self.user = Some(user.into());
+        self
+    }
+
+    pub fn max_concurrency(&self) -> usize {
+        self.max_concurrency
+    }
+
+    /// Split `input` into requests for `model` within the limits of these options.
+    pub fn chunk_requests(
+        &self,
+        model: &str,
+        input: EmbeddingInput,
+    ) -> Vec<CreateEmbeddingRequest> {
+        let inputs = match input {
+            EmbeddingInput::StringArray(strings) => {
+                Self::chunk(strings, self.max_inputs, self.max_tokens, |s| {
+                    s.len().div_ceil(4)
+                })
+                .into_iter()
+                .map(EmbeddingInput::StringArray)
+                .collect()
+            }
+            EmbeddingInput::ArrayOfIntegerArray(tokens) => {
+                Self::chunk(tokens, self.max_inputs, self.max_tokens, Vec::len)
+                    .into_iter()
+                    .map(EmbeddingInput::ArrayOfIntegerArray)
+                    .collect()
+            }
+            input => vec![input],
+        };
+
+        inputs
+            .into_iter()
+            .map(|input| CreateEmbeddingRequest {
+                model: model.to_string(),
+                input,
+                encoding_format: Some(EncodingFormat::Base64),
+                user: self.user.clone(),
+                dimensions: self.dimensions,
+            })
+            .collect()
+    }
+
+    fn chunk<T>(
+        items: Vec<T>,
+        max_inputs: usize,
+        max_tokens: usize,
+        tokens: impl Fn(&T) -> usize,
+    ) -> Vec<Vec<T>> {
+        let mut chunks = vec![];
+        let mut current = vec![];
+        let mut current_tokens = 0;
+
+        for item in items {
+            let item_tokens = tokens(&item);
+            if !current.is_empty()
+                && (current.len() == max_inputs || current_tokens + item_tokens > max_tokens)
+            {
+                chunks.push(std::mem::take(&mut current));
+                current_tokens = 0;
+            }
+            current_tokens += item_tokens;
+            current.push(item);
+        }
+        if !current.is_empty() {
+            chunks.push(current);
+        }
+        chunks
+    }
+}
+
 /// Represents an embedding vector returned by embedding endpoint.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Embedding {
@@ -68,15 +202,39 @@ pub struct Embedding {
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Base64EmbeddingVector(pub String);
 
-impl From<Base64EmbeddingVector> for Vec<f32> {
-    fn from(value: Base64EmbeddingVector) -> Self {
+impl Base64EmbeddingVector {
+    /// Decode the little-endian `f32` values of the vector.
+    ///
+    /// A vector which is not valid base64 or not a whole number of `f32` is an invalid
+    /// response, reported as [OpenAIError::JSONDeserialize].
+    pub fn decode(&self) -> Result<Vec<f32>, OpenAIError> {
+        let invalid = |message: String| {
+            OpenAIError::JSONDeserialize(serde::de::Error::custom(message), self.0.clone())
+        };
         let bytes = general_purpose::STANDARD
-            .decode(value.0)
-            .expect("openai base64 encoding to be valid");
-        let chunks = bytes.chunks_exact(4);
-        chunks
+            .decode(&self.0)
+            .map_err(|e| invalid(format!("invalid base64 embedding: {e}")))?;
+        if bytes.len() % 4 != 0 {
+            return Err(invalid(format!(
+                "invalid base64 embedding: {} bytes is not a whole number of f32",
+                bytes.len()
+            )));
+        }
+        Ok(bytes
+            .chunks_exact(4)
             .map(|chunk| f32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
-            .collect()
+            .collect())
+    }
+
+    /// Same as [Base64EmbeddingVector::decode] with values widened to `f64`.
+    pub fn decode_f64(&self) -> Result<Vec<f64>, OpenAIError> {
+        Ok(self.decode()?.into_iter().map(f64::from).collect())
+    }
+}
+
+impl From<Base64EmbeddingVector> for Vec<f32> {
+    fn from(value: Base64EmbeddingVector) -> Self {
+        value.decode().expect("openai base64 encoding to be valid")
     }
 }
 
@@ -91,6 +249,17 @@ pub struct Base64Embedding {
     pub embedding: Base64EmbeddingVector,
 }
 
+impl Base64Embedding {
+    /// Decode into an [Embedding] with a list of floats.
+    pub fn decode(self) -> Result<Embedding, OpenAIError> {
+        Ok(Embedding {
+            index: self.index,
+            object: self.object,
+            embedding: self.embedding.decode()?,
+        })
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct EmbeddingUsage {
     /// The number of tokens used by the prompt.
@@ -120,3 +289,19 @@ pub struct CreateBase64EmbeddingResponse {
     /// The usage information for the request.
     pub usage: EmbeddingUsage,
 }
+
+impl CreateBase64EmbeddingResponse {
+    /// Decode all embeddings, the same response as if `encoding_format` had been `float`.
+    pub fn decode(self) -> Result<CreateEmbeddingResponse, OpenAIError> {
+        Ok(CreateEmbeddingResponse {
+            object: self.object,
+            model: self.model,
+            data: self
+                .data
+                .into_iter()
+                .map(Base64Embedding::decode)
+                .collect::<Result<_, _>>()?,
+            usage: self.usage,
+        })
+    }
+}
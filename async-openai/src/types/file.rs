@@ -1,15 +1,54 @@
+use std::{path::PathBuf, pin::Pin};
+
+use bytes::Bytes;
 use derive_builder::Builder;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::InputSource;
+use super::{Cursor, Deleted, InputSource, SortOrder, Timestamp};
 
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct FileInput {
     pub source: InputSource,
 }
 
+/// A file given by its ID, its content or a url, for endpoints taking either a file ID or the
+/// file content: [crate::Files::resolve] uploads it when an ID is needed, such as for message
+/// attachments, vector stores or fine-tuning, and [crate::Files::resolve_input] downloads it when
+/// the content is needed, such as for image or audio inputs.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FileReference {
+    /// ID of an uploaded file.
+    Id(String),
+    /// Local file or in-memory content.
+    Source(InputSource),
+    /// Url of the content, downloaded without the API credentials.
+    Url(String),
+}
+
+impl FileReference {
+    pub fn id<S: Into<String>>(file_id: S) -> Self {
+        Self::Id(file_id.into())
+    }
+
+    pub fn path<P: Into<PathBuf>>(path: P) -> Self {
+        Self::Source(InputSource::Path { path: path.into() })
+    }
+
+    pub fn bytes<S: Into<String>>(filename: S, bytes: Bytes) -> Self {
+        Self::Source(InputSource::Bytes {
+            filename: filename.into(),
+            bytes,
+        })
+    }
+
+    pub fn url<S: Into<String>>(url: S) -> Self {
+        Self::Url(url.into())
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub enum FilePurpose {
     Assistants,
@@ -19,12 +58,41 @@ pub enum FilePurpose {
     Vision,
 }
 
+impl FilePurpose {
+    /// Extensions of the files accepted for the purpose, `None` when any file is accepted.
+    pub fn extensions(&self) -> Option<&'static [&'static str]> {
+        match self {
+            Self::Assistants => None,
+            Self::Batch | Self::FineTune => Some(&["jsonl"]),
+            Self::Vision => Some(&["png", "jpg", "jpeg", "gif", "webp"]),
+        }
+    }
+
+    /// Check that a file named `file_name` can be uploaded for the purpose, to fail before
+    /// uploading it: batch and fine-tuning files must be `.jsonl` and vision files images.
+    pub fn validate_file_name(&self, file_name: &str) -> Result<(), OpenAIError> {
+        let Some(extensions) = self.extensions() else {
+            return Ok(());
+        };
+        let extension = file_name
+            .rsplit_once('.')
+            .map(|(_, extension)| extension.to_ascii_lowercase());
+        if extension.is_some_and(|extension| extensions.contains(&extension.as_str())) {
+            return Ok(());
+        }
+        Err(OpenAIError::InvalidArgument(format!(
+            "files of purpose {self} must be .{}, got {file_name}",
+            extensions.join(", .")
+        )))
+    }
+}
+
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
 #[builder(name = "CreateFileRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateFileRequest {
     /// The File object (not file name) to be uploaded.
     pub file: FileInput,
@@ -35,19 +103,57 @@ pub struct CreateFileRequest {
     pub purpose: FilePurpose,
 }
 
+impl CreateFileRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        let purpose = self.purpose.clone().unwrap_or_default();
+        let file_name = match self.file.as_ref().map(|file| &file.source) {
+            Some(InputSource::Path { path }) => path.file_name().and_then(|name| name.to_str()),
+            Some(InputSource::Bytes { filename, .. } | InputSource::VecU8 { filename, .. }) => {
+                Some(filename.as_str())
+            }
+            None => None,
+        };
+        match file_name {
+            Some(file_name) => purpose.validate_file_name(file_name),
+            None => Ok(()),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct ListFilesResponse {
     pub object: String,
     pub data: Vec<OpenAIFile>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
-pub struct DeleteFileResponse {
-    pub id: String,
-    pub object: String,
-    pub deleted: bool,
+/// Query of [Files::list](crate::Files::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListFilesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListFilesQuery {
+    /// Only return files with the given purpose.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<OpenAIFilePurpose>,
+
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
 }
 
+/// Confirmation of a deleted [OpenAIFile].
+pub type DeleteFileResponse = Deleted<OpenAIFile>;
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub enum OpenAIFilePurpose {
     #[serde(rename = "assistants")]
@@ -76,7 +182,8 @@ pub struct OpenAIFile {
     /// The size of the file in bytes.
     pub bytes: u32,
     /// The Unix timestamp (in seconds) for when the file was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The name of the file.
     pub filename: String,
     /// The intended purpose of the file. Supported values are `assistants`, `assistants_output`, `batch`, `batch_output`, `fine-tune`, `fine-tune-results` and `vision`.
@@ -88,3 +195,15 @@ pub struct OpenAIFile {
     #[deprecated]
     pub status_details: Option<String>, // nullable: true
 }
+
+/// Stream of the chunks of a file content, see [crate::Client::files].
+pub type FileContentStream = Pin<Box<dyn Stream<Item = Result<Bytes, OpenAIError>> + Send>>;
+
+/// Progress of a file download, reported after every chunk written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DownloadProgress {
+    /// Bytes written so far.
+    pub downloaded: u64,
+    /// Size of the file, if the server reported it.
+    pub total: Option<u64>,
+}
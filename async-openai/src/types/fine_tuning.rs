@@ -3,6 +3,8 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
+use super::{Cursor, Deleted, Grader, Metadata, Timestamp};
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
 #[serde(untagged)]
 pub enum NEpochs {
@@ -65,12 +67,69 @@ pub struct DPOHyperparameters {
     pub n_epochs: NEpochs,
 }
 
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum ComputeMultiplier {
+    ComputeMultiplier(f32),
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum EvalInterval {
+    EvalInterval(u32),
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(untagged)]
+pub enum EvalSamples {
+    EvalSamples(u32),
+    #[default]
+    #[serde(rename = "auto")]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ReinforcementReasoningEffort {
+    #[default]
+    Default,
+    Low,
+    Medium,
+    High,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq)]
+pub struct ReinforcementHyperparameters {
+    /// Number of examples in each batch. A larger batch size means that model parameters
+    /// are updated less frequently, but with lower variance.
+    pub batch_size: BatchSize,
+    /// Scaling factor for the learning rate. A smaller learning rate may be useful to avoid
+    /// overfitting.
+    pub learning_rate_multiplier: LearningRateMultiplier,
+    /// The number of epochs to train the model for. An epoch refers to one full cycle through the training dataset.
+    pub n_epochs: NEpochs,
+    /// Level of reasoning effort.
+    pub reasoning_effort: ReinforcementReasoningEffort,
+    /// Multiplier on amount of compute used for exploring search space during training.
+    pub compute_multiplier: ComputeMultiplier,
+    /// The number of training steps between evaluation runs.
+    pub eval_interval: EvalInterval,
+    /// Number of evaluation samples to generate per training step.
+    pub eval_samples: EvalSamples,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, Default, Builder, PartialEq)]
 #[builder(name = "CreateFineTuningJobRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateFineTuningJobRequest {
     /// The name of the model to fine-tune. You can select one of the
     /// [supported models](https://platform.openai.com/docs/guides/fine-tuning#which-models-can-be-fine-tuned).
@@ -122,6 +181,19 @@ pub struct CreateFineTuningJobRequest {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub method: Option<FineTuneMethod>,
+
+    /// Set of 16 key-value pairs that can be attached to the job.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Metadata>,
+}
+
+impl CreateFineTuningJobRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
 /// The method used for fine-tuning.
@@ -134,6 +206,9 @@ pub enum FineTuneMethod {
     DPO {
         dpo: FineTuneDPOMethod,
     },
+    Reinforcement {
+        reinforcement: FineTuneReinforcementMethod,
+    },
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -146,6 +221,13 @@ pub struct FineTuneDPOMethod {
     pub hyperparameters: DPOHyperparameters,
 }
 
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct FineTuneReinforcementMethod {
+    /// The grader used for the fine-tuning job.
+    pub grader: Grader,
+    pub hyperparameters: ReinforcementHyperparameters,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum FineTuningJobIntegrationType {
@@ -199,6 +281,7 @@ pub enum FineTuningJobStatus {
     ValidatingFiles,
     Queued,
     Running,
+    Paused,
     Succeeded,
     Failed,
     Cancelled,
@@ -210,7 +293,8 @@ pub struct FineTuningJob {
     /// The object identifier, which can be referenced in the API endpoints.
     pub id: String,
     /// The Unix timestamp (in seconds) for when the fine-tuning job was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// For fine-tuning jobs that have `failed`, this will contain more information on the cause of the failure.
     pub error: Option<FineTuneJobError>,
     /// The name of the fine-tuned model that is being created.
@@ -218,7 +302,11 @@ pub struct FineTuningJob {
     pub fine_tuned_model: Option<String>, // nullable: true
     /// The Unix timestamp (in seconds) for when the fine-tuning job was finished.
     /// The value will be null if the fine-tuning job is still running.
-    pub finished_at: Option<u32>, // nullable true
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub finished_at: Option<Timestamp>, // nullable true
 
     /// The hyperparameters used for the fine-tuning job.
     /// See the [fine-tuning guide](/docs/guides/fine-tuning) for more details.
@@ -237,7 +325,7 @@ pub struct FineTuningJob {
     pub result_files: Vec<String>,
 
     /// The current status of the fine-tuning job, which can be either
-    /// `validating_files`, `queued`, `running`, `succeeded`, `failed`, or `cancelled`.
+    /// `validating_files`, `queued`, `running`, `paused`, `succeeded`, `failed`, or `cancelled`.
     pub status: FineTuningJobStatus,
 
     /// The total number of billable tokens processed by this fine-tuning job. The value will be null if the fine-tuning job is still running.
@@ -256,9 +344,67 @@ pub struct FineTuningJob {
     pub seed: u32,
 
     /// The Unix timestamp (in seconds) for when the fine-tuning job is estimated to finish. The value will be null if the fine-tuning job is not running.
-    pub estimated_finish: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub estimated_finish: Option<Timestamp>,
 
     pub method: Option<FineTuneMethod>,
+
+    /// Set of 16 key-value pairs attached to the job.
+    pub metadata: Option<Metadata>,
+}
+
+/// Query of [FineTuning::list_paginated](crate::FineTuning::list_paginated).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListFineTuningJobsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListFineTuningJobsQuery {
+    /// Identifier for the last job from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// Number of fine-tuning jobs to retrieve. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Query of [FineTuning::list_events](crate::FineTuning::list_events).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListFineTuningEventsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListFineTuningEventsQuery {
+    /// Identifier for the last event from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// Number of events to retrieve. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+}
+
+/// Query of [FineTuning::list_checkpoints](crate::FineTuning::list_checkpoints).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListFineTuningCheckpointsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListFineTuningCheckpointsQuery {
+    /// Identifier for the last checkpoint ID from the previous pagination request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// Number of checkpoints to retrieve. Defaults to 10.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -297,7 +443,8 @@ pub struct FineTuningJobEvent {
     /// The object identifier.
     pub id: String,
     /// The Unix timestamp (in seconds) for when the fine-tuning job event was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The log level of the event.
     pub level: Level,
     /// The message of the event.
@@ -310,6 +457,30 @@ pub struct FineTuningJobEvent {
     pub data: Option<serde_json::Value>,
 }
 
+impl FineTuningJobEvent {
+    /// The training metrics of a `metrics` event, `None` for other events.
+    pub fn metrics(&self) -> Option<FineTuningJobMetrics> {
+        if self.r#type != Some(FineTuningJobEventType::Metrics) {
+            return None;
+        }
+        serde_json::from_value(self.data.clone()?).ok()
+    }
+}
+
+/// The `data` of a `metrics` [FineTuningJobEvent], reported at a step of the training.
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct FineTuningJobMetrics {
+    pub step: Option<u32>,
+    pub total_steps: Option<u32>,
+    pub train_loss: Option<f64>,
+    pub train_mean_token_accuracy: Option<f64>,
+    pub valid_loss: Option<f64>,
+    pub valid_mean_token_accuracy: Option<f64>,
+    /// The validation loss on the full validation file, at the end of an epoch.
+    pub full_valid_loss: Option<f64>,
+    pub full_valid_mean_token_accuracy: Option<f64>,
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 #[serde(rename_all = "lowercase")]
 pub enum FineTuningJobEventType {
@@ -323,7 +494,8 @@ pub struct FineTuningJobCheckpoint {
     /// The checkpoint identifier, which can be referenced in the API endpoints.
     pub id: String,
     /// The Unix timestamp (in seconds) for when the checkpoint was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The name of the fine-tuned checkpoint model that is created.
     pub fine_tuned_model_checkpoint: String,
     /// The step number that the checkpoint was created at.
@@ -346,3 +518,40 @@ pub struct FineTuningJobCheckpointMetrics {
     pub full_valid_loss: f32,
     pub full_valid_mean_token_accuracy: f32,
 }
+
+/// The `checkpoint.permission` object represents a permission for a fine-tuned model checkpoint.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct FineTuningCheckpointPermission {
+    /// The permission identifier, which can be referenced in the API endpoints.
+    pub id: String,
+    /// The Unix timestamp (in seconds) for when the permission was created.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
+    /// The project identifier that the permission is for.
+    pub project_id: String,
+    /// The object type, which is always "checkpoint.permission".
+    pub object: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ListFineTuningCheckpointPermissionResponse {
+    pub data: Vec<FineTuningCheckpointPermission>,
+    pub object: String,
+    pub first_id: Option<String>,
+    pub last_id: Option<String>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default, Builder, PartialEq)]
+#[builder(name = "CreateFineTuningCheckpointPermissionRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateFineTuningCheckpointPermissionRequest {
+    /// The project identifiers to grant access to.
+    pub project_ids: Vec<String>,
+}
+
+/// Confirmation of a deleted [FineTuningCheckpointPermission].
+pub type DeleteFineTuningCheckpointPermissionResponse = Deleted<FineTuningCheckpointPermission>;
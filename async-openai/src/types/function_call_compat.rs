@@ -0,0 +1,317 @@
+//! Compatibility shim between the deprecated `functions` / `function_call` fields
+//! and their `tools` / `tool_choice` replacements.
+#![allow(deprecated)]
+
+use super::{
+    ChatCompletionFunctionCall, ChatCompletionFunctions, ChatCompletionMessageToolCall,
+    ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
+    ChatCompletionRequestMessage, ChatCompletionRequestToolMessage, ChatCompletionTool,
+    ChatCompletionToolChoiceOption, ChatCompletionToolType, CreateChatCompletionRequest,
+    CreateChatCompletionResponse, CreateChatCompletionStreamResponse, FinishReason,
+    FunctionCallStream, FunctionName, FunctionObject,
+};
+
+impl From<ChatCompletionFunctions> for FunctionObject {
+    fn from(value: ChatCompletionFunctions) -> Self {
+        Self {
+            name: value.name,
+            description: value.description,
+            parameters: Some(value.parameters),
+            strict: None,
+        }
+    }
+}
+
+impl From<ChatCompletionFunctions> for ChatCompletionTool {
+    fn from(value: ChatCompletionFunctions) -> Self {
+        Self {
+            r#type: ChatCompletionToolType::Function,
+            function: value.into(),
+        }
+    }
+}
+
+impl From<ChatCompletionFunctionCall> for ChatCompletionToolChoiceOption {
+    fn from(value: ChatCompletionFunctionCall) -> Self {
+        match value {
+            ChatCompletionFunctionCall::None => ChatCompletionToolChoiceOption::None,
+            ChatCompletionFunctionCall::Auto => ChatCompletionToolChoiceOption::Auto,
+            ChatCompletionFunctionCall::Function { name } => {
+                ChatCompletionToolChoiceOption::Named(ChatCompletionNamedToolChoice {
+                    r#type: ChatCompletionToolType::Function,
+                    function: FunctionName { name },
+                })
+            }
+        }
+    }
+}
+
+impl CreateChatCompletionRequest {
+    /// Whether the request uses `functions`, `function_call`, function role messages
+    /// or assistant messages carrying a `function_call`.
+    pub fn uses_legacy_functions(&self) -> bool {
+        self.functions.is_some()
+            || self.function_call.is_some()
+            || self.messages.iter().any(|message| match message {
+                ChatCompletionRequestMessage::Function(_) => true,
+                ChatCompletionRequestMessage::Assistant(assistant) => {
+                    assistant.function_call.is_some()
+                }
+                _ => false,
+            })
+    }
+
+    /// Whether the caller expects the response in the deprecated `function_call` shape: the
+    /// request uses `functions` or `function_call`, and no `tools` whose calls the response
+    /// carries as `tool_calls`.
+    pub fn expects_legacy_function_call(&self) -> bool {
+        (self.functions.is_some() || self.function_call.is_some()) && self.tools.is_none()
+    }
+
+    /// Rewrites the deprecated function calling fields and messages into their `tools` equivalent:
+    ///
+    /// - `functions` become `tools` and `function_call` becomes `tool_choice`.
+    /// - `parallel_tool_calls` is disabled unless set, because legacy responses carry a single call.
+    /// - assistant `function_call`s become `tool_calls` and `function` messages become `tool`
+    ///   messages answering the preceding call, with generated tool call ids.
+    pub fn upgrade_legacy_functions(&mut self) {
+        if let Some(functions) = self.functions.take() {
+            self.tools
+                .get_or_insert_with(Vec::new)
+                .extend(functions.into_iter().map(ChatCompletionTool::from));
+            self.parallel_tool_calls.get_or_insert(false);
+        }
+
+        if let Some(function_call) = self.function_call.take() {
+            self.tool_choice = Some(function_call.into());
+        }
+
+        let mut last_call_id: Option<String> = None;
+        for (index, message) in self.messages.iter_mut().enumerate() {
+            match message {
+                ChatCompletionRequestMessage::Assistant(
+                    ChatCompletionRequestAssistantMessage {
+                        function_call,
+                        tool_calls,
+                        ..
+                    },
+                ) => {
+                    if let Some(function_call) = function_call.take() {
+                        let id = format!("call_{index}_{}", function_call.name);
+                        tool_calls.get_or_insert_with(Vec::new).push(
+                            ChatCompletionMessageToolCall {
+                                id: id.clone(),
+                                r#type: ChatCompletionToolType::Function,
+                                function: function_call,
+                            },
+                        );
+                        last_call_id = Some(id);
+                    }
+                }
+                ChatCompletionRequestMessage::Function(function) => {
+                    let tool_call_id = last_call_id
+                        .take()
+                        .unwrap_or_else(|| format!("call_{index}_{}", function.name));
+                    *message =
+                        ChatCompletionRequestMessage::Tool(ChatCompletionRequestToolMessage {
+                            content: function.content.take().unwrap_or_default().into(),
+                            tool_call_id,
+                        });
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+impl CreateChatCompletionResponse {
+    /// Moves the first tool call of every choice into the deprecated `function_call` field,
+    /// the shape expected by callers of the legacy function calling API.
+    pub fn downgrade_to_legacy_function_call(&mut self) {
+        for choice in self.choices.iter_mut() {
+            let message = &mut choice.message;
+            if message.function_call.is_some() {
+                continue;
+            }
+            if let Some(tool_call) = message
+                .tool_calls
+                .take()
+                .and_then(|tool_calls| tool_calls.into_iter().next())
+            {
+                message.function_call = Some(tool_call.function);
+                if choice.finish_reason == Some(FinishReason::ToolCalls) {
+                    choice.finish_reason = Some(FinishReason::FunctionCall);
+                }
+            }
+        }
+    }
+}
+
+impl CreateChatCompletionStreamResponse {
+    /// Streaming counterpart of [CreateChatCompletionResponse::downgrade_to_legacy_function_call].
+    pub fn downgrade_to_legacy_function_call(&mut self) {
+        for choice in self.choices.iter_mut() {
+            let delta = &mut choice.delta;
+            if let Some(tool_call) = delta.tool_calls.take().and_then(|tool_calls| {
+                tool_calls
+                    .into_iter()
+                    .find(|tool_call| tool_call.index == 0)
+            }) {
+                if delta.function_call.is_none() {
+                    delta.function_call = tool_call.function.map(|function| FunctionCallStream {
+                        name: function.name,
+                        arguments: function.arguments,
+                    });
+                }
+            }
+            if choice.finish_reason == Some(FinishReason::ToolCalls) {
+                choice.finish_reason = Some(FinishReason::FunctionCall);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{ChatCompletionRequestFunctionMessage, FunctionCall};
+
+    #[test]
+    fn test_upgrade_legacy_functions() {
+        let mut request = CreateChatCompletionRequest {
+            functions: Some(vec![ChatCompletionFunctions {
+                name: "get_weather".into(),
+                description: None,
+                parameters: serde_json::json!({"type": "object"}),
+            }]),
+            function_call: Some(ChatCompletionFunctionCall::Function {
+                name: "get_weather".into(),
+            }),
+            messages: vec![
+                ChatCompletionRequestMessage::Assistant(ChatCompletionRequestAssistantMessage {
+                    function_call: Some(FunctionCall {
+                        name: "get_weather".into(),
+                        arguments: "{}".into(),
+                    }),
+                    ..Default::default()
+                }),
+                ChatCompletionRequestMessage::Function(ChatCompletionRequestFunctionMessage {
+                    content: Some("sunny".into()),
+                    name: "get_weather".into(),
+                }),
+            ],
+            ..Default::default()
+        };
+        assert!(request.uses_legacy_functions());
+
+        request.upgrade_legacy_functions();
+        assert!(!request.uses_legacy_functions());
+        assert_eq!(request.tools.as_ref().unwrap().len(), 1);
+        assert_eq!(request.parallel_tool_calls, Some(false));
+        assert!(matches!(
+            request.tool_choice,
+            Some(ChatCompletionToolChoiceOption::Named(_))
+        ));
+
+        let ChatCompletionRequestMessage::Assistant(assistant) = &request.messages[0] else {
+            panic!("expected assistant message");
+        };
+        let ChatCompletionRequestMessage::Tool(tool) = &request.messages[1] else {
+            panic!("expected tool message");
+        };
+        assert_eq!(
+            assistant.tool_calls.as_ref().unwrap()[0].id,
+            tool.tool_call_id
+        );
+    }
+
+    /// Answers chat completions with two tool calls.
+    #[cfg(not(feature = "byot"))]
+    fn two_tool_calls() -> std::sync::Arc<crate::transport::mock::MockTransport> {
+        let call = |id: &str, city: &str| {
+            serde_json::json!({"id": id, "type": "function", "function": {
+                "name": "get_weather", "arguments": format!(r#"{{"city": "{city}"}}"#)
+            }})
+        };
+        crate::transport::mock::MockTransport::body(
+            serde_json::json!({
+                "id": "1", "object": "chat.completion", "created": 1, "model": "m",
+                "choices": [{
+                    "index": 0, "finish_reason": "tool_calls",
+                    "message": {"role": "assistant", "content": null,
+                        "tool_calls": [call("call_1", "Paris"), call("call_2", "Rome")]}
+                }]
+            })
+            .to_string(),
+        )
+    }
+
+    // the requests and responses of byot clients are not translated
+    #[tokio::test]
+    #[cfg(not(feature = "byot"))]
+    async fn test_mixed_legacy_functions_and_tools() {
+        let tool = ChatCompletionTool {
+            r#type: ChatCompletionToolType::Function,
+            function: FunctionObject {
+                name: "get_weather".into(),
+                description: None,
+                parameters: Some(serde_json::json!({"type": "object"})),
+                strict: None,
+            },
+        };
+        // a legacy exchange in the history of a request using tools
+        let request = CreateChatCompletionRequest {
+            model: "m".into(),
+            tools: Some(vec![tool]),
+            messages: vec![
+                ChatCompletionRequestMessage::Assistant(ChatCompletionRequestAssistantMessage {
+                    function_call: Some(FunctionCall {
+                        name: "get_weather".into(),
+                        arguments: "{}".into(),
+                    }),
+                    ..Default::default()
+                }),
+                ChatCompletionRequestMessage::Function(ChatCompletionRequestFunctionMessage {
+                    content: Some("sunny".into()),
+                    name: "get_weather".into(),
+                }),
+            ],
+            ..Default::default()
+        };
+        assert!(request.uses_legacy_functions());
+        assert!(!request.expects_legacy_function_call());
+
+        let client = crate::Client::new().with_transport(two_tool_calls());
+        let response = client.chat().create(request).await.unwrap();
+        let message = &response.choices[0].message;
+        assert_eq!(message.tool_calls.as_ref().unwrap().len(), 2);
+        assert!(message.function_call.is_none());
+        assert_eq!(
+            response.choices[0].finish_reason,
+            Some(FinishReason::ToolCalls)
+        );
+
+        // callers of the legacy API only get the first call
+        let request = CreateChatCompletionRequest {
+            model: "m".into(),
+            functions: Some(vec![ChatCompletionFunctions {
+                name: "get_weather".into(),
+                description: None,
+                parameters: serde_json::json!({"type": "object"}),
+            }]),
+            ..Default::default()
+        };
+        assert!(request.expects_legacy_function_call());
+        let response = client.chat().create(request).await.unwrap();
+        let message = &response.choices[0].message;
+        assert!(message.tool_calls.is_none());
+        assert_eq!(
+            message.function_call.as_ref().unwrap().arguments,
+            r#"{"city": "Paris"}"#
+        );
+        assert_eq!(
+            response.choices[0].finish_reason,
+            Some(FinishReason::FunctionCall)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use super::ReasoningEffort;
+
+/// A grader scores model outputs, used as the reward of [reinforcement fine-tuning](https://platform.openai.com/docs/guides/reinforcement-fine-tuning).
+///
+/// Templates such as `{{item.reference_answer}}` and `{{sample.output_text}}` can be used in
+/// grader inputs to refer to the dataset item and the model sample.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum Grader {
+    /// Compares `input` and `reference` with a string operation.
+    StringCheck(StringCheckGrader),
+    /// Grades `input` against `reference` with a text similarity metric.
+    TextSimilarity(TextSimilarityGrader),
+    /// Runs a Python script returning a score.
+    Python(PythonGrader),
+    /// Asks a model to score the sample.
+    ScoreModel(ScoreModelGrader),
+    /// Asks a model to classify the sample with one of the labels.
+    LabelModel(LabelModelGrader),
+    /// Combines the output of multiple graders with a formula.
+    Multi(MultiGrader),
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum StringCheckOperation {
+    Eq,
+    Ne,
+    Like,
+    Ilike,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct StringCheckGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The input text. This may include template strings.
+    pub input: String,
+    /// The reference text. This may include template strings.
+    pub reference: String,
+    /// The string check operation to perform.
+    pub operation: StringCheckOperation,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum TextSimilarityMetric {
+    FuzzyMatch,
+    Bleu,
+    Gleu,
+    Meteor,
+    Rouge1,
+    Rouge2,
+    Rouge3,
+    Rouge4,
+    Rouge5,
+    RougeL,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TextSimilarityGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The text being graded.
+    pub input: String,
+    /// The text being graded against.
+    pub reference: String,
+    /// The evaluation metric to use.
+    pub evaluation_metric: TextSimilarityMetric,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct PythonGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The source code of the python script, defining a `grade(sample, item)` function.
+    pub source: String,
+    /// The image tag to use for the python script.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image_tag: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum GraderMessageRole {
+    User,
+    Assistant,
+    System,
+    Developer,
+}
+
+/// A message of the prompt sent to a model grader.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct GraderMessage {
+    pub role: GraderMessageRole,
+    /// Text of the message. This may include template strings.
+    pub content: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default)]
+pub struct GraderSamplingParams {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub max_completions_tokens: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ScoreModelGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The model to use for the evaluation.
+    pub model: String,
+    /// The input messages evaluated by the grader.
+    pub input: Vec<GraderMessage>,
+    /// The range of the score. Defaults to `[0, 1]`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub range: Option<[f32; 2]>,
+    /// The sampling parameters for the model.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sampling_params: Option<GraderSamplingParams>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LabelModelGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The model to use for the evaluation. Must support structured outputs.
+    pub model: String,
+    /// The input messages evaluated by the grader.
+    pub input: Vec<GraderMessage>,
+    /// The labels to assign to each item in the evaluation.
+    pub labels: Vec<String>,
+    /// The labels that indicate a passing result. Must be a subset of labels.
+    pub passing_labels: Vec<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct MultiGrader {
+    /// The name of the grader.
+    pub name: String,
+    /// The graders to combine, by name referenced in `calculate_output`.
+    pub graders: HashMap<String, Grader>,
+    /// A formula to calculate the output based on grader results, e.g. `0.5 * accuracy + 0.5 * style`.
+    pub calculate_output: String,
+}
@@ -1,9 +1,12 @@
+use std::pin::Pin;
+
 use derive_builder::Builder;
+use futures::Stream;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::InputSource;
+use super::{InputSource, Timestamp};
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 pub enum ImageSize {
@@ -18,6 +21,12 @@ pub enum ImageSize {
     S1792x1024,
     #[serde(rename = "1024x1792")]
     S1024x1792,
+    #[serde(rename = "1536x1024")]
+    S1536x1024,
+    #[serde(rename = "1024x1536")]
+    S1024x1536,
+    #[serde(rename = "auto")]
+    Auto,
 }
 
 #[derive(Default, Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
@@ -47,6 +56,8 @@ pub enum ImageModel {
     DallE2,
     #[serde(rename = "dall-e-3")]
     DallE3,
+    #[serde(rename = "gpt-image-1")]
+    GptImage1,
     #[serde(untagged)]
     Other(String),
 }
@@ -79,6 +90,24 @@ pub enum ImageModeration {
     Low,
 }
 
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageBackground {
+    Transparent,
+    Opaque,
+    #[default]
+    Auto,
+}
+
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "lowercase")]
+pub enum ImageOutputFormat {
+    #[default]
+    Png,
+    Jpeg,
+    Webp,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default, Builder, PartialEq)]
 #[builder(name = "CreateImageRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -98,17 +127,24 @@ pub struct CreateImageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub n: Option<u8>, // min:1 max:10 default:1
 
-    /// The quality of the image that will be generated. `hd` creates images with finer details and greater
-    /// consistency across the image. This param is only supported for `dall-e-3`.
+    /// The quality of the image that will be generated.
+    ///
+    /// - `auto` (default value) will automatically select the best quality for the given model.
+    /// - `high`, `medium` and `low` are supported for `gpt-image-1`.
+    /// - `hd` and `standard` are supported for `dall-e-3`. `hd` creates images with finer details and greater
+    ///   consistency across the image.
+    /// - `standard` is the only option for `dall-e-2`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub quality: Option<ImageQuality>,
 
     /// The format in which the generated images are returned. Must be one of `url` or `b64_json`. URLs are only valid for 60 minutes after the image has been generated.
+    /// This parameter isn't supported for `gpt-image-1` which will always return base64-encoded images.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<ImageResponseFormat>,
 
-    /// The size of the generated images. Must be one of `256x256`, `512x512`, or `1024x1024` for `dall-e-2`.
-    /// Must be one of `1024x1024`, `1792x1024`, or `1024x1792` for `dall-e-3` models.
+    /// The size of the generated images. Must be one of `1024x1024`, `1536x1024` (landscape), `1024x1536` (portrait),
+    /// or `auto` (default value) for `gpt-image-1`, one of `256x256`, `512x512`, or `1024x1024` for `dall-e-2`,
+    /// and one of `1024x1024`, `1792x1024`, or `1024x1792` for `dall-e-3`.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub size: Option<ImageSize>,
 
@@ -127,6 +163,33 @@ pub struct CreateImageRequest {
     /// Must be either `low` for less restrictive filtering or `auto` (default value).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub moderation: Option<ImageModeration>,
+
+    /// Allows to set transparency for the background of the generated image(s).
+    /// This parameter is only supported for `gpt-image-1`. When `auto` is used, the model will
+    /// automatically determine the best background for the image.
+    ///
+    /// If `transparent`, the output format needs to support transparency, so it should be set to either `png` (default value) or `webp`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+
+    /// The format in which the generated images are returned. This parameter is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level (0-100%) for the generated images. This parameter is only supported for
+    /// `gpt-image-1` with the `webp` or `jpeg` output formats, and defaults to 100.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_compression: Option<u8>,
+
+    /// Generate the image in streaming mode, use [crate::Images::create_stream] instead of setting this.
+    /// This parameter is only supported for `gpt-image-1`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stream: Option<bool>,
+
+    /// The number of partial images to generate when streaming, between 0 and 3.
+    /// The final image may be sent before the full number of partial images are generated if it completes faster.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_images: Option<u8>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
@@ -146,32 +209,136 @@ pub enum Image {
 
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct ImagesResponse {
-    pub created: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
     pub data: Vec<std::sync::Arc<Image>>,
+    /// The background parameter used for the image generation, `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<ImageBackground>,
+    /// The output format of the generated images, `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_format: Option<ImageOutputFormat>,
+    /// The quality of the generated images, `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub quality: Option<ImageQuality>,
+    /// The size of the generated images, `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub size: Option<ImageSize>,
+    /// Token usage of the image generation, `gpt-image-1` only.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub usage: Option<ImageGenUsage>,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ImageGenInputUsageDetails {
+    /// The number of text tokens in the input prompt.
+    pub text_tokens: u32,
+    /// The number of image tokens in the input prompt.
+    pub image_tokens: u32,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, Default, PartialEq)]
+pub struct ImageGenUsage {
+    /// The number of tokens (images and text) in the input prompt.
+    pub input_tokens: u32,
+    /// The number of image tokens in the output image.
+    pub output_tokens: u32,
+    /// The total number of tokens (images and text) used for the image generation.
+    pub total_tokens: u32,
+    /// The input tokens detailed information for the image generation.
+    pub input_tokens_details: ImageGenInputUsageDetails,
+}
+
+/// Emitted when a partial image is available during image generation streaming.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ImageGenPartialImageEvent {
+    /// Base64-encoded partial image data, suitable for rendering as an image.
+    pub b64_json: String,
+    /// The Unix timestamp when the event was created.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
+    /// The size of the requested image.
+    pub size: ImageSize,
+    /// The quality setting for the requested image.
+    pub quality: ImageQuality,
+    /// The background setting for the requested image.
+    pub background: ImageBackground,
+    /// The output format for the requested image.
+    pub output_format: ImageOutputFormat,
+    /// 0-based index for the partial image (streaming).
+    pub partial_image_index: u8,
+}
+
+/// Emitted when image generation has completed and the final image is available.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct ImageGenCompletedEvent {
+    /// Base64-encoded image data, suitable for rendering as an image.
+    pub b64_json: String,
+    /// The Unix timestamp when the event was created.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
+    /// The size of the generated image.
+    pub size: ImageSize,
+    /// The quality setting for the generated image.
+    pub quality: ImageQuality,
+    /// The background setting for the generated image.
+    pub background: ImageBackground,
+    /// The output format for the generated image.
+    pub output_format: ImageOutputFormat,
+    /// Token usage of the image generation.
+    pub usage: ImageGenUsage,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+#[serde(tag = "type")]
+pub enum ImageGenStreamEvent {
+    #[serde(rename = "image_generation.partial_image")]
+    PartialImage(ImageGenPartialImageEvent),
+    #[serde(rename = "image_generation.completed")]
+    Completed(ImageGenCompletedEvent),
 }
 
+/// Parsed server side events stream until the final image is received from server.
+pub type ImageGenStream =
+    Pin<Box<dyn Stream<Item = Result<ImageGenStreamEvent, OpenAIError>> + Send>>;
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct ImageInput {
     pub source: InputSource,
 }
 
+/// The image(s) to edit, `gpt-image-1` accepts up to 16 images.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ImageEditInput {
+    Image(ImageInput),
+    Images(Vec<ImageInput>),
+}
+
 #[derive(Debug, Clone, Default, Builder, PartialEq)]
 #[builder(name = "CreateImageEditRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageEditRequest {
-    /// The image to edit. Must be a valid PNG file, less than 4MB, and square. If mask is not provided, image must have transparency, which will be used as the mask.
-    pub image: ImageInput,
-
-    /// A text description of the desired image(s). The maximum length is 1000 characters.
+    /// The image(s) to edit.
+    ///
+    /// For `gpt-image-1`, each image should be a `png`, `webp`, or `jpg` file less than 25MB, up to 16 images can be provided.
+    ///
+    /// For `dall-e-2`, you can only provide one image, and it should be a square `png` file less than 4MB.
+    /// If mask is not provided, image must have transparency, which will be used as the mask.
+    pub image: ImageEditInput,
+
+    /// A text description of the desired image(s). The maximum length is 1000 characters for `dall-e-2`,
+    /// and 32000 characters for `gpt-image-1`.
     pub prompt: String,
 
-    /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where `image` should be edited. Must be a valid PNG file, less than 4MB, and have the same dimensions as `image`.
+    /// An additional image whose fully transparent areas (e.g. where alpha is zero) indicate where `image` should be edited.
+    /// If there are multiple images provided, the mask will be applied on the first image.
+    /// Must be a valid PNG file, less than 4MB, and have the same dimensions as `image`.
     pub mask: Option<ImageInput>,
 
-    /// The model to use for image generation. Only `dall-e-2` is supported at this time.
+    /// The model to use for image generation. Only `dall-e-2` and `gpt-image-1` are supported.
     pub model: Option<ImageModel>,
 
     /// The number of images to generate. Must be between 1 and 10.
@@ -185,6 +352,36 @@ pub struct CreateImageEditRequest {
 
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     pub user: Option<String>,
+
+    /// The quality of the image that will be generated. `high`, `medium` and `low` are only supported for `gpt-image-1`.
+    pub quality: Option<ImageQuality>,
+
+    /// Allows to set transparency for the background of the generated image(s), `gpt-image-1` only.
+    pub background: Option<ImageBackground>,
+
+    /// The format in which the generated images are returned, `gpt-image-1` only.
+    pub output_format: Option<ImageOutputFormat>,
+
+    /// The compression level (0-100%) for the generated images, `gpt-image-1` with `webp` or `jpeg` output formats only.
+    pub output_compression: Option<u8>,
+}
+
+impl CreateImageEditRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(ImageEditInput::Images(images)) = &self.image {
+            if images.is_empty() || images.len() > 16 {
+                return Err(OpenAIError::InvalidArgument(
+                    "between 1 and 16 images can be edited".into(),
+                ));
+            }
+            if images.len() > 1 && matches!(self.model, Some(Some(ImageModel::DallE2))) {
+                return Err(OpenAIError::InvalidArgument(
+                    "dall-e-2 edits a single image".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Default, Clone, Builder, PartialEq)]
@@ -192,7 +389,7 @@ pub struct CreateImageEditRequest {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateImageVariationRequest {
     /// The image to use as the basis for the variation(s). Must be a valid PNG file, less than 4MB, and square.
     pub image: ImageInput,
@@ -212,3 +409,41 @@ pub struct CreateImageVariationRequest {
     /// A unique identifier representing your end-user, which will help OpenAI to monitor and detect abuse. [Learn more](https://platform.openai.com/docs/usage-policies/end-user-ids).
     pub user: Option<String>,
 }
+
+impl CreateImageVariationRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(model)) = &self.model {
+            model.check_variations()?;
+        }
+        if let Some(Some(n)) = self.n {
+            if !(1..=10).contains(&n) {
+                return Err(OpenAIError::InvalidArgument(
+                    "between 1 and 10 image variations can be created".into(),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ImageModel {
+    /// Whether this model can create image variations, only `dall-e-2` can.
+    /// Models unknown to this crate are assumed to.
+    pub fn supports_variations(&self) -> bool {
+        !matches!(self, ImageModel::DallE3 | ImageModel::GptImage1)
+    }
+
+    /// Error guiding to edits or generations for models which cannot create image variations.
+    pub(crate) fn check_variations(&self) -> Result<(), OpenAIError> {
+        if self.supports_variations() {
+            return Ok(());
+        }
+        let alternative = match self {
+            ImageModel::GptImage1 => "Images::create_edit with the image as input",
+            _ => "Images::create with a prompt describing the variation",
+        };
+        Err(OpenAIError::InvalidArgument(format!(
+            "image variations are only supported by dall-e-2, use {alternative} for {self}"
+        )))
+    }
+}
@@ -4,32 +4,43 @@ use std::{
 };
 
 use crate::{
-    download::{download_url, save_b64},
+    download::{decode_b64, download_url, fetch_url, save_b64},
     error::OpenAIError,
     traits::AsyncTryFrom,
     types::InputSource,
     util::{create_all_dir, create_file_part},
 };
 
+use base64::{engine::general_purpose, Engine as _};
 use bytes::Bytes;
 
 use super::{
     responses::{CodeInterpreterContainer, Input, InputContent, Role as ResponsesRole},
     AddUploadPartRequest, AudioInput, AudioResponseFormat, ChatCompletionFunctionCall,
-    ChatCompletionFunctions, ChatCompletionNamedToolChoice, ChatCompletionRequestAssistantMessage,
-    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestDeveloperMessage,
-    ChatCompletionRequestDeveloperMessageContent, ChatCompletionRequestFunctionMessage,
-    ChatCompletionRequestMessage, ChatCompletionRequestMessageContentPartAudio,
-    ChatCompletionRequestMessageContentPartImage, ChatCompletionRequestMessageContentPartText,
-    ChatCompletionRequestSystemMessage, ChatCompletionRequestSystemMessageContent,
+    ChatCompletionFunctions, ChatCompletionMessageToolCall, ChatCompletionNamedToolChoice,
+    ChatCompletionRequestAssistantMessage, ChatCompletionRequestAssistantMessageAudio,
+    ChatCompletionRequestAssistantMessageContent, ChatCompletionRequestAssistantMessageContentPart,
+    ChatCompletionRequestDeveloperMessage, ChatCompletionRequestDeveloperMessageContent,
+    ChatCompletionRequestFunctionMessage, ChatCompletionRequestMessage,
+    ChatCompletionRequestMessageContentPartAudio, ChatCompletionRequestMessageContentPartImage,
+    ChatCompletionRequestMessageContentPartText, ChatCompletionRequestSystemMessage,
+    ChatCompletionRequestSystemMessageContent, ChatCompletionRequestSystemMessageContentPart,
     ChatCompletionRequestToolMessage, ChatCompletionRequestToolMessageContent,
-    ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContent,
-    ChatCompletionRequestUserMessageContentPart, ChatCompletionToolChoiceOption, CreateFileRequest,
-    CreateImageEditRequest, CreateImageVariationRequest, CreateMessageRequestContent,
-    CreateSpeechResponse, CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize,
-    EmbeddingInput, FileInput, FilePurpose, FunctionName, Image, ImageInput, ImageModel,
-    ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, ModerationInput, Prompt, Role, Stop,
-    TimestampGranularity,
+    ChatCompletionRequestToolMessageContentPart, ChatCompletionRequestUserMessage,
+    ChatCompletionRequestUserMessageContent, ChatCompletionRequestUserMessageContentPart,
+    ChatCompletionResponseMessage, ChatCompletionResponseMessageAudio,
+    ChatCompletionStreamResponseAudioDelta, ChatCompletionToolChoiceOption, ChatCompletionToolType,
+    CompletionUsage, CreateFileRequest, CreateImageEditRequest, CreateImageVariationRequest,
+    CreateMessageRequest, CreateMessageRequestContent, CreateSpeechResponse,
+    CreateTranscriptionRequest, CreateTranslationRequest, DallE2ImageSize, EmbeddingInput,
+    FileInput, FilePurpose, FileReference, FunctionCall, FunctionName, Image, ImageBackground,
+    ImageEditInput, ImageFile, ImageGenCompletedEvent, ImageGenPartialImageEvent,
+    ImageGenStreamEvent, ImageInput, ImageModel, ImageOutputFormat, ImageQuality,
+    ImageResponseFormat, ImageSize, ImageUrl, ImagesResponse, InputAudio, InputAudioFormat,
+    MessageAttachment, MessageAttachmentTool, MessageContentImageFileObject,
+    MessageContentImageUrlObject, MessageContentInput, MessageRequestContentTextObject,
+    ModerationContentPart, ModerationImageUrl, ModerationInput, PredictionContent,
+    PredictionContentContent, Prompt, Role, Stop, TimestampGranularity, UploadPartStream,
 };
 
 /// for `impl_from!(T, Enum)`, implements
@@ -128,7 +139,7 @@ impl Default for InputSource {
 ///     source: InputSource
 /// }
 /// ```
-/// implements methods `from_bytes` and `from_vec_u8`,
+/// implements methods `from_bytes`, `from_vec_u8` and `from_reader`,
 /// and `From<P>` for `P: AsRef<Path>`
 macro_rules! impl_input {
     ($for_typ:ty) => {
@@ -144,6 +155,21 @@ macro_rules! impl_input {
                     source: InputSource::VecU8 { filename, vec },
                 }
             }
+
+            /// Read `reader` to the end into memory, e.g. to pass through an upload received by a web service.
+            pub async fn from_reader<R>(
+                filename: String,
+                mut reader: R,
+            ) -> Result<Self, OpenAIError>
+            where
+                R: tokio::io::AsyncRead + Unpin,
+            {
+                let mut vec = Vec::new();
+                tokio::io::AsyncReadExt::read_to_end(&mut reader, &mut vec)
+                    .await
+                    .map_err(|e| OpenAIError::FileReadError(e.to_string()))?;
+                Ok(Self::from_vec_u8(filename, vec))
+            }
         }
 
         impl<P: AsRef<Path>> From<P> for $for_typ {
@@ -161,6 +187,68 @@ impl_input!(AudioInput);
 impl_input!(FileInput);
 impl_input!(ImageInput);
 
+impl From<InputSource> for FileReference {
+    fn from(source: InputSource) -> Self {
+        Self::Source(source)
+    }
+}
+
+impl From<AudioInput> for FileReference {
+    fn from(input: AudioInput) -> Self {
+        Self::Source(input.source)
+    }
+}
+
+impl From<FileInput> for FileReference {
+    fn from(input: FileInput) -> Self {
+        Self::Source(input.source)
+    }
+}
+
+impl From<ImageInput> for FileReference {
+    fn from(input: ImageInput) -> Self {
+        Self::Source(input.source)
+    }
+}
+
+impl Default for ImageEditInput {
+    fn default() -> Self {
+        Self::Image(ImageInput::default())
+    }
+}
+
+impl From<ImageInput> for ImageEditInput {
+    fn from(value: ImageInput) -> Self {
+        Self::Image(value)
+    }
+}
+
+impl From<Vec<ImageInput>> for ImageEditInput {
+    fn from(value: Vec<ImageInput>) -> Self {
+        Self::Images(value)
+    }
+}
+
+impl<const N: usize> From<[ImageInput; N]> for ImageEditInput {
+    fn from(value: [ImageInput; N]) -> Self {
+        Self::Images(value.to_vec())
+    }
+}
+
+macro_rules! impl_image_edit_input_from_path {
+    ($($from_typ:ty),*) => {
+        $(
+            impl From<$from_typ> for ImageEditInput {
+                fn from(value: $from_typ) -> Self {
+                    Self::Image(value.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_image_edit_input_from_path!(&str, String, &String, &Path, PathBuf, &PathBuf);
+
 impl Display for ImageSize {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -172,6 +260,9 @@ impl Display for ImageSize {
                 Self::S1024x1024 => "1024x1024",
                 Self::S1792x1024 => "1792x1024",
                 Self::S1024x1792 => "1024x1792",
+                Self::S1536x1024 => "1536x1024",
+                Self::S1024x1536 => "1024x1536",
+                Self::Auto => "auto",
             }
         )
     }
@@ -199,6 +290,7 @@ impl Display for ImageModel {
             match self {
                 Self::DallE2 => "dall-e-2",
                 Self::DallE3 => "dall-e-3",
+                Self::GptImage1 => "gpt-image-1",
                 Self::Other(other) => other,
             }
         )
@@ -218,6 +310,51 @@ impl Display for ImageResponseFormat {
     }
 }
 
+impl Display for ImageOutputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Png => "png",
+                Self::Jpeg => "jpeg",
+                Self::Webp => "webp",
+            }
+        )
+    }
+}
+
+impl Display for ImageQuality {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Standard => "standard",
+                Self::HD => "hd",
+                Self::High => "high",
+                Self::Medium => "medium",
+                Self::Low => "low",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
+impl Display for ImageBackground {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                Self::Transparent => "transparent",
+                Self::Opaque => "opaque",
+                Self::Auto => "auto",
+            }
+        )
+    }
+}
+
 impl Display for AudioResponseFormat {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(
@@ -284,10 +421,15 @@ impl ImagesResponse {
     pub async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<Vec<PathBuf>, OpenAIError> {
         create_all_dir(dir.as_ref())?;
 
+        let extension = self.output_format.unwrap_or_default().to_string();
+
         let mut handles = vec![];
         for id in self.data.clone() {
             let dir_buf = PathBuf::from(dir.as_ref());
-            handles.push(tokio::spawn(async move { id.save(dir_buf).await }));
+            let extension = extension.clone();
+            handles.push(tokio::spawn(
+                async move { id.save(dir_buf, &extension).await },
+            ));
         }
 
         let results = futures::future::join_all(handles).await;
@@ -316,6 +458,11 @@ impl ImagesResponse {
             ))
         }
     }
+
+    /// Contents of each image, downloading images returned as [ImageResponseFormat::Url].
+    pub async fn bytes(&self) -> Result<Vec<Bytes>, OpenAIError> {
+        futures::future::try_join_all(self.data.iter().map(|image| image.bytes())).await
+    }
 }
 
 impl CreateSpeechResponse {
@@ -335,10 +482,221 @@ impl CreateSpeechResponse {
 }
 
 impl Image {
-    async fn save<P: AsRef<Path>>(&self, dir: P) -> Result<PathBuf, OpenAIError> {
+    async fn save<P: AsRef<Path>>(&self, dir: P, extension: &str) -> Result<PathBuf, OpenAIError> {
         match self {
             Image::Url { url, .. } => download_url(url, dir).await,
-            Image::B64Json { b64_json, .. } => save_b64(b64_json, dir).await,
+            Image::B64Json { b64_json, .. } => save_b64(b64_json, dir, extension).await,
+        }
+    }
+
+    /// Contents of the image, downloaded when returned as [ImageResponseFormat::Url].
+    pub async fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        match self {
+            Image::Url { url, .. } => fetch_url(url).await,
+            Image::B64Json { b64_json, .. } => decode_b64(b64_json),
+        }
+    }
+}
+
+impl From<&str> for PredictionContent {
+    fn from(value: &str) -> Self {
+        PredictionContent::Content(PredictionContentContent::Text(value.into()))
+    }
+}
+
+impl From<String> for PredictionContent {
+    fn from(value: String) -> Self {
+        PredictionContent::Content(PredictionContentContent::Text(value))
+    }
+}
+
+impl From<Vec<ChatCompletionRequestMessageContentPartText>> for PredictionContent {
+    fn from(value: Vec<ChatCompletionRequestMessageContentPartText>) -> Self {
+        PredictionContent::Content(PredictionContentContent::Array(value))
+    }
+}
+
+impl InputAudio {
+    /// Base64 encode `bytes` of audio in the given format.
+    pub fn from_bytes(bytes: impl AsRef<[u8]>, format: InputAudioFormat) -> Self {
+        Self {
+            data: general_purpose::STANDARD.encode(bytes),
+            format,
+        }
+    }
+}
+
+impl From<InputAudio> for ChatCompletionRequestMessageContentPartAudio {
+    fn from(input_audio: InputAudio) -> Self {
+        Self { input_audio }
+    }
+}
+
+impl From<InputAudio> for ChatCompletionRequestUserMessageContentPart {
+    fn from(input_audio: InputAudio) -> Self {
+        ChatCompletionRequestUserMessageContentPart::InputAudio(input_audio.into())
+    }
+}
+
+impl ChatCompletionResponseMessageAudio {
+    /// Decoded audio bytes, in the format requested with [crate::types::ChatCompletionAudio].
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        decode_b64(&self.data)
+    }
+}
+
+/// Refer to a previous audio response in a multi-turn conversation.
+impl From<&ChatCompletionResponseMessageAudio> for ChatCompletionRequestAssistantMessageAudio {
+    fn from(audio: &ChatCompletionResponseMessageAudio) -> Self {
+        Self {
+            id: audio.id.clone(),
+        }
+    }
+}
+
+impl CompletionUsage {
+    /// Prompt tokens read from the prompt cache, billed at a discount.
+    pub fn cached_tokens(&self) -> u32 {
+        self.prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.cached_tokens)
+            .unwrap_or(0)
+    }
+
+    /// Prompt tokens not read from the prompt cache.
+    pub fn uncached_prompt_tokens(&self) -> u32 {
+        self.prompt_tokens.saturating_sub(self.cached_tokens())
+    }
+
+    /// Completion tokens the model used for reasoning, billed as output but not part of the message.
+    pub fn reasoning_tokens(&self) -> u32 {
+        self.completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.reasoning_tokens)
+            .unwrap_or(0)
+    }
+
+    /// Audio tokens of the prompt and the completion.
+    pub fn audio_tokens(&self) -> u32 {
+        let prompt = self
+            .prompt_tokens_details
+            .as_ref()
+            .and_then(|details| details.audio_tokens)
+            .unwrap_or(0);
+        let completion = self
+            .completion_tokens_details
+            .as_ref()
+            .and_then(|details| details.audio_tokens)
+            .unwrap_or(0);
+        prompt + completion
+    }
+}
+
+impl ChatCompletionMessageToolCall {
+    /// A call of the function `name`, `arguments` being JSON encoded.
+    pub fn function<I: Into<String>, N: Into<String>, A: Into<String>>(
+        id: I,
+        name: N,
+        arguments: A,
+    ) -> Self {
+        Self {
+            id: id.into(),
+            r#type: ChatCompletionToolType::Function,
+            function: FunctionCall {
+                name: name.into(),
+                arguments: arguments.into(),
+            },
+        }
+    }
+
+    /// Tool message answering this call with `content`.
+    pub fn respond<C: Into<ChatCompletionRequestToolMessageContent>>(
+        &self,
+        content: C,
+    ) -> ChatCompletionRequestToolMessage {
+        ChatCompletionRequestToolMessage::new(self.id.clone(), content)
+    }
+}
+
+impl ChatCompletionRequestToolMessage {
+    pub fn new<I: Into<String>, C: Into<ChatCompletionRequestToolMessageContent>>(
+        tool_call_id: I,
+        content: C,
+    ) -> Self {
+        Self {
+            content: content.into(),
+            tool_call_id: tool_call_id.into(),
+        }
+    }
+}
+
+/// Assistant message only carrying tool calls.
+impl From<Vec<ChatCompletionMessageToolCall>> for ChatCompletionRequestAssistantMessage {
+    fn from(tool_calls: Vec<ChatCompletionMessageToolCall>) -> Self {
+        Self {
+            tool_calls: Some(tool_calls),
+            ..Default::default()
+        }
+    }
+}
+
+/// Replay a response message, including its tool calls, in the messages of a new request.
+impl From<&ChatCompletionResponseMessage> for ChatCompletionRequestAssistantMessage {
+    #[allow(deprecated)]
+    fn from(message: &ChatCompletionResponseMessage) -> Self {
+        Self {
+            content: message.content.clone().map(Into::into),
+            refusal: message.refusal.clone(),
+            name: None,
+            audio: message.audio.as_ref().map(Into::into),
+            tool_calls: message.tool_calls.clone(),
+            function_call: message.function_call.clone(),
+        }
+    }
+}
+
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestAssistantMessage {
+    fn from(message: ChatCompletionResponseMessage) -> Self {
+        (&message).into()
+    }
+}
+
+impl From<ChatCompletionResponseMessage> for ChatCompletionRequestMessage {
+    fn from(message: ChatCompletionResponseMessage) -> Self {
+        Self::Assistant(message.into())
+    }
+}
+
+impl ChatCompletionStreamResponseAudioDelta {
+    /// Decoded audio bytes of this chunk, empty when the chunk has no audio data.
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        self.data
+            .as_deref()
+            .map(decode_b64)
+            .unwrap_or_else(|| Ok(Bytes::new()))
+    }
+}
+
+impl ImageGenPartialImageEvent {
+    /// Decoded contents of the partial image.
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        decode_b64(&self.b64_json)
+    }
+}
+
+impl ImageGenCompletedEvent {
+    /// Decoded contents of the final image.
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        decode_b64(&self.b64_json)
+    }
+}
+
+impl ImageGenStreamEvent {
+    /// Decoded contents of the partial or final image carried by this event.
+    pub fn bytes(&self) -> Result<Bytes, OpenAIError> {
+        match self {
+            ImageGenStreamEvent::PartialImage(event) => event.bytes(),
+            ImageGenStreamEvent::Completed(event) => event.bytes(),
         }
     }
 }
@@ -615,6 +973,37 @@ impl From<ChatCompletionRequestAssistantMessageContent> for ChatCompletionReques
     }
 }
 
+impl ModerationContentPart {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        ModerationContentPart::Text { text: text.into() }
+    }
+
+    /// An image to classify, by URL or as a base64 data URL.
+    pub fn image_url<S: Into<String>>(url: S) -> Self {
+        ModerationContentPart::ImageUrl {
+            image_url: ModerationImageUrl { url: url.into() },
+        }
+    }
+}
+
+impl From<ModerationContentPart> for ModerationInput {
+    fn from(part: ModerationContentPart) -> Self {
+        ModerationInput::MultiModal(vec![part])
+    }
+}
+
+impl From<Vec<ModerationContentPart>> for ModerationInput {
+    fn from(parts: Vec<ModerationContentPart>) -> Self {
+        ModerationInput::MultiModal(parts)
+    }
+}
+
+impl<const N: usize> From<[ModerationContentPart; N]> for ModerationInput {
+    fn from(parts: [ModerationContentPart; N]) -> Self {
+        ModerationInput::MultiModal(parts.to_vec())
+    }
+}
+
 impl From<&str> for ChatCompletionRequestUserMessageContent {
     fn from(value: &str) -> Self {
         ChatCompletionRequestUserMessageContent::Text(value.into())
@@ -797,6 +1186,258 @@ impl From<&str> for CreateMessageRequestContent {
     }
 }
 
+impl ChatCompletionRequestUserMessageContentPart {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        ChatCompletionRequestUserMessageContentPart::Text(text.into().into())
+    }
+
+    /// An image by URL or as a base64 data URL, with the default detail.
+    pub fn image_url<I: Into<ImageUrl>>(image_url: I) -> Self {
+        ChatCompletionRequestUserMessageContentPart::ImageUrl(image_url.into().into())
+    }
+}
+
+impl From<ImageUrl> for ChatCompletionRequestMessageContentPartImage {
+    fn from(image_url: ImageUrl) -> Self {
+        Self { image_url }
+    }
+}
+
+impl From<ImageUrl> for ChatCompletionRequestUserMessageContentPart {
+    fn from(image_url: ImageUrl) -> Self {
+        ChatCompletionRequestUserMessageContentPart::ImageUrl(image_url.into())
+    }
+}
+
+/// A text part, use [ImageUrl] for an image part.
+impl From<&str> for ChatCompletionRequestUserMessageContentPart {
+    fn from(value: &str) -> Self {
+        ChatCompletionRequestUserMessageContentPart::text(value)
+    }
+}
+
+impl From<String> for ChatCompletionRequestUserMessageContentPart {
+    fn from(value: String) -> Self {
+        ChatCompletionRequestUserMessageContentPart::text(value)
+    }
+}
+
+impl<const N: usize> From<[ChatCompletionRequestUserMessageContentPart; N]>
+    for ChatCompletionRequestUserMessageContent
+{
+    fn from(value: [ChatCompletionRequestUserMessageContentPart; N]) -> Self {
+        ChatCompletionRequestUserMessageContent::Array(value.to_vec())
+    }
+}
+
+impl From<Vec<ChatCompletionRequestUserMessageContentPart>> for ChatCompletionRequestUserMessage {
+    fn from(value: Vec<ChatCompletionRequestUserMessageContentPart>) -> Self {
+        ChatCompletionRequestUserMessageContent::Array(value).into()
+    }
+}
+
+impl<const N: usize> From<[ChatCompletionRequestUserMessageContentPart; N]>
+    for ChatCompletionRequestUserMessage
+{
+    fn from(value: [ChatCompletionRequestUserMessageContentPart; N]) -> Self {
+        ChatCompletionRequestUserMessageContent::from(value).into()
+    }
+}
+
+impl From<ChatCompletionRequestMessageContentPartText>
+    for ChatCompletionRequestSystemMessageContentPart
+{
+    fn from(value: ChatCompletionRequestMessageContentPartText) -> Self {
+        ChatCompletionRequestSystemMessageContentPart::Text(value)
+    }
+}
+
+impl From<ChatCompletionRequestMessageContentPartText>
+    for ChatCompletionRequestAssistantMessageContentPart
+{
+    fn from(value: ChatCompletionRequestMessageContentPartText) -> Self {
+        ChatCompletionRequestAssistantMessageContentPart::Text(value)
+    }
+}
+
+impl From<ChatCompletionRequestMessageContentPartText>
+    for ChatCompletionRequestToolMessageContentPart
+{
+    fn from(value: ChatCompletionRequestMessageContentPartText) -> Self {
+        ChatCompletionRequestToolMessageContentPart::Text(value)
+    }
+}
+
+impl From<Vec<ChatCompletionRequestSystemMessageContentPart>>
+    for ChatCompletionRequestSystemMessageContent
+{
+    fn from(value: Vec<ChatCompletionRequestSystemMessageContentPart>) -> Self {
+        ChatCompletionRequestSystemMessageContent::Array(value)
+    }
+}
+
+impl From<Vec<ChatCompletionRequestMessageContentPartText>>
+    for ChatCompletionRequestDeveloperMessageContent
+{
+    fn from(value: Vec<ChatCompletionRequestMessageContentPartText>) -> Self {
+        ChatCompletionRequestDeveloperMessageContent::Array(value)
+    }
+}
+
+impl From<Vec<ChatCompletionRequestAssistantMessageContentPart>>
+    for ChatCompletionRequestAssistantMessageContent
+{
+    fn from(value: Vec<ChatCompletionRequestAssistantMessageContentPart>) -> Self {
+        ChatCompletionRequestAssistantMessageContent::Array(value)
+    }
+}
+
+impl From<Vec<ChatCompletionRequestToolMessageContentPart>>
+    for ChatCompletionRequestToolMessageContent
+{
+    fn from(value: Vec<ChatCompletionRequestToolMessageContentPart>) -> Self {
+        ChatCompletionRequestToolMessageContent::Array(value)
+    }
+}
+
+impl MessageContentInput {
+    pub fn text<S: Into<String>>(text: S) -> Self {
+        MessageContentInput::Text(MessageRequestContentTextObject { text: text.into() })
+    }
+
+    /// An image by URL, with the default detail.
+    pub fn image_url<I: Into<ImageUrl>>(image_url: I) -> Self {
+        MessageContentInput::ImageUrl(image_url.into().into())
+    }
+
+    /// An image of a [File](https://platform.openai.com/docs/api-reference/files) uploaded with
+    /// `purpose="vision"`, with the default detail.
+    pub fn image_file<I: Into<ImageFile>>(image_file: I) -> Self {
+        MessageContentInput::ImageFile(image_file.into().into())
+    }
+}
+
+impl From<&str> for MessageRequestContentTextObject {
+    fn from(value: &str) -> Self {
+        Self { text: value.into() }
+    }
+}
+
+impl From<String> for MessageRequestContentTextObject {
+    fn from(value: String) -> Self {
+        Self { text: value }
+    }
+}
+
+/// An image file by its file ID.
+impl From<&str> for ImageFile {
+    fn from(value: &str) -> Self {
+        Self {
+            file_id: value.into(),
+            detail: None,
+        }
+    }
+}
+
+impl From<String> for ImageFile {
+    fn from(value: String) -> Self {
+        Self {
+            file_id: value,
+            detail: None,
+        }
+    }
+}
+
+impl From<ImageFile> for MessageContentImageFileObject {
+    fn from(image_file: ImageFile) -> Self {
+        Self { image_file }
+    }
+}
+
+impl From<ImageUrl> for MessageContentImageUrlObject {
+    fn from(image_url: ImageUrl) -> Self {
+        Self { image_url }
+    }
+}
+
+impl From<MessageRequestContentTextObject> for MessageContentInput {
+    fn from(value: MessageRequestContentTextObject) -> Self {
+        MessageContentInput::Text(value)
+    }
+}
+
+/// A text part, use [ImageUrl] or [ImageFile] for an image part.
+impl From<&str> for MessageContentInput {
+    fn from(value: &str) -> Self {
+        MessageContentInput::text(value)
+    }
+}
+
+impl From<String> for MessageContentInput {
+    fn from(value: String) -> Self {
+        MessageContentInput::text(value)
+    }
+}
+
+impl From<ImageUrl> for MessageContentInput {
+    fn from(value: ImageUrl) -> Self {
+        MessageContentInput::image_url(value)
+    }
+}
+
+impl From<ImageFile> for MessageContentInput {
+    fn from(value: ImageFile) -> Self {
+        MessageContentInput::image_file(value)
+    }
+}
+
+impl From<MessageContentInput> for CreateMessageRequestContent {
+    fn from(value: MessageContentInput) -> Self {
+        Self::ContentArray(vec![value])
+    }
+}
+
+impl From<Vec<MessageContentInput>> for CreateMessageRequestContent {
+    fn from(value: Vec<MessageContentInput>) -> Self {
+        Self::ContentArray(value)
+    }
+}
+
+impl<const N: usize> From<[MessageContentInput; N]> for CreateMessageRequestContent {
+    fn from(value: [MessageContentInput; N]) -> Self {
+        Self::ContentArray(value.to_vec())
+    }
+}
+
+/// A user message with the given content.
+impl<C: Into<CreateMessageRequestContent>> From<C> for CreateMessageRequest {
+    fn from(content: C) -> Self {
+        Self {
+            content: content.into(),
+            ..Default::default()
+        }
+    }
+}
+
+impl MessageAttachment {
+    pub fn new<S: Into<String>>(file_id: S, tools: Vec<MessageAttachmentTool>) -> Self {
+        Self {
+            file_id: file_id.into(),
+            tools,
+        }
+    }
+
+    /// Attach the file to the `file_search` tool of the thread.
+    pub fn for_file_search<S: Into<String>>(file_id: S) -> Self {
+        Self::new(file_id, vec![MessageAttachmentTool::FileSearch])
+    }
+
+    /// Attach the file to the `code_interpreter` tool of the thread.
+    pub fn for_code_interpreter<S: Into<String>>(file_id: S) -> Self {
+        Self::new(file_id, vec![MessageAttachmentTool::CodeInterpreter])
+    }
+}
+
 impl Default for ChatCompletionRequestUserMessageContent {
     fn default() -> Self {
         ChatCompletionRequestUserMessageContent::Text("".into())
@@ -894,11 +1535,18 @@ impl AsyncTryFrom<CreateImageEditRequest> for reqwest::multipart::Form {
     type Error = OpenAIError;
 
     async fn try_from(request: CreateImageEditRequest) -> Result<Self, Self::Error> {
-        let image_part = create_file_part(request.image.source).await?;
+        let mut form = reqwest::multipart::Form::new().text("prompt", request.prompt);
 
-        let mut form = reqwest::multipart::Form::new()
-            .part("image", image_part)
-            .text("prompt", request.prompt);
+        match request.image {
+            ImageEditInput::Image(image) => {
+                form = form.part("image", create_file_part(image.source).await?);
+            }
+            ImageEditInput::Images(images) => {
+                for image in images {
+                    form = form.part("image[]", create_file_part(image.source).await?);
+                }
+            }
+        }
 
         if let Some(mask) = request.mask {
             let mask_part = create_file_part(mask.source).await?;
@@ -927,6 +1575,22 @@ impl AsyncTryFrom<CreateImageEditRequest> for reqwest::multipart::Form {
         if request.user.is_some() {
             form = form.text("user", request.user.unwrap())
         }
+
+        if let Some(quality) = request.quality {
+            form = form.text("quality", quality.to_string())
+        }
+
+        if let Some(background) = request.background {
+            form = form.text("background", background.to_string())
+        }
+
+        if let Some(output_format) = request.output_format {
+            form = form.text("output_format", output_format.to_string())
+        }
+
+        if let Some(output_compression) = request.output_compression {
+            form = form.text("output_compression", output_compression.to_string())
+        }
         Ok(form)
     }
 }
@@ -987,6 +1651,32 @@ impl AsyncTryFrom<AddUploadPartRequest> for reqwest::multipart::Form {
     }
 }
 
+impl AsyncTryFrom<UploadPartStream> for reqwest::multipart::Form {
+    type Error = OpenAIError;
+
+    async fn try_from(request: UploadPartStream) -> Result<Self, Self::Error> {
+        let file_part = request.part("part")?;
+        Ok(reqwest::multipart::Form::new().part("data", file_part))
+    }
+}
+
+impl UploadPartStream {
+    /// Multipart part of the stream named `file_name`, failing once the stream was sent.
+    pub(crate) fn part(&self, file_name: &str) -> Result<reqwest::multipart::Part, OpenAIError> {
+        let stream = self.take().ok_or_else(|| {
+            OpenAIError::InvalidArgument(
+                "the part stream was sent already and cannot be sent again".into(),
+            )
+        })?;
+        let body = reqwest::Body::wrap_stream(stream);
+        let part = match self.length() {
+            Some(length) => reqwest::multipart::Part::stream_with_length(body, length),
+            None => reqwest::multipart::Part::stream(body),
+        };
+        Ok(part.file_name(file_name.to_string()))
+    }
+}
+
 // end: types to multipart form
 
 impl Default for Input {
@@ -2,7 +2,7 @@ use crate::types::OpenAIError;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::OrganizationRole;
+use super::{Cursor, Deleted, OrganizationRole, Timestamp};
 
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -32,14 +32,26 @@ pub struct InviteListResponse {
     pub has_more: Option<bool>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct InviteDeleteResponse {
-    /// The object type, which is always `organization.invite.deleted`
-    pub object: String,
-    pub id: String,
-    pub deleted: bool,
+/// Query of [Invites::list](crate::Invites::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListInvitesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListInvitesQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
 }
 
+/// Confirmation of a deleted [Invite].
+pub type InviteDeleteResponse = Deleted<Invite>;
+
 /// Represents an individual `invite` to the organization.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Invite {
@@ -54,9 +66,15 @@ pub struct Invite {
     /// `accepted`, `expired`, or `pending`
     pub status: InviteStatus,
     /// The Unix timestamp (in seconds) of when the invite was sent.
-    pub invited_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub invited_at: Timestamp,
     /// The Unix timestamp (in seconds) of when the invite expires.
-    pub expires_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: Timestamp,
     /// The Unix timestamp (in seconds) of when the invite was accepted.
-    pub accepted_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub accepted_at: Option<Timestamp>,
 }
@@ -0,0 +1,404 @@
+use serde_json::{Map, Value};
+
+#[cfg(feature = "schemars")]
+use super::{FunctionObject, ResponseFormat, ResponseFormatJsonSchema};
+use crate::{error::OpenAIError, inspect::escape};
+
+/// Keywords of JSON Schema which the API rejects in strict mode.
+const UNSUPPORTED_KEYWORDS: &[&str] = &[
+    "$schema",
+    "default",
+    // strings
+    "minLength",
+    "maxLength",
+    "pattern",
+    "format",
+    // numbers
+    "minimum",
+    "maximum",
+    "exclusiveMinimum",
+    "exclusiveMaximum",
+    "multipleOf",
+    // objects
+    "unevaluatedProperties",
+    "propertyNames",
+    "minProperties",
+    "maxProperties",
+    // arrays
+    "unevaluatedItems",
+    "contains",
+    "minContains",
+    "maxContains",
+    "minItems",
+    "maxItems",
+    "uniqueItems",
+];
+
+/// Make `schema` compatible with the strict mode of [Structured Outputs](https://platform.openai.com/docs/guides/structured-outputs/supported-schemas):
+/// every object gets `additionalProperties: false` and all of its properties required,
+/// `oneOf` becomes `anyOf`, an `allOf` of a single schema is merged into its parent, and the
+/// keywords the API does not support, such as `format` or `minLength`, are removed.
+///
+/// Optional properties stay nullable when their schema allows `null`, like the schemas of
+/// `Option` fields do.
+///
+/// Fails with an [OpenAIError::InvalidArgument] naming the schema which strict mode cannot
+/// represent: a root which is not an object, or a map, an object with `additionalProperties`
+/// or `patternProperties` schemas for its keys.
+///
+/// ```
+/// use async_openai::types::strict_schema;
+/// use serde_json::json;
+///
+/// let schema = strict_schema(json!({
+///     "type": "object",
+///     "properties": {
+///         "name": {"type": "string", "minLength": 1},
+///         "age": {"type": ["integer", "null"], "format": "uint32"}
+///     },
+///     "required": ["name"]
+/// }))?;
+/// assert_eq!(schema, json!({
+///     "type": "object",
+///     "properties": {
+///         "name": {"type": "string"},
+///         "age": {"type": ["integer", "null"]}
+///     },
+///     "required": ["age", "name"],
+///     "additionalProperties": false
+/// }));
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+pub fn strict_schema(mut schema: Value) -> Result<Value, OpenAIError> {
+    if !schema.as_object().is_some_and(is_object) {
+        return Err(unrepresentable(
+            "#",
+            "the root of a strict schema must be an object",
+        ));
+    }
+    make_strict(&mut schema, "#")?;
+    Ok(schema)
+}
+
+/// Make the schema at the JSON pointer `pointer` strict, see [strict_schema].
+fn make_strict(schema: &mut Value, pointer: &str) -> Result<(), OpenAIError> {
+    let Value::Object(object) = schema else {
+        return Ok(());
+    };
+    if object.contains_key("patternProperties")
+        || !matches!(
+            object.get("additionalProperties"),
+            None | Some(Value::Bool(false))
+        )
+    {
+        return Err(unrepresentable(
+            pointer,
+            "strict mode requires the properties of objects to be known, maps are not supported",
+        ));
+    }
+    for keyword in UNSUPPORTED_KEYWORDS {
+        object.remove(*keyword);
+    }
+
+    if let Some(Value::Array(mut all_of)) = object.remove("allOf") {
+        if all_of.len() == 1 {
+            if let Value::Object(inner) = all_of.remove(0) {
+                for (key, value) in inner {
+                    object.entry(key).or_insert(value);
+                }
+            }
+        } else {
+            object.insert("allOf".into(), Value::Array(all_of));
+        }
+    }
+    if let Some(one_of) = object.remove("oneOf") {
+        object.insert("anyOf".into(), one_of);
+    }
+
+    if is_object(object) {
+        let properties = object
+            .entry("properties")
+            .or_insert_with(|| Value::Object(Map::new()));
+        let required: Vec<Value> = properties
+            .as_object()
+            .map(|properties| properties.keys().cloned().map(Value::String).collect())
+            .unwrap_or_default();
+        object.insert("required".into(), Value::Array(required));
+        object.insert("additionalProperties".into(), Value::Bool(false));
+    }
+
+    for keyword in ["properties", "$defs", "definitions"] {
+        if let Some(Value::Object(schemas)) = object.get_mut(keyword) {
+            for (key, schema) in schemas {
+                make_strict(schema, &format!("{pointer}/{keyword}/{}", escape(key)))?;
+            }
+        }
+    }
+    for keyword in ["anyOf", "allOf", "prefixItems"] {
+        if let Some(Value::Array(schemas)) = object.get_mut(keyword) {
+            for (index, schema) in schemas.iter_mut().enumerate() {
+                make_strict(schema, &format!("{pointer}/{keyword}/{index}"))?;
+            }
+        }
+    }
+    match object.get_mut("items") {
+        Some(Value::Array(items)) => {
+            for (index, schema) in items.iter_mut().enumerate() {
+                make_strict(schema, &format!("{pointer}/items/{index}"))?;
+            }
+        }
+        Some(items) => make_strict(items, &format!("{pointer}/items"))?,
+        None => {}
+    }
+    Ok(())
+}
+
+/// Whether the schema `object` describes objects.
+fn is_object(object: &Map<String, Value>) -> bool {
+    object.contains_key("properties") || object.get("type").is_some_and(|ty| has_type(ty, "object"))
+}
+
+fn unrepresentable(pointer: &str, reason: &str) -> OpenAIError {
+    OpenAIError::InvalidArgument(format!("{reason}, see the schema at `{pointer}`"))
+}
+
+/// Whether the `type` keyword `ty` is or contains `name`.
+fn has_type(ty: &Value, name: &str) -> bool {
+    match ty {
+        Value::String(ty) => ty == name,
+        Value::Array(types) => types.iter().any(|ty| ty == name),
+        _ => false,
+    }
+}
+
+/// Strict JSON schema of `T`, see [strict_schema].
+#[cfg(feature = "schemars")]
+#[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+pub fn strict_schema_for<T: schemars::JsonSchema>() -> Result<Value, OpenAIError> {
+    let schema = schemars::gen::SchemaSettings::draft07()
+        .with(|settings| {
+            settings.option_add_null_type = true;
+            settings.definitions_path = "#/$defs/".into();
+        })
+        .into_generator()
+        .into_root_schema_for::<T>();
+    let mut schema = serde_json::to_value(schema).unwrap_or_default();
+    // the definitions of schemars 0.8 are always under `definitions`
+    if let Some(object) = schema.as_object_mut() {
+        if let Some(definitions) = object.remove("definitions") {
+            object.insert("$defs".into(), definitions);
+        }
+    }
+    strict_schema(schema)
+}
+
+#[cfg(feature = "schemars")]
+impl ResponseFormatJsonSchema {
+    /// Strict response format of the JSON of `T`, named `name`.
+    ///
+    /// ```
+    /// use async_openai::types::ResponseFormat;
+    /// use schemars::JsonSchema;
+    ///
+    /// /// A step of the solution.
+    /// #[derive(serde::Deserialize, JsonSchema)]
+    /// struct Step {
+    ///     explanation: String,
+    ///     output: String,
+    /// }
+    ///
+    /// #[derive(serde::Deserialize, JsonSchema)]
+    /// struct MathReasoning {
+    ///     steps: Vec<Step>,
+    ///     final_answer: String,
+    /// }
+    ///
+    /// let response_format = ResponseFormat::json_schema_for::<MathReasoning>("math_reasoning")?;
+    ///
+    /// let ResponseFormat::JsonSchema { json_schema } = response_format else { unreachable!() };
+    /// let schema = json_schema.schema.unwrap();
+    /// assert_eq!(schema["additionalProperties"], false);
+    /// assert_eq!(schema["$defs"]["Step"]["required"], serde_json::json!(["explanation", "output"]));
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    pub fn for_type<T: schemars::JsonSchema>(name: impl Into<String>) -> Result<Self, OpenAIError> {
+        Ok(Self {
+            description: None,
+            name: name.into(),
+            schema: Some(strict_schema_for::<T>()?),
+            strict: Some(true),
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl ResponseFormat {
+    /// [ResponseFormat::JsonSchema] of [ResponseFormatJsonSchema::for_type].
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    pub fn json_schema_for<T: schemars::JsonSchema>(
+        name: impl Into<String>,
+    ) -> Result<Self, OpenAIError> {
+        Ok(ResponseFormat::JsonSchema {
+            json_schema: ResponseFormatJsonSchema::for_type::<T>(name)?,
+        })
+    }
+}
+
+#[cfg(feature = "schemars")]
+impl FunctionObject {
+    /// Strict function named `name` taking the JSON of `Args` as arguments, see
+    /// [crate::traits::OpenAiTool] to derive the name and description from `Args`.
+    ///
+    /// ```
+    /// use async_openai::types::FunctionObject;
+    /// use schemars::JsonSchema;
+    ///
+    /// #[derive(serde::Deserialize, JsonSchema)]
+    /// struct GetWeather {
+    ///     /// The city and state, e.g. San Francisco, CA
+    ///     location: String,
+    ///     unit: Option<String>,
+    /// }
+    ///
+    /// let function = FunctionObject::from_fn::<GetWeather>("get_weather", "Get the current weather")?;
+    /// let parameters = function.parameters.unwrap();
+    /// assert_eq!(parameters["required"], serde_json::json!(["location", "unit"]));
+    /// # Ok::<(), async_openai::error::OpenAIError>(())
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "schemars")))]
+    pub fn from_fn<Args: schemars::JsonSchema>(
+        name: impl Into<String>,
+        description: impl Into<String>,
+    ) -> Result<Self, OpenAIError> {
+        let mut parameters = strict_schema_for::<Args>()?;
+        if let Some(parameters) = parameters.as_object_mut() {
+            // the name and description of the function describe the arguments already
+            parameters.remove("title");
+            parameters.remove("description");
+        }
+        let description = description.into();
+        Ok(Self {
+            name: name.into(),
+            description: (!description.is_empty()).then_some(description),
+            parameters: Some(parameters),
+            strict: Some(true),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn test_strict_schema_defs() {
+        let schema = strict_schema(json!({
+            "type": "object",
+            "properties": {"step": {"$ref": "#/$defs/Step"}},
+            "$defs": {
+                "Step": {
+                    "type": "object",
+                    "properties": {"output": {"type": "string", "minLength": 1}}
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            schema["$defs"]["Step"],
+            json!({
+                "type": "object",
+                "properties": {"output": {"type": "string"}},
+                "required": ["output"],
+                "additionalProperties": false
+            })
+        );
+        assert_eq!(
+            schema["properties"]["step"],
+            json!({"$ref": "#/$defs/Step"})
+        );
+    }
+
+    #[test]
+    fn test_strict_schema_one_of() {
+        let schema = strict_schema(json!({
+            "type": "object",
+            "properties": {
+                "shape": {
+                    "oneOf": [
+                        {"type": "object", "properties": {"radius": {"type": "number"}}},
+                        {"type": "string", "format": "uuid"}
+                    ]
+                }
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            schema["properties"]["shape"],
+            json!({
+                "anyOf": [
+                    {
+                        "type": "object",
+                        "properties": {"radius": {"type": "number"}},
+                        "required": ["radius"],
+                        "additionalProperties": false
+                    },
+                    {"type": "string"}
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn test_strict_schema_nullable_optional() {
+        let schema = strict_schema(json!({
+            "type": "object",
+            "properties": {
+                "unit": {"type": ["string", "null"], "default": null},
+                "step": {"anyOf": [{"$ref": "#/$defs/Step"}, {"type": "null"}]}
+            },
+            "required": []
+        }))
+        .unwrap();
+        assert_eq!(schema["required"], json!(["step", "unit"]));
+        assert_eq!(
+            schema["properties"],
+            json!({
+                "unit": {"type": ["string", "null"]},
+                "step": {"anyOf": [{"$ref": "#/$defs/Step"}, {"type": "null"}]}
+            })
+        );
+    }
+
+    #[test]
+    fn test_strict_schema_maps() {
+        let error = strict_schema(json!({
+            "type": "object",
+            "properties": {
+                "scores": {"type": "object", "additionalProperties": {"type": "number"}}
+            }
+        }))
+        .unwrap_err();
+        assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+        assert!(error.to_string().contains("`#/properties/scores`"));
+
+        let error = strict_schema(json!({
+            "type": "object",
+            "$defs": {"Tags": {"type": "object", "patternProperties": {"^t": {"type": "string"}}}}
+        }))
+        .unwrap_err();
+        assert!(error.to_string().contains("`#/$defs/Tags`"));
+
+        let error =
+            strict_schema(json!({"type": "object", "additionalProperties": true})).unwrap_err();
+        assert!(error.to_string().contains("`#`"));
+
+        let error =
+            strict_schema(json!({"type": "array", "items": {"type": "string"}})).unwrap_err();
+        assert!(error
+            .to_string()
+            .contains("the root of a strict schema must be an object"));
+    }
+}
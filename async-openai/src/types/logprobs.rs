@@ -0,0 +1,157 @@
+use std::ops::Range;
+
+use super::{ChatChoiceLogprobs, ChatCompletionTokenLogprob, TopLogprobs};
+
+impl TopLogprobs {
+    /// Probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        f64::from(self.logprob).exp()
+    }
+}
+
+impl ChatCompletionTokenLogprob {
+    /// Probability of the token, between 0 and 1.
+    pub fn probability(&self) -> f64 {
+        f64::from(self.logprob).exp()
+    }
+
+    /// The `k` most likely tokens at this position with their probability, the most likely first.
+    ///
+    /// With a single token answer, e.g. of a classification, these are the probabilities of
+    /// the classes.
+    pub fn top_k(&self, k: usize) -> Vec<(&str, f64)> {
+        let mut alternatives: Vec<(&str, f64)> = self
+            .top_logprobs
+            .iter()
+            .map(|top| (top.token.as_str(), top.probability()))
+            .collect();
+        alternatives.sort_by(|a, b| b.1.total_cmp(&a.1));
+        alternatives.truncate(k);
+        alternatives
+    }
+
+    /// Probability of `token` at this position, `None` when it is not among the top tokens.
+    pub fn probability_of(&self, token: &str) -> Option<f64> {
+        self.top_logprobs
+            .iter()
+            .find(|top| top.token == token)
+            .map(TopLogprobs::probability)
+    }
+
+    /// UTF-8 bytes of the token, its text when the API returned no bytes.
+    fn token_bytes(&self) -> &[u8] {
+        self.bytes.as_deref().unwrap_or(self.token.as_bytes())
+    }
+}
+
+impl ChatChoiceLogprobs {
+    /// Tokens of the message content, empty without content.
+    pub fn tokens(&self) -> &[ChatCompletionTokenLogprob] {
+        self.content.as_deref().unwrap_or_default()
+    }
+
+    /// Log probability of the whole content, the sum of the log probabilities of its tokens.
+    pub fn sequence_logprob(&self) -> f64 {
+        self.tokens()
+            .iter()
+            .map(|token| f64::from(token.logprob))
+            .sum()
+    }
+
+    /// Joint probability of the whole content, between 0 and 1.
+    pub fn sequence_probability(&self) -> f64 {
+        self.sequence_logprob().exp()
+    }
+
+    /// [Perplexity](https://en.wikipedia.org/wiki/Perplexity) of the content, 1 when the model
+    /// was certain of every token, `None` without tokens.
+    pub fn perplexity(&self) -> Option<f64> {
+        let count = self.tokens().len();
+        (count > 0).then(|| (-self.sequence_logprob() / count as f64).exp())
+    }
+
+    /// The content, decoded from the bytes of its tokens.
+    pub fn text(&self) -> String {
+        let bytes: Vec<u8> = self
+            .tokens()
+            .iter()
+            .flat_map(|token| token.token_bytes().iter().copied())
+            .collect();
+        String::from_utf8_lossy(&bytes).into_owned()
+    }
+
+    /// Range of characters of each token in [Self::text], e.g. to highlight unlikely tokens.
+    ///
+    /// A character whose bytes are split across tokens belongs to the token of its first byte,
+    /// so the following tokens of the character have an empty range.
+    pub fn char_ranges(&self) -> Vec<Range<usize>> {
+        let mut start = 0;
+        self.tokens()
+            .iter()
+            .map(|token| {
+                // count the first bytes of characters, not their continuation bytes
+                let chars = token
+                    .token_bytes()
+                    .iter()
+                    .filter(|byte| *byte & 0xC0 != 0x80)
+                    .count();
+                let range = start..start + chars;
+                start = range.end;
+                range
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(token: &str, bytes: &[u8], logprob: f32) -> ChatCompletionTokenLogprob {
+        ChatCompletionTokenLogprob {
+            token: token.into(),
+            logprob,
+            bytes: Some(bytes.to_vec()),
+            top_logprobs: vec![],
+        }
+    }
+
+    #[test]
+    fn test_chat_choice_logprobs() {
+        // "é" split across two tokens
+        let logprobs = ChatChoiceLogprobs {
+            content: Some(vec![
+                token("Caf", b"Caf", 0.0),
+                token("\\xc3", &[0xc3], -1.0),
+                token("\\xa9", &[0xa9], -1.0),
+                token("!", b"!", -2.0),
+            ]),
+            refusal: None,
+        };
+        assert_eq!(logprobs.text(), "Café!");
+        assert_eq!(logprobs.char_ranges(), [0..3, 3..4, 4..4, 4..5]);
+        assert_eq!(logprobs.sequence_logprob(), -4.0);
+        assert_eq!(logprobs.perplexity(), Some(1f64.exp()));
+
+        let mut answer = token("yes", b"yes", -0.1);
+        answer.top_logprobs = ["no", "yes", "maybe"]
+            .into_iter()
+            .zip([-2.5, -0.1, -4.0])
+            .map(|(token, logprob)| TopLogprobs {
+                token: token.into(),
+                logprob,
+                bytes: None,
+            })
+            .collect();
+        let top: Vec<&str> = answer
+            .top_k(2)
+            .into_iter()
+            .map(|(token, _)| token)
+            .collect();
+        assert_eq!(top, ["yes", "no"]);
+        assert_eq!(
+            answer.probability_of("maybe"),
+            Some(f64::from(-4.0f32).exp())
+        );
+    }
+}
@@ -1,11 +1,9 @@
-use std::collections::HashMap;
-
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::{ImageDetail, ImageUrl};
+use super::{Cursor, Deleted, ImageDetail, ImageUrl, Metadata, SortOrder, Timestamp, TimestampI32};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq, Default)]
 #[serde(rename_all = "lowercase")]
@@ -47,7 +45,8 @@ pub struct MessageObject {
     /// The object type, which is always `thread.message`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the message was created.
-    pub created_at: i32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampI32,
     /// The [thread](https://platform.openai.com/docs/api-reference/threads) ID that this message belongs to.
     pub thread_id: String,
 
@@ -58,10 +57,18 @@ pub struct MessageObject {
     pub incomplete_details: Option<MessageIncompleteDetails>,
 
     /// The Unix timestamp (in seconds) for when the message was completed.
-    pub completed_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub completed_at: Option<Timestamp>,
 
     /// The Unix timestamp (in seconds) for when the message was marked as incomplete.
-    pub incomplete_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub incomplete_at: Option<Timestamp>,
 
     /// The entity that produced the message. One of `user` or `assistant`.
     pub role: MessageRole,
@@ -78,7 +85,7 @@ pub struct MessageObject {
     /// A list of files attached to the message, and the tools they were added to.
     pub attachments: Option<Vec<MessageAttachment>>,
 
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
@@ -216,7 +223,7 @@ pub enum MessageContentInput {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateMessageRequest {
     /// The role of the entity that is creating the message. Allowed values include:
     /// - `user`: Indicates the message is sent by an actual user and should be used in most cases to represent user-generated messages.
@@ -229,20 +236,68 @@ pub struct CreateMessageRequest {
     pub attachments: Option<Vec<MessageAttachment>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+impl CreateMessageRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[builder(name = "ModifyMessageRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ModifyMessageRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
-pub struct DeleteMessageResponse {
-    pub id: String,
-    pub deleted: bool,
-    pub object: String,
+impl ModifyMessageRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Confirmation of a deleted [MessageObject].
+pub type DeleteMessageResponse = Deleted<MessageObject>;
+
+/// Query of [Messages::list](crate::Messages::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListMessagesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListMessagesQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
+
+    /// Only return messages created by this run.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub run_id: Option<String>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
@@ -0,0 +1,172 @@
+use std::{
+    collections::{hash_map, HashMap},
+    str::FromStr,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::OpenAIError;
+
+/// Key-value pairs attached to an object, to store additional information about it in a
+/// structured format and to query objects by their metadata.
+///
+/// The API accepts up to [Metadata::MAX_PAIRS] pairs, keys of up to [Metadata::MAX_KEY_LEN]
+/// characters and values of up to [Metadata::MAX_VALUE_LEN] characters: [Metadata::insert]
+/// rejects pairs beyond these limits, [Metadata::validate] checks metadata converted from a map.
+///
+/// ```
+/// use async_openai::types::Metadata;
+///
+/// let mut metadata = Metadata::new();
+/// metadata.insert("department", "accounting")?;
+/// metadata.insert("attempt", 3)?;
+/// assert_eq!(metadata.get("department"), Some("accounting"));
+/// assert_eq!(metadata.get_as::<u32>("attempt"), Some(Ok(3)));
+/// assert!(metadata.insert("x".repeat(65), "too long key").is_err());
+/// # Ok::<(), async_openai::error::OpenAIError>(())
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Metadata(HashMap<String, String>);
+
+impl Metadata {
+    /// Maximum number of key-value pairs.
+    pub const MAX_PAIRS: usize = 16;
+    /// Maximum length of a key, in characters.
+    pub const MAX_KEY_LEN: usize = 64;
+    /// Maximum length of a value, in characters.
+    pub const MAX_VALUE_LEN: usize = 512;
+
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Insert a pair, returning the previous value of `key`, or an error if the pair is beyond
+    /// the limits of the API, leaving the metadata unchanged. Values which are not strings,
+    /// such as numbers, are stored formatted, see [Metadata::get_as].
+    pub fn insert<K: Into<String>, V: ToString>(
+        &mut self,
+        key: K,
+        value: V,
+    ) -> Result<Option<String>, OpenAIError> {
+        let (key, value) = (key.into(), value.to_string());
+        check_pair(&key, &value)?;
+        if self.0.len() >= Self::MAX_PAIRS && !self.0.contains_key(&key) {
+            return Err(too_many_pairs(self.0.len() + 1));
+        }
+        Ok(self.0.insert(key, value))
+    }
+
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).map(String::as_str)
+    }
+
+    /// The value of `key` parsed as a `T`, `None` if there is no such key.
+    pub fn get_as<T: FromStr>(&self, key: &str) -> Option<Result<T, T::Err>> {
+        self.get(key).map(str::parse)
+    }
+
+    pub fn remove(&mut self, key: &str) -> Option<String> {
+        self.0.remove(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.0.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, String, String> {
+        self.0.iter()
+    }
+
+    /// Check the limits of the API, for metadata converted from a map.
+    pub fn validate(&self) -> Result<(), OpenAIError> {
+        if self.0.len() > Self::MAX_PAIRS {
+            return Err(too_many_pairs(self.0.len()));
+        }
+        self.0
+            .iter()
+            .try_for_each(|(key, value)| check_pair(key, value))
+    }
+
+    pub fn into_inner(self) -> HashMap<String, String> {
+        self.0
+    }
+}
+
+fn check_pair(key: &str, value: &str) -> Result<(), OpenAIError> {
+    if key.chars().count() > Metadata::MAX_KEY_LEN {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "metadata key `{key}` is longer than {} characters",
+            Metadata::MAX_KEY_LEN
+        )));
+    }
+    if value.chars().count() > Metadata::MAX_VALUE_LEN {
+        return Err(OpenAIError::InvalidArgument(format!(
+            "metadata value of `{key}` is longer than {} characters",
+            Metadata::MAX_VALUE_LEN
+        )));
+    }
+    Ok(())
+}
+
+fn too_many_pairs(len: usize) -> OpenAIError {
+    OpenAIError::InvalidArgument(format!(
+        "metadata has {len} pairs, at most {} are allowed",
+        Metadata::MAX_PAIRS
+    ))
+}
+
+impl From<HashMap<String, String>> for Metadata {
+    fn from(map: HashMap<String, String>) -> Self {
+        Self(map)
+    }
+}
+
+impl From<Metadata> for HashMap<String, String> {
+    fn from(metadata: Metadata) -> Self {
+        metadata.0
+    }
+}
+
+impl<K: Into<String>, V: Into<String>, const N: usize> From<[(K, V); N]> for Metadata {
+    fn from(pairs: [(K, V); N]) -> Self {
+        pairs.into_iter().collect()
+    }
+}
+
+impl<K: Into<String>, V: Into<String>> FromIterator<(K, V)> for Metadata {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(pairs: I) -> Self {
+        Self(
+            pairs
+                .into_iter()
+                .map(|(key, value)| (key.into(), value.into()))
+                .collect(),
+        )
+    }
+}
+
+impl IntoIterator for Metadata {
+    type Item = (String, String);
+    type IntoIter = hash_map::IntoIter<String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a Metadata {
+    type Item = (&'a String, &'a String);
+    type IntoIter = hash_map::Iter<'a, String, String>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
@@ -6,15 +6,25 @@ mod assistant_stream;
 mod audio;
 mod audit_log;
 mod batch;
+mod certificate;
 mod chat;
+mod chat_stream;
 mod common;
 mod completion;
+mod context;
+pub mod conversations;
+mod cost;
 mod embedding;
 mod file;
 mod fine_tuning;
+mod function_call_compat;
+mod grader;
 mod image;
 mod invites;
+mod json_schema;
+mod logprobs;
 mod message;
+mod metadata;
 mod model;
 mod moderation;
 mod project_api_key;
@@ -29,6 +39,9 @@ mod run;
 mod step;
 mod thread;
 mod tokenize;
+#[cfg_attr(docsrs, doc(cfg(feature = "typestate")))]
+#[cfg(feature = "typestate")]
+pub mod typestate;
 mod upload;
 mod users;
 mod vector_store;
@@ -38,15 +51,22 @@ pub use assistant_stream::*;
 pub use audio::*;
 pub use audit_log::*;
 pub use batch::*;
+pub use certificate::*;
 pub use chat::*;
+pub use chat_stream::*;
 pub use common::*;
 pub use completion::*;
+pub use context::*;
+pub use cost::*;
 pub use embedding::*;
 pub use file::*;
 pub use fine_tuning::*;
+pub use grader::*;
 pub use image::*;
 pub use invites::*;
+pub use json_schema::*;
 pub use message::*;
+pub use metadata::*;
 pub use model::*;
 pub use moderation::*;
 pub use project_api_key::*;
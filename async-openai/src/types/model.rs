@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 
+use super::{Deleted, Timestamp};
+
 /// Describes an OpenAI model offering that can be used with the API.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Model {
@@ -8,7 +10,8 @@ pub struct Model {
     /// The object type, which is always "model".
     pub object: String,
     /// The Unix timestamp (in seconds) when the model was created.
-    pub created: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created: Timestamp,
     /// The organization that owns the model.
     pub owned_by: String,
     // The model's max length
@@ -21,9 +24,16 @@ pub struct ListModelResponse {
     pub data: Vec<Model>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
-pub struct DeleteModelResponse {
-    pub id: String,
-    pub object: String,
-    pub deleted: bool,
+/// Confirmation of a deleted [Model].
+pub type DeleteModelResponse = Deleted<Model>;
+
+/// Features of a model detected by [crate::Models::probe].
+#[derive(Debug, Deserialize, Serialize, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Accepts `tools` in chat completion requests.
+    pub tools: bool,
+    /// Accepts image content parts in user messages.
+    pub vision: bool,
+    /// Accepts `response_format: {"type": "json_object"}`.
+    pub json_mode: bool,
 }
@@ -47,7 +47,7 @@ pub struct ModerationImageUrl {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateModerationRequest {
     /// Input (or inputs) to classify. Can be a single string, an array of strings, or
     /// an array of multi-modal input objects similar to other models.
@@ -60,6 +60,24 @@ pub struct CreateModerationRequest {
     pub model: Option<String>,
 }
 
+impl CreateModerationRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        let has_image = matches!(
+            &self.input,
+            Some(ModerationInput::MultiModal(parts))
+                if parts.iter().any(|part| matches!(part, ModerationContentPart::ImageUrl { .. }))
+        );
+        if let Some(Some(model)) = &self.model {
+            if has_image && !model.starts_with("omni-moderation") {
+                return Err(OpenAIError::InvalidArgument(format!(
+                    "{model} does not classify images, use omni-moderation-latest"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct Category {
     /// Content that expresses, incites, or promotes hate based on race, gender,
@@ -78,9 +96,10 @@ pub struct Category {
     #[serde(rename = "harassment/threatening")]
     pub harassment_threatening: bool,
     /// Content that includes instructions or advice that facilitate the planning or execution of wrongdoing, or that gives advice or instruction on how to commit illicit acts. For example, "how to shoplift" would fit this category.
+    #[serde(default)]
     pub illicit: bool,
     /// Content that includes instructions or advice that facilitate the planning or execution of wrongdoing that also includes violence, or that gives advice or instruction on the procurement of any weapon.
-    #[serde(rename = "illicit/violent")]
+    #[serde(rename = "illicit/violent", default)]
     pub illicit_violent: bool,
     /// Content that promotes, encourages, or depicts acts of self-harm, such as suicide, cutting, and eating disorders.
     #[serde(rename = "self-harm")]
@@ -116,10 +135,11 @@ pub struct CategoryScore {
     /// The score for the category 'harassment/threatening'.
     #[serde(rename = "harassment/threatening")]
     pub harassment_threatening: f32,
-    /// The score for the category 'illicit'.
+    /// The score for the category 'illicit', 0 for the `text-moderation` models.
+    #[serde(default)]
     pub illicit: f32,
     /// The score for the category 'illicit/violent'.
-    #[serde(rename = "illicit/violent")]
+    #[serde(rename = "illicit/violent", default)]
     pub illicit_violent: f32,
     /// The score for the category 'self-harm'.
     #[serde(rename = "self-harm")]
@@ -150,7 +170,9 @@ pub struct ContentModerationResult {
     pub categories: Category,
     /// A list of the categories along with their scores as predicted by model.
     pub category_scores: CategoryScore,
-    /// A list of the categories along with the input type(s) that the score applies to.
+    /// A list of the categories along with the input type(s) that the score applies to,
+    /// empty for the `text-moderation` models.
+    #[serde(default)]
     pub category_applied_input_types: CategoryAppliedInputTypes,
 }
 
@@ -166,7 +188,7 @@ pub struct CreateModerationResponse {
 }
 
 /// A list of the categories along with the input type(s) that the score applies to.
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
 pub struct CategoryAppliedInputTypes {
     /// The applied input type(s) for the category 'hate'.
     pub hate: Vec<ModInputType>,
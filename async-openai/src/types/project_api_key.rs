@@ -1,6 +1,9 @@
+use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::{ProjectServiceAccount, ProjectUser};
+use crate::error::OpenAIError;
+
+use super::{Cursor, Deleted, ProjectServiceAccount, ProjectUser, Timestamp};
 
 /// Represents an individual API key in a project.
 #[derive(Debug, Serialize, Deserialize)]
@@ -8,33 +11,64 @@ pub struct ProjectApiKey {
     /// The object type, which is always `organization.project.api_key`.
     pub object: String,
     /// The redacted value of the API key.
-    pub redacted_value: String,
+    pub redacted_value: RedactedApiKey,
     /// The name of the API key.
     pub name: String,
     /// The Unix timestamp (in seconds) of when the API key was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The identifier, which can be referenced in API endpoints.
     pub id: String,
     /// The owner of the API key.
     pub owner: ProjectApiKeyOwner,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(rename = "snake_case")]
+/// Redacted form of an API key, such as `sk-abc...def`, as returned when listing or retrieving keys.
+///
+/// The full key is only returned once, on creation, as a [secrecy::SecretString].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(transparent)]
+pub struct RedactedApiKey(pub String);
+
+impl RedactedApiKey {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for RedactedApiKey {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
 pub enum ProjectApiKeyOwnerType {
     User,
     ServiceAccount,
 }
 
-/// Represents the owner of a project API key.
+/// Represents the owner of a project API key, either a user or a service account.
 #[derive(Debug, Serialize, Deserialize)]
-pub struct ProjectApiKeyOwner {
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ProjectApiKeyOwner {
+    /// The user owner of the API key.
+    User { user: ProjectUser },
+    /// The service account owner of the API key.
+    ServiceAccount {
+        service_account: ProjectServiceAccount,
+    },
+}
+
+impl ProjectApiKeyOwner {
     /// The type of owner, which is either `user` or `service_account`.
-    pub r#type: ProjectApiKeyOwnerType,
-    /// The user owner of the API key, if applicable.
-    pub user: Option<ProjectUser>,
-    /// The service account owner of the API key, if applicable.
-    pub service_account: Option<ProjectServiceAccount>,
+    pub fn r#type(&self) -> ProjectApiKeyOwnerType {
+        match self {
+            ProjectApiKeyOwner::User { .. } => ProjectApiKeyOwnerType::User,
+            ProjectApiKeyOwner::ServiceAccount { .. } => ProjectApiKeyOwnerType::ServiceAccount,
+        }
+    }
 }
 
 /// Represents the response object for listing project API keys.
@@ -52,13 +86,24 @@ pub struct ProjectApiKeyListResponse {
     pub has_more: bool,
 }
 
-/// Represents the response object for deleting a project API key.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProjectApiKeyDeleteResponse {
-    /// The object type, which is always `organization.project.api_key.deleted`.
-    pub object: String,
-    /// The ID of the deleted API key.
-    pub id: String,
-    /// Indicates if the API key was successfully deleted.
-    pub deleted: bool,
+/// Query of [ProjectAPIKeys::list](crate::ProjectAPIKeys::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListProjectApiKeysQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListProjectApiKeysQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
 }
+
+/// Confirmation of a deleted [ProjectApiKey].
+pub type ProjectApiKeyDeleteResponse = Deleted<ProjectApiKey>;
+
+crate::pagination::impl_cursor_page!(ProjectApiKeyListResponse, ProjectApiKey);
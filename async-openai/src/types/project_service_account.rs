@@ -1,6 +1,10 @@
-use serde::{Deserialize, Serialize};
+use derive_builder::Builder;
+use secrecy::{ExposeSecret, SecretString};
+use serde::{Deserialize, Serialize, Serializer};
 
-use super::ProjectUserRole;
+use crate::error::OpenAIError;
+
+use super::{Cursor, Deleted, ProjectUserRole, Timestamp};
 
 /// Represents an individual service account in a project.
 #[derive(Debug, Serialize, Deserialize)]
@@ -14,7 +18,8 @@ pub struct ProjectServiceAccount {
     /// `owner` or `member`.
     pub role: ProjectUserRole,
     /// The Unix timestamp (in seconds) of when the service account was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
 }
 
 /// Represents the response object for listing project service accounts.
@@ -32,6 +37,23 @@ pub struct ProjectServiceAccountListResponse {
     pub has_more: bool,
 }
 
+/// Query of [ProjectServiceAccounts::list](crate::ProjectServiceAccounts::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListProjectServiceAccountsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListProjectServiceAccountsQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+}
+
 /// Represents the request object for creating a project service account.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ProjectServiceAccountCreateRequest {
@@ -51,7 +73,8 @@ pub struct ProjectServiceAccountCreateResponse {
     /// Service accounts can only have one role of type `member`.
     pub role: String,
     /// The Unix timestamp (in seconds) of when the service account was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The API key associated with the created service account.
     pub api_key: ProjectServiceAccountApiKey,
 }
@@ -61,23 +84,26 @@ pub struct ProjectServiceAccountCreateResponse {
 pub struct ProjectServiceAccountApiKey {
     /// The object type, which is always `organization.project.service_account.api_key`.
     pub object: String,
-    /// The value of the API key.
-    pub value: String,
+    /// The full value of the API key, only returned on creation. Redacted in `Debug` output.
+    #[serde(serialize_with = "serialize_secret")]
+    pub value: SecretString,
     /// The name of the API key.
     pub name: String,
     /// The Unix timestamp (in seconds) of when the API key was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The ID of the API key.
     pub id: String,
 }
 
-/// Represents the response object for deleting a project service account.
-#[derive(Debug, Serialize, Deserialize)]
-pub struct ProjectServiceAccountDeleteResponse {
-    /// The object type, which is always `organization.project.service_account.deleted`.
-    pub object: String,
-    /// The ID of the deleted service account.
-    pub id: String,
-    /// Indicates if the service account was successfully deleted.
-    pub deleted: bool,
+/// Confirmation of a deleted [ProjectServiceAccount].
+pub type ProjectServiceAccountDeleteResponse = Deleted<ProjectServiceAccount>;
+
+fn serialize_secret<S: Serializer>(
+    secret: &SecretString,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(secret.expose_secret())
 }
+
+crate::pagination::impl_cursor_page!(ProjectServiceAccountListResponse, ProjectServiceAccount);
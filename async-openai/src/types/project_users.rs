@@ -2,6 +2,8 @@ use crate::types::OpenAIError;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
+use super::{Cursor, Deleted, Timestamp};
+
 /// Represents an individual user in a project.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct ProjectUser {
@@ -16,7 +18,8 @@ pub struct ProjectUser {
     /// `owner` or `member`
     pub role: ProjectUserRole,
     /// The Unix timestamp (in seconds) of when the project was added.
-    pub added_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub added_at: Timestamp,
 }
 
 /// `owner` or `member`
@@ -36,6 +39,23 @@ pub struct ProjectUserListResponse {
     pub has_more: String,
 }
 
+/// Query of [ProjectUsers::list](crate::ProjectUsers::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListProjectUsersQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListProjectUsersQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+}
+
 /// The project user create request payload.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
 #[builder(name = "ProjectUserCreateRequestArgs")]
@@ -61,9 +81,5 @@ pub struct ProjectUserUpdateRequest {
     pub role: ProjectUserRole,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct ProjectUserDeleteResponse {
-    pub object: String,
-    pub id: String,
-    pub deleted: bool,
-}
+/// Confirmation of a deleted [ProjectUser].
+pub type ProjectUserDeleteResponse = Deleted<ProjectUser>;
@@ -2,6 +2,8 @@ use crate::types::OpenAIError;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
+use super::{Cursor, Timestamp};
+
 /// `active` or `archived`
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -20,9 +22,14 @@ pub struct Project {
     /// The name of the project. This appears in reporting.
     pub name: String,
     /// The Unix timestamp (in seconds) of when the project was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The Unix timestamp (in seconds) of when the project was archived or `null`.
-    pub archived_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub archived_at: Option<Timestamp>,
     /// `active` or `archived`
     pub status: ProjectStatus,
 }
@@ -37,6 +44,27 @@ pub struct ProjectListResponse {
     pub has_more: String,
 }
 
+/// Query of [Projects::list](crate::Projects::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListProjectsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListProjectsQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// Whether to include archived projects. Defaults to `false`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include_archived: Option<bool>,
+}
+
 /// The project create request payload.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
 #[builder(name = "ProjectCreateRequestArgs")]
@@ -0,0 +1,169 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{error::OpenAIError, types::TimestampU64};
+
+use super::{AudioFormat, AudioTranscription, SessionResource, TurnDetection};
+
+/// Ephemeral key authenticating a client, e.g. a browser connecting with WebRTC, to the
+/// Realtime API on behalf of the backend which created it.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct RealtimeClientSecret {
+    /// The ephemeral key, to send as a bearer token.
+    pub value: String,
+    /// The Unix timestamp (in seconds) for when the key expires.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: TimestampU64,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum ClientSecretAnchor {
+    #[default]
+    CreatedAt,
+}
+
+/// Expiration of an ephemeral key, 10 minutes after its creation by default.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct ClientSecretExpiresAfter {
+    /// The timestamp the expiration is counted from, only `created_at`.
+    pub anchor: ClientSecretAnchor,
+    /// Seconds until the key expires, between 10 and 7200.
+    pub seconds: u32,
+}
+
+/// Expiration of the ephemeral key of a session.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq)]
+pub struct SessionClientSecret {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<ClientSecretExpiresAfter>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum NoiseReductionType {
+    /// For close-talking microphones such as headphones.
+    NearField,
+    /// For far-field microphones such as laptop or conference room microphones.
+    FarField,
+}
+
+/// Noise reduction of the input audio, before it is sent to VAD and the model.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct InputAudioNoiseReduction {
+    pub r#type: NoiseReductionType,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Builder)]
+#[builder(name = "CreateRealtimeSessionRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateRealtimeSessionRequest {
+    /// Default configuration of the session, which the client can update once connected.
+    #[serde(flatten)]
+    pub session: SessionResource,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_noise_reduction: Option<InputAudioNoiseReduction>,
+
+    /// The speed of the spoken response, between 0.25 and 1.5. 1.0 is the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub speed: Option<f32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<SessionClientSecret>,
+}
+
+/// A Realtime session created for a client, with its ephemeral key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct RealtimeSession {
+    /// Unique identifier of the session.
+    pub id: Option<String>,
+    /// The object type, which is always `realtime.session`.
+    pub object: Option<String>,
+    #[serde(flatten)]
+    pub session: SessionResource,
+    pub client_secret: RealtimeClientSecret,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Builder)]
+#[builder(name = "CreateTranscriptionSessionRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateTranscriptionSessionRequest {
+    /// The format of input audio. Options are "pcm16", "g711_ulaw", or "g711_alaw".
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_format: Option<AudioFormat>,
+
+    /// The model, language and prompt of the transcription.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_transcription: Option<AudioTranscription>,
+
+    /// Configuration for turn detection.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub turn_detection: Option<TurnDetection>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub input_audio_noise_reduction: Option<InputAudioNoiseReduction>,
+
+    /// Additional fields of the transcription events, e.g. `item.input_audio_transcription.logprobs`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub include: Option<Vec<String>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_secret: Option<SessionClientSecret>,
+}
+
+/// A Realtime transcription session created for a client, with its ephemeral key.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TranscriptionSession {
+    /// Unique identifier of the session.
+    pub id: Option<String>,
+    /// The object type, which is always `realtime.transcription_session`.
+    pub object: Option<String>,
+    pub modalities: Option<Vec<String>>,
+    pub input_audio_format: Option<AudioFormat>,
+    pub input_audio_transcription: Option<AudioTranscription>,
+    pub turn_detection: Option<TurnDetection>,
+    pub client_secret: RealtimeClientSecret,
+}
+
+/// Configuration of the session an ephemeral key is created for.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientSecretSession {
+    Realtime(SessionResource),
+    Transcription(CreateTranscriptionSessionRequest),
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Builder)]
+#[builder(name = "CreateClientSecretRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct CreateClientSecretRequest {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expires_after: Option<ClientSecretExpiresAfter>,
+
+    /// Configuration of the session, a realtime session by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session: Option<ClientSecretSession>,
+}
+
+/// An ephemeral key and the configuration of the session it is valid for.
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct CreateClientSecretResponse {
+    /// The ephemeral key, to send as a bearer token.
+    pub value: String,
+    /// The Unix timestamp (in seconds) for when the key expires.
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: TimestampU64,
+    /// The session as configured by the API, in the format of the generally available
+    /// Realtime API which nests the audio settings under `audio`.
+    pub session: Option<serde_json::Value>,
+}
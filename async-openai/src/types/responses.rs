@@ -6,10 +6,13 @@ pub use crate::types::{
 use derive_builder::Builder;
 use futures::Stream;
 use serde::{Deserialize, Serialize};
+
 use serde_json::Value;
 use std::collections::HashMap;
 use std::pin::Pin;
 
+use super::{Metadata, TimestampU64};
+
 /// Role of messages in the API.
 #[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
 #[serde(rename_all = "lowercase")]
@@ -96,7 +99,7 @@ pub enum ContentType {
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct InputText {
-    text: String,
+    pub text: String,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Default, Builder)]
@@ -147,7 +150,8 @@ pub struct InputFile {
     setter(into, strip_option),
     default
 )]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateResponse {
     /// Text, image, or file inputs to the model, used to generate a response.
     pub input: Input,
@@ -207,7 +211,7 @@ pub struct CreateResponse {
     /// Keys are strings with a maximum length of 64 characters. Values are
     /// strings with a maximum length of 512 characters.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
 
     /// Whether to allow the model to run tool calls in parallel.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -305,6 +309,15 @@ pub struct CreateResponse {
     pub user: Option<String>,
 }
 
+impl CreateResponseArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
 /// Service tier request options.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct PromptConfig {
@@ -1336,7 +1349,8 @@ pub struct Usage {
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
 pub struct Response {
     /// Unix timestamp (in seconds) when this Response was created.
-    pub created_at: u64,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampU64,
 
     /// Error object if the API failed to generate a response.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1359,7 +1373,7 @@ pub struct Response {
 
     /// Metadata tags/values that were attached to this response.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
 
     /// Model ID used to generate the response.
     pub model: String,
@@ -1638,6 +1652,9 @@ pub struct ResponseOutputTextDelta {
     pub delta: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub logprobs: Option<serde_json::Value>,
+    /// Random characters padding the event to normalize payload sizes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -1711,6 +1728,9 @@ pub struct ResponseRefusalDelta {
     pub output_index: u32,
     pub content_index: u32,
     pub delta: String,
+    /// Random characters padding the event to normalize payload sizes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation: Option<String>,
 }
 
 /// Refusal done event
@@ -1732,6 +1752,9 @@ pub struct ResponseFunctionCallArgumentsDelta {
     pub item_id: String,
     pub output_index: u32,
     pub delta: String,
+    /// Random characters padding the event to normalize payload sizes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation: Option<String>,
 }
 
 /// Function call arguments done event
@@ -1839,6 +1862,9 @@ pub struct ResponseReasoningSummaryTextDelta {
     pub output_index: u32,
     pub summary_index: u32,
     pub delta: String,
+    /// Random characters padding the event to normalize payload sizes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub obfuscation: Option<String>,
 }
 
 /// Reasoning summary text done event
@@ -2040,7 +2066,8 @@ pub struct ResponseMetadata {
     pub id: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub object: Option<String>,
-    pub created_at: u64,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampU64,
     pub status: Status,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub model: Option<String>,
@@ -2093,7 +2120,7 @@ pub struct ResponseMetadata {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub user: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, String>>,
+    pub metadata: Option<Metadata>,
     /// Prompt cache key for improved performance
     #[serde(skip_serializing_if = "Option::is_none")]
     pub prompt_cache_key: Option<String>,
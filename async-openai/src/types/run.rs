@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -7,7 +5,7 @@ use crate::{error::OpenAIError, types::FunctionCall};
 
 use super::{
     AssistantTools, AssistantsApiResponseFormatOption, AssistantsApiToolChoiceOption,
-    CreateMessageRequest,
+    CreateMessageRequest, Cursor, Metadata, SortOrder, TimestampI32,
 };
 
 /// Represents an execution run on a [thread](https://platform.openai.com/docs/api-reference/threads).
@@ -18,7 +16,8 @@ pub struct RunObject {
     /// The object type, which is always `thread.run`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the run was created.
-    pub created_at: i32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampI32,
     ///The ID of the [thread](https://platform.openai.com/docs/api-reference/threads) that was executed on as a part of this run.
     pub thread_id: String,
 
@@ -35,15 +34,35 @@ pub struct RunObject {
     pub last_error: Option<LastError>,
 
     /// The Unix timestamp (in seconds) for when the run will expire.
-    pub expires_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub expires_at: Option<TimestampI32>,
     ///  The Unix timestamp (in seconds) for when the run was started.
-    pub started_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub started_at: Option<TimestampI32>,
     /// The Unix timestamp (in seconds) for when the run was cancelled.
-    pub cancelled_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub cancelled_at: Option<TimestampI32>,
     /// The Unix timestamp (in seconds) for when the run failed.
-    pub failed_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub failed_at: Option<TimestampI32>,
     ///The Unix timestamp (in seconds) for when the run was completed.
-    pub completed_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub completed_at: Option<TimestampI32>,
 
     /// Details on why the run is incomplete. Will be `null` if the run is not incomplete.
     pub incomplete_details: Option<RunObjectIncompleteDetails>,
@@ -57,7 +76,7 @@ pub struct RunObject {
     /// The list of tools that the [assistant](https://platform.openai.com/docs/api-reference/assistants) used for this run.
     pub tools: Vec<AssistantTools>,
 
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// Usage statistics related to the run. This value will be `null` if the run is not in a terminal state (i.e. `in_progress`, `queued`, etc.).
     pub usage: Option<RunCompletionUsage>,
@@ -183,7 +202,7 @@ pub struct RunCompletionUsage {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateRunRequest {
     /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) to use to execute this run.
     pub assistant_id: String,
@@ -209,7 +228,7 @@ pub struct CreateRunRequest {
     pub tools: Option<Vec<AssistantTools>>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// The sampling temperature used for this run. If not set, defaults to 1.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -248,10 +267,61 @@ pub struct CreateRunRequest {
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+impl CreateRunRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(response_format)) = &self.response_format {
+            response_format.validate()?;
+        }
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[builder(name = "ModifyRunRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ModifyRunRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
+}
+
+impl ModifyRunRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
+
+/// Query of [Runs::list](crate::Runs::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListRunsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListRunsQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
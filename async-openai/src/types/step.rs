@@ -1,8 +1,12 @@
-use std::collections::HashMap;
-
+use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::{FileSearchRankingOptions, ImageFile, LastError, RunStatus};
+use crate::error::OpenAIError;
+
+use super::{
+    Cursor, FileSearchRankingOptions, ImageFile, LastError, Metadata, RunStatus, SortOrder,
+    TimestampI32,
+};
 
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "snake_case")]
@@ -19,7 +23,8 @@ pub struct RunStepObject {
     /// The object type, which is always `thread.run.step`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the run step was created.
-    pub created_at: i32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampI32,
 
     /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) associated with the run step.
     pub assistant_id: Option<String>,
@@ -43,18 +48,34 @@ pub struct RunStepObject {
     pub last_error: Option<LastError>,
 
     ///The Unix timestamp (in seconds) for when the run step expired. A step is considered expired if the parent run is expired.
-    pub expires_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub expires_at: Option<TimestampI32>,
 
     /// The Unix timestamp (in seconds) for when the run step was cancelled.
-    pub cancelled_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub cancelled_at: Option<TimestampI32>,
 
     /// The Unix timestamp (in seconds) for when the run step failed.
-    pub failed_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub failed_at: Option<TimestampI32>,
 
     /// The Unix timestamp (in seconds) for when the run step completed.
-    pub completed_at: Option<i32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub completed_at: Option<TimestampI32>,
 
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// Usage statistics related to the run step. This value will be `null` while the run step's status is `in_progress`.
     pub usage: Option<RunStepCompletionUsage>,
@@ -221,6 +242,31 @@ pub struct ListRunStepsResponse {
     pub has_more: bool,
 }
 
+/// Query of [Steps::list](crate::Steps::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListRunStepsQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListRunStepsQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
+}
+
 /// Represents a run step delta i.e. any changed fields on a run step during streaming.
 #[derive(Clone, Serialize, Debug, Deserialize, PartialEq)]
 pub struct RunStepDeltaObject {
@@ -1,5 +1,3 @@
-use std::collections::HashMap;
-
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
@@ -7,8 +5,8 @@ use crate::error::OpenAIError;
 
 use super::{
     AssistantToolResources, AssistantTools, AssistantsApiResponseFormatOption,
-    AssistantsApiToolChoiceOption, CreateAssistantToolResources, CreateMessageRequest,
-    TruncationObject,
+    AssistantsApiToolChoiceOption, CreateAssistantToolResources, CreateMessageRequest, Deleted,
+    Metadata, TimestampI32, TruncationObject,
 };
 
 /// Represents a thread that contains [messages](https://platform.openai.com/docs/api-reference/messages).
@@ -19,12 +17,13 @@ pub struct ThreadObject {
     /// The object type, which is always `thread`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the thread was created.
-    pub created_at: i32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: TimestampI32,
 
     /// A set of resources that are made available to the assistant's tools in this thread. The resources are specific to the type of tool. For example, the `code_interpreter` tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
     pub tool_resources: Option<AssistantToolResources>,
 
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
@@ -32,7 +31,7 @@ pub struct ThreadObject {
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateThreadRequest {
     /// A list of [messages](https://platform.openai.com/docs/api-reference/messages) to start the thread with.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -43,32 +42,51 @@ pub struct CreateThreadRequest {
     pub tool_resources: Option<CreateAssistantToolResources>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
+}
+
+impl CreateThreadRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
+#[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
+#[builder(name = "ModifyThreadRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct ModifyThreadRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// A set of resources that are made available to the assistant's tools in this thread. The resources are specific to the type of tool. For example, the `code_interpreter` tool requires a list of file IDs, while the `file_search` tool requires a list of vector store IDs.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub tool_resources: Option<AssistantToolResources>,
 }
 
-#[derive(Clone, Serialize, Default, Debug, Deserialize, PartialEq)]
-pub struct DeleteThreadResponse {
-    pub id: String,
-    pub deleted: bool,
-    pub object: String,
+impl ModifyThreadRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
+/// Confirmation of a deleted [ThreadObject].
+pub type DeleteThreadResponse = Deleted<ThreadObject>;
+
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
 #[builder(name = "CreateThreadAndRunRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateThreadAndRunRequest {
     /// The ID of the [assistant](https://platform.openai.com/docs/api-reference/assistants) to use to execute this run.
     pub assistant_id: String,
@@ -94,7 +112,7 @@ pub struct CreateThreadAndRunRequest {
     pub tool_resources: Option<AssistantToolResources>,
 
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 
     /// What sampling temperature to use, between 0 and 2. Higher values like 0.8 will make the output more random, while lower values like 0.2 will make it more focused and deterministic.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -132,3 +150,15 @@ pub struct CreateThreadAndRunRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub response_format: Option<AssistantsApiResponseFormatOption>,
 }
+
+impl CreateThreadAndRunRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(response_format)) = &self.response_format {
+            response_format.validate()?;
+        }
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+//! Type-state builders checking at compile time that the required fields of a request are set,
+//! enabled with the `typestate` feature.
+//!
+//! `build` is only available once every required field was set; optional fields are set on the
+//! regular builder with `options`. `build` still fails for invalid combinations of fields, see the
+//! validation of the regular builder.
+//!
+//! ```
+//! use async_openai::types::{
+//!     typestate::StrictCreateChatCompletionRequestArgs, ChatCompletionRequestUserMessage,
+//! };
+//!
+//! let request = StrictCreateChatCompletionRequestArgs::new()
+//!     .model("gpt-4o-mini")
+//!     .messages([ChatCompletionRequestUserMessage::from("Hello!").into()])
+//!     .options(|args| args.temperature(0.2).max_completion_tokens(100u32))
+//!     .build()?;
+//! # Ok::<(), async_openai::error::OpenAIError>(())
+//! ```
+//!
+//! Forgetting the messages does not compile:
+//!
+//! ```compile_fail
+//! use async_openai::types::typestate::StrictCreateChatCompletionRequestArgs;
+//!
+//! let request = StrictCreateChatCompletionRequestArgs::new()
+//!     .model("gpt-4o-mini")
+//!     .build();
+//! ```
+use std::marker::PhantomData;
+
+use crate::error::OpenAIError;
+
+use super::{
+    responses::{CreateResponse, CreateResponseArgs, Input},
+    ChatCompletionRequestMessage, CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    CreateCompletionRequest, CreateCompletionRequestArgs, CreateEmbeddingRequest,
+    CreateEmbeddingRequestArgs, EmbeddingInput, Prompt,
+};
+
+/// State of a required field which is not set yet.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Missing;
+
+/// State of a required field which is set.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Set;
+
+macro_rules! strict_builder {
+    (
+        $(#[$meta:meta])*
+        $name:ident => $request:ident, $args:ident,
+        $first:ident: $first_ty:ty,
+        $second:ident: $second_ty:ty
+    ) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone)]
+        pub struct $name<A = Missing, B = Missing> {
+            args: $args,
+            state: PhantomData<(A, B)>,
+        }
+
+        impl $name<Missing, Missing> {
+            pub fn new() -> Self {
+                Self {
+                    args: $args::default(),
+                    state: PhantomData,
+                }
+            }
+        }
+
+        impl Default for $name<Missing, Missing> {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
+        impl<B> $name<Missing, B> {
+            pub fn $first<V: Into<$first_ty>>(mut self, value: V) -> $name<Set, B> {
+                self.args.$first(value);
+                $name {
+                    args: self.args,
+                    state: PhantomData,
+                }
+            }
+        }
+
+        impl<A> $name<A, Missing> {
+            pub fn $second<V: Into<$second_ty>>(mut self, value: V) -> $name<A, Set> {
+                self.args.$second(value);
+                $name {
+                    args: self.args,
+                    state: PhantomData,
+                }
+            }
+        }
+
+        impl<A, B> $name<A, B> {
+            /// Set optional fields on the regular builder.
+            pub fn options<F>(mut self, options: F) -> Self
+            where
+                F: FnOnce(&mut $args) -> &mut $args,
+            {
+                options(&mut self.args);
+                self
+            }
+        }
+
+        impl $name<Set, Set> {
+            pub fn build(&self) -> Result<$request, OpenAIError> {
+                self.args.build()
+            }
+        }
+
+        impl $request {
+            #[doc = concat!("[", stringify!($name), "] checking required fields at compile time.")]
+            pub fn strict_builder() -> $name {
+                $name::new()
+            }
+        }
+    };
+}
+
+strict_builder!(
+    /// Builder of [CreateChatCompletionRequest] requiring `model` and `messages`.
+    StrictCreateChatCompletionRequestArgs => CreateChatCompletionRequest, CreateChatCompletionRequestArgs,
+    model: String,
+    messages: Vec<ChatCompletionRequestMessage>
+);
+
+strict_builder!(
+    /// Builder of [CreateCompletionRequest] requiring `model` and `prompt`.
+    StrictCreateCompletionRequestArgs => CreateCompletionRequest, CreateCompletionRequestArgs,
+    model: String,
+    prompt: Prompt
+);
+
+strict_builder!(
+    /// Builder of [CreateEmbeddingRequest] requiring `model` and `input`.
+    StrictCreateEmbeddingRequestArgs => CreateEmbeddingRequest, CreateEmbeddingRequestArgs,
+    model: String,
+    input: EmbeddingInput
+);
+
+strict_builder!(
+    /// Builder of [CreateResponse] requiring `model` and `input`.
+    StrictCreateResponseArgs => CreateResponse, CreateResponseArgs,
+    model: String,
+    input: Input
+);
@@ -1,8 +1,17 @@
+use std::{
+    fmt,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
 use crate::error::OpenAIError;
+use bytes::Bytes;
 use derive_builder::Builder;
+use futures::{Stream, StreamExt};
 use serde::{Deserialize, Serialize};
+use tokio::io::AsyncRead;
 
-use super::{InputSource, OpenAIFile};
+use super::{InputSource, OpenAIFile, Timestamp};
 
 /// Request to create an upload object that can accept byte chunks in the form of Parts.
 #[derive(Clone, Serialize, Default, Debug, Deserialize, Builder, PartialEq)]
@@ -52,7 +61,8 @@ pub struct Upload {
     pub id: String,
 
     /// The Unix timestamp (in seconds) for when the Upload was created
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
 
     /// The name of the file to be uploaded
     pub filename: String,
@@ -67,7 +77,8 @@ pub struct Upload {
     pub status: UploadStatus,
 
     /// The Unix timestamp (in seconds) for when the Upload was created
-    pub expires_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub expires_at: Timestamp,
 
     /// The object type, which is always "upload"
     pub object: String,
@@ -98,7 +109,8 @@ pub struct UploadPart {
     pub id: String,
 
     /// The Unix timestamp (in seconds) for when the Part was created
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
 
     /// The ID of the Upload object that this Part was added to
     pub upload_id: String,
@@ -114,6 +126,64 @@ pub struct AddUploadPartRequest {
     pub data: InputSource,
 }
 
+type PartBody = Pin<Box<dyn Stream<Item = Result<Bytes, std::io::Error>> + Send>>;
+
+/// The chunk of bytes of a Part read from a stream as it is sent, instead of buffered in
+/// memory, see [crate::Uploads::add_part_stream].
+///
+/// The stream is sent once: a call failing after it started sending is not retried.
+#[derive(Clone)]
+pub struct UploadPartStream {
+    stream: Arc<Mutex<Option<PartBody>>>,
+    length: Option<u64>,
+}
+
+impl UploadPartStream {
+    /// Part of the chunks of `stream`.
+    pub fn from_stream<S, E>(stream: S) -> Self
+    where
+        S: Stream<Item = Result<Bytes, E>> + Send + 'static,
+        E: Into<Box<dyn std::error::Error + Send + Sync>>,
+    {
+        let stream = stream.map(|chunk| chunk.map_err(std::io::Error::other));
+        Self {
+            stream: Arc::new(Mutex::new(Some(Box::pin(stream)))),
+            length: None,
+        }
+    }
+
+    /// Part of the bytes of `reader` until its end, e.g. a range of an object in storage.
+    pub fn from_reader<R: AsyncRead + Send + 'static>(reader: R) -> Self {
+        Self::from_stream(tokio_util::io::ReaderStream::new(reader))
+    }
+
+    /// Send the number of bytes of the part, when known, as its content length.
+    pub fn with_length(mut self, length: u64) -> Self {
+        self.length = Some(length);
+        self
+    }
+
+    pub fn length(&self) -> Option<u64> {
+        self.length
+    }
+
+    /// The stream, `None` once sent.
+    pub(crate) fn take(&self) -> Option<PartBody> {
+        self.stream
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+            .take()
+    }
+}
+
+impl fmt::Debug for UploadPartStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("UploadPartStream")
+            .field("length", &self.length)
+            .finish_non_exhaustive()
+    }
+}
+
 /// Request parameters for completing an Upload
 #[derive(Debug, Serialize)]
 pub struct CompleteUploadRequest {
@@ -2,7 +2,7 @@ use crate::types::OpenAIError;
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use super::OrganizationRole;
+use super::{Cursor, Deleted, OrganizationRole, Timestamp};
 
 /// Represents an individual `user` within an organization.
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -18,7 +18,8 @@ pub struct User {
     /// `owner` or `reader`
     pub role: OrganizationRole,
     /// The Unix timestamp (in seconds) of when the users was added.
-    pub added_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub added_at: Timestamp,
 }
 
 /// A list of `User` objects.
@@ -31,6 +32,23 @@ pub struct UserListResponse {
     pub has_more: bool,
 }
 
+/// Query of [Users::list](crate::Users::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListUsersQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListUsersQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Builder)]
 #[builder(name = "UserRoleUpdateRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -42,10 +60,5 @@ pub struct UserRoleUpdateRequest {
     pub role: OrganizationRole,
 }
 
-/// Confirmation of the deleted user
-#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
-pub struct UserDeleteResponse {
-    pub object: String,
-    pub id: String,
-    pub deleted: bool,
-}
+/// Confirmation of a deleted [User].
+pub type UserDeleteResponse = Deleted<User>;
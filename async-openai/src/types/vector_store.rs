@@ -5,14 +5,14 @@ use serde::{Deserialize, Serialize};
 
 use crate::error::OpenAIError;
 
-use super::StaticChunkingStrategy;
+use super::{Cursor, Deleted, Metadata, SortOrder, StaticChunkingStrategy, Timestamp};
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
 #[builder(name = "CreateVectorStoreRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct CreateVectorStoreRequest {
     /// A list of [File](https://platform.openai.com/docs/api-reference/files) IDs that the vector store should use. Useful for tools like `file_search` that can access files.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -31,7 +31,16 @@ pub struct CreateVectorStoreRequest {
 
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
+}
+
+impl CreateVectorStoreRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Serialize, Deserialize, Default, Clone, PartialEq)]
@@ -65,7 +74,8 @@ pub struct VectorStoreObject {
     /// The object type, which is always `vector_store`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the vector store was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The name of the vector store.
     pub name: Option<String>,
     /// The total number of bytes used by the files in the vector store.
@@ -75,12 +85,20 @@ pub struct VectorStoreObject {
     pub status: VectorStoreStatus,
     pub expires_after: Option<VectorStoreExpirationAfter>,
     /// The Unix timestamp (in seconds) for when the vector store will expire.
-    pub expires_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub expires_at: Option<Timestamp>,
     /// The Unix timestamp (in seconds) for when the vector store was last active.
-    pub last_active_at: Option<u32>,
+    #[cfg_attr(
+        feature = "chrono",
+        serde(default, with = "chrono::serde::ts_seconds_option")
+    )]
+    pub last_active_at: Option<Timestamp>,
 
     /// Set of 16 key-value pairs that can be attached to an object. This can be useful for storing additional information about the object in a structured format. Keys can be a maximum of 64 characters long and values can be a maximum of 512 characters long.
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -114,26 +132,56 @@ pub struct ListVectorStoresResponse {
     pub has_more: bool,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
-pub struct DeleteVectorStoreResponse {
-    pub id: String,
-    pub object: String,
-    pub deleted: bool,
+/// Query of [VectorStores::list](crate::VectorStores::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListVectorStoresQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListVectorStoresQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
 }
 
+/// Confirmation of a deleted [VectorStoreObject].
+pub type DeleteVectorStoreResponse = Deleted<VectorStoreObject>;
+
 #[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
 #[builder(name = "UpdateVectorStoreRequestArgs")]
 #[builder(pattern = "mutable")]
 #[builder(setter(into, strip_option), default)]
 #[builder(derive(Debug))]
-#[builder(build_fn(error = "OpenAIError"))]
+#[builder(build_fn(error = "OpenAIError", validate = "Self::validate"))]
 pub struct UpdateVectorStoreRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub name: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expires_after: Option<VectorStoreExpirationAfter>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub metadata: Option<HashMap<String, serde_json::Value>>,
+    pub metadata: Option<Metadata>,
+}
+
+impl UpdateVectorStoreRequestArgs {
+    fn validate(&self) -> Result<(), OpenAIError> {
+        if let Some(Some(metadata)) = &self.metadata {
+            metadata.validate()?;
+        }
+        Ok(())
+    }
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -145,6 +193,36 @@ pub struct ListVectorStoreFilesResponse {
     pub has_more: bool,
 }
 
+/// Query of [VectorStoreFiles::list](crate::VectorStoreFiles::list) and
+/// [VectorStoreFileBatches::list](crate::VectorStoreFileBatches::list).
+#[derive(Debug, Serialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "ListVectorStoreFilesQueryArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct ListVectorStoreFilesQuery {
+    /// A limit on the number of objects to be returned, between 1 and 100. Defaults to 20.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<u32>,
+
+    /// Sort order by the `created_at` timestamp of the objects. Defaults to `desc`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub order: Option<SortOrder>,
+
+    /// A cursor for pagination: the ID of the last object of the previous page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<Cursor>,
+
+    /// A cursor for pagination: the ID of the first object of the next page.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub before: Option<Cursor>,
+
+    /// Only return files with the given status.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub filter: Option<VectorStoreFileStatus>,
+}
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct VectorStoreFileObject {
     /// The identifier, which can be referenced in API endpoints.
@@ -154,7 +232,8 @@ pub struct VectorStoreFileObject {
     /// The total vector store usage in bytes. Note that this may be different from the original file size.
     pub usage_bytes: u64,
     /// The Unix timestamp (in seconds) for when the vector store file was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The ID of the [vector store](https://platform.openai.com/docs/api-reference/vector-stores/object) that the [File](https://platform.openai.com/docs/api-reference/files) is attached to.
     pub vector_store_id: String,
     /// The status of the vector store file, which can be either `in_progress`, `completed`, `cancelled`, or `failed`. The status `completed` indicates that the vector store file is ready for use.
@@ -163,8 +242,14 @@ pub struct VectorStoreFileObject {
     pub last_error: Option<VectorStoreFileError>,
     /// The strategy used to chunk the file.
     pub chunking_strategy: Option<VectorStoreFileObjectChunkingStrategy>,
+    /// The attributes of the file, which [search filters](ComparisonFilter) compare against.
+    pub attributes: Option<VectorStoreFileAttributes>,
 }
 
+/// Attributes of a vector store file: up to 16 key-value pairs, keys of up to 64 characters
+/// and string values of up to 512 characters.
+pub type VectorStoreFileAttributes = HashMap<String, AttributeValue>;
+
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 #[serde(rename_all = "snake_case")]
 pub enum VectorStoreFileStatus {
@@ -212,16 +297,23 @@ pub struct CreateVectorStoreFileRequest {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub chunking_strategy: Option<VectorStoreChunkingStrategy>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub attributes: Option<HashMap<String, AttributeValue>>,
+    pub attributes: Option<VectorStoreFileAttributes>,
 }
 
-#[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
-pub struct DeleteVectorStoreFileResponse {
-    pub id: String,
-    pub object: String,
-    pub deleted: bool,
+#[derive(Debug, Serialize, Deserialize, Default, Clone, Builder, PartialEq)]
+#[builder(name = "UpdateVectorStoreFileAttributesRequestArgs")]
+#[builder(pattern = "mutable")]
+#[builder(setter(into, strip_option), default)]
+#[builder(derive(Debug))]
+#[builder(build_fn(error = "OpenAIError"))]
+pub struct UpdateVectorStoreFileAttributesRequest {
+    /// Replaces the attributes of the file, `None` removing them.
+    pub attributes: Option<VectorStoreFileAttributes>,
 }
 
+/// Confirmation of a deleted [VectorStoreFileObject].
+pub type DeleteVectorStoreFileResponse = Deleted<VectorStoreFileObject>;
+
 #[derive(Debug, Serialize, Default, Clone, Builder, PartialEq, Deserialize)]
 #[builder(name = "CreateVectorStoreFileBatchRequestArgs")]
 #[builder(pattern = "mutable")]
@@ -232,6 +324,9 @@ pub struct CreateVectorStoreFileBatchRequest {
     /// A list of [File](https://platform.openai.com/docs/api-reference/files) IDs that the vector store should use. Useful for tools like `file_search` that can access files.
     pub file_ids: Vec<String>, // minItems: 1, maxItems: 500
     pub chunking_strategy: Option<VectorStoreChunkingStrategy>,
+    /// The attributes of each file of the batch.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub attributes: Option<VectorStoreFileAttributes>,
 }
 
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
@@ -265,7 +360,8 @@ pub struct VectorStoreFileBatchObject {
     /// The object type, which is always `vector_store.file_batch`.
     pub object: String,
     /// The Unix timestamp (in seconds) for when the vector store files batch was created.
-    pub created_at: u32,
+    #[cfg_attr(feature = "chrono", serde(with = "chrono::serde::ts_seconds"))]
+    pub created_at: Timestamp,
     /// The ID of the [vector store](https://platform.openai.com/docs/api-reference/vector-stores/object) that the [File](https://platform.openai.com/docs/api-reference/files) is attached to.
     pub vector_store_id: String,
     /// The status of the vector store files batch, which can be either `in_progress`, `completed`, `cancelled` or `failed`.
@@ -289,6 +385,17 @@ pub struct VectorStoreFileContentResponse {
     pub next_page: Option<String>,
 }
 
+impl VectorStoreFileContentResponse {
+    /// The text of the parsed content, its parts separated by new lines.
+    pub fn text(&self) -> String {
+        self.data
+            .iter()
+            .map(|content| content.text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
 /// Represents the parsed content of a vector store file.
 #[derive(Debug, Deserialize, Clone, PartialEq, Serialize)]
 pub struct VectorStoreFileContentObject {
@@ -409,6 +516,8 @@ pub enum ComparisonType {
 pub enum AttributeValue {
     String(String),
     Number(i64),
+    /// A number with a fractional part.
+    Float(f64),
     Boolean(bool),
 }
 
@@ -424,6 +533,12 @@ impl From<i64> for AttributeValue {
     }
 }
 
+impl From<f64> for AttributeValue {
+    fn from(value: f64) -> Self {
+        Self::Float(value)
+    }
+}
+
 impl From<bool> for AttributeValue {
     fn from(value: bool) -> Self {
         Self::Boolean(value)
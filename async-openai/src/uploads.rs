@@ -1,18 +1,36 @@
+use futures::TryStreamExt;
+use md5::{Digest, Md5};
+use tokio::io::{AsyncRead, AsyncReadExt};
+
 use crate::{
     config::Config,
     error::OpenAIError,
-    types::{AddUploadPartRequest, CompleteUploadRequest, CreateUploadRequest, Upload, UploadPart},
+    request_options::{impl_request_options, RequestOptions},
+    types::{
+        AddUploadPartRequest, CompleteUploadRequest, CreateUploadRequest, InputSource, Upload,
+        UploadPart, UploadPartStream,
+    },
     Client,
 };
 
+/// Size limit of a Part, 64 MB.
+const MAX_PART_SIZE: usize = 64 * 1024 * 1024;
+
 /// Allows you to upload large files in multiple parts.
 pub struct Uploads<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Uploads);
+crate::util::impl_api_group_debug!(Uploads, "/uploads");
+
 impl<'c, C: Config> Uploads<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Creates an intermediate [Upload](https://platform.openai.com/docs/api-reference/uploads/object) object that
@@ -30,7 +48,9 @@ impl<'c, C: Config> Uploads<'c, C> {
     /// [creating a File](https://platform.openai.com/docs/api-reference/files/create).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn create(&self, request: CreateUploadRequest) -> Result<Upload, OpenAIError> {
-        self.client.post("/uploads", request).await
+        self.client
+            .post("/uploads", request, &self.request_options)
+            .await
     }
 
     /// Adds a [Part](https://platform.openai.com/docs/api-reference/uploads/part-object) to an
@@ -52,10 +72,101 @@ impl<'c, C: Config> Uploads<'c, C> {
         request: AddUploadPartRequest,
     ) -> Result<UploadPart, OpenAIError> {
         self.client
-            .post_form(&format!("/uploads/{upload_id}/parts"), request)
+            .post_form(
+                &format!("/uploads/{upload_id}/parts"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
+    /// Adds a Part whose bytes are sent as they are read from `part`, e.g. a range of an object
+    /// in storage, instead of buffered in memory like with [Uploads::add_part].
+    ///
+    /// The stream being consumed as it is sent, the call is not retried on rate limits or
+    /// server errors, nor sent again once the credentials of the client are refreshed on
+    /// `401 Unauthorized`: these errors are returned as they are.
+    pub async fn add_part_stream(
+        &self,
+        upload_id: &str,
+        part: UploadPartStream,
+    ) -> Result<UploadPart, OpenAIError> {
+        self.client
+            .post_form_once(
+                &format!("/uploads/{upload_id}/parts"),
+                part,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Adds the bytes of `reader` to the Upload as Parts of `part_size` bytes, at most 64 MB,
+    /// sending up to `parallelism` Parts at once.
+    ///
+    /// Returns the request completing the Upload with the IDs of the Parts in order and the
+    /// md5 checksum of the bytes read, for the API to verify the bytes it received.
+    ///
+    /// At most `parallelism` Parts are held in memory.
+    pub async fn add_parts<R>(
+        &self,
+        upload_id: &str,
+        reader: R,
+        part_size: usize,
+        parallelism: usize,
+    ) -> Result<CompleteUploadRequest, OpenAIError>
+    where
+        R: AsyncRead + Unpin + Send,
+    {
+        if part_size == 0 {
+            return Err(OpenAIError::InvalidArgument(
+                "part size must be greater than 0".into(),
+            ));
+        }
+        if part_size > MAX_PART_SIZE {
+            return Err(OpenAIError::InvalidArgument(format!(
+                "part size must be at most {MAX_PART_SIZE} bytes"
+            )));
+        }
+
+        let mut md5 = Md5::new();
+        let parts =
+            futures::stream::try_unfold((reader, &mut md5), |(mut reader, md5)| async move {
+                let mut part = Vec::with_capacity(part_size);
+                (&mut reader)
+                    .take(part_size as u64)
+                    .read_to_end(&mut part)
+                    .await
+                    .map_err(|error| OpenAIError::FileReadError(error.to_string()))?;
+                if part.is_empty() {
+                    return Ok(None);
+                }
+                md5.update(&part);
+                Ok(Some((part, (reader, md5))))
+            });
+
+        let part_ids = parts
+            .map_ok(|part| {
+                self.add_part(
+                    upload_id,
+                    AddUploadPartRequest {
+                        data: InputSource::VecU8 {
+                            filename: "part".into(),
+                            vec: part,
+                        },
+                    },
+                )
+            })
+            .try_buffered(parallelism.max(1))
+            .map_ok(|part| part.id)
+            .try_collect()
+            .await?;
+
+        Ok(CompleteUploadRequest {
+            part_ids,
+            md5: Some(format!("{:x}", md5.finalize())),
+        })
+    }
+
     /// Completes the [Upload](https://platform.openai.com/docs/api-reference/uploads/object).
     ///
     /// Within the returned Upload object, there is a nested [File](https://platform.openai.com/docs/api-reference/files/object)
@@ -73,7 +184,11 @@ impl<'c, C: Config> Uploads<'c, C> {
         request: CompleteUploadRequest,
     ) -> Result<Upload, OpenAIError> {
         self.client
-            .post(&format!("/uploads/{upload_id}/complete"), request)
+            .post(
+                &format!("/uploads/{upload_id}/complete"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -84,7 +199,146 @@ impl<'c, C: Config> Uploads<'c, C> {
             .post(
                 &format!("/uploads/{upload_id}/cancel"),
                 serde_json::json!({}),
+                &self.request_options,
             )
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use md5::{Digest, Md5};
+
+    use super::MAX_PART_SIZE;
+    use crate::{
+        error::OpenAIError,
+        transport::mock::{MockRequest, MockResponse, MockTransport},
+        types::UploadPartStream,
+        Client,
+    };
+
+    /// Bytes of the `data` field of a Part request.
+    fn part_data(request: &MockRequest) -> String {
+        let body = String::from_utf8_lossy(&request.body);
+        let (_, data) = body.split_once("\r\n\r\n").unwrap();
+        data.split("\r\n").next().unwrap().to_string()
+    }
+
+    #[tokio::test]
+    async fn test_add_parts_part_size() {
+        let client = Client::new();
+        for part_size in [0, MAX_PART_SIZE + 1] {
+            let error = client
+                .uploads()
+                .add_parts("upload_abc", &b"bytes"[..], part_size, 1)
+                .await
+                .unwrap_err();
+            assert!(matches!(error, OpenAIError::InvalidArgument(_)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_add_parts() {
+        // names the Parts after their bytes, answering the first one last
+        let transport = MockTransport::new(|request, index| {
+            if request.path().ends_with("/complete") {
+                return Ok(MockResponse::json(serde_json::json!({
+                    "id": "upload_abc", "object": "upload", "bytes": 10, "created_at": 1,
+                    "filename": "data.jsonl", "purpose": "batch", "status": "completed",
+                    "expires_at": 2
+                })));
+            }
+            let response = MockResponse::json(serde_json::json!({
+                "id": format!("part_{}", part_data(request)), "object": "upload.part",
+                "created_at": 1, "upload_id": "upload_abc"
+            }));
+            Ok(match index {
+                0 => response.delay(Duration::from_millis(50)),
+                _ => response,
+            })
+        });
+        let client = Client::new().with_transport(transport.clone());
+
+        let bytes = b"abcdefghij";
+        let request = client
+            .uploads()
+            .add_parts("upload_abc", &bytes[..], 4, 3)
+            .await
+            .unwrap();
+        assert_eq!(request.part_ids, ["part_abcd", "part_efgh", "part_ij"]);
+        assert_eq!(
+            request.md5,
+            Some(format!("{:x}", Md5::new().chain_update(bytes).finalize()))
+        );
+
+        client
+            .uploads()
+            .complete("upload_abc", request)
+            .await
+            .unwrap();
+        let requests = transport.requests();
+        assert_eq!(requests.len(), 4);
+        assert!(requests[..3]
+            .iter()
+            .all(|request| request.path() == "/v1/uploads/upload_abc/parts"));
+        let complete = requests[3].json();
+        assert_eq!(
+            complete["part_ids"],
+            serde_json::json!(["part_abcd", "part_efgh", "part_ij"])
+        );
+        assert_eq!(complete["md5"], "a925576942e94b2ef57a066101b48876");
+    }
+
+    #[tokio::test]
+    async fn test_add_part_stream_not_resent() {
+        let part = || {
+            UploadPartStream::from_stream(futures::stream::iter([Ok::<_, std::io::Error>(
+                bytes::Bytes::from_static(b"abcd"),
+            )]))
+        };
+        let backoff = backoff::ExponentialBackoffBuilder::new()
+            .with_initial_interval(Duration::from_millis(1))
+            .build();
+
+        for (status, body) in [
+            (
+                429,
+                r#"{"error": {"message": "slow down", "type": "requests"}}"#,
+            ),
+            (503, r#"{"error": {"message": "overloaded"}}"#),
+        ] {
+            let transport =
+                MockTransport::new(move |_, _| Ok(MockResponse::new(body).status(status)));
+            let client = Client::new()
+                .with_transport(transport.clone())
+                .with_backoff(backoff.clone());
+            let error = client
+                .uploads()
+                .add_part_stream("upload_abc", part())
+                .await
+                .unwrap_err();
+            assert_eq!(error.status().map(|status| status.as_u16()), Some(status));
+            assert_eq!(transport.len(), 1);
+            assert_eq!(part_data(&transport.requests()[0]), "abcd");
+        }
+
+        // the credentials are refreshed for the next calls, the Part is not sent again
+        let transport = MockTransport::new(|_, _| {
+            Ok(MockResponse::new(r#"{"error": {"message": "expired"}}"#).status(401))
+        });
+        let client = Client::new()
+            .with_transport(transport.clone())
+            .with_credential_provider(crate::credentials::TokenProvider::new(|| async {
+                Ok("token".to_string())
+            }));
+        let error = client
+            .uploads()
+            .add_part_stream("upload_abc", part())
+            .await
+            .unwrap_err();
+        assert!(error.is_auth_error());
+        assert_eq!(transport.len(), 1);
+    }
+}
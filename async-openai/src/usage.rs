@@ -0,0 +1,141 @@
+//! Tokens used by the requests of a [crate::Client] session, per model.
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+/// Tokens used by the responses of a model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ModelUsage {
+    /// Responses reporting their usage.
+    pub requests: u64,
+    pub prompt_tokens: u64,
+    pub completion_tokens: u64,
+    pub total_tokens: u64,
+}
+
+impl ModelUsage {
+    fn add(&mut self, other: &ModelUsage) {
+        self.requests += other.requests;
+        self.prompt_tokens += other.prompt_tokens;
+        self.completion_tokens += other.completion_tokens;
+        self.total_tokens += other.total_tokens;
+    }
+}
+
+/// Snapshot of the usage recorded by a [UsageRecorder].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UsageStats {
+    /// Usage of each model, by the model name of the responses, e.g. `gpt-4o-2024-08-06`.
+    pub models: HashMap<String, ModelUsage>,
+}
+
+impl UsageStats {
+    pub fn model(&self, model: &str) -> Option<&ModelUsage> {
+        self.models.get(model)
+    }
+
+    /// Usage of all the models.
+    pub fn total(&self) -> ModelUsage {
+        let mut total = ModelUsage::default();
+        for usage in self.models.values() {
+            total.add(usage);
+        }
+        total
+    }
+}
+
+/// Accumulates the usage reported by the responses of a client, streamed responses included:
+/// chat completion streams report it with `stream_options.include_usage`.
+///
+/// Clones share the same usage, so that a recorder can be shared by several clients.
+///
+/// ```
+/// use async_openai::{usage::UsageRecorder, Client};
+///
+/// let client = Client::new().with_usage_recorder(UsageRecorder::new());
+/// // ... send requests, then:
+/// let stats = client.usage_stats().unwrap();
+/// println!("{} tokens", stats.total().total_tokens);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UsageRecorder(Arc<Mutex<HashMap<String, ModelUsage>>>);
+
+impl UsageRecorder {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Count a response of `model` and the tokens it used.
+    pub fn record(
+        &self,
+        model: &str,
+        prompt_tokens: u64,
+        completion_tokens: u64,
+        total_tokens: u64,
+    ) {
+        let usage = ModelUsage {
+            requests: 1,
+            prompt_tokens,
+            completion_tokens,
+            total_tokens,
+        };
+        self.lock()
+            .entry(model.to_string())
+            .or_default()
+            .add(&usage);
+    }
+
+    pub fn snapshot(&self) -> UsageStats {
+        UsageStats {
+            models: self.lock().clone(),
+        }
+    }
+
+    /// Forget the usage recorded so far.
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, ModelUsage>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::observability::Observation;
+
+    #[test]
+    fn test_usage_of_responses() {
+        let recorder = UsageRecorder::new();
+        let mut observation = Observation::new().with_usage(Some(recorder.clone()));
+        observation.response(
+            br#"{"id":"chatcmpl_1","model":"gpt-4o","choices":[],"usage":{"prompt_tokens":5,"completion_tokens":7,"total_tokens":12}}"#,
+        );
+        // streamed chunks without usage are not counted
+        observation.event(r#"{"id":"chatcmpl_2","model":"gpt-4o","choices":[]}"#);
+        observation.event(
+            r#"{"type":"response.completed","response":{"id":"resp_1","model":"gpt-4o-mini","usage":{"input_tokens":3,"output_tokens":4,"total_tokens":7}}}"#,
+        );
+
+        let stats = recorder.snapshot();
+        assert_eq!(
+            stats.model("gpt-4o"),
+            Some(&ModelUsage {
+                requests: 1,
+                prompt_tokens: 5,
+                completion_tokens: 7,
+                total_tokens: 12,
+            })
+        );
+        assert_eq!(stats.total().total_tokens, 19);
+        assert_eq!(stats.total().requests, 2);
+
+        recorder.reset();
+        assert!(recorder.snapshot().models.is_empty());
+    }
+}
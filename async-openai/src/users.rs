@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{User, UserDeleteResponse, UserListResponse, UserRoleUpdateRequest},
     Client,
 };
@@ -10,21 +11,30 @@ use crate::{
 /// Manage users and their role in an organization. Users will be automatically added to the Default project.
 pub struct Users<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(Users);
+crate::util::impl_api_group_debug!(Users, "/organization/users");
+
 impl<'c, C: Config> Users<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// Lists all of the users in the organization.
+    ///
+    /// `query` is usually a [ListUsersQuery](crate::types::ListUsersQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<UserListResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
         self.client
-            .get_with_query("/organization/users", &query)
+            .get_with_query("/organization/users", &query, &self.request_options)
             .await
     }
 
@@ -36,7 +46,11 @@ impl<'c, C: Config> Users<'c, C> {
         request: UserRoleUpdateRequest,
     ) -> Result<User, OpenAIError> {
         self.client
-            .post(format!("/organization/users/{user_id}").as_str(), request)
+            .post(
+                format!("/organization/users/{user_id}").as_str(),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -44,7 +58,10 @@ impl<'c, C: Config> Users<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, user_id: &str) -> Result<User, OpenAIError> {
         self.client
-            .get(format!("/organization/users/{user_id}").as_str())
+            .get(
+                format!("/organization/users/{user_id}").as_str(),
+                &self.request_options,
+            )
             .await
     }
 
@@ -52,7 +69,10 @@ impl<'c, C: Config> Users<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(&self, user_id: &str) -> Result<UserDeleteResponse, OpenAIError> {
         self.client
-            .delete(format!("/organizations/users/{user_id}").as_str())
+            .delete(
+                format!("/organizations/users/{user_id}").as_str(),
+                &self.request_options,
+            )
             .await
     }
 }
@@ -1,12 +1,68 @@
-use std::path::Path;
+use std::{
+    future::Future,
+    path::Path,
+    sync::{Arc, Mutex},
+};
 
 use reqwest::Body;
 use tokio::fs::File;
 use tokio_util::codec::{BytesCodec, FramedRead};
 
-use crate::error::OpenAIError;
+use crate::error::{OpenAIError, UploadedFile};
 use crate::types::InputSource;
 
+tokio::task_local! {
+    static UPLOADED_FILES: UploadedFiles;
+}
+
+/// Files of the parts created by [create_file_part] while building a multipart form.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct UploadedFiles(Arc<Mutex<Vec<UploadedFile>>>);
+
+impl UploadedFiles {
+    /// Build a form with `form`, recording its files instead of the ones of a previous attempt.
+    pub(crate) async fn record<T, F: Future<Output = T>>(&self, form: F) -> T {
+        self.lock().clear();
+        UPLOADED_FILES.scope(self.clone(), form).await
+    }
+
+    pub(crate) fn files(&self) -> Vec<UploadedFile> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<UploadedFile>> {
+        self.0
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+/// MIME type of the formats accepted by the upload endpoints, from the extension of `file_name`.
+pub(crate) fn mime_type(file_name: &str) -> Option<&'static str> {
+    let (_, extension) = file_name.rsplit_once('.')?;
+    let mime_type = match extension.to_ascii_lowercase().as_str() {
+        "flac" => "audio/flac",
+        "m4a" => "audio/mp4",
+        "mp3" | "mpga" | "mpeg" => "audio/mpeg",
+        "mp4" => "video/mp4",
+        "ogg" => "audio/ogg",
+        "wav" => "audio/wav",
+        "webm" => "audio/webm",
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "gif" => "image/gif",
+        "pdf" => "application/pdf",
+        "json" => "application/json",
+        "csv" => "text/csv",
+        "md" => "text/markdown",
+        "html" => "text/html",
+        "txt" => "text/plain",
+        _ => return None,
+    };
+    Some(mime_type)
+}
+
 pub(crate) async fn file_stream_body(source: InputSource) -> Result<Body, OpenAIError> {
     let body = match source {
         InputSource::Path { path } => {
@@ -29,7 +85,7 @@ pub(crate) async fn file_stream_body(source: InputSource) -> Result<Body, OpenAI
 pub(crate) async fn create_file_part(
     source: InputSource,
 ) -> Result<reqwest::multipart::Part, OpenAIError> {
-    let (stream, file_name) = match source {
+    let (stream, file_name, size) = match source {
         InputSource::Path { path } => {
             let file_name = path
                 .file_name()
@@ -43,16 +99,43 @@ pub(crate) async fn create_file_part(
                 .unwrap()
                 .to_string();
 
+            let size = tokio::fs::metadata(&path)
+                .await
+                .ok()
+                .map(|metadata| metadata.len());
             (
                 file_stream_body(InputSource::Path { path }).await?,
                 file_name,
+                size,
             )
         }
-        InputSource::Bytes { filename, bytes } => (Body::from(bytes), filename),
-        InputSource::VecU8 { filename, vec } => (Body::from(vec), filename),
+        InputSource::Bytes { filename, bytes } => {
+            let size = bytes.len() as u64;
+            (Body::from(bytes), filename, Some(size))
+        }
+        InputSource::VecU8 { filename, vec } => {
+            let size = vec.len() as u64;
+            (Body::from(vec), filename, Some(size))
+        }
     };
 
-    let file_part = reqwest::multipart::Part::stream(stream).file_name(file_name);
+    let content_type = mime_type(&file_name);
+    let _ = UPLOADED_FILES.try_with(|files| {
+        files.lock().push(UploadedFile {
+            file_name: file_name.clone(),
+            content_type: content_type.map(str::to_string),
+            size,
+        })
+    });
+
+    let mut file_part = match size {
+        Some(size) => reqwest::multipart::Part::stream_with_length(stream, size),
+        None => reqwest::multipart::Part::stream(stream),
+    }
+    .file_name(file_name);
+    if let Some(content_type) = content_type {
+        file_part = file_part.mime_str(content_type)?;
+    }
 
     Ok(file_part)
 }
@@ -70,3 +153,22 @@ pub(crate) fn create_all_dir<P: AsRef<Path>>(dir: P) -> Result<(), OpenAIError>
 
     Ok(())
 }
+
+/// Macro to implement `Debug` on an API group, showing the URL of its endpoint
+/// instead of the whole client. `$field`s are displayed and substituted in `$path`.
+macro_rules! impl_api_group_debug {
+    ($group:ident, $path:literal $(, $field:ident)*) => {
+        impl<'c, C: $crate::config::Config> std::fmt::Debug for $group<'c, C> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                let path = format!($path $(, self.$field)*);
+                f.debug_struct(stringify!($group))
+                    .field("url", &self.client.config().url(&path))
+                    $(.field(stringify!($field), &self.$field))*
+                    .field("request_options", &self.request_options)
+                    .finish()
+            }
+        }
+    };
+}
+
+pub(crate) use impl_api_group_debug;
@@ -3,6 +3,8 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    poll::{poll_until_done, PollOptions},
+    request_options::{impl_request_options, RequestOptions},
     types::{
         CreateVectorStoreFileBatchRequest, ListVectorStoreFilesResponse, VectorStoreFileBatchObject,
     },
@@ -15,13 +17,22 @@ use crate::{
 pub struct VectorStoreFileBatches<'c, C: Config> {
     client: &'c Client<C>,
     pub vector_store_id: String,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(VectorStoreFileBatches);
+crate::util::impl_api_group_debug!(
+    VectorStoreFileBatches,
+    "/vector_stores/{}/file_batches",
+    vector_store_id
+);
+
 impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
     pub fn new(client: &'c Client<C>, vector_store_id: &str) -> Self {
         Self {
             client,
             vector_store_id: vector_store_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
@@ -35,6 +46,7 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
             .post(
                 &format!("/vector_stores/{}/file_batches", &self.vector_store_id),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -46,13 +58,25 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
         batch_id: &str,
     ) -> Result<VectorStoreFileBatchObject, OpenAIError> {
         self.client
-            .get(&format!(
-                "/vector_stores/{}/file_batches/{batch_id}",
-                &self.vector_store_id
-            ))
+            .get(
+                &format!(
+                    "/vector_stores/{}/file_batches/{batch_id}",
+                    &self.vector_store_id
+                ),
+                &self.request_options,
+            )
             .await
     }
 
+    /// Polls a vector store file batch until all of its files are processed, or the batch is `cancelled` or `failed`.
+    pub async fn wait(
+        &self,
+        batch_id: &str,
+        options: &PollOptions,
+    ) -> Result<VectorStoreFileBatchObject, OpenAIError> {
+        poll_until_done(options, || self.retrieve(batch_id)).await
+    }
+
     /// Cancel a vector store file batch. This attempts to cancel the processing of files in this batch as soon as possible.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn cancel(&self, batch_id: &str) -> Result<VectorStoreFileBatchObject, OpenAIError> {
@@ -63,11 +87,14 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
                     &self.vector_store_id
                 ),
                 serde_json::json!({}),
+                &self.request_options,
             )
             .await
     }
 
     /// Returns a list of vector store files in a batch.
+    ///
+    /// `query` is usually a [ListVectorStoreFilesQuery](crate::types::ListVectorStoreFilesQuery).
     #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(
         &self,
@@ -84,6 +111,7 @@ impl<'c, C: Config> VectorStoreFileBatches<'c, C> {
                     &self.vector_store_id
                 ),
                 &query,
+                &self.request_options,
             )
             .await
     }
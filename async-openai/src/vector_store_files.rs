@@ -3,11 +3,14 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    poll::{poll_until_done, PollOptions},
+    request_options::{impl_request_options, RequestOptions},
     types::{
-        CreateVectorStoreFileRequest, DeleteVectorStoreFileResponse, ListVectorStoreFilesResponse,
+        CreateVectorStoreFileRequest, DeleteVectorStoreFileResponse, FilePurpose, FileReference,
+        ListVectorStoreFilesResponse, UpdateVectorStoreFileAttributesRequest,
         VectorStoreFileContentResponse, VectorStoreFileObject,
     },
-    Client,
+    Client, Files,
 };
 
 /// Vector store files represent files inside a vector store.
@@ -16,13 +19,18 @@ use crate::{
 pub struct VectorStoreFiles<'c, C: Config> {
     client: &'c Client<C>,
     pub vector_store_id: String,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(VectorStoreFiles);
+crate::util::impl_api_group_debug!(VectorStoreFiles, "/vector_stores/{}/files", vector_store_id);
+
 impl<'c, C: Config> VectorStoreFiles<'c, C> {
     pub fn new(client: &'c Client<C>, vector_store_id: &str) -> Self {
         Self {
             client,
             vector_store_id: vector_store_id.into(),
+            request_options: RequestOptions::new(),
         }
     }
 
@@ -36,6 +44,7 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
             .post(
                 &format!("/vector_stores/{}/files", &self.vector_store_id),
                 request,
+                &self.request_options,
             )
             .await
     }
@@ -44,13 +53,53 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, file_id: &str) -> Result<VectorStoreFileObject, OpenAIError> {
         self.client
-            .get(&format!(
-                "/vector_stores/{}/files/{file_id}",
-                &self.vector_store_id
-            ))
+            .get(
+                &format!("/vector_stores/{}/files/{file_id}", &self.vector_store_id),
+                &self.request_options,
+            )
             .await
     }
 
+    /// Update the attributes of a vector store file, to filter searches with.
+    #[crate::byot(T0 = std::fmt::Display, T1 = serde::Serialize, R = serde::de::DeserializeOwned)]
+    pub async fn update(
+        &self,
+        file_id: &str,
+        request: UpdateVectorStoreFileAttributesRequest,
+    ) -> Result<VectorStoreFileObject, OpenAIError> {
+        self.client
+            .post(
+                &format!("/vector_stores/{}/files/{file_id}", &self.vector_store_id),
+                request,
+                &self.request_options,
+            )
+            .await
+    }
+
+    /// Attach `file` to the vector store, uploading it first unless it is a file ID.
+    pub async fn create_from<F: Into<FileReference>>(
+        &self,
+        file: F,
+    ) -> Result<VectorStoreFileObject, OpenAIError> {
+        let file_id = Files::new(self.client)
+            .resolve(file, FilePurpose::Assistants)
+            .await?;
+        self.create(CreateVectorStoreFileRequest {
+            file_id,
+            ..Default::default()
+        })
+        .await
+    }
+
+    /// Polls an uploaded vector store file until its processing is `completed`, `cancelled` or `failed`.
+    pub async fn wait(
+        &self,
+        file_id: &str,
+        options: &PollOptions,
+    ) -> Result<VectorStoreFileObject, OpenAIError> {
+        poll_until_done(options, || self.retrieve(file_id)).await
+    }
+
     /// Delete a vector store file. This will remove the file from the vector store but the file itself will not be deleted. To delete the file, use the [delete file](https://platform.openai.com/docs/api-reference/files/delete) endpoint.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn delete(
@@ -58,14 +107,16 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
         file_id: &str,
     ) -> Result<DeleteVectorStoreFileResponse, OpenAIError> {
         self.client
-            .delete(&format!(
-                "/vector_stores/{}/files/{file_id}",
-                &self.vector_store_id
-            ))
+            .delete(
+                &format!("/vector_stores/{}/files/{file_id}", &self.vector_store_id),
+                &self.request_options,
+            )
             .await
     }
 
     /// Returns a list of vector store files.
+    ///
+    /// `query` is usually a [ListVectorStoreFilesQuery](crate::types::ListVectorStoreFilesQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListVectorStoreFilesResponse, OpenAIError>
     where
@@ -75,6 +126,7 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
             .get_with_query(
                 &format!("/vector_stores/{}/files", &self.vector_store_id),
                 &query,
+                &self.request_options,
             )
             .await
     }
@@ -86,10 +138,13 @@ impl<'c, C: Config> VectorStoreFiles<'c, C> {
         file_id: &str,
     ) -> Result<VectorStoreFileContentResponse, OpenAIError> {
         self.client
-            .get(&format!(
-                "/vector_stores/{}/files/{file_id}/content",
-                &self.vector_store_id
-            ))
+            .get(
+                &format!(
+                    "/vector_stores/{}/files/{file_id}/content",
+                    &self.vector_store_id
+                ),
+                &self.request_options,
+            )
             .await
     }
 }
@@ -3,6 +3,7 @@ use serde::Serialize;
 use crate::{
     config::Config,
     error::OpenAIError,
+    request_options::{impl_request_options, RequestOptions},
     types::{
         CreateVectorStoreRequest, DeleteVectorStoreResponse, ListVectorStoresResponse,
         UpdateVectorStoreRequest, VectorStoreObject, VectorStoreSearchRequest,
@@ -14,21 +15,30 @@ use crate::{
 
 pub struct VectorStores<'c, C: Config> {
     client: &'c Client<C>,
+    request_options: RequestOptions,
 }
 
+impl_request_options!(VectorStores);
+crate::util::impl_api_group_debug!(VectorStores, "/vector_stores");
+
 impl<'c, C: Config> VectorStores<'c, C> {
     pub fn new(client: &'c Client<C>) -> Self {
-        Self { client }
+        Self {
+            client,
+            request_options: RequestOptions::new(),
+        }
     }
 
     /// [VectorStoreFiles] API group
     pub fn files(&self, vector_store_id: &str) -> VectorStoreFiles<C> {
         VectorStoreFiles::new(self.client, vector_store_id)
+            .with_options(self.request_options.clone())
     }
 
     /// [VectorStoreFileBatches] API group
     pub fn file_batches(&self, vector_store_id: &str) -> VectorStoreFileBatches<C> {
         VectorStoreFileBatches::new(self.client, vector_store_id)
+            .with_options(self.request_options.clone())
     }
 
     /// Create a vector store.
@@ -37,24 +47,33 @@ impl<'c, C: Config> VectorStores<'c, C> {
         &self,
         request: CreateVectorStoreRequest,
     ) -> Result<VectorStoreObject, OpenAIError> {
-        self.client.post("/vector_stores", request).await
+        self.client
+            .post("/vector_stores", request, &self.request_options)
+            .await
     }
 
     /// Retrieves a vector store.
     #[crate::byot(T0 = std::fmt::Display, R = serde::de::DeserializeOwned)]
     pub async fn retrieve(&self, vector_store_id: &str) -> Result<VectorStoreObject, OpenAIError> {
         self.client
-            .get(&format!("/vector_stores/{vector_store_id}"))
+            .get(
+                &format!("/vector_stores/{vector_store_id}"),
+                &self.request_options,
+            )
             .await
     }
 
     /// Returns a list of vector stores.
+    ///
+    /// `query` is usually a [ListVectorStoresQuery](crate::types::ListVectorStoresQuery).
     #[crate::byot(T0 = serde::Serialize, R = serde::de::DeserializeOwned)]
     pub async fn list<Q>(&self, query: &Q) -> Result<ListVectorStoresResponse, OpenAIError>
     where
         Q: Serialize + ?Sized,
     {
-        self.client.get_with_query("/vector_stores", &query).await
+        self.client
+            .get_with_query("/vector_stores", &query, &self.request_options)
+            .await
     }
 
     /// Delete a vector store.
@@ -64,7 +83,10 @@ impl<'c, C: Config> VectorStores<'c, C> {
         vector_store_id: &str,
     ) -> Result<DeleteVectorStoreResponse, OpenAIError> {
         self.client
-            .delete(&format!("/vector_stores/{vector_store_id}"))
+            .delete(
+                &format!("/vector_stores/{vector_store_id}"),
+                &self.request_options,
+            )
             .await
     }
 
@@ -76,7 +98,11 @@ impl<'c, C: Config> VectorStores<'c, C> {
         request: UpdateVectorStoreRequest,
     ) -> Result<VectorStoreObject, OpenAIError> {
         self.client
-            .post(&format!("/vector_stores/{vector_store_id}"), request)
+            .post(
+                &format!("/vector_stores/{vector_store_id}"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 
@@ -88,7 +114,11 @@ impl<'c, C: Config> VectorStores<'c, C> {
         request: VectorStoreSearchRequest,
     ) -> Result<VectorStoreSearchResultsPage, OpenAIError> {
         self.client
-            .post(&format!("/vector_stores/{vector_store_id}/search"), request)
+            .post(
+                &format!("/vector_stores/{vector_store_id}/search"),
+                request,
+                &self.request_options,
+            )
             .await
     }
 }
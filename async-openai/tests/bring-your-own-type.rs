@@ -266,6 +266,11 @@ async fn test_byot_vector_store_files() {
         .files("vector_store_id")
         .retrieve_byot("file_id")
         .await;
+    let _r: Result<Value, OpenAIError> = client
+        .vector_stores()
+        .files("vector_store_id")
+        .update_byot("file_id", json!({}))
+        .await;
     let _r: Result<Value, OpenAIError> = client
         .vector_stores()
         .files("vector_store_id")
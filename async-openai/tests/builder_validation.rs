@@ -0,0 +1,228 @@
+use async_openai::{
+    error::OpenAIError,
+    types::{
+        ChatCompletionMessageToolCall, ChatCompletionRequestAssistantMessage,
+        ChatCompletionRequestMessage, ChatCompletionRequestUserMessageArgs,
+        ChatCompletionStreamOptions, CreateChatCompletionRequestArgs, CreateCompletionRequestArgs,
+        ResponseFormat, ResponseFormatJsonSchema,
+    },
+};
+
+#[test]
+fn chat_tool_choice_requires_tools() {
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .tool_choice("get_weather")
+        .build();
+
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn chat_json_schema_requires_valid_name() {
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .response_format(ResponseFormat::JsonSchema {
+            json_schema: ResponseFormatJsonSchema {
+                description: None,
+                name: "has spaces".into(),
+                schema: None,
+                strict: None,
+            },
+        })
+        .build();
+
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn chat_stream_options_require_stream() {
+    let stream_options = ChatCompletionStreamOptions {
+        include_usage: true,
+    };
+
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream(false)
+        .stream_options(stream_options)
+        .build();
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+
+    // stream is set by create_stream when left unset
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .stream_options(stream_options)
+        .build();
+    assert!(result.is_ok());
+}
+
+#[test]
+fn completion_best_of_cannot_be_streamed() {
+    let result = CreateCompletionRequestArgs::default()
+        .model("gpt-3.5-turbo-instruct")
+        .prompt("Say this is a test")
+        .best_of(2)
+        .stream(true)
+        .build();
+
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[tokio::test]
+async fn image_edit_accepts_multiple_in_memory_images() {
+    use async_openai::types::{CreateImageEditRequestArgs, ImageEditInput, ImageInput, ImageModel};
+
+    let image = ImageInput::from_reader("lamp.png".into(), &b"png"[..])
+        .await
+        .unwrap();
+    let mask = ImageInput::from_bytes("mask.png".into(), bytes::Bytes::from_static(b"png"));
+
+    let request = CreateImageEditRequestArgs::default()
+        .image([image.clone(), image.clone()])
+        .mask(mask)
+        .prompt("a lamp")
+        .model(ImageModel::GptImage1)
+        .build()
+        .unwrap();
+    assert!(matches!(request.image, ImageEditInput::Images(ref images) if images.len() == 2));
+
+    let result = CreateImageEditRequestArgs::default()
+        .image([image.clone(), image])
+        .prompt("a lamp")
+        .model(ImageModel::DallE2)
+        .build();
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn chat_prediction_rejects_multiple_choices() {
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Rename the variable")
+            .build()
+            .unwrap()
+            .into()])
+        .prediction("fn main() {}")
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["prediction"],
+        serde_json::json!({"type": "content", "content": "fn main() {}"})
+    );
+
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .prediction("fn main() {}")
+        .n(2)
+        .build();
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn chat_tool_exchange_replay() {
+    let call =
+        ChatCompletionMessageToolCall::function("call_1", "get_weather", r#"{"city":"Paris"}"#);
+    let messages: Vec<ChatCompletionRequestMessage> = vec![
+        ChatCompletionRequestUserMessageArgs::default()
+            .content("Weather in Paris?")
+            .build()
+            .unwrap()
+            .into(),
+        ChatCompletionRequestAssistantMessage::from(vec![call.clone()]).into(),
+        call.respond("sunny").into(),
+    ];
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages(messages.clone())
+        .build()
+        .unwrap();
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(
+        json["messages"][1],
+        serde_json::json!({
+            "role": "assistant",
+            "tool_calls": [{
+                "id": "call_1",
+                "type": "function",
+                "function": {"name": "get_weather", "arguments": "{\"city\":\"Paris\"}"}
+            }]
+        })
+    );
+    assert_eq!(
+        json["messages"][2],
+        serde_json::json!({"role": "tool", "content": "sunny", "tool_call_id": "call_1"})
+    );
+
+    // a tool message without the assistant message holding its call
+    let result = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([messages[0].clone(), messages[2].clone()])
+        .build();
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+}
+
+#[test]
+fn chat_context_budget_and_ranking() {
+    use async_openai::types::{ContextDocument, ContextPlacement, ContextStrategy};
+
+    let mut request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Question?")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap();
+    let documents = [
+        ContextDocument::new("far").with_embedding(vec![0.0, 1.0]),
+        ContextDocument::new("x".repeat(400)).with_embedding(vec![1.0, 0.1]),
+        ContextDocument::new("near").with_embedding(vec![1.0, 0.0]),
+    ];
+    let strategy = ContextStrategy::new()
+        .with_max_tokens(40)
+        .with_placement(ContextPlacement::LastUserMessage)
+        .with_query_embedding(vec![1.0, 0.0]);
+
+    // the second document does not fit in the budget
+    assert_eq!(
+        strategy.apply(&mut request, &documents).unwrap(),
+        vec![2, 0]
+    );
+    let content = &serde_json::to_value(&request).unwrap()["messages"][0]["content"];
+    assert!(content
+        .as_str()
+        .unwrap()
+        .ends_with("[1] near\n\n[2] far\n\nQuestion?"));
+}
+
+#[test]
+fn image_variations_require_dall_e_2() {
+    use async_openai::types::{CreateImageVariationRequestArgs, ImageModel};
+
+    let result = CreateImageVariationRequestArgs::default()
+        .image("image.png")
+        .model(ImageModel::GptImage1)
+        .build();
+    assert!(
+        matches!(result, Err(OpenAIError::InvalidArgument(message)) if message.contains("create_edit"))
+    );
+
+    let result = CreateImageVariationRequestArgs::default()
+        .image("image.png")
+        .n(11)
+        .build();
+    assert!(matches!(result, Err(OpenAIError::InvalidArgument(_))));
+
+    assert!(CreateImageVariationRequestArgs::default()
+        .image("image.png")
+        .model(ImageModel::DallE2)
+        .build()
+        .is_ok());
+}
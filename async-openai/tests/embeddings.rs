@@ -44,3 +44,44 @@ fn create_embedding_input() {
     let _ = embedding_input(&input);
     let _ = embedding_input(input);
 }
+
+#[test]
+fn decode_base64_embedding() {
+    use async_openai::{error::OpenAIError, types::Base64EmbeddingVector};
+
+    // 1.0 and -2.5 as little-endian f32
+    let vector = Base64EmbeddingVector("AACAPwAAIMA=".into());
+    assert_eq!(vector.decode().unwrap(), vec![1.0, -2.5]);
+    assert_eq!(vector.decode_f64().unwrap(), vec![1.0, -2.5]);
+
+    // invalid responses, not invalid arguments
+    for invalid in ["AACA", "not base64!"] {
+        assert!(matches!(
+            Base64EmbeddingVector(invalid.into()).decode(),
+            Err(OpenAIError::JSONDeserialize(..))
+        ));
+    }
+}
+
+#[test]
+fn chunk_embedding_requests() {
+    use async_openai::types::EmbeddingChunkOptions;
+
+    let input: Vec<String> = (0..5).map(|i| format!("input {i}")).collect();
+    let requests = EmbeddingChunkOptions::new()
+        .with_max_inputs(2)
+        .chunk_requests("text-embedding-3-small", input.into());
+    assert_eq!(requests.len(), 3);
+    assert_eq!(
+        requests[2].input,
+        EmbeddingInput::StringArray(vec!["input 4".into()])
+    );
+
+    let requests = EmbeddingChunkOptions::new()
+        .with_max_tokens(5)
+        .chunk_requests(
+            "text-embedding-3-small",
+            vec![vec![1, 2, 3], vec![4, 5, 6], vec![7]].into(),
+        );
+    assert_eq!(requests.len(), 2);
+}
@@ -0,0 +1,14 @@
+use async_openai::examples::{generate, ApiGroup};
+
+#[tokio::test]
+async fn every_group_builds_serializable_requests() {
+    for group in ApiGroup::ALL {
+        let examples = generate(*group).await.unwrap();
+        assert!(!examples.is_empty(), "{group:?} has no examples");
+        for example in examples {
+            if let Some(body) = example.body {
+                assert!(body.is_object(), "{} is not a JSON object", example.path);
+            }
+        }
+    }
+}
@@ -26,3 +26,684 @@ async fn chat_types_serde() {
     let deserialized: CreateChatCompletionRequest = serde_json::from_str(&serialized).unwrap();
     assert_eq!(request, deserialized);
 }
+
+#[cfg(feature = "extra-fields")]
+#[test]
+fn chat_response_keeps_extra_fields() {
+    use async_openai::types::CreateChatCompletionResponse;
+
+    let json = serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o",
+        "provider": "openrouter",
+        "choices": [{
+            "index": 0,
+            "message": {"role": "assistant", "content": "hi", "reasoning": "short"},
+            "finish_reason": "stop"
+        }]
+    });
+    let response: CreateChatCompletionResponse = serde_json::from_value(json.clone()).unwrap();
+    assert_eq!(response.extra["provider"], "openrouter");
+    assert_eq!(response.choices[0].message.extra["reasoning"], "short");
+    assert_eq!(
+        serde_json::to_value(&response).unwrap()["provider"],
+        "openrouter"
+    );
+}
+
+#[test]
+fn stream_metadata_accumulates_chunks() {
+    use async_openai::types::{CreateChatCompletionStreamResponse, StreamMetadata};
+
+    let chunks: Vec<CreateChatCompletionStreamResponse> = [
+        r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[]}"#,
+        r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o","system_fingerprint":"fp_1","choices":[],"obfuscation":"a1b2"}"#,
+        r#"{"id":"chatcmpl-1","object":"chat.completion.chunk","created":1,"model":"gpt-4o","choices":[],"usage":{"prompt_tokens":1,"completion_tokens":2,"total_tokens":3}}"#,
+    ]
+    .iter()
+    .map(|chunk| serde_json::from_str(chunk).unwrap())
+    .collect();
+
+    let metadata = StreamMetadata::from_chunks(&chunks);
+    assert_eq!(metadata.id, "chatcmpl-1");
+    assert_eq!(metadata.system_fingerprint.as_deref(), Some("fp_1"));
+    assert_eq!(metadata.usage.unwrap().total_tokens, 3);
+    assert_eq!(chunks[1].obfuscation.as_deref(), Some("a1b2"));
+}
+
+#[test]
+fn project_api_key_owner() {
+    use async_openai::types::{ProjectApiKey, ProjectApiKeyOwnerType};
+
+    let key: ProjectApiKey = serde_json::from_value(serde_json::json!({
+        "object": "organization.project.api_key",
+        "redacted_value": "sk-abc...def",
+        "name": "My API Key",
+        "created_at": 1711471533,
+        "id": "key_abc",
+        "owner": {
+            "type": "service_account",
+            "service_account": {
+                "object": "organization.project.service_account",
+                "id": "svc_acct_abc",
+                "name": "Service Account",
+                "role": "owner",
+                "created_at": 1711471533
+            }
+        }
+    }))
+    .unwrap();
+    assert_eq!(key.redacted_value.as_str(), "sk-abc...def");
+    assert_eq!(key.owner.r#type(), ProjectApiKeyOwnerType::ServiceAccount);
+}
+
+#[test]
+fn fine_tuning_reinforcement_method() {
+    use async_openai::types::{
+        FineTuneMethod, FineTuneReinforcementMethod, Grader, ReinforcementHyperparameters,
+        StringCheckGrader, StringCheckOperation,
+    };
+
+    let method = FineTuneMethod::Reinforcement {
+        reinforcement: FineTuneReinforcementMethod {
+            grader: Grader::StringCheck(StringCheckGrader {
+                name: "exact".into(),
+                input: "{{sample.output_text}}".into(),
+                reference: "{{item.answer}}".into(),
+                operation: StringCheckOperation::Eq,
+            }),
+            hyperparameters: ReinforcementHyperparameters::default(),
+        },
+    };
+    let value = serde_json::to_value(&method).unwrap();
+    assert_eq!(value["type"], "reinforcement");
+    assert_eq!(value["reinforcement"]["grader"]["type"], "string_check");
+    assert_eq!(
+        serde_json::from_value::<FineTuneMethod>(value).unwrap(),
+        method
+    );
+}
+
+#[cfg(feature = "chrono")]
+#[test]
+fn batch_timestamps_as_datetime() {
+    use async_openai::types::Batch;
+    use chrono::{TimeZone, Utc};
+
+    let json = serde_json::json!({
+        "id": "batch_1",
+        "object": "batch",
+        "endpoint": "/v1/chat/completions",
+        "input_file_id": "file-1",
+        "completion_window": "24h",
+        "status": "in_progress",
+        "created_at": 1714508499,
+        "in_progress_at": null,
+        "expires_at": 1714594899
+    });
+    let batch: Batch = serde_json::from_value(json).unwrap();
+    assert_eq!(batch.created_at, Utc.timestamp_opt(1714508499, 0).unwrap());
+    assert_eq!(batch.in_progress_at, None);
+    assert_eq!(batch.completed_at, None);
+    assert_eq!(
+        batch.expires_at,
+        Some(Utc.timestamp_opt(1714594899, 0).unwrap())
+    );
+
+    let value = serde_json::to_value(&batch).unwrap();
+    assert_eq!(value["created_at"], 1714508499);
+    assert_eq!(value["expires_at"], 1714594899);
+}
+
+#[test]
+fn image_gen_stream_event() {
+    use async_openai::types::{ImageGenStreamEvent, ImageOutputFormat, ImageSize};
+
+    let json = serde_json::json!({
+        "type": "image_generation.partial_image",
+        "b64_json": "aGVsbG8=",
+        "created_at": 1620000000,
+        "size": "1024x1536",
+        "quality": "high",
+        "background": "transparent",
+        "output_format": "webp",
+        "partial_image_index": 0
+    });
+    let event: ImageGenStreamEvent = serde_json::from_value(json).unwrap();
+    let ImageGenStreamEvent::PartialImage(partial) = &event else {
+        panic!("expected partial image event");
+    };
+    assert_eq!(partial.size, ImageSize::S1024x1536);
+    assert_eq!(partial.output_format, ImageOutputFormat::Webp);
+    assert_eq!(event.bytes().unwrap().as_ref(), b"hello");
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn costs_amounts_as_decimal() {
+    use async_openai::types::CostsResponse;
+    use rust_decimal::Decimal;
+
+    let json = serde_json::json!({
+        "object": "page",
+        "data": [{
+            "object": "bucket",
+            "start_time": 1730419200,
+            "end_time": 1730505600,
+            "results": [
+                {"object": "organization.costs.result", "amount": {"value": 0.1, "currency": "usd"}, "line_item": null, "project_id": null},
+                {"object": "organization.costs.result", "amount": {"value": 0.2, "currency": "usd"}, "line_item": null, "project_id": null}
+            ]
+        }],
+        "has_more": false,
+        "next_page": null
+    });
+    let costs: CostsResponse = serde_json::from_value(json).unwrap();
+    assert_eq!(costs.data[0].total("usd"), Decimal::new(3, 1));
+    assert_eq!(
+        serde_json::to_value(&costs.data[0].results[0].amount).unwrap()["value"],
+        0.1
+    );
+}
+
+#[test]
+fn chat_audio_output_and_input() {
+    use async_openai::types::{
+        ChatCompletionRequestUserMessageContentPart, CreateChatCompletionStreamResponse,
+        InputAudio, InputAudioFormat,
+    };
+
+    let chunk: CreateChatCompletionStreamResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion.chunk",
+        "created": 0,
+        "model": "gpt-4o-audio-preview",
+        "choices": [{
+            "index": 0,
+            "delta": {"audio": {"id": "audio_1", "data": "aGVsbG8=", "transcript": "hel"}},
+            "finish_reason": null
+        }]
+    }))
+    .unwrap();
+    let audio = chunk.choices[0].delta.audio.as_ref().unwrap();
+    assert_eq!(audio.id.as_deref(), Some("audio_1"));
+    assert_eq!(audio.bytes().unwrap().as_ref(), b"hello");
+
+    let part: ChatCompletionRequestUserMessageContentPart =
+        InputAudio::from_bytes(b"hello", InputAudioFormat::Wav).into();
+    assert_eq!(
+        serde_json::to_value(part).unwrap(),
+        serde_json::json!({"type": "input_audio", "input_audio": {"data": "aGVsbG8=", "format": "wav"}})
+    );
+}
+
+#[test]
+fn chat_reasoning_usage_details() {
+    use async_openai::types::{CreateChatCompletionResponse, ServiceTierResponse};
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "o3-mini",
+        "service_tier": "flex",
+        "choices": [],
+        "usage": {
+            "prompt_tokens": 1200,
+            "completion_tokens": 900,
+            "total_tokens": 2100,
+            "prompt_tokens_details": {"cached_tokens": 1024, "audio_tokens": 0},
+            "completion_tokens_details": {
+                "reasoning_tokens": 640,
+                "audio_tokens": 0,
+                "accepted_prediction_tokens": 0,
+                "rejected_prediction_tokens": 0
+            }
+        }
+    }))
+    .unwrap();
+    assert_eq!(response.service_tier, Some(ServiceTierResponse::Flex));
+    let usage = response.usage.unwrap();
+    assert_eq!(usage.cached_tokens(), 1024);
+    assert_eq!(usage.uncached_prompt_tokens(), 176);
+    assert_eq!(usage.reasoning_tokens(), 640);
+    assert_eq!(usage.audio_tokens(), 0);
+}
+
+#[test]
+fn list_query_serde() {
+    use async_openai::types::{
+        Cursor, ListMessagesQueryArgs, ListVectorStoreFilesQueryArgs, SortOrder,
+        VectorStoreFileStatus,
+    };
+
+    let query = ListMessagesQueryArgs::default()
+        .limit(50u32)
+        .order(SortOrder::Asc)
+        .after("msg_abc")
+        .build()
+        .unwrap();
+    let request = reqwest::Client::new()
+        .get("https://api.openai.com/v1/threads/thread_abc/messages")
+        .query(&query)
+        .build()
+        .unwrap();
+    assert_eq!(
+        request.url().query(),
+        Some("limit=50&order=asc&after=msg_abc")
+    );
+
+    let query = ListVectorStoreFilesQueryArgs::default()
+        .before(Cursor::new("file_abc"))
+        .filter(VectorStoreFileStatus::InProgress)
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&query).unwrap(),
+        serde_json::json!({"before": "file_abc", "filter": "in_progress"})
+    );
+}
+
+#[test]
+fn message_content_from_parts() {
+    use async_openai::types::{
+        ChatCompletionRequestUserMessage, ChatCompletionRequestUserMessageContentPart,
+        CreateMessageRequest, ImageUrl, MessageAttachment, MessageContentInput,
+    };
+
+    let mut message = CreateMessageRequest::from([
+        MessageContentInput::text("What is in this image?"),
+        MessageContentInput::image_file("file_abc"),
+    ]);
+    message.attachments = Some(vec![MessageAttachment::for_file_search("file_def")]);
+    assert_eq!(
+        serde_json::to_value(&message).unwrap(),
+        serde_json::json!({
+            "role": "user",
+            "content": [
+                {"type": "text", "text": "What is in this image?"},
+                {"type": "image_file", "image_file": {"file_id": "file_abc", "detail": null}}
+            ],
+            "attachments": [{"file_id": "file_def", "tools": [{"type": "file_search"}]}]
+        })
+    );
+
+    let message = ChatCompletionRequestUserMessage::from([
+        "What is in this image?".into(),
+        ImageUrl::from("https://example.com/image.png").into(),
+    ]);
+    assert_eq!(
+        serde_json::to_value(&message.content).unwrap(),
+        serde_json::to_value([
+            ChatCompletionRequestUserMessageContentPart::text("What is in this image?"),
+            ChatCompletionRequestUserMessageContentPart::image_url("https://example.com/image.png"),
+        ])
+        .unwrap()
+    );
+}
+
+#[test]
+fn deleted_object_serde() {
+    use async_openai::types::DeleteFileResponse;
+
+    let deleted: DeleteFileResponse =
+        serde_json::from_str(r#"{"id": "file-abc", "object": "file", "deleted": true}"#).unwrap();
+    assert!(deleted.is_deleted());
+    assert_eq!(deleted, DeleteFileResponse::new("file-abc", "file", true));
+    assert_eq!(
+        serde_json::to_value(&deleted).unwrap(),
+        serde_json::json!({"id": "file-abc", "object": "file", "deleted": true})
+    );
+}
+
+#[test]
+fn metadata_serde() {
+    use async_openai::types::{Metadata, UpdateVectorStoreRequest};
+
+    let request: UpdateVectorStoreRequest = serde_json::from_value(serde_json::json!({
+        "metadata": {"project": "alpha"}
+    }))
+    .unwrap();
+    let metadata = request.metadata.unwrap();
+    assert_eq!(metadata.get("project"), Some("alpha"));
+    assert_eq!(
+        serde_json::to_value(&metadata).unwrap(),
+        serde_json::json!({"project": "alpha"})
+    );
+
+    let too_many: Metadata = (0..17).map(|i| (i.to_string(), "value")).collect();
+    assert!(too_many.validate().is_err());
+
+    // builders reject metadata beyond the limits of the API
+    let error = async_openai::types::CreateThreadRequestArgs::default()
+        .metadata(too_many.clone())
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        async_openai::error::OpenAIError::InvalidArgument(_)
+    ));
+    let error = async_openai::types::responses::CreateResponseArgs::default()
+        .model("gpt-4o")
+        .input("Hello")
+        .metadata(too_many.clone())
+        .build()
+        .unwrap_err();
+    assert!(matches!(
+        error,
+        async_openai::error::OpenAIError::InvalidArgument(_)
+    ));
+    assert!(async_openai::types::ModifyMessageRequestArgs::default()
+        .metadata(too_many.clone())
+        .build()
+        .is_err());
+    assert!(async_openai::types::ModifyRunRequestArgs::default()
+        .metadata(too_many.clone())
+        .build()
+        .is_err());
+    assert!(async_openai::types::ModifyThreadRequestArgs::default()
+        .metadata(too_many)
+        .build()
+        .is_err());
+}
+
+#[test]
+fn moderation_multi_modal_serde() {
+    use async_openai::types::{
+        CreateModerationRequestArgs, CreateModerationResponse, ModerationContentPart,
+    };
+
+    let request = CreateModerationRequestArgs::default()
+        .model("omni-moderation-latest")
+        .input([
+            ModerationContentPart::text("is this ok?"),
+            ModerationContentPart::image_url("https://example.com/image.png"),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({
+            "model": "omni-moderation-latest",
+            "input": [
+                {"type": "text", "text": "is this ok?"},
+                {"type": "image_url", "image_url": {"url": "https://example.com/image.png"}}
+            ]
+        })
+    );
+    assert!(CreateModerationRequestArgs::default()
+        .model("text-moderation-latest")
+        .input(ModerationContentPart::image_url(
+            "https://example.com/image.png"
+        ))
+        .build()
+        .is_err());
+
+    // text-moderation models report neither the illicit categories nor the input types
+    let categories = [
+        "hate",
+        "hate/threatening",
+        "harassment",
+        "harassment/threatening",
+        "self-harm",
+        "self-harm/intent",
+        "self-harm/instructions",
+        "sexual",
+        "sexual/minors",
+        "violence",
+        "violence/graphic",
+    ];
+    let flags: serde_json::Map<_, _> = categories
+        .iter()
+        .map(|c| (c.to_string(), false.into()))
+        .collect();
+    let scores: serde_json::Map<_, _> = categories
+        .iter()
+        .map(|c| (c.to_string(), 0.0.into()))
+        .collect();
+    let response: CreateModerationResponse = serde_json::from_value(serde_json::json!({
+        "id": "modr-1",
+        "model": "text-moderation-007",
+        "results": [{"flagged": false, "categories": flags, "category_scores": scores}]
+    }))
+    .unwrap();
+    let result = &response.results[0];
+    assert!(!result.categories.illicit);
+    assert!(result.category_applied_input_types.hate.is_empty());
+}
+
+#[test]
+fn conversation_items_serde() {
+    use async_openai::types::conversations::{ConversationItem, ConversationItemList};
+
+    let list: ConversationItemList = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "data": [
+            {
+                "type": "message",
+                "id": "msg_1",
+                "role": "user",
+                "status": "completed",
+                "content": [{"type": "input_text", "text": "Hello!"}]
+            },
+            {"type": "future_call", "id": "fc_1"}
+        ],
+        "first_id": "msg_1",
+        "last_id": "fc_1",
+        "has_more": false
+    }))
+    .unwrap();
+
+    match &list.data[0] {
+        ConversationItem::Message(message) => assert_eq!(message.text(), "Hello!"),
+        item => panic!("unexpected item {item:?}"),
+    }
+    assert!(matches!(list.data[1], ConversationItem::Other(_)));
+    assert_eq!(list.data[1].id(), Some("fc_1"));
+}
+
+#[cfg(feature = "realtime")]
+#[test]
+fn realtime_session_serde() {
+    use async_openai::types::realtime::{
+        CreateRealtimeSessionRequest, RealtimeSession, SessionClientSecret, SessionResource,
+    };
+
+    let request = CreateRealtimeSessionRequest {
+        session: SessionResource {
+            model: Some("gpt-4o-realtime-preview".into()),
+            ..Default::default()
+        },
+        client_secret: Some(SessionClientSecret::default()),
+        ..Default::default()
+    };
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({"model": "gpt-4o-realtime-preview", "client_secret": {}})
+    );
+
+    let session: RealtimeSession = serde_json::from_value(serde_json::json!({
+        "id": "sess_1",
+        "object": "realtime.session",
+        "model": "gpt-4o-realtime-preview",
+        "client_secret": {"value": "ek_abc", "expires_at": 1234567890}
+    }))
+    .unwrap();
+    assert_eq!(session.client_secret.value, "ek_abc");
+    assert_eq!(
+        session.session.model.as_deref(),
+        Some("gpt-4o-realtime-preview")
+    );
+}
+
+#[test]
+fn vector_store_file_attributes_serde() {
+    use async_openai::types::{
+        AttributeValue, UpdateVectorStoreFileAttributesRequestArgs, VectorStoreFileObject,
+    };
+
+    let request = UpdateVectorStoreFileAttributesRequestArgs::default()
+        .attributes([("author".to_string(), AttributeValue::from("jane"))])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap(),
+        serde_json::json!({"attributes": {"author": "jane"}})
+    );
+
+    let file: VectorStoreFileObject = serde_json::from_value(serde_json::json!({
+        "id": "file_1",
+        "object": "vector_store.file",
+        "usage_bytes": 1234,
+        "created_at": 1698107661,
+        "vector_store_id": "vs_1",
+        "status": "completed",
+        "last_error": null,
+        "attributes": {"year": 2024, "score": 4.5, "draft": false}
+    }))
+    .unwrap();
+    let attributes = file.attributes.unwrap();
+    assert_eq!(attributes["year"], AttributeValue::Number(2024));
+    assert_eq!(attributes["score"], AttributeValue::Float(4.5));
+    assert_eq!(attributes["draft"], AttributeValue::Boolean(false));
+}
+
+#[test]
+fn certificate_serde() {
+    use async_openai::types::CertificateListResponse;
+
+    let list: CertificateListResponse = serde_json::from_value(serde_json::json!({
+        "object": "list",
+        "data": [{
+            "object": "organization.certificate",
+            "id": "cert_abc",
+            "name": "My Cert",
+            "active": true,
+            "created_at": 1234567,
+            "certificate_details": {"valid_at": 12345667, "expires_at": 12345678}
+        }],
+        "first_id": "cert_abc",
+        "last_id": "cert_abc",
+        "has_more": false
+    }))
+    .unwrap();
+    assert_eq!(list.data[0].active, Some(true));
+    assert_eq!(list.data[0].certificate_details.content, None);
+}
+
+#[test]
+fn audit_log_serde() {
+    use async_openai::types::{AuditLog, AuditLogEvent, AuditLogEventType, ListAuditLogsQueryArgs};
+
+    let actor = serde_json::json!({
+        "type": "session",
+        "session": {"user": {"id": "user_1", "email": "a@example.com"}, "ip_address": "127.0.0.1"}
+    });
+    let failed: AuditLog = serde_json::from_value(serde_json::json!({
+        "id": "audit_1",
+        "type": "login.failed",
+        "effective_at": 1720804090,
+        "actor": actor,
+        "login.failed": {"error_code": "invalid_password", "error_message": "Invalid password"}
+    }))
+    .unwrap();
+    let AuditLogEvent::LoginFailed(details) = failed.event() else {
+        panic!("expected a failed login, got {:?}", failed.event());
+    };
+    assert_eq!(details.error_code, "invalid_password");
+
+    let unknown: AuditLog = serde_json::from_value(serde_json::json!({
+        "id": "audit_2",
+        "type": "project.deleted",
+        "effective_at": 1720804090,
+        "actor": actor,
+        "project.deleted": {"id": "proj_1"}
+    }))
+    .unwrap();
+    assert_eq!(
+        unknown.event(),
+        AuditLogEvent::Other {
+            r#type: "project.deleted".into(),
+            details: Some(serde_json::json!({"id": "proj_1"})),
+        }
+    );
+
+    let query = ListAuditLogsQueryArgs::default()
+        .effective_at_gte(1720000000u64)
+        .event_types(vec![
+            AuditLogEventType::LoginFailed,
+            AuditLogEventType::Other("project.deleted".into()),
+        ])
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&query).unwrap(),
+        serde_json::json!([
+            ["effective_at[gte]", "1720000000"],
+            ["event_types[]", "login.failed"],
+            ["event_types[]", "project.deleted"]
+        ])
+    );
+}
+
+#[test]
+fn chat_web_search_citations() {
+    use async_openai::types::{
+        CreateChatCompletionResponse, WebSearchContextSize, WebSearchLocation, WebSearchOptions,
+    };
+
+    let request = CreateChatCompletionRequestArgs::default()
+        .model("gpt-4o-search-preview")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("What happened today?")
+            .build()
+            .unwrap()
+            .into()])
+        .web_search_options(
+            WebSearchOptions::default()
+                .with_search_context_size(WebSearchContextSize::Low)
+                .with_user_location(WebSearchLocation {
+                    country: Some("GB".into()),
+                    ..Default::default()
+                }),
+        )
+        .build()
+        .unwrap();
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["web_search_options"],
+        serde_json::json!({
+            "search_context_size": "low",
+            "user_location": {"type": "approximate", "approximate": {"country": "GB"}}
+        })
+    );
+
+    let response: CreateChatCompletionResponse = serde_json::from_value(serde_json::json!({
+        "id": "chatcmpl-1",
+        "object": "chat.completion",
+        "created": 0,
+        "model": "gpt-4o-search-preview",
+        "choices": [{
+            "index": 0,
+            "finish_reason": "stop",
+            "message": {
+                "role": "assistant",
+                "content": "It rained (example.com).",
+                "annotations": [
+                    {"type": "url_citation", "url_citation": {
+                        "start_index": 10, "end_index": 23,
+                        "url": "https://example.com", "title": "Weather"
+                    }},
+                    {"type": "file_citation", "file_citation": {"file_id": "file-1"}}
+                ]
+            }
+        }]
+    }))
+    .unwrap();
+    let message = &response.choices[0].message;
+    let citations: Vec<_> = message.url_citations().collect();
+    assert_eq!(citations.len(), 1);
+    assert_eq!(citations[0].url, "https://example.com");
+    assert_eq!(message.annotations.as_ref().map(Vec::len), Some(2));
+}
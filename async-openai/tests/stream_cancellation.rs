@@ -0,0 +1,88 @@
+//! Streams stop reading their response when cancelled or dropped.
+use std::time::Duration;
+
+use async_openai::{
+    config::OpenAIConfig,
+    request_options::CancellationToken,
+    types::{ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs},
+    Client,
+};
+use futures::StreamExt;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+    sync::oneshot,
+    time::timeout,
+};
+
+const CHUNK: &str = r#"{"id":"1","object":"chat.completion.chunk","created":1,"model":"m","choices":[{"index":0,"delta":{"content":"a"},"finish_reason":null}]}"#;
+
+/// Serves one event stream which sends a chunk and stays open, the receiver resolves once the
+/// client closed the connection.
+async fn serve() -> (Client<OpenAIConfig>, oneshot::Receiver<()>) {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let (closed_tx, closed_rx) = oneshot::channel();
+    tokio::spawn(async move {
+        let (mut socket, _) = listener.accept().await.unwrap();
+        let mut request = Vec::new();
+        let mut buf = [0; 1024];
+        while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+            let n = socket.read(&mut buf).await.unwrap();
+            request.extend_from_slice(&buf[..n]);
+        }
+        let response =
+            format!("HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\n\r\ndata: {CHUNK}\n\n");
+        socket.write_all(response.as_bytes()).await.unwrap();
+        // the rest of the request body, then the end of the connection
+        while socket.read(&mut buf).await.is_ok_and(|n| n > 0) {}
+        let _ = closed_tx.send(());
+    });
+    let config = OpenAIConfig::new().with_api_base(format!("http://{addr}/v1"));
+    (Client::with_config(config), closed_rx)
+}
+
+fn request() -> async_openai::types::CreateChatCompletionRequest {
+    CreateChatCompletionRequestArgs::default()
+        .model("m")
+        .messages([ChatCompletionRequestUserMessageArgs::default()
+            .content("Hello")
+            .build()
+            .unwrap()
+            .into()])
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn stream_ends_when_cancelled() {
+    let (client, closed) = serve().await;
+    let token = CancellationToken::new();
+    let mut stream = client
+        .chat()
+        .cancellation_token(token.clone())
+        .create_stream(request())
+        .await
+        .unwrap();
+    assert!(stream.next().await.unwrap().is_ok());
+
+    token.cancel();
+    assert!(stream.next().await.is_none());
+    timeout(Duration::from_secs(5), closed)
+        .await
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn stream_closes_when_dropped() {
+    let (client, closed) = serve().await;
+    let mut stream = client.chat().create_stream(request()).await.unwrap();
+    assert!(stream.next().await.unwrap().is_ok());
+
+    drop(stream);
+    timeout(Duration::from_secs(5), closed)
+        .await
+        .unwrap()
+        .unwrap();
+}
@@ -5,7 +5,6 @@ use async_openai::{
     },
     Client,
 };
-use serde_json::json;
 use std::error::Error;
 
 #[tokio::main]
@@ -16,11 +15,11 @@ async fn main() -> Result<(), Box<dyn Error>> {
         .max_tokens(512u32)
         .model("gpt-3.5-turbo")
         .store(true)
-        .metadata(json!({
-            "role": "manager",
-            "department": "accounting",
-            "source": "homepage",
-        }))
+        .metadata([
+            ("role", "manager"),
+            ("department", "accounting"),
+            ("source", "homepage"),
+        ])
         .messages([
             ChatCompletionRequestSystemMessageArgs::default()
                 .content("You are a corporate IT support expert.")
@@ -1,7 +1,7 @@
 use std::error::Error;
 
 use async_openai::{
-    config::OpenAIConfig,
+    config::OllamaConfig,
     types::{
         ChatCompletionRequestAssistantMessageArgs, ChatCompletionRequestSystemMessageArgs,
         ChatCompletionRequestUserMessageArgs, CreateChatCompletionRequestArgs,
@@ -11,17 +11,9 @@ use async_openai::{
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn Error>> {
-    // This is the default host:port for Ollama's OpenAI endpoint.
+    // OllamaConfig defaults to http://localhost:11434/v1, the default host:port for Ollama's OpenAI endpoint.
     // Should match the config in docker-compose.yml.
-    let api_base = "http://localhost:11434/v1";
-    // Required but ignored
-    let api_key = "ollama";
-
-    let client = Client::with_config(
-        OpenAIConfig::new()
-            .with_api_key(api_key)
-            .with_api_base(api_base),
-    );
+    let client = Client::with_config(OllamaConfig::new());
 
     // This should match whatever model is downloaded in Ollama docker container.
     let model = "llama3.2:1b";
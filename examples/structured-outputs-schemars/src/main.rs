@@ -4,11 +4,10 @@ use async_openai::{
     types::{
         ChatCompletionRequestMessage, ChatCompletionRequestSystemMessage,
         ChatCompletionRequestUserMessage, CreateChatCompletionRequestArgs, ResponseFormat,
-        ResponseFormatJsonSchema,
     },
     Client,
 };
-use schemars::{schema_for, JsonSchema};
+use schemars::JsonSchema;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema)]
@@ -28,16 +27,8 @@ pub struct MathReasoningResponse {
 pub async fn structured_output<T: serde::Serialize + DeserializeOwned + JsonSchema>(
     messages: Vec<ChatCompletionRequestMessage>,
 ) -> Result<Option<T>, Box<dyn Error>> {
-    let schema = schema_for!(T);
-    let schema_value = serde_json::to_value(&schema)?;
-    let response_format = ResponseFormat::JsonSchema {
-        json_schema: ResponseFormatJsonSchema {
-            description: None,
-            name: "math_reasoning".into(),
-            schema: Some(schema_value),
-            strict: Some(true),
-        },
-    };
+    // strict schema with `additionalProperties: false` and all fields required
+    let response_format = ResponseFormat::json_schema_for::<T>("math_reasoning")?;
 
     let request = CreateChatCompletionRequestArgs::default()
         .max_tokens(512u32)